@@ -1,9 +1,16 @@
 use fluent_builder::SharedFluentBuilder;
 use futures::Future;
-use reqwest::r#async::Client as AsyncHttpClient;
+use reqwest::{
+    r#async::{
+        Client as AsyncHttpClient,
+        ClientBuilder as AsyncHttpClientBuilder,
+    },
+    Error as ReqwestError,
+};
 use std::{
     error::Error as StdError,
     sync::Arc,
+    time::Duration,
 };
 use tokio_threadpool::ThreadPool;
 
@@ -13,12 +20,28 @@ use crate::{
     http::{
         sender::{
             sniffed_nodes::SniffedNodesBuilder,
+            static_nodes::{
+                RoundRobin,
+                Strategy,
+            },
             AsyncPreSend,
+            AsyncPreSendMiddleware,
             AsyncSender,
+            ClientTimeouts,
+            CredentialsProvider,
+            FixtureTransport,
+            Fixtures,
+            HttpClients,
+            MetricsObserver,
+            Middleware,
+            MockTransport,
             NodeAddress,
             NodeAddressesBuilder,
+            NodeHealth,
             PreRequestParams,
+            RetryPolicy,
         },
+        AsyncBody,
         AsyncHttpRequest,
     },
 };
@@ -69,6 +92,15 @@ pub struct AsyncClientBuilder {
     nodes: NodeAddressesBuilder,
     params: SharedFluentBuilder<PreRequestParams>,
     pre_send: Option<Arc<AsyncPreSend>>,
+    middleware: Vec<Box<dyn Middleware<AsyncBody>>>,
+    metrics: Option<Arc<dyn MetricsObserver>>,
+    credentials: Option<Arc<dyn CredentialsProvider>>,
+    gzip: bool,
+    mock: Option<MockTransport>,
+    fixtures: Option<Fixtures>,
+    retry_policy: RetryPolicy,
+    health: NodeHealth,
+    strategy: Arc<dyn Strategy>,
 }
 
 impl Default for AsyncClientBuilder {
@@ -87,6 +119,9 @@ impl AsyncClientBuilder {
     - Not deserialise repsonses on a cpu pool
     - Not use any authentication
     - Not use TLS
+    - Not retry failed requests
+    - Skip nodes that fail to connect for 60 seconds, doubling up to 30 minutes
+    - Select nodes to send requests to in a round-robin order
     */
     pub fn new() -> Self {
         AsyncClientBuilder {
@@ -95,6 +130,15 @@ impl AsyncClientBuilder {
             params: SharedFluentBuilder::new(),
             nodes: NodeAddressesBuilder::default(),
             pre_send: None,
+            middleware: Vec::new(),
+            metrics: None,
+            credentials: None,
+            gzip: false,
+            mock: None,
+            fixtures: None,
+            retry_policy: RetryPolicy::never(),
+            health: NodeHealth::default(),
+            strategy: Arc::new(RoundRobin::default()),
         }
     }
 
@@ -108,6 +152,15 @@ impl AsyncClientBuilder {
             params: SharedFluentBuilder::new().value(params),
             nodes: NodeAddressesBuilder::default(),
             pre_send: None,
+            middleware: Vec::new(),
+            metrics: None,
+            credentials: None,
+            gzip: false,
+            mock: None,
+            fixtures: None,
+            retry_policy: RetryPolicy::never(),
+            health: NodeHealth::default(),
+            strategy: Arc::new(RoundRobin::default()),
         }
     }
 
@@ -132,6 +185,34 @@ impl AsyncClientBuilder {
         self
     }
 
+    /**
+    Specify the cloud id of a cluster hosted on Elastic Cloud to send requests to.
+
+    The cloud id is decoded into the address of the cluster, which is used as a static node.
+    Requests to hosted clusters go through a proxy, so the nodes behind it can't be sniffed.
+    See [`NodeAddress::from_cloud_id`][from_cloud_id] for details on how the cloud id is decoded.
+
+    # Examples
+
+    Connect to a cluster on Elastic Cloud using an API key:
+
+    ```
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let cloud_id = "my-cluster:Y2xvdWQuZXMuaW8kZXMtaWQka2liYW5hLWlk";
+    let builder = AsyncClientBuilder::new()
+        .cloud_id(cloud_id)?
+        .params(PreRequestParams::default().api_key("id", "key")?);
+    # Ok(())
+    # }
+    ```
+
+    [from_cloud_id]: ../http/sender/struct.NodeAddress.html#method.from_cloud_id
+    */
+    pub fn cloud_id(self, cloud_id: &str) -> Result<Self, Error> {
+        Ok(self.static_node(NodeAddress::from_cloud_id(cloud_id)?))
+    }
+
     /**
     Specify a node address to sniff other nodes in the cluster from.
 
@@ -189,6 +270,16 @@ impl AsyncClientBuilder {
         .params_fluent(|p| p
             .url_param("pretty", true));
     ```
+
+    Time out all requests that take longer than 10 seconds:
+
+    ```
+    # use std::time::Duration;
+    # use elastic::prelude::*;
+    let builder = AsyncClientBuilder::new()
+        .params_fluent(|p| p
+            .total_timeout(Duration::from_secs(10)));
+    ```
     */
     pub fn params_fluent(
         mut self,
@@ -247,12 +338,212 @@ impl AsyncClientBuilder {
         self
     }
 
+    /**
+    Specify a policy for retrying requests that fail with a transient error.
+
+    Retried requests are sent to the next node.
+    By default, requests aren't retried.
+
+    # Examples
+
+    Retry requests that fail with a transient error up to 3 times:
+
+    ```
+    # use elastic::prelude::*;
+    let builder = AsyncClientBuilder::new()
+        .retry_policy(RetryPolicy::new().max_attempts(3));
+    ```
+    */
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+
+        self
+    }
+
+    /**
+    Specify how long to skip a node for after a request to it fails to connect.
+
+    Once the timeout expires the node is pinged in the background, without retries or middleware, and used again if it responds.
+    The timeout doubles each time the node fails in a row, up to the given maximum.
+    If every node has failed then requests are sent to all of them.
+
+    # Examples
+
+    Skip nodes that fail to connect for 10 seconds, doubling up to 5 minutes:
+
+    ```
+    # use std::time::Duration;
+    # use elastic::prelude::*;
+    let builder = AsyncClientBuilder::new()
+        .dead_node_timeout(Duration::from_secs(10), Duration::from_secs(300));
+    ```
+    */
+    pub fn dead_node_timeout(mut self, timeout: Duration, max_timeout: Duration) -> Self {
+        self.health = NodeHealth::new(timeout, max_timeout);
+
+        self
+    }
+
+    /**
+    Specify the strategy for selecting the node to send each request to.
+
+    The strategy is used for both static and sniffed nodes.
+    Some built-in strategies are available in the [`static_nodes`][static_nodes] module,
+    or a custom strategy can be given by implementing the [`Strategy`][Strategy] trait.
+    By default, nodes are selected in a round-robin order.
+
+    # Examples
+
+    Send each request to the node with the fewest requests in flight:
+
+    ```
+    # use elastic::prelude::*;
+    use elastic::http::sender::static_nodes::LeastInFlight;
+
+    let builder = AsyncClientBuilder::new()
+        .static_nodes(vec!["http://a:9200", "http://b:9200"])
+        .strategy(LeastInFlight::default());
+    ```
+
+    [static_nodes]: ../http/sender/static_nodes/index.html
+    [Strategy]: ../http/sender/static_nodes/trait.Strategy.html
+    */
+    pub fn strategy(mut self, strategy: impl Strategy + 'static) -> Self {
+        self.strategy = Arc::new(strategy);
+
+        self
+    }
+
+    /**
+    Specify a source of credentials to authenticate each request with.
+
+    The provider is called before every request is sent, including requests to sniff nodes and ping dead ones.
+    Credentials returned by the provider replace any `Authorization` header set in the request parameters.
+    Use a provider instead of the `api_key` or `bearer_token` parameters when credentials are rotated so the client doesn't need to be rebuilt.
+
+    # Examples
+
+    Authenticate with a bearer token that's refreshed elsewhere:
+
+    ```
+    # use std::sync::{Arc, RwLock};
+    # use elastic::prelude::*;
+    let token = Arc::new(RwLock::new(String::from("a token")));
+
+    let builder = AsyncClientBuilder::new()
+        .credentials_provider(move || {
+            let token = token.read().expect("lock poisoned");
+
+            Ok(Some(Credentials::bearer_token(&*token)))
+        });
+    ```
+    */
+    pub fn credentials_provider(mut self, provider: impl CredentialsProvider + 'static) -> Self {
+        self.credentials = Some(Arc::new(provider));
+
+        self
+    }
+
+    /**
+    Compress request bodies with gzip.
+
+    Requests with a body are sent with a `Content-Encoding: gzip` header.
+    Compressed responses are requested and decompressed by the underlying `reqwest` client, which does this by default, so a client given to the `http_client` method must keep its `gzip` option enabled.
+    Compression is disabled by default.
+    The body is compressed on the serde pool when one is set.
+
+    # Examples
+
+    ```
+    # use elastic::prelude::*;
+    let builder = AsyncClientBuilder::new().gzip(true);
+    ```
+    */
+    pub fn gzip(mut self, gzip: bool) -> Self {
+        self.gzip = gzip;
+
+        self
+    }
+
+    /**
+    Add a middleware that's called before every request is sent and after every response is received.
+
+    Middleware are called in the order they're added before a request is sent and in reverse order after its response is received.
+    They can be used for request signing, audit logging, caching responses or injecting faults.
+    See [`Middleware`][Middleware] for more details.
+
+    # Examples
+
+    ```
+    # use std::error::Error;
+    # use elastic::prelude::*;
+    # use elastic::http::HttpRequest;
+    # use elastic::http::sender::{BufferedResponse, Middleware};
+    struct AuditLog;
+
+    impl<TBody> Middleware<TBody> for AuditLog {
+        fn after_receive(
+            &self,
+            req: &HttpRequest<()>,
+            res: &mut BufferedResponse,
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            println!("{} {}: {}", req.method(), req.url(), res.status());
+
+            Ok(())
+        }
+
+        fn receives_responses(&self) -> bool {
+            true
+        }
+    }
+
+    let builder = AsyncClientBuilder::new().middleware(AuditLog);
+    ```
+
+    [Middleware]: ../http/sender/trait.Middleware.html
+    */
+    pub fn middleware(mut self, middleware: impl Middleware<AsyncBody> + 'static) -> Self {
+        self.middleware.push(Box::new(middleware));
+
+        self
+    }
+
+    /**
+    Specify an observer for metrics about each request.
+
+    The observer is told when each attempt at a request is sent to a node, when its response is received and read, and when it's retried.
+    See [`MetricsObserver`][MetricsObserver] for more details.
+
+    # Examples
+
+    Collect metrics in memory:
+
+    ```
+    # use elastic::prelude::*;
+    # use elastic::http::sender::InMemoryMetrics;
+    let metrics = InMemoryMetrics::new();
+
+    let builder = AsyncClientBuilder::new().metrics(metrics.clone());
+
+    // Later...
+    let snapshot = metrics.snapshot();
+    ```
+
+    [MetricsObserver]: ../http/sender/trait.MetricsObserver.html
+    */
+    pub fn metrics(mut self, observer: impl MetricsObserver + 'static) -> Self {
+        self.metrics = Some(Arc::new(observer));
+
+        self
+    }
+
     /**
     Specify a function to tweak a raw request before sending.
 
     This function will be applied to all outgoing requests and gives you the chance to perform operations the require the complete raw request,
     such as request signing.
     Prefer the `params` method on the client or individual requests where possible.
+    The function runs after any middleware added with the `middleware` method.
     */
     pub fn pre_send_raw(
         mut self,
@@ -269,30 +560,128 @@ impl AsyncClientBuilder {
         self
     }
 
-    /** Use the given `reqwest::Client` for sending requests. */
+    /**
+    Use the given `reqwest::Client` for sending requests.
+
+    Connect timeouts set in the request parameters aren't applied to requests sent by a custom client.
+    Configure the connect timeout on the `reqwest::Client` itself instead.
+    */
     pub fn http_client(mut self, client: AsyncHttpClient) -> Self {
         self.http = Some(client);
 
         self
     }
 
+    /**
+    Send requests to an in-memory mock transport instead of over the network.
+
+    The mock responds to every request sent by the client, including requests to sniff or ping nodes.
+    The node addresses and `http_client` are still used to build requests, but no connections are made.
+    See [`MockTransport`][MockTransport] for more details.
+
+    # Examples
+
+    ```
+    # use elastic::prelude::*;
+    # use elastic::http::{Method, StatusCode};
+    # use elastic::http::sender::{MockRequest, MockResponse, MockTransport};
+    let mock = MockTransport::new().respond(
+        MockRequest::new(Method::HEAD, "/"),
+        MockResponse::new(StatusCode::OK),
+    );
+
+    let builder = AsyncClientBuilder::new().mock(mock);
+    ```
+
+    [MockTransport]: ../http/sender/struct.MockTransport.html
+    */
+    pub fn mock(mut self, mock: MockTransport) -> Self {
+        self.mock = Some(mock);
+
+        self
+    }
+
+    /**
+    Record requests to a fixture file or replay them from one.
+
+    When recording, requests are still sent to the nodes the client is configured with.
+    When replaying, fixtures take the place of any mock transport given to the `mock` method.
+    The fixture file is opened when the client is built.
+    See [`Fixtures`][Fixtures] for more details.
+
+    # Examples
+
+    ```no_run
+    # use elastic::prelude::*;
+    # use elastic::http::sender::Fixtures;
+    let builder = AsyncClientBuilder::new()
+        .fixtures(Fixtures::replay("tests/fixtures/search.ndjson").normalize("took", 0));
+    ```
+
+    [Fixtures]: ../http/sender/struct.Fixtures.html
+    */
+    pub fn fixtures(mut self, fixtures: Fixtures) -> Self {
+        self.fixtures = Some(fixtures);
+
+        self
+    }
+
     /**
     Construct an [`AsyncClient`][AsyncClient] from this builder.
 
     [AsyncClient]: type.AsyncClient.html
     */
     pub fn build(self) -> Result<AsyncClient, Error> {
-        let http = self.http.unwrap_or_else(AsyncHttpClient::new);
+        let http = match self.http {
+            Some(http) => HttpClients::custom(http),
+            None => HttpClients::new(AsyncHttpClient::new(), build_http_client),
+        };
+
+        let (mock, recorder) = match self.fixtures.map(Fixtures::open).transpose()? {
+            Some(FixtureTransport::Replay(mock)) => (Some(mock), None),
+            Some(FixtureTransport::Record(recorder)) => (self.mock, Some(recorder)),
+            None => (self.mock, None),
+        };
+
+        let mut middleware = self.middleware;
+        if let Some(pre_send) = self.pre_send {
+            middleware.push(Box::new(AsyncPreSendMiddleware(pre_send)));
+        }
+
         let params = self.params.into_value(PreRequestParams::default);
 
         let sender = AsyncSender {
             http,
             serde_pool: self.serde_pool,
-            pre_send: self.pre_send,
+            middleware: middleware.into(),
+            metrics: self.metrics,
+            credentials: self.credentials,
+            gzip: self.gzip,
+            mock,
+            recorder,
+            retry_policy: Arc::new(self.retry_policy),
         };
 
-        let addresses = self.nodes.build(params, sender.clone());
+        let addresses = self
+            .nodes
+            .build(params, self.health, self.strategy, sender.clone());
 
         Ok(AsyncClient { sender, addresses })
     }
 }
+
+/**
+Build a `reqwest::Client` for requests with the given timeouts.
+
+Only the connect timeout is set on the client.
+Other timeouts are enforced on the request future so they don't apply to reading the response body.
+*/
+fn build_http_client(timeouts: ClientTimeouts) -> Result<AsyncHttpClient, ReqwestError> {
+    let mut builder = AsyncHttpClientBuilder::new();
+
+    if let Some(connect) = timeouts.connect {
+        builder = builder.connect_timeout(connect);
+    }
+
+    builder.build()
+}
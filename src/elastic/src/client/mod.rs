@@ -138,7 +138,9 @@ These request methods are called directly on a [`Client`][`Client`].
 Client method                                                 | Elasticsearch API                  | Raw request type                                        | Response type
 ------------------------------------------------------------- | ---------------------------------- | ------------------------------------------------------- | ------------------------------------
 [`search`][Client.search]                                     | [Search][docs-search]              | [`SearchRequest`][SearchRequest]                        | [`SearchResponse`][SearchResponse]
+[`msearch`][Client.msearch]                                   | [Multi Search][docs-msearch]       | [`MsearchRequest`][MsearchRequest]                      | [`MsearchResponse`][MsearchResponse]
 [`bulk`][Client.bulk]                                         | [Bulk][docs-bulk]                  | [`BulkRequest`][BulkRequest]                            | [`BulkResponse`][BulkResponse]
+[`reindex`][Client.reindex]                                   | [Reindex][docs-reindex]            | [`ReindexRequest`][ReindexRequest]                      | [`ReindexResponse`][ReindexResponse]
 [`ping`][Client.ping]                                         | -                                  | [`PingRequest`][PingRequest]                            | [`PingResponse`][PingResponse]
 [`sql`][Client.sql]                                           | [SQL][docs-sql]                    | [`SqlQueryRequest`][SqlQueryRequest]                    | [`SqlQueryResponse`][SqlQueryResponse]
 
@@ -150,10 +152,14 @@ Client method                                                 | Elasticsearch AP
 ------------------------------------------------------------- | ---------------------------------- | ------------------------------------------------------- | ------------------------------------
 [`document.search`][Client.document.search]                   | [Search][docs-search]              | [`SearchRequest`][SearchRequest]                        | [`SearchResponse`][SearchResponse]
 [`document.get`][Client.document.get]                         | [Get Document][docs-get]           | [`GetRequest`][GetRequest]                              | [`GetResponse`][GetResponse]
+[`document.get_many`][Client.document.get_many]               | [Multi Get][docs-mget]             | [`MgetRequest`][MgetRequest]                            | [`GetManyResponse`][GetManyResponse]
 [`document.index`][Client.document.index]                     | [Index Document][docs-index]       | [`IndexRequest`][IndexRequest]                          | [`IndexResponse`][IndexResponse]
 [`document.update`][Client.document.update]                   | [Update Document][docs-update]     | [`UpdateRequest`][UpdateRequest]                        | [`UpdateResponse`][UpdateResponse]
+[`document.update_by_query`][Client.document.update_by_query] | [Update By Query][docs-update-by-query] | [`UpdateByQueryRequest`][UpdateByQueryRequest]   | [`UpdateByQueryResponse`][UpdateByQueryResponse]
 [`document.delete`][Client.document.delete]                   | [Delete Document][docs-delete]     | [`DeleteRequest`][DeleteRequest]                        | [`DeleteResponse`][DeleteResponse]
+[`document.delete_by_query`][Client.document.delete_by_query] | [Delete By Query][docs-delete-by-query] | [`DeleteByQueryRequest`][DeleteByQueryRequest]   | [`DeleteByQueryResponse`][DeleteByQueryResponse]
 [`document.put_mapping`][Client.document.put_mapping]         | [Put Mapping][docs-mapping]        | [`IndicesPutMappingRequest`][IndicesPutMappingRequest]  | [`CommandResponse`][CommandResponse]
+[`document.mapping_diff`][Client.document.mapping_diff]       | [Get Mapping][docs-get-mapping]    | [`IndicesGetMappingRequest`][IndicesGetMappingRequest]  | [`MappingDiffResponse`][MappingDiffResponse]
 
 ## Index requests
 
@@ -166,6 +172,26 @@ Client method                                                 | Elasticsearch AP
 [`index.close`][Client.index.close]                           | [Close Index][docs-close-index]    | [`IndicesCloseRequest`][IndicesCloseRequest]            | [`CommandResponse`][CommandResponse]
 [`index.delete`][Client.index.delete]                         | [Delete Index][docs-delete-index]  | [`IndicesDeleteRequest`][IndicesDeleteRequest]          | [`CommandResponse`][CommandResponse]
 [`index.exists`][Client.index.exists]                         | [Index Exists][docs-index-exists]  | [`IndicesExistsRequest`][IndicesExistsRequest]          | [`IndicesExistsResponse`][IndicesExistsResponse]
+[`index.settings`][Client.index.settings]                     | [Get Settings][docs-get-settings]  | [`IndicesGetSettingsRequest`][IndicesGetSettingsRequest] | [`IndexSettingsResponse`][IndexSettingsResponse]
+[`index.put_settings`][Client.index.put_settings]             | [Update Settings][docs-update-settings] | [`IndicesPutSettingsRequest`][IndicesPutSettingsRequest] | [`CommandResponse`][CommandResponse]
+[`index.add_alias`][Client.index.add_alias]                   | [Index Aliases][docs-aliases]      | [`IndicesUpdateAliasesRequest`][IndicesUpdateAliasesRequest] | [`CommandResponse`][CommandResponse]
+[`index.remove_alias`][Client.index.remove_alias]             | [Index Aliases][docs-aliases]      | [`IndicesUpdateAliasesRequest`][IndicesUpdateAliasesRequest] | [`CommandResponse`][CommandResponse]
+[`index.swap_alias`][Client.index.swap_alias]                 | [Index Aliases][docs-aliases]      | [`IndicesUpdateAliasesRequest`][IndicesUpdateAliasesRequest] | [`CommandResponse`][CommandResponse]
+[`index.get_aliases`][Client.index.get_aliases]               | [Get Alias][docs-get-alias]        | [`IndicesGetAliasRequest`][IndicesGetAliasRequest]      | [`AliasesResponse`][AliasesResponse]
+[`index.resolve_alias`][Client.index.resolve_alias]           | [Get Alias][docs-get-alias]        | [`IndicesGetAliasRequest`][IndicesGetAliasRequest]      | [`AliasesResponse`][AliasesResponse]
+[`index.alias_exists`][Client.index.alias_exists]             | [Alias Exists][docs-get-alias]     | [`IndicesExistsAliasRequest`][IndicesExistsAliasRequest] | [`AliasExistsResponse`][AliasExistsResponse]
+[`index.migrate`][Client.index.migrate]                       | [Index Aliases][docs-aliases]      | [`IndicesUpdateAliasesRequest`][IndicesUpdateAliasesRequest] | [`IndexMigrateResponse`][IndexMigrateResponse]
+
+## Index template requests
+
+These request methods are called on a [`IndexTemplateClient`][`IndexTemplateClient`].
+
+Client method                                                 | Elasticsearch API                  | Raw request type                                        | Response type
+------------------------------------------------------------- | ---------------------------------- | ------------------------------------------------------- | ------------------------------------
+[`index_template.put`][Client.index_template.put]             | [Put Template][docs-templates]     | [`IndicesPutTemplateRequest`][IndicesPutTemplateRequest] | [`CommandResponse`][CommandResponse]
+[`index_template.get`][Client.index_template.get]             | [Get Template][docs-templates]     | [`IndicesGetTemplateRequest`][IndicesGetTemplateRequest] | [`IndexTemplatesResponse`][IndexTemplatesResponse]
+[`index_template.delete`][Client.index_template.delete]       | [Delete Template][docs-templates]  | [`IndicesDeleteTemplateRequest`][IndicesDeleteTemplateRequest] | [`CommandResponse`][CommandResponse]
+[`index_template.exists`][Client.index_template.exists]       | [Template Exists][docs-templates]  | [`IndicesExistsTemplateRequest`][IndicesExistsTemplateRequest] | [`IndexTemplateExistsResponse`][IndexTemplateExistsResponse]
 
 All builders follow a standard pattern:
 
@@ -458,18 +484,29 @@ future.and_then(|body| {
 For more details see the [`responses`][responses-mod] module.
 
 [docs-bulk]: http://www.elastic.co/guide/en/elasticsearch/reference/current/docs-bulk.html
+[docs-reindex]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-reindex.html
 [docs-search]: http://www.elastic.co/guide/en/elasticsearch/reference/current/search-search.html
+[docs-msearch]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-multi-search.html
 [docs-sql]: https://www.elastic.co/guide/en/elasticsearch/reference/current/sql-spec.html
 [docs-get]: http://www.elastic.co/guide/en/elasticsearch/reference/current/docs-get.html
+[docs-mget]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-multi-get.html
 [docs-update]: http://www.elastic.co/guide/en/elasticsearch/reference/current/docs-update.html
+[docs-update-by-query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-update-by-query.html
 [docs-delete]: http://www.elastic.co/guide/en/elasticsearch/reference/current/docs-delete.html
+[docs-delete-by-query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-delete-by-query.html
 [docs-index]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-index_.html
 [docs-mapping]: https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping.html
+[docs-get-mapping]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-get-mapping.html
 [docs-create-index]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-create-index.html
 [docs-close-index]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-open-close.html
 [docs-open-index]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-open-close.html
 [docs-index-exists]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-exists.html
 [docs-delete-index]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-delete-index.html
+[docs-get-settings]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-get-settings.html
+[docs-update-settings]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-update-settings.html
+[docs-aliases]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-aliases.html
+[docs-get-alias]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-aliases.html#alias-retrieving
+[docs-templates]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-templates.html
 
 [tokio]: https://tokio.rs
 
@@ -482,39 +519,74 @@ For more details see the [`responses`][responses-mod] module.
 [`Client`]: struct.Client.html
 [`DocumentClient`]: struct.DocumentClient.html
 [`IndexClient`]: struct.IndexClient.html
+[`IndexTemplateClient`]: struct.IndexTemplateClient.html
 [Client.request]: struct.Client.html#method.request
 [Client.bulk]: struct.Client.html#bulk-request
+[Client.reindex]: struct.Client.html#reindex-request
 [Client.search]: struct.Client.html#search-request
+[Client.msearch]: struct.Client.html#multi-search-request
 [Client.sql]: struct.Client.html#sql-request
 [Client.document.search]: struct.DocumentClient.html#search-request
 [Client.document.get]: struct.DocumentClient.html#get-document-request
+[Client.document.get_many]: struct.DocumentClient.html#multi-get-document-request
 [Client.document.update]: struct.DocumentClient.html#update-document-request
+[Client.document.update_by_query]: struct.DocumentClient.html#update-by-query-request
 [Client.document.delete]: struct.DocumentClient.html#delete-document-request
+[Client.document.delete_by_query]: struct.DocumentClient.html#delete-by-query-request
 [Client.document.index]: struct.DocumentClient.html#index-document-request
 [Client.document.put_mapping]: struct.DocumentClient.html#method.put_mapping
+[Client.document.mapping_diff]: struct.DocumentClient.html#mapping-diff-request
 [Client.index.create]: struct.IndexClient.html#create-index-request
 [Client.index.open]: struct.IndexClient.html#open-index-request
 [Client.index.close]: struct.IndexClient.html#close-index-request
 [Client.index.delete]: struct.IndexClient.html#delete-index-request
 [Client.index.exists]: struct.IndexClient.html#index-exists-request
+[Client.index.settings]: struct.IndexClient.html#get-settings-request
+[Client.index.put_settings]: struct.IndexClient.html#put-settings-request
+[Client.index.add_alias]: struct.IndexClient.html#update-aliases-request
+[Client.index.remove_alias]: struct.IndexClient.html#update-aliases-request
+[Client.index.swap_alias]: struct.IndexClient.html#update-aliases-request
+[Client.index.get_aliases]: struct.IndexClient.html#get-aliases-request
+[Client.index.resolve_alias]: struct.IndexClient.html#get-aliases-request
+[Client.index.alias_exists]: struct.IndexClient.html#alias-exists-request
+[Client.index.migrate]: struct.IndexClient.html#migrate-index-request
+[Client.index_template.put]: struct.IndexTemplateClient.html#put-index-template-request
+[Client.index_template.get]: struct.IndexTemplateClient.html#get-index-template-request
+[Client.index_template.delete]: struct.IndexTemplateClient.html#delete-index-template-request
+[Client.index_template.exists]: struct.IndexTemplateClient.html#index-template-exists-request
 [Client.ping]: struct.Client.html#ping-request
 
 [RequestBuilder]: requests/struct.RequestBuilder.html
 [RequestBuilder.params]: requests/struct.RequestBuilder.html#method.params
 [RawRequestBuilder]: requests/type.RawRequestBuilder.html
 [SearchRequest]: ../endpoints/struct.SearchRequest.html
+[MsearchRequest]: ../endpoints/struct.MsearchRequest.html
 [SqlQueryRequest]: ../endpoints/struct.SqlQueryRequest.html
 [BulkRequest]: ../endpoints/struct.BulkRequest.html
+[ReindexRequest]: ../endpoints/struct.ReindexRequest.html
 [GetRequest]: ../endpoints/struct.GetRequest.html
+[MgetRequest]: ../endpoints/struct.MgetRequest.html
 [UpdateRequest]: ../endpoints/struct.UpdateRequest.html
+[UpdateByQueryRequest]: ../endpoints/struct.UpdateByQueryRequest.html
 [DeleteRequest]: ../endpoints/struct.DeleteRequest.html
+[DeleteByQueryRequest]: ../endpoints/struct.DeleteByQueryRequest.html
 [IndexRequest]: ../endpoints/struct.IndexRequest.html
 [IndicesPutMappingRequest]: ../endpoints/struct.IndicesPutMappingRequest.html
+[IndicesGetMappingRequest]: ../endpoints/struct.IndicesGetMappingRequest.html
 [IndicesCreateRequest]: ../endpoints/struct.IndicesCreateRequest.html
 [IndicesOpenRequest]: ../endpoints/struct.IndicesOpenRequest.html
 [IndicesCloseRequest]: ../endpoints/struct.IndicesCloseRequest.html
 [IndicesDeleteRequest]: ../endpoints/struct.IndicesDeleteRequest.html
 [IndicesExistsRequest]: ../endpoints/struct.IndicesExistsRequest.html
+[IndicesGetSettingsRequest]: ../endpoints/struct.IndicesGetSettingsRequest.html
+[IndicesPutSettingsRequest]: ../endpoints/struct.IndicesPutSettingsRequest.html
+[IndicesUpdateAliasesRequest]: ../endpoints/struct.IndicesUpdateAliasesRequest.html
+[IndicesGetAliasRequest]: ../endpoints/struct.IndicesGetAliasRequest.html
+[IndicesExistsAliasRequest]: ../endpoints/struct.IndicesExistsAliasRequest.html
+[IndicesPutTemplateRequest]: ../endpoints/struct.IndicesPutTemplateRequest.html
+[IndicesGetTemplateRequest]: ../endpoints/struct.IndicesGetTemplateRequest.html
+[IndicesDeleteTemplateRequest]: ../endpoints/struct.IndicesDeleteTemplateRequest.html
+[IndicesExistsTemplateRequest]: ../endpoints/struct.IndicesExistsTemplateRequest.html
 [PingRequest]: ../endpoints/struct.PingRequest.html
 
 [responses-mod]: responses/index.html
@@ -525,13 +597,25 @@ For more details see the [`responses`][responses-mod] module.
 [AsyncResponseBuilder.into_response]: ../http/receiver/struct.AsyncResponseBuilder.html#method.into_response
 [AsyncResponseBuilder.into_raw]: ../http/receiver/struct.AsyncResponseBuilder.html#method.into_raw
 [SearchResponse]: responses/struct.SearchResponse.html
+[MsearchResponse]: responses/struct.MsearchResponse.html
 [SqlQueryResponse]: responses/struct.SqlQueryResponse.html
 [BulkResponse]: responses/struct.BulkResponse.html
+[ReindexResponse]: responses/type.ReindexResponse.html
 [GetResponse]: responses/struct.GetResponse.html
+[GetManyResponse]: responses/struct.GetManyResponse.html
 [UpdateResponse]: responses/struct.UpdateResponse.html
+[UpdateByQueryResponse]: responses/type.UpdateByQueryResponse.html
 [DeleteResponse]: responses/struct.DeleteResponse.html
+[DeleteByQueryResponse]: responses/type.DeleteByQueryResponse.html
 [IndexResponse]: responses/struct.IndexResponse.html
 [IndicesExistsResponse]: responses/struct.IndicesExistsResponse.html
+[IndexSettingsResponse]: responses/struct.IndexSettingsResponse.html
+[MappingDiffResponse]: responses/struct.MappingDiffResponse.html
+[IndexMigrateResponse]: responses/struct.IndexMigrateResponse.html
+[AliasesResponse]: responses/struct.AliasesResponse.html
+[AliasExistsResponse]: responses/struct.AliasExistsResponse.html
+[IndexTemplatesResponse]: responses/struct.IndexTemplatesResponse.html
+[IndexTemplateExistsResponse]: responses/struct.IndexTemplateExistsResponse.html
 [PingResponse]: responses/struct.PingResponse.html
 [CommandResponse]: responses/struct.CommandResponse.html
 [SyncHttpResponse]: ../http/receiver/struct.SyncHttpResponse.html
@@ -553,8 +637,12 @@ pub use self::{
 
 #[doc(inline)]
 pub use crate::http::sender::{
+    Credentials,
+    CredentialsProvider,
     PreRequestParams,
     RequestParams,
+    RetryPolicy,
+    RetryableError,
 };
 
 use crate::{
@@ -562,7 +650,10 @@ use crate::{
         NodeAddresses,
         Sender,
     },
-    params::Index,
+    params::{
+        Index,
+        Name,
+    },
 };
 
 use std::marker::PhantomData;
@@ -644,6 +735,16 @@ where
             index: index.into(),
         }
     }
+
+    /**
+    Get a client for working with a specific index template.
+    */
+    pub fn index_template(&self, name: impl Into<Name<'static>>) -> IndexTemplateClient<TSender> {
+        IndexTemplateClient {
+            inner: (*self).clone(),
+            name: name.into(),
+        }
+    }
 }
 
 /**
@@ -668,6 +769,17 @@ pub struct IndexClient<TSender> {
     index: Index<'static>,
 }
 
+/**
+A [`Client`] for a specific index template.
+
+[`Client`]: struct.Client.html
+*/
+#[derive(Clone)]
+pub struct IndexTemplateClient<TSender> {
+    inner: Client<TSender>,
+    name: Name<'static>,
+}
+
 pub mod prelude {
     /*! A glob import for convenience. */
 
@@ -676,8 +788,12 @@ pub mod prelude {
         responses::prelude::*,
         AsyncClient,
         AsyncClientBuilder,
+        Credentials,
+        CredentialsProvider,
         PreRequestParams,
         RequestParams,
+        RetryPolicy,
+        RetryableError,
         SyncClient,
         SyncClientBuilder,
     };
@@ -2,17 +2,41 @@
 Types that are common between requests.
 */
 
-use std::ops::Not;
+use std::{
+    fmt,
+    ops::Not,
+    str::FromStr,
+};
 
-use serde::ser::{
-    Serialize,
-    Serializer,
+use serde::{
+    de::{
+        self,
+        Deserialize,
+        Deserializer,
+    },
+    ser::{
+        Serialize,
+        Serializer,
+    },
 };
 use serde_json::{
+    self,
     Map,
     Value,
 };
 
+use crate::{
+    error::{
+        self,
+        Error,
+    },
+    types::document::{
+        DocumentType,
+        StaticType,
+        DEFAULT_DOC_TYPE,
+    },
+};
+
 /** Update an indexed document using a new document. */
 #[derive(Serialize)]
 pub struct Doc<TDocument> {
@@ -199,3 +223,172 @@ impl<'a> From<&'a str> for ScriptBuilder<DefaultParams> {
         ScriptBuilder::new(source)
     }
 }
+
+/**
+Settings for an index.
+
+`IndexSettings` can be used when creating an index, updating the settings of an existing index, or in an index template.
+It's also returned when getting the settings of an index.
+
+Only some commonly used settings have their own fields.
+Any other settings can be set in `other`.
+
+# Examples
+
+Create settings for an index with a custom analyzer:
+
+```
+# #[macro_use] extern crate serde_json;
+# use elastic::prelude::*;
+# fn main() {
+let mut settings = IndexSettings {
+    number_of_shards: Some(3),
+    number_of_replicas: Some(1),
+    refresh_interval: Some("30s".to_owned()),
+    ..Default::default()
+};
+
+settings.analysis.analyzer.insert("lowercase_keyword".to_owned(), json!({
+    "type": "custom",
+    "tokenizer": "keyword",
+    "filter": ["lowercase"]
+}));
+# }
+```
+*/
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct IndexSettings {
+    /**
+    The number of primary shards.
+    This can only be set when an index is created.
+    */
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_setting"
+    )]
+    pub number_of_shards: Option<u32>,
+    /** The number of replicas each primary shard has. */
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_setting"
+    )]
+    pub number_of_replicas: Option<u32>,
+    /**
+    How often to make recent changes visible to search, like `1s`.
+    Use `-1` to disable refreshing.
+    */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_interval: Option<String>,
+    /** The maximum value of `from + size` for searches. */
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_setting"
+    )]
+    pub max_result_window: Option<u64>,
+    /**
+    Custom analyzers, tokenizers, filters and normalizers.
+    Analysis can only be changed on a closed index.
+    */
+    #[serde(default, skip_serializing_if = "Analysis::is_empty")]
+    pub analysis: Analysis,
+    /**
+    Any other settings.
+
+    Settings returned by Elasticsearch can include values like `uuid` and `creation_date` that can't be updated.
+    */
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/**
+The analysis settings for an index.
+
+Each component is a map from its name to its definition.
+*/
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct Analysis {
+    /** Custom analyzers. */
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub analyzer: Map<String, Value>,
+    /** Custom tokenizers. */
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub tokenizer: Map<String, Value>,
+    /** Custom token filters. */
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub filter: Map<String, Value>,
+    /** Custom character filters. */
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub char_filter: Map<String, Value>,
+    /** Custom normalizers for keyword fields. */
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub normalizer: Map<String, Value>,
+}
+
+impl Analysis {
+    /** Whether or not there are any custom analysis components. */
+    pub fn is_empty(&self) -> bool {
+        self.analyzer.is_empty()
+            && self.tokenizer.is_empty()
+            && self.filter.is_empty()
+            && self.char_filter.is_empty()
+            && self.normalizer.is_empty()
+    }
+}
+
+/**
+Deserialize a numeric setting.
+
+Elasticsearch returns all settings as strings, so a setting may be either a number or a string containing a number.
+*/
+fn deserialize_setting<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + FromStr,
+    T::Err: fmt::Display,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Setting<T> {
+        Value(T),
+        String(String),
+    }
+
+    match Option::<Setting<T>>::deserialize(deserializer)? {
+        Some(Setting::Value(value)) => Ok(Some(value)),
+        Some(Setting::String(value)) => value.parse().map(Some).map_err(de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+/**
+Get the mappings for a document type in the form expected by an index or template.
+
+Mappings for a document type other than `_doc` are nested under the type name.
+*/
+pub(crate) fn document_mappings<TDocument>() -> Result<Value, Error>
+where
+    TDocument: DocumentType + StaticType,
+{
+    let mapping = serde_json::to_value(TDocument::index_mapping()).map_err(error::request)?;
+    let ty = TDocument::static_ty();
+
+    if &ty[..] == DEFAULT_DOC_TYPE {
+        Ok(mapping)
+    } else {
+        let mut mappings = Map::new();
+        mappings.insert(ty.to_string(), mapping);
+
+        Ok(Value::Object(mappings))
+    }
+}
+
+/** Whether or not the mappings for a document type are nested under its type name. */
+pub(crate) fn includes_type_name<TDocument>() -> bool
+where
+    TDocument: StaticType,
+{
+    &TDocument::static_ty()[..] != DEFAULT_DOC_TYPE
+}
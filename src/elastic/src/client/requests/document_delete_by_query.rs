@@ -0,0 +1,488 @@
+/*!
+Builders for [delete by query requests][docs-delete-by-query].
+
+[docs-delete-by-query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-delete-by-query.html
+*/
+
+use futures::Future;
+use serde_json::Value;
+use std::marker::PhantomData;
+
+use crate::{
+    client::{
+        requests::{
+            raw::RawRequestInner,
+            task::{
+                TaskHandle,
+                TaskKind,
+            },
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::DeleteByQueryResponse,
+        DocumentClient,
+    },
+    endpoints::DeleteByQueryRequest,
+    error::Error,
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::{
+        Index,
+        Type,
+    },
+    types::document::{
+        DocumentType,
+        StaticIndex,
+        StaticType,
+        DEFAULT_DOC_TYPE,
+    },
+};
+
+/**
+A [delete by query request][docs-delete-by-query] builder that can be configured before sending.
+
+Call [`Client.document.delete_by_query`][Client.document.delete_by_query] to get a `DeleteByQueryRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-delete-by-query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-delete-by-query.html
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.document.delete_by_query]: ../../struct.DocumentClient.html#delete-by-query-request
+*/
+pub type DeleteByQueryRequestBuilder<TSender, TDocument> =
+    RequestBuilder<TSender, DeleteByQueryRequestInner<TDocument>>;
+
+#[doc(hidden)]
+pub struct DeleteByQueryRequestInner<TDocument> {
+    index: Index<'static>,
+    ty: Type<'static>,
+    query: Value,
+    _marker: PhantomData<TDocument>,
+}
+
+/**
+# Delete by query request
+*/
+impl<TSender, TDocument> DocumentClient<TSender, TDocument>
+where
+    TSender: Sender,
+{
+    /**
+    Create a [`DeleteByQueryRequestBuilder`][DeleteByQueryRequestBuilder] with this `Client` that can be configured before sending.
+
+    For more details, see:
+
+    - [builder methods][builder-methods]
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Delete all [`DocumentType`][documents-mod]s called `MyType` with a `title` of `Old Title`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.document::<MyType>()
+                         .delete_by_query(json!({
+                             "match": {
+                                 "title": "Old Title"
+                             }
+                         }))
+                         .send()?;
+
+    println!("deleted {} documents", response.deleted());
+    # Ok(())
+    # }
+    ```
+
+    [DeleteByQueryRequestBuilder]: requests/document_delete_by_query/type.DeleteByQueryRequestBuilder.html
+    [builder-methods]: requests/document_delete_by_query/type.DeleteByQueryRequestBuilder.html#builder-methods
+    [send-sync]: requests/document_delete_by_query/type.DeleteByQueryRequestBuilder.html#send-synchronously
+    [send-async]: requests/document_delete_by_query/type.DeleteByQueryRequestBuilder.html#send-asynchronously
+    [documents-mod]: ../types/document/index.html
+    */
+    pub fn delete_by_query(
+        self,
+        query: impl Into<Value>,
+    ) -> DeleteByQueryRequestBuilder<TSender, TDocument>
+    where
+        TDocument: DocumentType + StaticIndex + StaticType,
+    {
+        let index = TDocument::static_index();
+        let ty = TDocument::static_ty();
+
+        RequestBuilder::initial(
+            self.inner,
+            DeleteByQueryRequestInner {
+                index,
+                ty,
+                query: query.into(),
+                _marker: PhantomData,
+            },
+        )
+    }
+}
+
+impl<TDocument> DeleteByQueryRequestInner<TDocument> {
+    fn into_request(self) -> DeleteByQueryRequest<'static, Value> {
+        let body = json!({ "query": self.query });
+
+        if &self.ty[..] == DEFAULT_DOC_TYPE {
+            DeleteByQueryRequest::for_index(self.index, body)
+        } else {
+            DeleteByQueryRequest::for_index_ty(self.index, self.ty, body)
+        }
+    }
+}
+
+/**
+# Builder methods
+
+Configure a `DeleteByQueryRequestBuilder` before sending it.
+*/
+impl<TSender, TDocument> DeleteByQueryRequestBuilder<TSender, TDocument>
+where
+    TSender: Sender,
+{
+    /** Set the index for the delete by query request. */
+    pub fn index(mut self, index: impl Into<Index<'static>>) -> Self {
+        self.inner.index = index.into();
+        self
+    }
+
+    /** Set the type for the delete by query request. */
+    pub fn ty(mut self, ty: impl Into<Type<'static>>) -> Self {
+        self.inner.ty = ty.into();
+        self
+    }
+
+    /**
+    Continue deleting documents when there are version conflicts.
+
+    By default the request is aborted on the first version conflict.
+    */
+    pub fn proceed_on_conflicts(mut self) -> Self {
+        self.params_builder = self
+            .params_builder
+            .fluent(|params| params.url_param("conflicts", "proceed"))
+            .shared();
+        self
+    }
+
+    /** Throttle the request to the given number of requests per second. */
+    pub fn requests_per_second(mut self, requests_per_second: f32) -> Self {
+        self.params_builder = self
+            .params_builder
+            .fluent(move |params| params.url_param("requests_per_second", requests_per_second))
+            .shared();
+        self
+    }
+
+    /**
+    Split the request into the given number of slices that run in parallel.
+
+    Use `0` to let Elasticsearch choose the number of slices.
+    */
+    pub fn slices(mut self, slices: u32) -> Self {
+        self.params_builder = self
+            .params_builder
+            .fluent(move |params| match slices {
+                0 => params.url_param("slices", "auto"),
+                slices => params.url_param("slices", slices),
+            })
+            .shared();
+        self
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl<TDocument> DeleteByQueryRequestBuilder<SyncSender, TDocument> {
+    /**
+    Send a `DeleteByQueryRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until all matching documents have been deleted.
+
+    # Examples
+
+    Delete all [`DocumentType`][documents-mod]s called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.document::<MyType>()
+                         .delete_by_query(json!({ "match_all": {} }))
+                         .send()?;
+
+    assert!(response.is_ok());
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    [documents-mod]: ../types/document/index.html
+    */
+    pub fn send(self) -> Result<DeleteByQueryResponse, Error> {
+        let req = self.inner.into_request();
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+
+    /**
+    Send a `DeleteByQueryRequestBuilder` synchronously without waiting for it to complete.
+
+    The request is sent with `wait_for_completion=false` and runs as a task in the background.
+    The returned [`TaskHandle`][TaskHandle] can be used to check the progress of the task or wait for it to complete.
+
+    # Examples
+
+    Delete all [`DocumentType`][documents-mod]s called `MyType` in the background:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let task = client.document::<MyType>()
+                     .delete_by_query(json!({ "match_all": {} }))
+                     .send_task()?;
+
+    let response = task.wait()?;
+
+    assert!(response.is_ok());
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    [TaskHandle]: ../task/struct.TaskHandle.html
+    [documents-mod]: ../types/document/index.html
+    */
+    pub fn send_task(self) -> Result<TaskHandle<SyncSender, DeleteByQueryResponse>, Error> {
+        let req = self.inner.into_request();
+        let client = self.client.clone();
+
+        let params_builder = self
+            .params_builder
+            .fluent(|params| params.url_param("wait_for_completion", false))
+            .shared();
+
+        let started = RequestBuilder::new(self.client, params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()?;
+
+        Ok(TaskHandle::new(client, started, TaskKind::DeleteByQuery))
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl<TDocument> DeleteByQueryRequestBuilder<AsyncSender, TDocument> {
+    /**
+    Send a `DeleteByQueryRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised delete by query response once all matching documents have been deleted.
+
+    # Examples
+
+    Delete all [`DocumentType`][documents-mod]s called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.document::<MyType>()
+                       .delete_by_query(json!({ "match_all": {} }))
+                       .send();
+
+    future.and_then(|response| {
+        assert!(response.is_ok());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    [documents-mod]: ../types/document/index.html
+    */
+    pub fn send(self) -> Pending {
+        let req = self.inner.into_request();
+
+        let res_future =
+            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response());
+
+        Pending::new(res_future)
+    }
+
+    /**
+    Send a `DeleteByQueryRequestBuilder` asynchronously without waiting for it to complete.
+
+    The request is sent with `wait_for_completion=false` and runs as a task in the background.
+    The returned future resolves to a [`TaskHandle`][TaskHandle] that can be used to check the progress of the task or wait for it to complete.
+
+    # Examples
+
+    Delete all [`DocumentType`][documents-mod]s called `MyType` in the background:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.document::<MyType>()
+                       .delete_by_query(json!({ "match_all": {} }))
+                       .send_task()
+                       .and_then(|task| task.wait());
+
+    future.and_then(|response| {
+        assert!(response.is_ok());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    [TaskHandle]: ../task/struct.TaskHandle.html
+    [documents-mod]: ../types/document/index.html
+    */
+    pub fn send_task(self) -> PendingTask {
+        let req = self.inner.into_request();
+        let client = self.client.clone();
+
+        let params_builder = self
+            .params_builder
+            .fluent(|params| params.url_param("wait_for_completion", false))
+            .shared();
+
+        let res_future = RequestBuilder::new(self.client, params_builder, RawRequestInner::new(req))
+            .send()
+            .and_then(|res| res.into_response())
+            .map(move |started| TaskHandle::new(client, started, TaskKind::DeleteByQuery));
+
+        PendingTask::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<DeleteByQueryResponse>;
+
+/** A future returned by calling `send_task`. */
+pub type PendingTask = BasePending<TaskHandle<AsyncSender, DeleteByQueryResponse>>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+        assert_send::<super::PendingTask>();
+    }
+
+    #[derive(ElasticType)]
+    #[elastic(crate_root = "crate::types")]
+    struct TestDoc {}
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .delete_by_query(json!({ "match_all": {} }))
+            .inner
+            .into_request();
+
+        assert_eq!("/testdoc/_delete_by_query", req.url.as_ref());
+        assert_eq!(json!({ "query": { "match_all": {} } }), req.body);
+    }
+
+    #[test]
+    fn specify_index() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .delete_by_query(json!({ "match_all": {} }))
+            .index("new-idx")
+            .inner
+            .into_request();
+
+        assert_eq!("/new-idx/_delete_by_query", req.url.as_ref());
+    }
+
+    #[test]
+    fn specify_ty() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .delete_by_query(json!({ "match_all": {} }))
+            .ty("new-ty")
+            .inner
+            .into_request();
+
+        assert_eq!("/testdoc/new-ty/_delete_by_query", req.url.as_ref());
+    }
+
+    #[test]
+    fn specify_proceed_on_conflicts() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let params = client
+            .document::<TestDoc>()
+            .delete_by_query(json!({ "match_all": {} }))
+            .proceed_on_conflicts()
+            .params_builder
+            .into_value(|| RequestParams::new("http://eshost:9200"));
+
+        assert_eq!(
+            Some(String::from("?conflicts=proceed")),
+            params.get_url_qry().1
+        );
+    }
+}
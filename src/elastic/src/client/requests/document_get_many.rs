@@ -0,0 +1,532 @@
+/*!
+Builders for [multi get document requests][docs-mget].
+
+[docs-mget]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-multi-get.html
+*/
+
+use futures::Future;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::marker::PhantomData;
+
+use crate::{
+    client::{
+        requests::{
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::GetManyResponse,
+        DocumentClient,
+    },
+    endpoints::MgetRequest,
+    error::Error,
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::{
+        Id,
+        Index,
+        Type,
+    },
+    types::document::{
+        DocumentType,
+        StaticIndex,
+        StaticType,
+        DEFAULT_DOC_TYPE,
+    },
+};
+
+/**
+A [multi get document request][docs-mget] builder that can be configured before sending.
+
+Call [`Client.document.get_many`][Client.document.get_many] to get a `GetManyRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-mget]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-multi-get.html
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.document.get_many]: ../../struct.DocumentClient.html#multi-get-document-request
+*/
+pub type GetManyRequestBuilder<TSender, TDocument> =
+    RequestBuilder<TSender, GetManyRequestInner<TDocument>>;
+
+#[doc(hidden)]
+pub struct GetManyRequestInner<TDocument> {
+    index: Index<'static>,
+    ty: Type<'static>,
+    ids: Vec<Id<'static>>,
+    source: Source,
+    _marker: PhantomData<TDocument>,
+}
+
+enum Source {
+    All,
+    None,
+    Filter {
+        includes: Vec<String>,
+        excludes: Vec<String>,
+    },
+}
+
+/**
+# Multi get document request
+*/
+impl<TSender, TDocument> DocumentClient<TSender, TDocument>
+where
+    TSender: Sender,
+{
+    /**
+    Create a [`GetManyRequestBuilder`][GetManyRequestBuilder] with this `Client` that can be configured before sending.
+
+    All documents are fetched in a single request.
+
+    For more details, see:
+
+    - [builder methods][builder-methods]
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Get some [`DocumentType`][documents-mod]s called `MyType` with ids of `1`, `2` and `3`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.document::<MyType>()
+                         .get_many(vec![1, 2, 3])
+                         .send()?;
+    # Ok(())
+    # }
+    ```
+
+    For more details on document types, see the [`types`][types-mod] module.
+
+    [GetManyRequestBuilder]: requests/document_get_many/type.GetManyRequestBuilder.html
+    [builder-methods]: requests/document_get_many/type.GetManyRequestBuilder.html#builder-methods
+    [send-sync]: requests/document_get_many/type.GetManyRequestBuilder.html#send-synchronously
+    [send-async]: requests/document_get_many/type.GetManyRequestBuilder.html#send-asynchronously
+    [types-mod]: ../types/index.html
+    [documents-mod]: ../types/document/index.html
+    */
+    pub fn get_many<TIter>(self, ids: TIter) -> GetManyRequestBuilder<TSender, TDocument>
+    where
+        TDocument: DeserializeOwned + DocumentType + StaticIndex + StaticType,
+        TIter: IntoIterator,
+        TIter::Item: Into<Id<'static>>,
+    {
+        let index = TDocument::static_index();
+        let ty = TDocument::static_ty();
+
+        RequestBuilder::initial(self.inner, GetManyRequestInner::new(index, ty, ids))
+    }
+
+    /**
+    Create a [`GetManyRequestBuilder`][GetManyRequestBuilder] with this `Client` that can be configured before sending.
+
+    For more details, see:
+
+    - [builder methods][builder-methods]
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Get some documents as `serde_json::Value`s:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.document::<Value>()
+                         .get_many_raw("myindex", vec![1, 2, 3])
+                         .send()?;
+    # Ok(())
+    # }
+    ```
+
+    [GetManyRequestBuilder]: requests/document_get_many/type.GetManyRequestBuilder.html
+    [builder-methods]: requests/document_get_many/type.GetManyRequestBuilder.html#builder-methods
+    [send-sync]: requests/document_get_many/type.GetManyRequestBuilder.html#send-synchronously
+    [send-async]: requests/document_get_many/type.GetManyRequestBuilder.html#send-asynchronously
+    */
+    pub fn get_many_raw<TIter>(
+        self,
+        index: impl Into<Index<'static>>,
+        ids: TIter,
+    ) -> GetManyRequestBuilder<TSender, TDocument>
+    where
+        TDocument: DeserializeOwned,
+        TIter: IntoIterator,
+        TIter::Item: Into<Id<'static>>,
+    {
+        RequestBuilder::initial(
+            self.inner,
+            GetManyRequestInner::new(index.into(), DEFAULT_DOC_TYPE.into(), ids),
+        )
+    }
+}
+
+impl<TDocument> GetManyRequestInner<TDocument> {
+    fn new<TIter>(index: Index<'static>, ty: Type<'static>, ids: TIter) -> Self
+    where
+        TIter: IntoIterator,
+        TIter::Item: Into<Id<'static>>,
+    {
+        GetManyRequestInner {
+            index,
+            ty,
+            ids: ids.into_iter().map(Into::into).collect(),
+            source: Source::All,
+            _marker: PhantomData,
+        }
+    }
+
+    fn into_request(self) -> MgetRequest<'static, Value> {
+        let source = match self.source {
+            Source::All => None,
+            Source::None => Some(Value::Bool(false)),
+            Source::Filter { includes, excludes } => Some(json!({
+                "includes": includes,
+                "excludes": excludes
+            })),
+        };
+
+        let docs = self
+            .ids
+            .iter()
+            .map(|id| {
+                let mut doc = json!({ "_id": &id[..] });
+
+                if let Some(ref source) = source {
+                    doc["_source"] = source.clone();
+                }
+
+                doc
+            })
+            .collect::<Vec<_>>();
+
+        let body = json!({ "docs": docs });
+
+        if &self.ty[..] == DEFAULT_DOC_TYPE {
+            MgetRequest::for_index(self.index, body)
+        } else {
+            MgetRequest::for_index_ty(self.index, self.ty, body)
+        }
+    }
+}
+
+/**
+# Builder methods
+
+Configure a `GetManyRequestBuilder` before sending it.
+*/
+impl<TSender, TDocument> GetManyRequestBuilder<TSender, TDocument>
+where
+    TSender: Sender,
+{
+    /** Set the index for the multi get request. */
+    pub fn index(mut self, index: impl Into<Index<'static>>) -> Self {
+        self.inner.index = index.into();
+        self
+    }
+
+    /** Set the type for the multi get request. */
+    pub fn ty(mut self, ty: impl Into<Type<'static>>) -> Self {
+        self.inner.ty = ty.into();
+        self
+    }
+
+    /**
+    Set whether or not to return the source of the documents.
+
+    If the source isn't returned then the documents in the response will be empty, but their `found` state will still be set.
+    */
+    pub fn source(mut self, source: bool) -> Self {
+        self.inner.source = if source { Source::All } else { Source::None };
+        self
+    }
+
+    /**
+    Only return the given fields from the source of the documents.
+
+    The fields may contain wildcards.
+    */
+    pub fn source_includes<TIter>(mut self, fields: TIter) -> Self
+    where
+        TIter: IntoIterator,
+        TIter::Item: Into<String>,
+    {
+        let fields = fields.into_iter().map(Into::into);
+
+        match self.inner.source {
+            Source::Filter {
+                ref mut includes, ..
+            } => includes.extend(fields),
+            _ => {
+                self.inner.source = Source::Filter {
+                    includes: fields.collect(),
+                    excludes: Vec::new(),
+                }
+            }
+        }
+        self
+    }
+
+    /**
+    Don't return the given fields from the source of the documents.
+
+    The fields may contain wildcards.
+    */
+    pub fn source_excludes<TIter>(mut self, fields: TIter) -> Self
+    where
+        TIter: IntoIterator,
+        TIter::Item: Into<String>,
+    {
+        let fields = fields.into_iter().map(Into::into);
+
+        match self.inner.source {
+            Source::Filter {
+                ref mut excludes, ..
+            } => excludes.extend(fields),
+            _ => {
+                self.inner.source = Source::Filter {
+                    includes: Vec::new(),
+                    excludes: fields.collect(),
+                }
+            }
+        }
+        self
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl<TDocument> GetManyRequestBuilder<SyncSender, TDocument>
+where
+    TDocument: DeserializeOwned,
+{
+    /**
+    Send a `GetManyRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    Get some [`DocumentType`][documents-mod]s called `MyType` with ids of `1`, `2` and `3`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # #[derive(Debug, ElasticType, Deserialize)]
+    # struct MyType { }
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.document::<MyType>()
+                         .get_many(vec![1, 2, 3])
+                         .send()?;
+
+    // Documents are returned in the same order as their ids
+    for doc in response {
+        let doc = doc?;
+
+        if doc.found() {
+            println!("{:?}", doc.document());
+        }
+    }
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    [documents-mod]: ../types/document/index.html
+    */
+    pub fn send(self) -> Result<GetManyResponse<TDocument>, Error> {
+        let req = self.inner.into_request();
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl<TDocument> GetManyRequestBuilder<AsyncSender, TDocument>
+where
+    TDocument: DeserializeOwned + Send + 'static,
+{
+    /**
+    Send a `GetManyRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised multi get document response.
+
+    # Examples
+
+    Get some [`DocumentType`][documents-mod]s called `MyType` with ids of `1`, `2` and `3`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use serde_json::Value;
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # #[derive(Debug, ElasticType, Deserialize)]
+    # struct MyType { }
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.document::<MyType>()
+                       .get_many(vec![1, 2, 3])
+                       .send();
+
+    future.and_then(|response| {
+        for doc in response.documents() {
+            println!("{:?}", doc);
+        }
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    [documents-mod]: ../types/document/index.html
+    */
+    pub fn send(self) -> Pending<TDocument> {
+        let req = self.inner.into_request();
+
+        let res_future =
+            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response());
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending<TDocument> = BasePending<GetManyResponse<TDocument>>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending<TestDoc>>();
+    }
+
+    #[derive(Deserialize, ElasticType)]
+    #[elastic(crate_root = "crate::types")]
+    struct TestDoc {}
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .get_many(vec!["1", "2"])
+            .inner
+            .into_request();
+
+        assert_eq!("/testdoc/_mget", req.url.as_ref());
+        assert_eq!(
+            json!({ "docs": [{ "_id": "1" }, { "_id": "2" }] }),
+            req.body
+        );
+    }
+
+    #[test]
+    fn specify_index() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .get_many(vec!["1"])
+            .index("new-idx")
+            .inner
+            .into_request();
+
+        assert_eq!("/new-idx/_mget", req.url.as_ref());
+    }
+
+    #[test]
+    fn specify_ty() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .get_many(vec!["1"])
+            .ty("new-ty")
+            .inner
+            .into_request();
+
+        assert_eq!("/testdoc/new-ty/_mget", req.url.as_ref());
+    }
+
+    #[test]
+    fn specify_no_source() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .get_many(vec!["1"])
+            .source(false)
+            .inner
+            .into_request();
+
+        assert_eq!(
+            json!({ "docs": [{ "_id": "1", "_source": false }] }),
+            req.body
+        );
+    }
+
+    #[test]
+    fn specify_source_filter() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .get_many(vec!["1"])
+            .source_includes(vec!["a", "b.*"])
+            .source_excludes(vec!["b.c"])
+            .inner
+            .into_request();
+
+        assert_eq!(
+            json!({
+                "docs": [{
+                    "_id": "1",
+                    "_source": {
+                        "includes": ["a", "b.*"],
+                        "excludes": ["b.c"]
+                    }
+                }]
+            }),
+            req.body
+        );
+    }
+}
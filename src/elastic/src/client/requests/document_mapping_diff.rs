@@ -0,0 +1,410 @@
+/*!
+Builders for mapping diff requests.
+
+A mapping diff compares the mapping for a [`DocumentType`][documents-mod] with the [live mapping][docs-get-mapping] of an index.
+
+[docs-get-mapping]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-get-mapping.html
+[documents-mod]: ../../../types/document/index.html
+*/
+
+use futures::Future;
+use serde_json::{
+    self,
+    Value,
+};
+use std::{
+    collections::BTreeMap,
+    marker::PhantomData,
+};
+
+use crate::{
+    client::{
+        requests::{
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::{
+            document_mapping_diff::MappingDiff,
+            MappingDiffResponse,
+        },
+        DocumentClient,
+    },
+    endpoints::IndicesGetMappingRequest,
+    error::{
+        self,
+        Error,
+    },
+    http::{
+        receiver::{
+            HttpResponseHead,
+            IsOk,
+            MaybeOkResponse,
+            ParseError,
+            ResponseBody,
+            Unbuffered,
+        },
+        sender::{
+            AsyncSender,
+            Sender,
+            SyncSender,
+        },
+        StatusCode,
+    },
+    params::{
+        Index,
+        Type,
+    },
+    types::document::{
+        DocumentType,
+        StaticIndex,
+        StaticType,
+    },
+};
+
+/**
+A mapping diff request builder that can be configured before sending.
+
+Call [`Client.document.mapping_diff`][Client.document.mapping_diff] to get a `MappingDiffRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.document.mapping_diff]: ../../struct.DocumentClient.html#mapping-diff-request
+*/
+pub type MappingDiffRequestBuilder<TSender, TDocument> =
+    RequestBuilder<TSender, MappingDiffRequestInner<TDocument>>;
+
+#[doc(hidden)]
+pub struct MappingDiffRequestInner<TDocument> {
+    index: Index<'static>,
+    ty: Type<'static>,
+    _marker: PhantomData<TDocument>,
+}
+
+/**
+# Mapping diff request
+*/
+impl<TSender, TDocument> DocumentClient<TSender, TDocument>
+where
+    TSender: Sender,
+{
+    /**
+    Create a [`MappingDiffRequestBuilder`][MappingDiffRequestBuilder] with this `Client` that can be configured before sending.
+
+    The request fetches the live mapping of the index and compares it with the mapping for the [`DocumentType`][documents-mod].
+    The diff reports fields that were added, removed or changed type.
+
+    For more details, see:
+
+    - [builder methods][builder-methods]
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Check the document mapping for a [`DocumentType`][documents-mod] called `MyType` can be applied to its index:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.document::<MyType>()
+                         .mapping_diff()
+                         .send()?;
+
+    for (index, diff) in response.iter() {
+        for change in diff.changes() {
+            println!("{}: {:?}", index, change);
+        }
+    }
+
+    assert!(response.is_compatible());
+    # Ok(())
+    # }
+    ```
+
+    [MappingDiffRequestBuilder]: requests/document_mapping_diff/type.MappingDiffRequestBuilder.html
+    [builder-methods]: requests/document_mapping_diff/type.MappingDiffRequestBuilder.html#builder-methods
+    [send-sync]: requests/document_mapping_diff/type.MappingDiffRequestBuilder.html#send-synchronously
+    [send-async]: requests/document_mapping_diff/type.MappingDiffRequestBuilder.html#send-asynchronously
+    [documents-mod]: ../types/document/index.html
+    */
+    pub fn mapping_diff(self) -> MappingDiffRequestBuilder<TSender, TDocument>
+    where
+        TDocument: DocumentType + StaticIndex + StaticType,
+    {
+        RequestBuilder::initial(
+            self.inner,
+            MappingDiffRequestInner::new(TDocument::static_index(), TDocument::static_ty()),
+        )
+    }
+}
+
+impl<TDocument> MappingDiffRequestInner<TDocument> {
+    pub(crate) fn new(index: Index<'static>, ty: Type<'static>) -> Self {
+        MappingDiffRequestInner {
+            index,
+            ty,
+            _marker: PhantomData,
+        }
+    }
+
+    fn to_request(&self) -> IndicesGetMappingRequest<'static> {
+        IndicesGetMappingRequest::for_index(self.index.clone())
+    }
+}
+
+impl<TDocument> MappingDiffRequestInner<TDocument>
+where
+    TDocument: DocumentType,
+{
+    fn into_response(self, live: LiveMappings) -> Result<MappingDiffResponse, Error> {
+        let local = serde_json::to_value(TDocument::index_mapping()).map_err(error::request)?;
+        let ty: &str = self.ty.as_ref();
+
+        let diffs = live
+            .0
+            .into_iter()
+            .map(|(index, live)| {
+                let diff = MappingDiff::new(document_mapping(&live.mappings, ty), &local);
+
+                (index, diff)
+            })
+            .collect();
+
+        Ok(MappingDiffResponse::new(diffs))
+    }
+}
+
+/**
+Get the mapping for a document type from the mappings of an index.
+
+The mappings may or may not be nested under the type name, depending on the version of Elasticsearch.
+*/
+fn document_mapping<'a>(mappings: &'a Value, ty: &str) -> &'a Value {
+    match mappings.get(ty) {
+        Some(mapping) if mapping.is_object() => mapping,
+        _ => mappings,
+    }
+}
+
+/** The live mappings for each index. */
+#[derive(Deserialize)]
+struct LiveMappings(BTreeMap<String, LiveIndexMappings>);
+
+#[derive(Deserialize)]
+struct LiveIndexMappings {
+    #[serde(default)]
+    mappings: Value,
+}
+
+impl IsOk for LiveMappings {
+    fn is_ok<B: ResponseBody>(
+        head: HttpResponseHead,
+        body: Unbuffered<B>,
+    ) -> Result<MaybeOkResponse<B>, ParseError> {
+        match head.status() {
+            status if status.is_success() => Ok(MaybeOkResponse::ok(body)),
+            StatusCode::NOT_FOUND => Ok(MaybeOkResponse::ok(json!({}))),
+            _ => Ok(MaybeOkResponse::err(body)),
+        }
+    }
+}
+
+/**
+# Builder methods
+
+Configure a `MappingDiffRequestBuilder` before sending it.
+*/
+impl<TSender, TDocument> MappingDiffRequestBuilder<TSender, TDocument>
+where
+    TSender: Sender,
+{
+    /** Set the index for the mapping diff request. */
+    pub fn index(mut self, index: impl Into<Index<'static>>) -> Self {
+        self.inner.index = index.into();
+        self
+    }
+
+    /** Set the type for the mapping diff request. */
+    pub fn ty(mut self, ty: impl Into<Type<'static>>) -> Self {
+        self.inner.ty = ty.into();
+        self
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl<TDocument> MappingDiffRequestBuilder<SyncSender, TDocument>
+where
+    TDocument: DocumentType,
+{
+    /**
+    Send a `MappingDiffRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is compared with the document mapping.
+
+    # Examples
+
+    Diff the mapping for a document type called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.document::<MyType>()
+                         .mapping_diff()
+                         .send()?;
+
+    assert!(response.is_compatible());
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<MappingDiffResponse, Error> {
+        let req = self.inner.to_request();
+
+        let live = RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response::<LiveMappings>()?;
+
+        self.inner.into_response(live)
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl<TDocument> MappingDiffRequestBuilder<AsyncSender, TDocument>
+where
+    TDocument: DocumentType + Send + 'static,
+{
+    /**
+    Send a `MappingDiffRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the mapping diff.
+
+    # Examples
+
+    Diff the mapping for a document type called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.document::<MyType>()
+                       .mapping_diff()
+                       .send();
+
+    future.and_then(|response| {
+        assert!(response.is_compatible());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending {
+        let (client, params_builder, inner) = (self.client, self.params_builder, self.inner);
+
+        let req = inner.to_request();
+        let sender = client.sender.clone();
+
+        let res_future = RequestBuilder::new(client, params_builder, RawRequestInner::new(req))
+            .send()
+            .and_then(|res| res.into_response::<LiveMappings>())
+            .and_then(move |live| sender.maybe_async(move || inner.into_response(live)));
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<MappingDiffResponse>;
+
+#[cfg(test)]
+mod tests {
+    use super::LiveMappings;
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+    use serde_json;
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+    }
+
+    #[derive(ElasticType)]
+    #[elastic(crate_root = "crate::types")]
+    struct TestDoc {
+        #[allow(dead_code)]
+        title: String,
+    }
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .mapping_diff()
+            .inner
+            .to_request();
+
+        assert_eq!("/testdoc/_mapping", req.url.as_ref());
+    }
+
+    #[test]
+    fn diff_typed_live_mapping() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let live: LiveMappings = serde_json::from_value(json!({
+            "testdoc": {
+                "mappings": {
+                    "_doc": {
+                        "properties": {
+                            "title": { "type": "keyword" },
+                            "created": { "type": "date" }
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        let res = client
+            .document::<TestDoc>()
+            .mapping_diff()
+            .inner
+            .into_response(live)
+            .unwrap();
+
+        let diff = res.get("testdoc").unwrap();
+
+        assert_eq!(1, diff.removed().count());
+        assert_eq!(1, diff.type_changed().count());
+        assert!(!res.is_compatible());
+    }
+}
@@ -4,18 +4,26 @@ Builders for [put mapping requests][docs-mapping].
 [docs-mapping]: https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping.html
 */
 
-use futures::Future;
+use futures::{
+    future::Either,
+    Future,
+    IntoFuture,
+};
 use serde_json;
 use std::marker::PhantomData;
 
 use crate::{
     client::{
         requests::{
+            document_mapping_diff::MappingDiffRequestInner,
             raw::RawRequestInner,
             Pending as BasePending,
             RequestBuilder,
         },
-        responses::CommandResponse,
+        responses::{
+            CommandResponse,
+            MappingDiffResponse,
+        },
         DocumentClient,
     },
     endpoints::IndicesPutMappingRequest,
@@ -58,6 +66,7 @@ pub type PutMappingRequestBuilder<TSender, TDocument> =
 pub struct PutMappingRequestInner<TDocument> {
     index: Index<'static>,
     ty: Type<'static>,
+    only_additive: bool,
     _marker: PhantomData<TDocument>,
 }
 
@@ -71,6 +80,9 @@ where
     /**
     Create a [`PutMappingRequestBuilder`][PutMappingRequestBuilder] with this `Client` that can be configured before sending.
 
+    The whole mapping for the document type is sent, so the request fails if it conflicts with the live mapping of the index.
+    Call [`only_additive`][only_additive] to only send the fields that can be added in place.
+
     For more details, see:
 
     - [builder methods][builder-methods]
@@ -106,6 +118,7 @@ where
     [send-async]: requests/document_put_mapping/type.PutMappingRequestBuilder.html#send-asynchronously
     [types-mod]: ../types/index.html
     [documents-mod]: ../types/document/index.html
+    [only_additive]: requests/document_put_mapping/type.PutMappingRequestBuilder.html#method.only_additive
     */
     pub fn put_mapping(self) -> PutMappingRequestBuilder<TSender, TDocument>
     where
@@ -119,6 +132,7 @@ where
             PutMappingRequestInner {
                 index,
                 ty,
+                only_additive: false,
                 _marker: PhantomData,
             },
         )
@@ -129,8 +143,25 @@ impl<TDocument> PutMappingRequestInner<TDocument>
 where
     TDocument: DocumentType,
 {
-    fn into_request(self) -> Result<IndicesPutMappingRequest<'static, Vec<u8>>, Error> {
-        let body = serde_json::to_vec(&TDocument::index_mapping()).map_err(error::request)?;
+    fn mapping_diff(&self) -> MappingDiffRequestInner<TDocument> {
+        MappingDiffRequestInner::new(self.index.clone(), self.ty.clone())
+    }
+
+    /**
+    Get the put mapping request.
+
+    If there's a diff then just its additive changes are sent.
+    If the diff is empty then the index doesn't exist yet and the whole mapping is sent.
+    */
+    fn into_request(
+        self,
+        diff: Option<&MappingDiffResponse>,
+    ) -> Result<IndicesPutMappingRequest<'static, Vec<u8>>, Error> {
+        let body = match diff.and_then(MappingDiffResponse::additive_mapping) {
+            Some(mapping) => serde_json::to_vec(&mapping),
+            None => serde_json::to_vec(&TDocument::index_mapping()),
+        }
+        .map_err(error::request)?;
 
         if &self.ty[..] == DEFAULT_DOC_TYPE {
             Ok(IndicesPutMappingRequest::for_index(self.index, body))
@@ -162,6 +193,21 @@ where
         self.inner.ty = ty.into();
         self
     }
+
+    /**
+    Only put the fields that can be added to the live mapping of the index in place.
+
+    The live mapping is [compared][Client.document.mapping_diff] with the mapping for the document type before it's updated.
+    Fields that have been removed or changed type are skipped instead of failing the request, so the response can be acknowledged even though the mapping was only partly applied.
+    Use [`mapping_diff`][Client.document.mapping_diff] to find changes that require the index to be [migrated][Client.index.migrate].
+
+    [Client.document.mapping_diff]: ../../struct.DocumentClient.html#mapping-diff-request
+    [Client.index.migrate]: ../../struct.IndexClient.html#migrate-index-request
+    */
+    pub fn only_additive(mut self) -> Self {
+        self.inner.only_additive = true;
+        self
+    }
 }
 
 /**
@@ -199,8 +245,17 @@ where
 
     [SyncClient]: ../../type.SyncClient.html
     */
-    pub fn send(self) -> Result<CommandResponse, Error> {
-        let req = self.inner.into_request()?;
+    pub fn send(mut self) -> Result<CommandResponse, Error> {
+        let diff = if self.inner.only_additive {
+            let diff_params = self.share_params();
+            let diff_req = self.inner.mapping_diff();
+
+            Some(RequestBuilder::new(self.client.clone(), diff_params, diff_req).send()?)
+        } else {
+            None
+        };
+
+        let req = self.inner.into_request(diff.as_ref())?;
 
         RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
             .send()?
@@ -248,15 +303,31 @@ where
 
     [AsyncClient]: ../../type.AsyncClient.html
     */
-    pub fn send(self) -> Pending {
-        let (client, params_builder, inner) = (self.client, self.params_builder, self.inner);
+    pub fn send(mut self) -> Pending {
+        let diff_future = if self.inner.only_additive {
+            let diff_params = self.share_params();
+            let diff_req = self.inner.mapping_diff();
+
+            Either::A(
+                RequestBuilder::new(self.client.clone(), diff_params, diff_req)
+                    .send()
+                    .map(Some),
+            )
+        } else {
+            Either::B(Ok(None).into_future())
+        };
 
-        let req_future = client.sender.maybe_async(move || inner.into_request());
+        let (client, params_builder, inner) = (self.client, self.params_builder, self.inner);
 
-        let res_future = req_future.and_then(move |req| {
-            RequestBuilder::new(client, params_builder, RawRequestInner::new(req))
-                .send()
-                .and_then(|res| res.into_response())
+        let res_future = diff_future.and_then(move |diff| {
+            client
+                .sender
+                .maybe_async(move || inner.into_request(diff.as_ref()))
+                .and_then(move |req| {
+                    RequestBuilder::new(client, params_builder, RawRequestInner::new(req))
+                        .send()
+                        .and_then(|res| res.into_response())
+                })
         });
 
         Pending::new(res_future)
@@ -269,6 +340,20 @@ pub type Pending = BasePending<CommandResponse>;
 #[cfg(test)]
 mod tests {
     use crate::{
+        client::responses::document_mapping_diff::MappingDiff,
+        http::{
+            header::{
+                HeaderValue,
+                AUTHORIZATION,
+            },
+            sender::{
+                MockRequest,
+                MockResponse,
+                MockTransport,
+            },
+            Method,
+            StatusCode,
+        },
         prelude::*,
         tests::*,
     };
@@ -276,6 +361,7 @@ mod tests {
         self,
         Value,
     };
+    use std::collections::BTreeMap;
 
     #[test]
     fn is_send() {
@@ -294,7 +380,7 @@ mod tests {
             .document::<TestDoc>()
             .put_mapping()
             .inner
-            .into_request()
+            .into_request(None)
             .unwrap();
 
         let expected_body = json!({
@@ -318,7 +404,7 @@ mod tests {
             .put_mapping()
             .index("new-idx")
             .inner
-            .into_request()
+            .into_request(None)
             .unwrap();
 
         assert_eq!("/new-idx/_mapping", req.url.as_ref());
@@ -333,9 +419,98 @@ mod tests {
             .put_mapping()
             .ty("new-ty")
             .inner
-            .into_request()
+            .into_request(None)
             .unwrap();
 
         assert_eq!("/testdoc/_mappings/new-ty", req.url.as_ref());
     }
+
+    #[test]
+    fn only_additive_changes() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let live = json!({
+            "properties": {
+                "title": { "type": "keyword" },
+                "created": { "type": "date" }
+            }
+        });
+
+        let local = json!({
+            "properties": {
+                "title": { "type": "text" },
+                "body": { "type": "text" }
+            }
+        });
+
+        let mut indices = BTreeMap::new();
+        indices.insert("testdoc".to_owned(), MappingDiff::new(&live, &local));
+
+        let req = client
+            .document::<TestDoc>()
+            .put_mapping()
+            .inner
+            .into_request(Some(&MappingDiffResponse::new(indices)))
+            .unwrap();
+
+        let expected_body = json!({
+            "properties": {
+                "body": { "type": "text" }
+            }
+        });
+
+        let actual_body: Value = serde_json::from_slice(&req.body).unwrap();
+
+        assert_eq!(expected_body.to_string(), actual_body.to_string());
+    }
+
+    #[test]
+    fn mapping_diff_uses_request_params() {
+        let mock = MockTransport::new()
+            .respond(
+                MockRequest::new(Method::GET, "/testdoc/_mapping"),
+                MockResponse::new(StatusCode::OK).body(json!({
+                    "testdoc": {
+                        "mappings": {
+                            "properties": {}
+                        }
+                    }
+                })),
+            )
+            .respond(
+                MockRequest::new(Method::POST, "/testdoc/_mapping"),
+                MockResponse::new(StatusCode::OK).body(json!({ "acknowledged": true })),
+            );
+
+        let client = SyncClientBuilder::new().mock(mock.clone()).build().unwrap();
+
+        client
+            .document::<TestDoc>()
+            .put_mapping()
+            .only_additive()
+            .params_fluent(|p| p.header(AUTHORIZATION, HeaderValue::from_static("Bearer token")))
+            .send()
+            .unwrap();
+
+        let requests = mock.requests();
+
+        assert_eq!(2, requests.len());
+        for req in requests {
+            assert_eq!("Bearer token", req.headers()[AUTHORIZATION]);
+        }
+    }
+
+    #[test]
+    fn put_whole_mapping_by_default() {
+        let mock = MockTransport::new().respond(
+            MockRequest::new(Method::POST, "/testdoc/_mapping"),
+            MockResponse::new(StatusCode::OK).body(json!({ "acknowledged": true })),
+        );
+
+        let client = SyncClientBuilder::new().mock(mock.clone()).build().unwrap();
+
+        client.document::<TestDoc>().put_mapping().send().unwrap();
+
+        assert_eq!(1, mock.requests().len());
+    }
 }
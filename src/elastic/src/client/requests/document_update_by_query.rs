@@ -0,0 +1,577 @@
+/*!
+Builders for [update by query requests][docs-update-by-query].
+
+[docs-update-by-query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-update-by-query.html
+*/
+
+use futures::Future;
+use serde::ser::Serialize;
+use serde_json::Value;
+
+use crate::{
+    client::{
+        requests::{
+            common::{
+                Script,
+                ScriptBuilder,
+            },
+            raw::RawRequestInner,
+            task::{
+                TaskHandle,
+                TaskKind,
+            },
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::UpdateByQueryResponse,
+        DocumentClient,
+    },
+    endpoints::UpdateByQueryRequest,
+    error::{
+        self,
+        Error,
+    },
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::{
+        Index,
+        Type,
+    },
+    types::document::{
+        DocumentType,
+        StaticIndex,
+        StaticType,
+        DEFAULT_DOC_TYPE,
+    },
+};
+
+/**
+An [update by query request][docs-update-by-query] builder that can be configured before sending.
+
+Call [`Client.document.update_by_query`][Client.document.update_by_query] to get an `UpdateByQueryRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-update-by-query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-update-by-query.html
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.document.update_by_query]: ../../struct.DocumentClient.html#update-by-query-request
+*/
+pub type UpdateByQueryRequestBuilder<TSender, TParams> =
+    RequestBuilder<TSender, UpdateByQueryRequestInner<TParams>>;
+
+#[doc(hidden)]
+pub struct UpdateByQueryRequestInner<TParams> {
+    index: Index<'static>,
+    ty: Type<'static>,
+    body: UpdateByQueryBody<TParams>,
+}
+
+#[derive(Serialize)]
+struct UpdateByQueryBody<TParams> {
+    query: Value,
+    #[serde(flatten)]
+    script: Script<TParams>,
+}
+
+/**
+# Update by query request
+*/
+impl<TSender, TDocument> DocumentClient<TSender, TDocument>
+where
+    TSender: Sender,
+{
+    /**
+    Create an [`UpdateByQueryRequestBuilder`][UpdateByQueryRequestBuilder] with this `Client` that can be configured before sending.
+
+    Documents matching the query are updated using [an inline script][painless-lang].
+
+    For more details, see:
+
+    - [builder methods][builder-methods]
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Increment the `likes` of all [`DocumentType`][documents-mod]s called `MyType` with a `title` of `Title`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.document::<MyType>()
+                         .update_by_query(json!({
+                             "match": {
+                                 "title": "Title"
+                             }
+                         }), "ctx._source.likes++")
+                         .send()?;
+
+    println!("updated {} documents", response.updated());
+    # Ok(())
+    # }
+    ```
+
+    Scripts can be configured with parameters:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # use elastic::client::requests::common::ScriptBuilder;
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let script = ScriptBuilder::new("ctx._source.title = params.newTitle")
+        .param("newTitle", "New Title");
+
+    let response = client.document::<MyType>()
+                         .update_by_query(json!({ "match_all": {} }), script)
+                         .send()?;
+    # Ok(())
+    # }
+    ```
+
+    [UpdateByQueryRequestBuilder]: requests/document_update_by_query/type.UpdateByQueryRequestBuilder.html
+    [builder-methods]: requests/document_update_by_query/type.UpdateByQueryRequestBuilder.html#builder-methods
+    [send-sync]: requests/document_update_by_query/type.UpdateByQueryRequestBuilder.html#send-synchronously
+    [send-async]: requests/document_update_by_query/type.UpdateByQueryRequestBuilder.html#send-asynchronously
+    [documents-mod]: ../types/document/index.html
+    [painless-lang]: https://www.elastic.co/guide/en/elasticsearch/reference/current/modules-scripting-painless.html
+    */
+    pub fn update_by_query<TScript, TParams>(
+        self,
+        query: impl Into<Value>,
+        script: TScript,
+    ) -> UpdateByQueryRequestBuilder<TSender, TParams>
+    where
+        TDocument: DocumentType + StaticIndex + StaticType,
+        TScript: Into<ScriptBuilder<TParams>>,
+    {
+        let index = TDocument::static_index();
+        let ty = TDocument::static_ty();
+
+        RequestBuilder::initial(
+            self.inner,
+            UpdateByQueryRequestInner {
+                index,
+                ty,
+                body: UpdateByQueryBody {
+                    query: query.into(),
+                    script: script.into().build(),
+                },
+            },
+        )
+    }
+}
+
+impl<TParams> UpdateByQueryRequestInner<TParams>
+where
+    TParams: Serialize,
+{
+    fn into_request(self) -> Result<UpdateByQueryRequest<'static, Vec<u8>>, Error> {
+        let body = serde_json::to_vec(&self.body).map_err(error::request)?;
+
+        if &self.ty[..] == DEFAULT_DOC_TYPE {
+            Ok(UpdateByQueryRequest::for_index(self.index, body))
+        } else {
+            Ok(UpdateByQueryRequest::for_index_ty(self.index, self.ty, body))
+        }
+    }
+}
+
+/**
+# Builder methods
+
+Configure an `UpdateByQueryRequestBuilder` before sending it.
+*/
+impl<TSender, TParams> UpdateByQueryRequestBuilder<TSender, TParams>
+where
+    TSender: Sender,
+{
+    /** Set the index for the update by query request. */
+    pub fn index(mut self, index: impl Into<Index<'static>>) -> Self {
+        self.inner.index = index.into();
+        self
+    }
+
+    /** Set the type for the update by query request. */
+    pub fn ty(mut self, ty: impl Into<Type<'static>>) -> Self {
+        self.inner.ty = ty.into();
+        self
+    }
+
+    /**
+    Continue updating documents when there are version conflicts.
+
+    By default the request is aborted on the first version conflict.
+    */
+    pub fn proceed_on_conflicts(mut self) -> Self {
+        self.params_builder = self
+            .params_builder
+            .fluent(|params| params.url_param("conflicts", "proceed"))
+            .shared();
+        self
+    }
+
+    /** Throttle the request to the given number of requests per second. */
+    pub fn requests_per_second(mut self, requests_per_second: f32) -> Self {
+        self.params_builder = self
+            .params_builder
+            .fluent(move |params| params.url_param("requests_per_second", requests_per_second))
+            .shared();
+        self
+    }
+
+    /**
+    Split the request into the given number of slices that run in parallel.
+
+    Use `0` to let Elasticsearch choose the number of slices.
+    */
+    pub fn slices(mut self, slices: u32) -> Self {
+        self.params_builder = self
+            .params_builder
+            .fluent(move |params| match slices {
+                0 => params.url_param("slices", "auto"),
+                slices => params.url_param("slices", slices),
+            })
+            .shared();
+        self
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl<TParams> UpdateByQueryRequestBuilder<SyncSender, TParams>
+where
+    TParams: Serialize,
+{
+    /**
+    Send an `UpdateByQueryRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until all matching documents have been updated.
+
+    # Examples
+
+    Increment the `likes` of all [`DocumentType`][documents-mod]s called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.document::<MyType>()
+                         .update_by_query(json!({ "match_all": {} }), "ctx._source.likes++")
+                         .send()?;
+
+    assert!(response.is_ok());
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    [documents-mod]: ../types/document/index.html
+    */
+    pub fn send(self) -> Result<UpdateByQueryResponse, Error> {
+        let req = self.inner.into_request()?;
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+
+    /**
+    Send an `UpdateByQueryRequestBuilder` synchronously without waiting for it to complete.
+
+    The request is sent with `wait_for_completion=false` and runs as a task in the background.
+    The returned [`TaskHandle`][TaskHandle] can be used to check the progress of the task or wait for it to complete.
+
+    # Examples
+
+    Increment the `likes` of all [`DocumentType`][documents-mod]s called `MyType` in the background:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let task = client.document::<MyType>()
+                     .update_by_query(json!({ "match_all": {} }), "ctx._source.likes++")
+                     .send_task()?;
+
+    let response = task.wait()?;
+
+    assert!(response.is_ok());
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    [TaskHandle]: ../task/struct.TaskHandle.html
+    [documents-mod]: ../types/document/index.html
+    */
+    pub fn send_task(self) -> Result<TaskHandle<SyncSender, UpdateByQueryResponse>, Error> {
+        let req = self.inner.into_request()?;
+        let client = self.client.clone();
+
+        let params_builder = self
+            .params_builder
+            .fluent(|params| params.url_param("wait_for_completion", false))
+            .shared();
+
+        let started = RequestBuilder::new(self.client, params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()?;
+
+        Ok(TaskHandle::new(client, started, TaskKind::UpdateByQuery))
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl<TParams> UpdateByQueryRequestBuilder<AsyncSender, TParams>
+where
+    TParams: Serialize + Send + 'static,
+{
+    /**
+    Send an `UpdateByQueryRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised update by query response once all matching documents have been updated.
+
+    # Examples
+
+    Increment the `likes` of all [`DocumentType`][documents-mod]s called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.document::<MyType>()
+                       .update_by_query(json!({ "match_all": {} }), "ctx._source.likes++")
+                       .send();
+
+    future.and_then(|response| {
+        assert!(response.is_ok());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    [documents-mod]: ../types/document/index.html
+    */
+    pub fn send(self) -> Pending {
+        let (client, params_builder, inner) = (self.client, self.params_builder, self.inner);
+
+        let req_future = client.sender.maybe_async(move || inner.into_request());
+
+        let res_future = req_future.and_then(move |req| {
+            RequestBuilder::new(client, params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response())
+        });
+
+        Pending::new(res_future)
+    }
+
+    /**
+    Send an `UpdateByQueryRequestBuilder` asynchronously without waiting for it to complete.
+
+    The request is sent with `wait_for_completion=false` and runs as a task in the background.
+    The returned future resolves to a [`TaskHandle`][TaskHandle] that can be used to check the progress of the task or wait for it to complete.
+
+    # Examples
+
+    Increment the `likes` of all [`DocumentType`][documents-mod]s called `MyType` in the background:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.document::<MyType>()
+                       .update_by_query(json!({ "match_all": {} }), "ctx._source.likes++")
+                       .send_task()
+                       .and_then(|task| task.wait());
+
+    future.and_then(|response| {
+        assert!(response.is_ok());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    [TaskHandle]: ../task/struct.TaskHandle.html
+    [documents-mod]: ../types/document/index.html
+    */
+    pub fn send_task(self) -> PendingTask {
+        let (client, inner) = (self.client, self.inner);
+
+        let params_builder = self
+            .params_builder
+            .fluent(|params| params.url_param("wait_for_completion", false))
+            .shared();
+
+        let req_future = client.sender.maybe_async(move || inner.into_request());
+
+        let res_future = req_future.and_then(move |req| {
+            RequestBuilder::new(client.clone(), params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response())
+                .map(move |started| TaskHandle::new(client, started, TaskKind::UpdateByQuery))
+        });
+
+        PendingTask::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<UpdateByQueryResponse>;
+
+/** A future returned by calling `send_task`. */
+pub type PendingTask = BasePending<TaskHandle<AsyncSender, UpdateByQueryResponse>>;
+
+#[cfg(test)]
+mod tests {
+    use super::ScriptBuilder;
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+    use serde_json::{
+        self,
+        Value,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+        assert_send::<super::PendingTask>();
+    }
+
+    #[derive(ElasticType)]
+    #[elastic(crate_root = "crate::types")]
+    struct TestDoc {}
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .update_by_query(json!({ "match_all": {} }), "ctx._source.a++")
+            .inner
+            .into_request()
+            .unwrap();
+
+        let expected_body = json!({
+            "query": {
+                "match_all": {}
+            },
+            "script": {
+                "inline": "ctx._source.a++"
+            }
+        });
+
+        let actual_body: Value = serde_json::from_slice(&req.body).unwrap();
+
+        assert_eq!("/testdoc/_update_by_query", req.url.as_ref());
+        assert_eq!(expected_body.to_string(), actual_body.to_string());
+    }
+
+    #[test]
+    fn specify_script_params() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .update_by_query(
+                json!({ "match_all": {} }),
+                ScriptBuilder::new("ctx._source.a = params.str").param("str", "some value"),
+            )
+            .inner
+            .into_request()
+            .unwrap();
+
+        let expected_body = json!({
+            "query": {
+                "match_all": {}
+            },
+            "script": {
+                "inline": "ctx._source.a = params.str",
+                "params": {
+                    "str": "some value"
+                }
+            }
+        });
+
+        let actual_body: Value = serde_json::from_slice(&req.body).unwrap();
+
+        assert_eq!(expected_body.to_string(), actual_body.to_string());
+    }
+
+    #[test]
+    fn specify_index() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .update_by_query(json!({ "match_all": {} }), "ctx._source.a++")
+            .index("new-idx")
+            .inner
+            .into_request()
+            .unwrap();
+
+        assert_eq!("/new-idx/_update_by_query", req.url.as_ref());
+    }
+
+    #[test]
+    fn specify_slices() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let params = client
+            .document::<TestDoc>()
+            .update_by_query(json!({ "match_all": {} }), "ctx._source.a++")
+            .slices(0)
+            .params_builder
+            .into_value(|| RequestParams::new("http://eshost:9200"));
+
+        assert_eq!(Some(String::from("?slices=auto")), params.get_url_qry().1);
+    }
+}
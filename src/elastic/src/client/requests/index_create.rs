@@ -5,10 +5,20 @@ Builders for [create index requests][docs-create-index].
 */
 
 use futures::Future;
+use serde_json::{
+    self,
+    Map,
+    Value,
+};
 
 use crate::{
     client::{
         requests::{
+            common::{
+                document_mappings,
+                includes_type_name,
+                IndexSettings,
+            },
             raw::RawRequestInner,
             Pending as BasePending,
             RequestBuilder,
@@ -17,7 +27,10 @@ use crate::{
         IndexClient,
     },
     endpoints::IndicesCreateRequest,
-    error::Error,
+    error::{
+        self,
+        Error,
+    },
     http::{
         empty_body,
         sender::{
@@ -28,6 +41,10 @@ use crate::{
         DefaultBody,
     },
     params::Index,
+    types::document::{
+        DocumentType,
+        StaticType,
+    },
 };
 
 /**
@@ -50,6 +67,12 @@ pub struct IndexCreateRequestInner<TBody> {
     body: TBody,
 }
 
+#[doc(hidden)]
+pub struct IndexCreateBody {
+    settings: Option<IndexSettings>,
+    mapping: Option<fn() -> Result<Value, Error>>,
+}
+
 /**
 # Create index request
 */
@@ -88,27 +111,21 @@ where
     ```no_run
     # #[macro_use] extern crate serde_derive;
     # #[macro_use] extern crate elastic_derive;
-    # #[macro_use] extern crate serde_json;
     # use elastic::prelude::*;
     # #[derive(Serialize, Deserialize, ElasticType)]
     # struct MyType { }
-        # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
     # let client = SyncClientBuilder::new().build()?;
-    let body = json!({
-        "settings": {
-            "index": {
-                "number_of_shards": 3,
-                "number_of_replicas": 2
-            }
-        },
-        "mappings": {
-            MyType::static_index(): MyType::index_mapping()
-        }
-    });
+    let settings = IndexSettings {
+        number_of_shards: Some(3),
+        number_of_replicas: Some(2),
+        ..Default::default()
+    };
 
     let response = client.index("myindex")
                          .create()
-                         .body(body.to_string())
+                         .settings(settings)
+                         .mapping::<MyType>()
                          .send()?;
 
     assert!(response.acknowledged());
@@ -142,6 +159,28 @@ impl<TBody> IndexCreateRequestInner<TBody> {
     }
 }
 
+impl IndexCreateRequestInner<IndexCreateBody> {
+    fn serialize(self) -> Result<IndexCreateRequestInner<Vec<u8>>, Error> {
+        let mut body = Map::new();
+
+        if let Some(settings) = self.body.settings {
+            let settings = serde_json::to_value(settings).map_err(error::request)?;
+            body.insert("settings".to_owned(), settings);
+        }
+
+        if let Some(mapping) = self.body.mapping {
+            body.insert("mappings".to_owned(), mapping()?);
+        }
+
+        let body = serde_json::to_vec(&body).map_err(error::request)?;
+
+        Ok(IndexCreateRequestInner {
+            index: self.index,
+            body,
+        })
+    }
+}
+
 /**
 # Builder methods
 
@@ -172,6 +211,87 @@ where
     }
 }
 
+impl<TSender> IndexCreateRequestBuilder<TSender, DefaultBody>
+where
+    TSender: Sender,
+{
+    /**
+    Set the settings for the new index.
+
+    The settings can be combined with document mappings using the [`mapping`](#method.mapping) method.
+    */
+    pub fn settings(
+        self,
+        settings: IndexSettings,
+    ) -> IndexCreateRequestBuilder<TSender, IndexCreateBody> {
+        self.typed().settings(settings)
+    }
+
+    /**
+    Set the mappings for the new index from a [`DocumentType`][documents-mod].
+
+    The mapping is generated from [`DocumentType::index_mapping`][DocumentType.index_mapping] when the request is sent.
+    The mapping can be combined with settings using the [`settings`](#method.settings) method.
+
+    [documents-mod]: ../../../types/document/index.html
+    [DocumentType.index_mapping]: ../../../types/document/trait.DocumentType.html#method.index_mapping
+    */
+    pub fn mapping<TDocument>(self) -> IndexCreateRequestBuilder<TSender, IndexCreateBody>
+    where
+        TDocument: DocumentType + StaticType,
+    {
+        self.typed().mapping::<TDocument>()
+    }
+
+    fn typed(self) -> IndexCreateRequestBuilder<TSender, IndexCreateBody> {
+        RequestBuilder::new(
+            self.client,
+            self.params_builder,
+            IndexCreateRequestInner {
+                index: self.inner.index,
+                body: IndexCreateBody {
+                    settings: None,
+                    mapping: None,
+                },
+            },
+        )
+    }
+}
+
+impl<TSender> IndexCreateRequestBuilder<TSender, IndexCreateBody>
+where
+    TSender: Sender,
+{
+    /** Set the settings for the new index. */
+    pub fn settings(mut self, settings: IndexSettings) -> Self {
+        self.inner.body.settings = Some(settings);
+        self
+    }
+
+    /**
+    Set the mappings for the new index from a [`DocumentType`][documents-mod].
+
+    The mapping is generated from [`DocumentType::index_mapping`][DocumentType.index_mapping] when the request is sent.
+
+    [documents-mod]: ../../../types/document/index.html
+    [DocumentType.index_mapping]: ../../../types/document/trait.DocumentType.html#method.index_mapping
+    */
+    pub fn mapping<TDocument>(mut self) -> Self
+    where
+        TDocument: DocumentType + StaticType,
+    {
+        if includes_type_name::<TDocument>() {
+            self.params_builder = self
+                .params_builder
+                .fluent(|params| params.url_param("include_type_name", true))
+                .shared();
+        }
+
+        self.inner.body.mapping = Some(document_mappings::<TDocument>);
+        self
+    }
+}
+
 /**
 # Send synchronously
 */
@@ -210,6 +330,23 @@ where
     }
 }
 
+impl IndexCreateRequestBuilder<SyncSender, IndexCreateBody> {
+    /**
+    Send an `IndexCreateRequestBuilder` with settings or document mappings synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<CommandResponse, Error> {
+        let req = self.inner.serialize()?.into_request();
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+}
+
 /**
 # Send asynchronously
 */
@@ -256,6 +393,31 @@ where
     }
 }
 
+impl IndexCreateRequestBuilder<AsyncSender, IndexCreateBody> {
+    /**
+    Send an `IndexCreateRequestBuilder` with settings or document mappings asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised command response.
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending {
+        let (client, params_builder, inner) = (self.client, self.params_builder, self.inner);
+
+        let req_future = client
+            .sender
+            .maybe_async(move || inner.serialize().map(|inner| inner.into_request()));
+
+        let res_future = req_future.and_then(move |req| {
+            RequestBuilder::new(client, params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response())
+        });
+
+        Pending::new(res_future)
+    }
+}
+
 /** A future returned by calling `send`. */
 pub type Pending = BasePending<CommandResponse>;
 
@@ -265,6 +427,10 @@ mod tests {
         prelude::*,
         tests::*,
     };
+    use serde_json::{
+        self,
+        Value,
+    };
 
     #[test]
     fn is_send() {
@@ -293,4 +459,75 @@ mod tests {
 
         assert_eq!("{}", req.body);
     }
+
+    #[derive(ElasticType)]
+    #[elastic(crate_root = "crate::types")]
+    struct TestDoc {}
+
+    #[derive(ElasticType)]
+    #[elastic(crate_root = "crate::types", ty = "test_ty")]
+    struct TestTypedDoc {}
+
+    #[test]
+    fn specify_settings_and_mapping() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let settings = IndexSettings {
+            number_of_shards: Some(3),
+            ..Default::default()
+        };
+
+        let req = client
+            .index("testindex")
+            .create()
+            .settings(settings)
+            .mapping::<TestDoc>()
+            .inner
+            .serialize()
+            .unwrap()
+            .into_request();
+
+        let expected_body = json!({
+            "settings": {
+                "number_of_shards": 3
+            },
+            "mappings": {
+                "properties": {}
+            }
+        });
+
+        let actual_body: Value = serde_json::from_slice(&req.body).unwrap();
+
+        assert_eq!("/testindex", req.url.as_ref());
+        assert_eq!(expected_body.to_string(), actual_body.to_string());
+    }
+
+    #[test]
+    fn specify_typed_mapping() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let builder = client.index("testindex").create().mapping::<TestTypedDoc>();
+
+        let params = builder
+            .params_builder
+            .into_value(|| RequestParams::new("http://eshost:9200"));
+
+        let req = builder.inner.serialize().unwrap().into_request();
+
+        let expected_body = json!({
+            "mappings": {
+                "test_ty": {
+                    "properties": {}
+                }
+            }
+        });
+
+        let actual_body: Value = serde_json::from_slice(&req.body).unwrap();
+
+        assert_eq!(
+            Some("?include_type_name=true".to_owned()),
+            params.get_url_qry().1
+        );
+        assert_eq!(expected_body.to_string(), actual_body.to_string());
+    }
 }
@@ -0,0 +1,281 @@
+/*!
+Builders for [get index aliases requests][docs-aliases].
+
+[docs-aliases]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-aliases.html#alias-retrieving
+*/
+
+use futures::{
+    future::{
+        self,
+        Either,
+    },
+    Future,
+};
+
+use crate::{
+    client::{
+        requests::{
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::AliasesResponse,
+        IndexClient,
+    },
+    endpoints::IndicesGetAliasRequest,
+    error::Error,
+    http::{
+        sender::{
+            AsyncSender,
+            Sender,
+            SyncSender,
+        },
+        StatusCode,
+    },
+    params::{
+        Index,
+        Name,
+    },
+};
+
+/**
+A [get index aliases request][docs-aliases] builder that can be configured before sending.
+
+Call [`Client.index.get_aliases`][Client.index.get_aliases] or [`Client.index.resolve_alias`][Client.index.resolve_alias] to get an `IndexGetAliasesRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-aliases]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-aliases.html#alias-retrieving
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.index.get_aliases]: ../../struct.IndexClient.html#get-aliases-request
+[Client.index.resolve_alias]: ../../struct.IndexClient.html#get-aliases-request
+*/
+pub type IndexGetAliasesRequestBuilder<TSender> =
+    RequestBuilder<TSender, IndexGetAliasesRequestInner>;
+
+#[doc(hidden)]
+pub enum IndexGetAliasesRequestInner {
+    Index(Index<'static>),
+    Alias(Name<'static>),
+}
+
+/**
+# Get aliases request
+*/
+impl<TSender> IndexClient<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create an [`IndexGetAliasesRequestBuilder`][IndexGetAliasesRequestBuilder] that lists the aliases on this index.
+
+    For more details, see:
+
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    List the aliases on an index called `myindex`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index("myindex").get_aliases().send()?;
+
+    for alias in response.aliases() {
+        println!("{}", alias.name());
+    }
+    # Ok(())
+    # }
+    ```
+
+    [IndexGetAliasesRequestBuilder]: requests/index_get_aliases/type.IndexGetAliasesRequestBuilder.html
+    [send-sync]: requests/index_get_aliases/type.IndexGetAliasesRequestBuilder.html#send-synchronously
+    [send-async]: requests/index_get_aliases/type.IndexGetAliasesRequestBuilder.html#send-asynchronously
+    */
+    pub fn get_aliases(self) -> IndexGetAliasesRequestBuilder<TSender> {
+        RequestBuilder::initial(self.inner, IndexGetAliasesRequestInner::Index(self.index))
+    }
+
+    /**
+    Create an [`IndexGetAliasesRequestBuilder`][IndexGetAliasesRequestBuilder] that resolves this name as an alias to the indices it points to.
+
+    If the alias doesn't exist then the response won't contain any indices.
+
+    # Examples
+
+    Find the indices an alias called `myalias` points to:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index("myalias").resolve_alias().send()?;
+
+    for index in response.indices() {
+        println!("{}", index);
+    }
+    # Ok(())
+    # }
+    ```
+
+    [IndexGetAliasesRequestBuilder]: requests/index_get_aliases/type.IndexGetAliasesRequestBuilder.html
+    */
+    pub fn resolve_alias(self) -> IndexGetAliasesRequestBuilder<TSender> {
+        let alias = Name::from(self.index.to_string());
+
+        RequestBuilder::initial(self.inner, IndexGetAliasesRequestInner::Alias(alias))
+    }
+}
+
+impl IndexGetAliasesRequestInner {
+    fn into_request(self) -> IndicesGetAliasRequest<'static> {
+        match self {
+            IndexGetAliasesRequestInner::Index(index) => IndicesGetAliasRequest::for_index(index),
+            IndexGetAliasesRequestInner::Alias(alias) => IndicesGetAliasRequest::for_name(alias),
+        }
+    }
+
+    /**
+    Whether a missing response should be treated as an empty one.
+
+    Elasticsearch responds with `404` when resolving an alias that doesn't exist.
+    */
+    fn allow_missing(&self) -> bool {
+        match *self {
+            IndexGetAliasesRequestInner::Alias(_) => true,
+            IndexGetAliasesRequestInner::Index(_) => false,
+        }
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl IndexGetAliasesRequestBuilder<SyncSender> {
+    /**
+    Send an `IndexGetAliasesRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    List the aliases on an index called `myindex`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index("myindex").get_aliases().send()?;
+
+    for alias in response.aliases() {
+        println!("{}", alias.name());
+    }
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<AliasesResponse, Error> {
+        let allow_missing = self.inner.allow_missing();
+        let req = self.inner.into_request();
+
+        let res = RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?;
+
+        if allow_missing && res.status() == StatusCode::NOT_FOUND {
+            return Ok(AliasesResponse::default());
+        }
+
+        res.into_response()
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl IndexGetAliasesRequestBuilder<AsyncSender> {
+    /**
+    Send an `IndexGetAliasesRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised aliases response.
+
+    # Examples
+
+    List the aliases on an index called `myindex`:
+
+    ```no_run
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.index("myindex").get_aliases().send();
+
+    future.and_then(|response| {
+        for alias in response.aliases() {
+            println!("{}", alias.name());
+        }
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending {
+        let allow_missing = self.inner.allow_missing();
+        let req = self.inner.into_request();
+
+        let res_future =
+            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(move |res| {
+                    if allow_missing && res.status() == StatusCode::NOT_FOUND {
+                        Either::A(future::ok(AliasesResponse::default()))
+                    } else {
+                        Either::B(res.into_response())
+                    }
+                });
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<AliasesResponse>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+    }
+
+    #[test]
+    fn get_aliases_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client.index("testindex").get_aliases().inner.into_request();
+
+        assert_eq!("/testindex/_alias", req.url.as_ref());
+    }
+
+    #[test]
+    fn resolve_alias_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client.index("testalias").resolve_alias().inner.into_request();
+
+        assert_eq!("/_alias/testalias", req.url.as_ref());
+    }
+}
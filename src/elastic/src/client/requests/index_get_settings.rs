@@ -0,0 +1,200 @@
+/*!
+Builders for [get index settings requests][docs-get-settings].
+
+[docs-get-settings]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-get-settings.html
+*/
+
+use futures::Future;
+
+use crate::{
+    client::{
+        requests::{
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::IndexSettingsResponse,
+        IndexClient,
+    },
+    endpoints::IndicesGetSettingsRequest,
+    error::Error,
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::Index,
+};
+
+/**
+A [get index settings request][docs-get-settings] builder that can be configured before sending.
+
+Call [`Client.index.settings`][Client.index.settings] to get an `IndexGetSettingsRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-get-settings]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-get-settings.html
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.index.settings]: ../../struct.IndexClient.html#get-settings-request
+*/
+pub type IndexGetSettingsRequestBuilder<TSender> =
+    RequestBuilder<TSender, IndexGetSettingsRequestInner>;
+
+#[doc(hidden)]
+pub struct IndexGetSettingsRequestInner {
+    index: Index<'static>,
+}
+
+/**
+# Get settings request
+*/
+impl<TSender> IndexClient<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create an [`IndexGetSettingsRequestBuilder`][IndexGetSettingsRequestBuilder] with this `Client` that can be configured before sending.
+
+    For more details, see:
+
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Get the settings for an index called `myindex`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index("myindex").settings().send()?;
+
+    if let Some(settings) = response.get("myindex") {
+        println!("{:?}", settings.number_of_replicas);
+    }
+    # Ok(())
+    # }
+    ```
+
+    [IndexGetSettingsRequestBuilder]: requests/index_get_settings/type.IndexGetSettingsRequestBuilder.html
+    [send-sync]: requests/index_get_settings/type.IndexGetSettingsRequestBuilder.html#send-synchronously
+    [send-async]: requests/index_get_settings/type.IndexGetSettingsRequestBuilder.html#send-asynchronously
+    */
+    pub fn settings(self) -> IndexGetSettingsRequestBuilder<TSender> {
+        RequestBuilder::initial(self.inner, IndexGetSettingsRequestInner { index: self.index })
+    }
+}
+
+impl IndexGetSettingsRequestInner {
+    fn into_request(self) -> IndicesGetSettingsRequest<'static> {
+        IndicesGetSettingsRequest::for_index(self.index)
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl IndexGetSettingsRequestBuilder<SyncSender> {
+    /**
+    Send an `IndexGetSettingsRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    Get the settings for an index called `myindex`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index("myindex").settings().send()?;
+
+    if let Some(settings) = response.get("myindex") {
+        println!("{:?}", settings.number_of_replicas);
+    }
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<IndexSettingsResponse, Error> {
+        let req = self.inner.into_request();
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl IndexGetSettingsRequestBuilder<AsyncSender> {
+    /**
+    Send an `IndexGetSettingsRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised settings response.
+
+    # Examples
+
+    Get the settings for an index called `myindex`:
+
+    ```no_run
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.index("myindex").settings().send();
+
+    future.and_then(|response| {
+        if let Some(settings) = response.get("myindex") {
+            println!("{:?}", settings.number_of_replicas);
+        }
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending {
+        let req = self.inner.into_request();
+
+        let res_future =
+            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response());
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<IndexSettingsResponse>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+    }
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client.index("testindex").settings().inner.into_request();
+
+        assert_eq!("/testindex/_settings", req.url.as_ref());
+    }
+}
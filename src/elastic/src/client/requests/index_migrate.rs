@@ -0,0 +1,690 @@
+/*!
+Builders for migrating an alias to a new index without downtime.
+
+A migration creates a new index with the mapping of a [`DocumentType`][documents-mod], [reindexes][docs-reindex] documents into it from the indices currently behind an [alias][docs-aliases], then atomically swaps the alias to point at the new index.
+
+[documents-mod]: ../../../types/document/index.html
+[docs-reindex]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-reindex.html
+[docs-aliases]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-aliases.html
+*/
+
+use futures::{
+    future::{
+        self,
+        Either,
+    },
+    Future,
+};
+use serde_json::Value;
+use std::{
+    error::Error as StdError,
+    fmt,
+    marker::PhantomData,
+};
+
+use crate::{
+    client::{
+        requests::Pending as BasePending,
+        responses::{
+            IndexMigrateResponse,
+            ReindexResponse,
+        },
+        Client,
+        IndexClient,
+    },
+    endpoints::{
+        CountRequest,
+        IndicesRefreshRequest,
+    },
+    error::{
+        self,
+        Error,
+    },
+    http::{
+        empty_body,
+        receiver::IsOkOnSuccess,
+        sender::{
+            AsyncSender,
+            Sender,
+            SyncSender,
+        },
+    },
+    params::Index,
+    types::document::{
+        DocumentType,
+        StaticIndex,
+        StaticType,
+    },
+};
+
+/**
+An index migration builder that can be configured before sending.
+
+Call [`Client.index.migrate`][Client.index.migrate] to get an `IndexMigrateBuilder`.
+The `send` method will either run the migration [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.index.migrate]: ../../struct.IndexClient.html#migrate-index-request
+*/
+pub struct IndexMigrateBuilder<TSender, TDocument>
+where
+    TSender: Sender,
+{
+    client: Client<TSender>,
+    index: Index<'static>,
+    alias: Index<'static>,
+    source: Option<Index<'static>>,
+    delete_old_indices: bool,
+    _marker: PhantomData<TDocument>,
+}
+
+/**
+# Migrate index request
+*/
+impl<TSender> IndexClient<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create an [`IndexMigrateBuilder`][IndexMigrateBuilder] that migrates an alias to this index.
+
+    The migration runs the following steps:
+
+    1. Create this index with the mapping of the [`DocumentType`][documents-mod] `TDocument`
+    1. Reindex documents from the indices behind the alias into this index
+    1. Verify the number of documents in this index matches the old indices
+    1. Atomically swap the alias to point at this index
+    1. Optionally delete the old indices
+
+    If any step before the alias is swapped fails then this index is deleted and the alias is left untouched.
+    Once the alias is swapped the migration has succeeded, so old indices that can't be deleted are returned in the response rather than as an error.
+
+    By default the alias is the static index of `TDocument`.
+
+    For more details, see:
+
+    - [builder methods][builder-methods]
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Migrate the `myindex` alias for a [`DocumentType`][documents-mod] called `MyType` to a new index called `myindex_v2`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    #[derive(Serialize, Deserialize, ElasticType)]
+    #[elastic(index = "myindex")]
+    struct MyType {
+        id: String,
+        title: String,
+    }
+
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index("myindex_v2")
+                         .migrate::<MyType>()
+                         .delete_old_indices(true)
+                         .send()?;
+
+    println!("migrated {} documents from {:?}", response.doc_count(), response.old_indices());
+    # Ok(())
+    # }
+    ```
+
+    [IndexMigrateBuilder]: requests/index_migrate/struct.IndexMigrateBuilder.html
+    [builder-methods]: requests/index_migrate/struct.IndexMigrateBuilder.html#builder-methods
+    [send-sync]: requests/index_migrate/struct.IndexMigrateBuilder.html#send-synchronously
+    [send-async]: requests/index_migrate/struct.IndexMigrateBuilder.html#send-asynchronously
+    [documents-mod]: ../types/document/index.html
+    */
+    pub fn migrate<TDocument>(self) -> IndexMigrateBuilder<TSender, TDocument>
+    where
+        TDocument: DocumentType + StaticIndex + StaticType,
+    {
+        IndexMigrateBuilder {
+            client: self.inner,
+            index: self.index,
+            alias: TDocument::static_index(),
+            source: None,
+            delete_old_indices: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/**
+# Builder methods
+
+Configure an `IndexMigrateBuilder` before sending it.
+*/
+impl<TSender, TDocument> IndexMigrateBuilder<TSender, TDocument>
+where
+    TSender: Sender,
+{
+    /** Set the alias to migrate. */
+    pub fn alias(mut self, alias: impl Into<Index<'static>>) -> Self {
+        self.alias = alias.into();
+        self
+    }
+
+    /**
+    Set the index to reindex documents from.
+
+    By default documents are reindexed from all indices the alias currently points to.
+    */
+    pub fn source(mut self, source: impl Into<Index<'static>>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /**
+    Set whether or not to delete the old indices once the alias has been swapped.
+
+    The old indices aren't deleted by default.
+    */
+    pub fn delete_old_indices(mut self, delete_old_indices: bool) -> Self {
+        self.delete_old_indices = delete_old_indices;
+        self
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl<TDocument> IndexMigrateBuilder<SyncSender, TDocument>
+where
+    TDocument: DocumentType + StaticIndex + StaticType,
+{
+    /**
+    Run an `IndexMigrateBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until the alias has been migrated, or the migration has been rolled back.
+
+    # Examples
+
+    Migrate the `myindex` alias for a [`DocumentType`][documents-mod] called `MyType` to a new index called `myindex_v2`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # #[elastic(index = "myindex")]
+    # struct MyType { }
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index("myindex_v2")
+                         .migrate::<MyType>()
+                         .send()?;
+
+    assert_eq!("myindex_v2", response.new_index());
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    [documents-mod]: ../../../types/document/index.html
+    */
+    pub fn send(self) -> Result<IndexMigrateResponse, Error> {
+        let client = self.client;
+        let new_index = self.index.to_string();
+        let alias = self.alias.to_string();
+
+        let old_indices = match self.source {
+            Some(source) => vec![source.to_string()],
+            None => resolve_alias_sync(&client, alias.clone())?,
+        };
+
+        client
+            .index(new_index.clone())
+            .create()
+            .mapping::<TDocument>()
+            .send()?;
+
+        let migrated = migrate_sync(&client, &alias, &old_indices, &new_index);
+
+        let (reindex, doc_count) = match migrated {
+            Ok(migrated) => migrated,
+            Err(err) => {
+                // Roll back by removing the new index. The alias hasn't been touched
+                let _ = client.index(new_index).delete().send();
+                return Err(err);
+            }
+        };
+
+        let mut undeleted_old_indices = Vec::new();
+        if self.delete_old_indices {
+            for old_index in &old_indices {
+                if let Err(err) = client.index(old_index.clone()).delete().send() {
+                    log_undeleted_old_index(old_index, &err);
+                    undeleted_old_indices.push(old_index.clone());
+                }
+            }
+        }
+
+        Ok(IndexMigrateResponse {
+            alias,
+            new_index,
+            old_indices,
+            reindex,
+            doc_count,
+            deleted_old_indices: self.delete_old_indices,
+            undeleted_old_indices,
+        })
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl<TDocument> IndexMigrateBuilder<AsyncSender, TDocument>
+where
+    TDocument: DocumentType + StaticIndex + StaticType + Send + 'static,
+{
+    /**
+    Run an `IndexMigrateBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve once the alias has been migrated, or the migration has been rolled back.
+
+    # Examples
+
+    Migrate the `myindex` alias for a [`DocumentType`][documents-mod] called `MyType` to a new index called `myindex_v2`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # #[elastic(index = "myindex")]
+    # struct MyType { }
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.index("myindex_v2")
+                       .migrate::<MyType>()
+                       .send();
+
+    future.and_then(|response| {
+        assert_eq!("myindex_v2", response.new_index());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    [documents-mod]: ../../../types/document/index.html
+    */
+    pub fn send(self) -> Pending {
+        let client = self.client;
+        let new_index = self.index.to_string();
+        let alias = self.alias.to_string();
+        let delete_old_indices = self.delete_old_indices;
+
+        let old_indices_future = match self.source {
+            Some(source) => Either::A(future::ok(vec![source.to_string()])),
+            None => Either::B(resolve_alias_async(&client, alias.clone())),
+        };
+
+        let res_future = old_indices_future.and_then(move |old_indices| {
+            client
+                .index(new_index.clone())
+                .create()
+                .mapping::<TDocument>()
+                .send()
+                .and_then(move |_| {
+                    let rollback_client = client.clone();
+                    let rollback_index = new_index.clone();
+
+                    migrate_async(&client, &alias, &old_indices, &new_index)
+                        .or_else(move |err| {
+                            // Roll back by removing the new index. The alias hasn't been touched
+                            rollback_client
+                                .index(rollback_index)
+                                .delete()
+                                .send()
+                                .then(move |_| Err(err))
+                        })
+                        .and_then(move |(reindex, doc_count)| {
+                            let undeleted = if delete_old_indices {
+                                let deletes = old_indices
+                                    .iter()
+                                    .map(|old_index| delete_old_index_async(&client, old_index))
+                                    .collect::<Vec<_>>();
+
+                                Either::A(future::join_all(deletes).map(|undeleted| {
+                                    undeleted.into_iter().flatten().collect()
+                                }))
+                            } else {
+                                Either::B(future::ok(Vec::new()))
+                            };
+
+                            undeleted.map(move |undeleted_old_indices| IndexMigrateResponse {
+                                alias,
+                                new_index,
+                                old_indices,
+                                reindex,
+                                doc_count,
+                                deleted_old_indices: delete_old_indices,
+                                undeleted_old_indices,
+                            })
+                        })
+                })
+        });
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<IndexMigrateResponse>;
+
+type BoxFuture<T> = Box<dyn Future<Item = T, Error = Error> + Send>;
+
+/**
+Run the steps of a migration that can be rolled back by deleting the new index.
+*/
+fn migrate_sync(
+    client: &Client<SyncSender>,
+    alias: &str,
+    old_indices: &[String],
+    new_index: &str,
+) -> Result<(Option<ReindexResponse>, u64), Error> {
+    let (reindex, doc_count) = if old_indices.is_empty() {
+        (None, 0)
+    } else {
+        let source = old_indices.join(",");
+
+        let reindex = client.reindex(source.clone(), new_index.to_owned()).send()?;
+        check_reindex(&reindex)?;
+
+        client
+            .request(IndicesRefreshRequest::for_index(
+                new_index.to_owned(),
+                empty_body(),
+            ))
+            .send()?
+            .into_response::<Value>()?;
+
+        let expected = count_sync(client, source)?;
+        let actual = count_sync(client, new_index.to_owned())?;
+        check_count(expected, actual)?;
+
+        (Some(reindex), actual)
+    };
+
+    if old_indices.is_empty() {
+        client
+            .index(new_index.to_owned())
+            .add_alias(alias.to_owned())
+            .send()?;
+    } else {
+        client
+            .index(new_index.to_owned())
+            .swap_alias(alias.to_owned(), old_indices.join(","))
+            .send()?;
+    }
+
+    Ok((reindex, doc_count))
+}
+
+/**
+Run the steps of a migration that can be rolled back by deleting the new index.
+*/
+fn migrate_async(
+    client: &Client<AsyncSender>,
+    alias: &str,
+    old_indices: &[String],
+    new_index: &str,
+) -> BoxFuture<(Option<ReindexResponse>, u64)> {
+    let reindex = if old_indices.is_empty() {
+        Either::A(future::ok((None, 0)))
+    } else {
+        let (client, new_index) = (client.clone(), new_index.to_owned());
+        let source = old_indices.join(",");
+
+        let reindex = client
+            .reindex(source.clone(), new_index.clone())
+            .send()
+            .and_then(|reindex| check_reindex(&reindex).map(|_| reindex))
+            .and_then(move |reindex| {
+                client
+                    .request(IndicesRefreshRequest::for_index(
+                        new_index.clone(),
+                        empty_body(),
+                    ))
+                    .send()
+                    .and_then(|res| res.into_response::<Value>())
+                    .and_then(move |_| {
+                        count_async(&client, source).join(count_async(&client, new_index))
+                    })
+                    .and_then(|(expected, actual)| check_count(expected, actual).map(|_| actual))
+                    .map(move |doc_count| (Some(reindex), doc_count))
+            });
+
+        Either::B(reindex)
+    };
+
+    let swap_alias = if old_indices.is_empty() {
+        client
+            .index(new_index.to_owned())
+            .add_alias(alias.to_owned())
+            .send()
+    } else {
+        client
+            .index(new_index.to_owned())
+            .swap_alias(alias.to_owned(), old_indices.join(","))
+            .send()
+    };
+
+    Box::new(reindex.and_then(|migrated| swap_alias.map(|_| migrated)))
+}
+
+#[derive(Deserialize)]
+struct CountResponse {
+    count: u64,
+}
+
+impl IsOkOnSuccess for CountResponse {}
+
+fn count_sync(client: &Client<SyncSender>, index: String) -> Result<u64, Error> {
+    let res = client
+        .request(CountRequest::for_index(index, empty_body()))
+        .send()?
+        .into_response::<CountResponse>()?;
+
+    Ok(res.count)
+}
+
+fn count_async(client: &Client<AsyncSender>, index: String) -> BoxFuture<u64> {
+    let res = client
+        .request(CountRequest::for_index(index, empty_body()))
+        .send()
+        .and_then(|res| res.into_response::<CountResponse>())
+        .map(|res| res.count);
+
+    Box::new(res)
+}
+
+fn resolve_alias_sync(client: &Client<SyncSender>, alias: String) -> Result<Vec<String>, Error> {
+    let res = client.index(alias).resolve_alias().send()?;
+
+    Ok(res.indices().map(|index| index.to_owned()).collect())
+}
+
+fn resolve_alias_async(client: &Client<AsyncSender>, alias: String) -> BoxFuture<Vec<String>> {
+    let res = client
+        .index(alias)
+        .resolve_alias()
+        .send()
+        .map(|res| res.indices().map(|index| index.to_owned()).collect());
+
+    Box::new(res)
+}
+
+/**
+Delete an old index once the alias has been swapped.
+
+The migration has already succeeded at this point, so a failure to delete the old index is logged and returned as the name of the index rather than an error.
+*/
+fn delete_old_index_async(
+    client: &Client<AsyncSender>,
+    old_index: &str,
+) -> BoxFuture<Option<String>> {
+    let old_index = old_index.to_owned();
+
+    let res = client
+        .index(old_index.clone())
+        .delete()
+        .send()
+        .then(move |res| match res {
+            Ok(_) => Ok(None),
+            Err(err) => {
+                log_undeleted_old_index(&old_index, &err);
+                Ok(Some(old_index))
+            }
+        });
+
+    Box::new(res)
+}
+
+fn log_undeleted_old_index(old_index: &str, err: &Error) {
+    warn!(
+        "Elasticsearch Index Migrate: failed to delete old index: '{}', error: '{:?}'",
+        old_index, err
+    );
+}
+
+fn check_reindex(reindex: &ReindexResponse) -> Result<(), Error> {
+    if reindex.is_ok() {
+        Ok(())
+    } else {
+        Err(error::request(IndexMigrateError(format!(
+            "reindexing failed for {} documents",
+            reindex.failures().len()
+        ))))
+    }
+}
+
+fn check_count(expected: u64, actual: u64) -> Result<(), Error> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(error::request(IndexMigrateError(format!(
+            "expected {} documents in the new index but found {}",
+            expected, actual
+        ))))
+    }
+}
+
+#[derive(Debug)]
+struct IndexMigrateError(String);
+
+impl fmt::Display for IndexMigrateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StdError for IndexMigrateError {
+    fn description(&self) -> &str {
+        "error migrating index"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        http::{
+            sender::{
+                MockRequest,
+                MockResponse,
+                MockTransport,
+            },
+            Method,
+            StatusCode,
+        },
+        prelude::*,
+        tests::*,
+    };
+
+    #[derive(ElasticType)]
+    #[elastic(crate_root = "crate::types", index = "myindex")]
+    struct TestDoc {}
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+    }
+
+    #[test]
+    fn mismatched_count_is_err() {
+        assert!(check_count(2, 2).is_ok());
+        assert!(check_count(2, 1).is_err());
+    }
+
+    #[test]
+    fn error_display_has_no_newline() {
+        assert_eq!("failed", IndexMigrateError("failed".to_owned()).to_string());
+    }
+
+    #[test]
+    fn failed_old_index_delete_is_ok() {
+        let acknowledged = MockResponse::new(StatusCode::OK).body(json!({ "acknowledged": true }));
+        let count = MockResponse::new(StatusCode::OK).body(json!({ "count": 1 }));
+
+        let mock = MockTransport::new()
+            .respond(MockRequest::new(Method::PUT, "/myindex_v2"), acknowledged.clone())
+            .respond(
+                MockRequest::new(Method::POST, "/_reindex"),
+                MockResponse::new(StatusCode::OK).body(json!({
+                    "took": 1,
+                    "timed_out": false,
+                    "total": 1,
+                    "created": 1,
+                    "failures": []
+                })),
+            )
+            .respond(
+                MockRequest::new(Method::POST, "/myindex_v2/_refresh"),
+                MockResponse::new(StatusCode::OK).body(json!({})),
+            )
+            .respond(MockRequest::new(Method::POST, "/myindex_v1/_count"), count.clone())
+            .respond(MockRequest::new(Method::POST, "/myindex_v2/_count"), count)
+            .respond(MockRequest::new(Method::POST, "/_aliases"), acknowledged)
+            .respond(
+                MockRequest::new(Method::DELETE, "/myindex_v1"),
+                MockResponse::new(StatusCode::INTERNAL_SERVER_ERROR).body(json!({
+                    "error": { "type": "exception", "reason": "failed" },
+                    "status": 500
+                })),
+            );
+
+        let client = SyncClientBuilder::new().mock(mock.clone()).build().unwrap();
+
+        let res = client
+            .index("myindex_v2")
+            .migrate::<TestDoc>()
+            .source("myindex_v1")
+            .delete_old_indices(true)
+            .send()
+            .unwrap();
+
+        assert_eq!(1, res.doc_count());
+        assert_eq!(&["myindex_v1".to_owned()], res.undeleted_old_indices());
+
+        let create: Value = serde_json::from_slice(mock.requests()[0].body().unwrap().as_ref())
+            .unwrap();
+        assert!(create["mappings"].is_object());
+        assert!(!mock
+            .requests()
+            .iter()
+            .any(|req| req.url().path().ends_with("/_mapping")));
+    }
+}
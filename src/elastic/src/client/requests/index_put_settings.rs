@@ -0,0 +1,254 @@
+/*!
+Builders for [update index settings requests][docs-update-settings].
+
+[docs-update-settings]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-update-settings.html
+*/
+
+use futures::Future;
+use serde_json;
+
+use crate::{
+    client::{
+        requests::{
+            common::IndexSettings,
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::CommandResponse,
+        IndexClient,
+    },
+    endpoints::IndicesPutSettingsRequest,
+    error::{
+        self,
+        Error,
+    },
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::Index,
+};
+
+/**
+An [update index settings request][docs-update-settings] builder that can be configured before sending.
+
+Call [`Client.index.put_settings`][Client.index.put_settings] to get an `IndexPutSettingsRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-update-settings]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-update-settings.html
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.index.put_settings]: ../../struct.IndexClient.html#put-settings-request
+*/
+pub type IndexPutSettingsRequestBuilder<TSender> =
+    RequestBuilder<TSender, IndexPutSettingsRequestInner>;
+
+#[doc(hidden)]
+pub struct IndexPutSettingsRequestInner {
+    index: Index<'static>,
+    settings: IndexSettings,
+}
+
+/**
+# Put settings request
+*/
+impl<TSender> IndexClient<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create an [`IndexPutSettingsRequestBuilder`][IndexPutSettingsRequestBuilder] with this `Client` that can be configured before sending.
+
+    Only the settings that are set will be updated.
+    Some settings, like `number_of_shards`, can't be changed on an existing index.
+    Analysis settings can only be changed on a closed index.
+
+    For more details, see:
+
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Disable refreshing and replicas for an index called `myindex` before a bulk load:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let settings = IndexSettings {
+        number_of_replicas: Some(0),
+        refresh_interval: Some("-1".to_owned()),
+        ..Default::default()
+    };
+
+    let response = client.index("myindex").put_settings(settings).send()?;
+
+    assert!(response.acknowledged());
+    # Ok(())
+    # }
+    ```
+
+    [IndexPutSettingsRequestBuilder]: requests/index_put_settings/type.IndexPutSettingsRequestBuilder.html
+    [send-sync]: requests/index_put_settings/type.IndexPutSettingsRequestBuilder.html#send-synchronously
+    [send-async]: requests/index_put_settings/type.IndexPutSettingsRequestBuilder.html#send-asynchronously
+    */
+    pub fn put_settings(self, settings: IndexSettings) -> IndexPutSettingsRequestBuilder<TSender> {
+        RequestBuilder::initial(
+            self.inner,
+            IndexPutSettingsRequestInner {
+                index: self.index,
+                settings,
+            },
+        )
+    }
+}
+
+impl IndexPutSettingsRequestInner {
+    fn into_request(self) -> Result<IndicesPutSettingsRequest<'static, Vec<u8>>, Error> {
+        let body = serde_json::to_vec(&self.settings).map_err(error::request)?;
+
+        Ok(IndicesPutSettingsRequest::for_index(self.index, body))
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl IndexPutSettingsRequestBuilder<SyncSender> {
+    /**
+    Send an `IndexPutSettingsRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    Set the number of replicas for an index called `myindex`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let settings = IndexSettings {
+        number_of_replicas: Some(2),
+        ..Default::default()
+    };
+
+    let response = client.index("myindex").put_settings(settings).send()?;
+
+    assert!(response.acknowledged());
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<CommandResponse, Error> {
+        let req = self.inner.into_request()?;
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl IndexPutSettingsRequestBuilder<AsyncSender> {
+    /**
+    Send an `IndexPutSettingsRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised command response.
+
+    # Examples
+
+    Set the number of replicas for an index called `myindex`:
+
+    ```no_run
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let settings = IndexSettings {
+        number_of_replicas: Some(2),
+        ..Default::default()
+    };
+
+    let future = client.index("myindex").put_settings(settings).send();
+
+    future.and_then(|response| {
+        assert!(response.acknowledged());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending {
+        let (client, params_builder, inner) = (self.client, self.params_builder, self.inner);
+
+        let req_future = client.sender.maybe_async(move || inner.into_request());
+
+        let res_future = req_future.and_then(move |req| {
+            RequestBuilder::new(client, params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response())
+        });
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<CommandResponse>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+    use serde_json::{
+        self,
+        Value,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+    }
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let settings = IndexSettings {
+            number_of_replicas: Some(2),
+            refresh_interval: Some("30s".to_owned()),
+            ..Default::default()
+        };
+
+        let req = client
+            .index("testindex")
+            .put_settings(settings)
+            .inner
+            .into_request()
+            .unwrap();
+
+        let expected_body = json!({
+            "number_of_replicas": 2,
+            "refresh_interval": "30s"
+        });
+
+        let actual_body: Value = serde_json::from_slice(&req.body).unwrap();
+
+        assert_eq!("/testindex/_settings", req.url.as_ref());
+        assert_eq!(expected_body.to_string(), actual_body.to_string());
+    }
+}
@@ -0,0 +1,197 @@
+/*!
+Builders for [delete index template requests][docs-templates].
+
+[docs-templates]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-templates.html#delete
+*/
+
+use futures::Future;
+
+use crate::{
+    client::{
+        requests::{
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::CommandResponse,
+        IndexTemplateClient,
+    },
+    endpoints::IndicesDeleteTemplateRequest,
+    error::Error,
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::Name,
+};
+
+/**
+A [delete index template request][docs-templates] builder that can be configured before sending.
+
+Call [`Client.index_template.delete`][Client.index_template.delete] to get an `IndexTemplateDeleteRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-templates]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-templates.html#delete
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.index_template.delete]: ../../struct.IndexTemplateClient.html#delete-index-template-request
+*/
+pub type IndexTemplateDeleteRequestBuilder<TSender> = RequestBuilder<TSender, IndexTemplateDeleteRequestInner>;
+
+#[doc(hidden)]
+pub struct IndexTemplateDeleteRequestInner {
+    name: Name<'static>,
+}
+
+/**
+# Delete index template request
+*/
+impl<TSender> IndexTemplateClient<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create an [`IndexTemplateDeleteRequestBuilder`][IndexTemplateDeleteRequestBuilder] with this `Client` that can be configured before sending.
+
+    For more details, see:
+
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Delete a template called `logs`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index_template("logs").delete().send()?;
+
+    assert!(response.acknowledged());
+    # Ok(())
+    # }
+    ```
+
+    [IndexTemplateDeleteRequestBuilder]: requests/index_template_delete/type.IndexTemplateDeleteRequestBuilder.html
+    [send-sync]: requests/index_template_delete/type.IndexTemplateDeleteRequestBuilder.html#send-synchronously
+    [send-async]: requests/index_template_delete/type.IndexTemplateDeleteRequestBuilder.html#send-asynchronously
+    */
+    pub fn delete(self) -> IndexTemplateDeleteRequestBuilder<TSender> {
+        RequestBuilder::initial(self.inner, IndexTemplateDeleteRequestInner { name: self.name })
+    }
+}
+
+impl IndexTemplateDeleteRequestInner {
+    fn into_request(self) -> IndicesDeleteTemplateRequest<'static> {
+        IndicesDeleteTemplateRequest::for_name(self.name)
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl IndexTemplateDeleteRequestBuilder<SyncSender> {
+    /**
+    Send an `IndexTemplateDeleteRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    Delete a template called `logs`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index_template("logs").delete().send()?;
+
+    assert!(response.acknowledged());
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<CommandResponse, Error> {
+        let req = self.inner.into_request();
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl IndexTemplateDeleteRequestBuilder<AsyncSender> {
+    /**
+    Send an `IndexTemplateDeleteRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised command response.
+
+    # Examples
+
+    Delete a template called `logs`:
+
+    ```no_run
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.index_template("logs").delete().send();
+
+    future.and_then(|response| {
+        assert!(response.acknowledged());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending {
+        let req = self.inner.into_request();
+
+        let res_future =
+            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response());
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<CommandResponse>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+    }
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .index_template("testtemplate")
+            .delete()
+            .inner
+            .into_request();
+
+        assert_eq!("/_template/testtemplate", req.url.as_ref());
+    }
+}
@@ -0,0 +1,197 @@
+/*!
+Builders for [get index template requests][docs-templates].
+
+[docs-templates]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-templates.html#getting
+*/
+
+use futures::Future;
+
+use crate::{
+    client::{
+        requests::{
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::IndexTemplatesResponse,
+        IndexTemplateClient,
+    },
+    endpoints::IndicesGetTemplateRequest,
+    error::Error,
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::Name,
+};
+
+/**
+A [get index template request][docs-templates] builder that can be configured before sending.
+
+Call [`Client.index_template.get`][Client.index_template.get] to get an `IndexTemplateGetRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-templates]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-templates.html#getting
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.index_template.get]: ../../struct.IndexTemplateClient.html#get-index-template-request
+*/
+pub type IndexTemplateGetRequestBuilder<TSender> = RequestBuilder<TSender, IndexTemplateGetRequestInner>;
+
+#[doc(hidden)]
+pub struct IndexTemplateGetRequestInner {
+    name: Name<'static>,
+}
+
+/**
+# Get index template request
+*/
+impl<TSender> IndexTemplateClient<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create an [`IndexTemplateGetRequestBuilder`][IndexTemplateGetRequestBuilder] with this `Client` that can be configured before sending.
+
+    For more details, see:
+
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Get a template called `logs`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index_template("logs").get().send()?;
+
+    println!("{:?}", response.get("logs"));
+    # Ok(())
+    # }
+    ```
+
+    [IndexTemplateGetRequestBuilder]: requests/index_template_get/type.IndexTemplateGetRequestBuilder.html
+    [send-sync]: requests/index_template_get/type.IndexTemplateGetRequestBuilder.html#send-synchronously
+    [send-async]: requests/index_template_get/type.IndexTemplateGetRequestBuilder.html#send-asynchronously
+    */
+    pub fn get(self) -> IndexTemplateGetRequestBuilder<TSender> {
+        RequestBuilder::initial(self.inner, IndexTemplateGetRequestInner { name: self.name })
+    }
+}
+
+impl IndexTemplateGetRequestInner {
+    fn into_request(self) -> IndicesGetTemplateRequest<'static> {
+        IndicesGetTemplateRequest::for_name(self.name)
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl IndexTemplateGetRequestBuilder<SyncSender> {
+    /**
+    Send an `IndexTemplateGetRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    Get a template called `logs`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index_template("logs").get().send()?;
+
+    println!("{:?}", response.get("logs"));
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<IndexTemplatesResponse, Error> {
+        let req = self.inner.into_request();
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl IndexTemplateGetRequestBuilder<AsyncSender> {
+    /**
+    Send an `IndexTemplateGetRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised templates response.
+
+    # Examples
+
+    Get a template called `logs`:
+
+    ```no_run
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.index_template("logs").get().send();
+
+    future.and_then(|response| {
+        println!("{:?}", response.get("logs"));
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending {
+        let req = self.inner.into_request();
+
+        let res_future =
+            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response());
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<IndexTemplatesResponse>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+    }
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .index_template("testtemplate")
+            .get()
+            .inner
+            .into_request();
+
+        assert_eq!("/_template/testtemplate", req.url.as_ref());
+    }
+}
@@ -0,0 +1,430 @@
+/*!
+Builders for [put index template requests][docs-templates].
+
+[docs-templates]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-templates.html
+*/
+
+use futures::Future;
+use serde_json::{
+    self,
+    Value,
+};
+use std::collections::BTreeMap;
+
+use crate::{
+    client::{
+        requests::{
+            common::{
+                document_mappings,
+                includes_type_name,
+            },
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::CommandResponse,
+        IndexTemplateClient,
+    },
+    endpoints::IndicesPutTemplateRequest,
+    error::{
+        self,
+        Error,
+    },
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::Name,
+    types::document::{
+        DocumentType,
+        StaticType,
+    },
+};
+
+/**
+A [put index template request][docs-templates] builder that can be configured before sending.
+
+Call [`Client.index_template.put`][Client.index_template.put] to get an `IndexTemplatePutRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-templates]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-templates.html
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.index_template.put]: ../../struct.IndexTemplateClient.html#put-index-template-request
+*/
+pub type IndexTemplatePutRequestBuilder<TSender> =
+    RequestBuilder<TSender, IndexTemplatePutRequestInner>;
+
+#[doc(hidden)]
+pub struct IndexTemplatePutRequestInner {
+    name: Name<'static>,
+    body: IndexTemplateBody,
+    mapping: Option<fn() -> Result<Value, Error>>,
+}
+
+#[derive(Serialize)]
+struct IndexTemplateBody {
+    index_patterns: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    order: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    settings: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mappings: Option<Value>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    aliases: BTreeMap<String, Value>,
+}
+
+/**
+# Put index template request
+*/
+impl<TSender> IndexTemplateClient<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create an [`IndexTemplatePutRequestBuilder`][IndexTemplatePutRequestBuilder] with this `Client` that can be configured before sending.
+
+    The template will be applied to new indices with names that match `index_pattern`.
+    Any existing template with the same name will be replaced.
+
+    For more details, see:
+
+    - [builder methods][builder-methods]
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Put a template called `logs` for time-based indices with settings and the mapping for a [`DocumentType`][documents-mod] called `LogEntry`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # #[macro_use] extern crate serde_json;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    #[derive(Serialize, Deserialize, ElasticType)]
+    struct LogEntry {
+        level: String,
+        message: String,
+    }
+
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index_template("logs")
+                         .put("logs-*")
+                         .settings(json!({ "index": { "number_of_shards": 1 } }))
+                         .mapping::<LogEntry>()
+                         .alias("logs")
+                         .send()?;
+
+    assert!(response.acknowledged());
+    # Ok(())
+    # }
+    ```
+
+    For more details on document types and mapping, see the [`types`][types-mod] module.
+
+    [IndexTemplatePutRequestBuilder]: requests/index_template_put/type.IndexTemplatePutRequestBuilder.html
+    [builder-methods]: requests/index_template_put/type.IndexTemplatePutRequestBuilder.html#builder-methods
+    [send-sync]: requests/index_template_put/type.IndexTemplatePutRequestBuilder.html#send-synchronously
+    [send-async]: requests/index_template_put/type.IndexTemplatePutRequestBuilder.html#send-asynchronously
+    [types-mod]: ../types/index.html
+    [documents-mod]: ../types/document/index.html
+    */
+    pub fn put(self, index_pattern: impl Into<String>) -> IndexTemplatePutRequestBuilder<TSender> {
+        RequestBuilder::initial(
+            self.inner,
+            IndexTemplatePutRequestInner {
+                name: self.name,
+                body: IndexTemplateBody {
+                    index_patterns: vec![index_pattern.into()],
+                    order: None,
+                    version: None,
+                    settings: None,
+                    mappings: None,
+                    aliases: BTreeMap::new(),
+                },
+                mapping: None,
+            },
+        )
+    }
+}
+
+impl IndexTemplatePutRequestInner {
+    fn into_request(self) -> Result<IndicesPutTemplateRequest<'static, Vec<u8>>, Error> {
+        let mut body = self.body;
+
+        if let Some(mapping) = self.mapping {
+            body.mappings = Some(mapping()?);
+        }
+
+        let body = serde_json::to_vec(&body).map_err(error::request)?;
+
+        Ok(IndicesPutTemplateRequest::for_name(self.name, body))
+    }
+}
+
+/**
+# Builder methods
+
+Configure an `IndexTemplatePutRequestBuilder` before sending it.
+*/
+impl<TSender> IndexTemplatePutRequestBuilder<TSender>
+where
+    TSender: Sender,
+{
+    /** Add another index name pattern the template applies to. */
+    pub fn index_pattern(mut self, index_pattern: impl Into<String>) -> Self {
+        self.inner.body.index_patterns.push(index_pattern.into());
+        self
+    }
+
+    /**
+    Set the order the template is applied in.
+
+    Templates with a higher order are applied after templates with a lower order, so their settings and mappings take precedence.
+    */
+    pub fn order(mut self, order: i64) -> Self {
+        self.inner.body.order = Some(order);
+        self
+    }
+
+    /** Set a version number for the template. */
+    pub fn version(mut self, version: i64) -> Self {
+        self.inner.body.version = Some(version);
+        self
+    }
+
+    /** Set the settings applied to new indices. */
+    pub fn settings(mut self, settings: impl Into<Value>) -> Self {
+        self.inner.body.settings = Some(settings.into());
+        self
+    }
+
+    /**
+    Set the mappings applied to new indices from a [`DocumentType`][documents-mod].
+
+    The mapping is generated from [`DocumentType::index_mapping`][DocumentType.index_mapping] when the request is sent.
+
+    [documents-mod]: ../../../types/document/index.html
+    [DocumentType.index_mapping]: ../../../types/document/trait.DocumentType.html#method.index_mapping
+    */
+    pub fn mapping<TDocument>(mut self) -> Self
+    where
+        TDocument: DocumentType + StaticType,
+    {
+        if includes_type_name::<TDocument>() {
+            self.params_builder = self
+                .params_builder
+                .fluent(|params| params.url_param("include_type_name", true))
+                .shared();
+        }
+
+        self.inner.mapping = Some(document_mappings::<TDocument>);
+        self
+    }
+
+    /** Add an alias to new indices. */
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.inner.body.aliases.insert(alias.into(), json!({}));
+        self
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl IndexTemplatePutRequestBuilder<SyncSender> {
+    /**
+    Send an `IndexTemplatePutRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    Put a template called `logs` for time-based indices:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index_template("logs").put("logs-*").send()?;
+
+    assert!(response.acknowledged());
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<CommandResponse, Error> {
+        let req = self.inner.into_request()?;
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl IndexTemplatePutRequestBuilder<AsyncSender> {
+    /**
+    Send an `IndexTemplatePutRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised command response.
+
+    # Examples
+
+    Put a template called `logs` for time-based indices:
+
+    ```no_run
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.index_template("logs").put("logs-*").send();
+
+    future.and_then(|response| {
+        assert!(response.acknowledged());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending {
+        let (client, params_builder, inner) = (self.client, self.params_builder, self.inner);
+
+        let req_future = client.sender.maybe_async(move || inner.into_request());
+
+        let res_future = req_future.and_then(move |req| {
+            RequestBuilder::new(client, params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response())
+        });
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<CommandResponse>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+    use serde_json::{
+        self,
+        Value,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+    }
+
+    #[derive(ElasticType)]
+    #[elastic(crate_root = "crate::types")]
+    struct TestDoc {}
+
+    #[derive(ElasticType)]
+    #[elastic(crate_root = "crate::types", ty = "test_ty")]
+    struct TestTypedDoc {}
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .index_template("testtemplate")
+            .put("test-*")
+            .inner
+            .into_request()
+            .unwrap();
+
+        let expected_body = json!({
+            "index_patterns": ["test-*"]
+        });
+
+        let actual_body: Value = serde_json::from_slice(&req.body).unwrap();
+
+        assert_eq!("/_template/testtemplate", req.url.as_ref());
+        assert_eq!(expected_body.to_string(), actual_body.to_string());
+    }
+
+    #[test]
+    fn specify_template() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .index_template("testtemplate")
+            .put("test-*")
+            .index_pattern("other-*")
+            .order(1)
+            .settings(json!({ "index": { "number_of_shards": 1 } }))
+            .mapping::<TestDoc>()
+            .alias("test")
+            .inner
+            .into_request()
+            .unwrap();
+
+        let expected_body = json!({
+            "index_patterns": ["test-*", "other-*"],
+            "order": 1,
+            "settings": {
+                "index": {
+                    "number_of_shards": 1
+                }
+            },
+            "mappings": {
+                "properties": {}
+            },
+            "aliases": {
+                "test": {}
+            }
+        });
+
+        let actual_body: Value = serde_json::from_slice(&req.body).unwrap();
+
+        assert_eq!(expected_body.to_string(), actual_body.to_string());
+    }
+
+    #[test]
+    fn specify_typed_mapping() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let builder = client
+            .index_template("testtemplate")
+            .put("test-*")
+            .mapping::<TestTypedDoc>();
+
+        let params = builder
+            .params_builder
+            .into_value(|| RequestParams::new("http://eshost:9200"));
+
+        let req = builder.inner.into_request().unwrap();
+
+        let actual_body: Value = serde_json::from_slice(&req.body).unwrap();
+
+        assert_eq!(
+            json!({ "test_ty": { "properties": {} } }),
+            actual_body["mappings"]
+        );
+        assert_eq!(
+            Some("?include_type_name=true".to_owned()),
+            params.get_url_qry().1
+        );
+    }
+}
@@ -0,0 +1,477 @@
+/*!
+Builders for [update index aliases requests][docs-aliases].
+
+[docs-aliases]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-aliases.html
+*/
+
+use futures::Future;
+use serde_json::Value;
+
+use crate::{
+    client::{
+        requests::{
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::CommandResponse,
+        IndexClient,
+    },
+    endpoints::IndicesUpdateAliasesRequest,
+    error::Error,
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::{
+        Index,
+        Name,
+    },
+};
+
+/**
+An [update index aliases request][docs-aliases] builder that can be configured before sending.
+
+Call [`Client.index.add_alias`][Client.index.add_alias], [`Client.index.remove_alias`][Client.index.remove_alias] or [`Client.index.swap_alias`][Client.index.swap_alias] to get an `IndexUpdateAliasesRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-aliases]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-aliases.html
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.index.add_alias]: ../../struct.IndexClient.html#update-aliases-request
+[Client.index.remove_alias]: ../../struct.IndexClient.html#update-aliases-request
+[Client.index.swap_alias]: ../../struct.IndexClient.html#update-aliases-request
+*/
+pub type IndexUpdateAliasesRequestBuilder<TSender> =
+    RequestBuilder<TSender, IndexUpdateAliasesRequestInner>;
+
+#[doc(hidden)]
+pub struct IndexUpdateAliasesRequestInner {
+    index: Index<'static>,
+    alias: Name<'static>,
+    remove_from: Option<Index<'static>>,
+    add: Option<AddAlias>,
+}
+
+#[derive(Serialize)]
+struct AddAliasAction<'a> {
+    index: &'a str,
+    alias: &'a str,
+    #[serde(flatten)]
+    options: AddAlias,
+}
+
+#[derive(Serialize, Default)]
+struct AddAlias {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filter: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    routing: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index_routing: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    search_routing: Option<String>,
+}
+
+/**
+# Update aliases request
+*/
+impl<TSender> IndexClient<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create an [`IndexUpdateAliasesRequestBuilder`][IndexUpdateAliasesRequestBuilder] that adds an alias to this index.
+
+    The alias can be given a filter and routing values using the [builder methods][builder-methods].
+
+    For more details, see:
+
+    - [builder methods][builder-methods]
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Add an alias called `myindex_errors` to an index called `myindex` that only matches errors:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index("myindex")
+                         .add_alias("myindex_errors")
+                         .filter(json!({ "term": { "level": "error" } }))
+                         .routing("1")
+                         .send()?;
+
+    assert!(response.acknowledged());
+    # Ok(())
+    # }
+    ```
+
+    [IndexUpdateAliasesRequestBuilder]: requests/index_update_aliases/type.IndexUpdateAliasesRequestBuilder.html
+    [builder-methods]: requests/index_update_aliases/type.IndexUpdateAliasesRequestBuilder.html#builder-methods
+    [send-sync]: requests/index_update_aliases/type.IndexUpdateAliasesRequestBuilder.html#send-synchronously
+    [send-async]: requests/index_update_aliases/type.IndexUpdateAliasesRequestBuilder.html#send-asynchronously
+    */
+    pub fn add_alias(
+        self,
+        alias: impl Into<Name<'static>>,
+    ) -> IndexUpdateAliasesRequestBuilder<TSender> {
+        RequestBuilder::initial(
+            self.inner,
+            IndexUpdateAliasesRequestInner {
+                index: self.index,
+                alias: alias.into(),
+                remove_from: None,
+                add: Some(AddAlias::default()),
+            },
+        )
+    }
+
+    /**
+    Create an [`IndexUpdateAliasesRequestBuilder`][IndexUpdateAliasesRequestBuilder] that removes an alias from this index.
+
+    # Examples
+
+    Remove an alias called `myalias` from an index called `myindex`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index("myindex").remove_alias("myalias").send()?;
+
+    assert!(response.acknowledged());
+    # Ok(())
+    # }
+    ```
+
+    [IndexUpdateAliasesRequestBuilder]: requests/index_update_aliases/type.IndexUpdateAliasesRequestBuilder.html
+    */
+    pub fn remove_alias(
+        self,
+        alias: impl Into<Name<'static>>,
+    ) -> IndexUpdateAliasesRequestBuilder<TSender> {
+        RequestBuilder::initial(
+            self.inner,
+            IndexUpdateAliasesRequestInner {
+                remove_from: Some(self.index.clone()),
+                index: self.index,
+                alias: alias.into(),
+                add: None,
+            },
+        )
+    }
+
+    /**
+    Create an [`IndexUpdateAliasesRequestBuilder`][IndexUpdateAliasesRequestBuilder] that atomically moves an alias from other indices to this index.
+
+    The alias is removed from the indices in `from` and added to this index in a single request, so there's no point where the alias doesn't resolve to any index.
+    Multiple indices can be given in `from` as a comma-separated list.
+
+    # Examples
+
+    Move an alias called `myalias` from an index called `myindex_v1` to an index called `myindex_v2`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index("myindex_v2")
+                         .swap_alias("myalias", "myindex_v1")
+                         .send()?;
+
+    assert!(response.acknowledged());
+    # Ok(())
+    # }
+    ```
+
+    [IndexUpdateAliasesRequestBuilder]: requests/index_update_aliases/type.IndexUpdateAliasesRequestBuilder.html
+    */
+    pub fn swap_alias(
+        self,
+        alias: impl Into<Name<'static>>,
+        from: impl Into<Index<'static>>,
+    ) -> IndexUpdateAliasesRequestBuilder<TSender> {
+        RequestBuilder::initial(
+            self.inner,
+            IndexUpdateAliasesRequestInner {
+                index: self.index,
+                alias: alias.into(),
+                remove_from: Some(from.into()),
+                add: Some(AddAlias::default()),
+            },
+        )
+    }
+}
+
+impl IndexUpdateAliasesRequestInner {
+    fn into_request(self) -> IndicesUpdateAliasesRequest<'static, Value> {
+        let alias: &str = self.alias.as_ref();
+        let mut actions = Vec::new();
+
+        if let Some(remove_from) = self.remove_from {
+            let remove_from: &str = remove_from.as_ref();
+            let indices = remove_from
+                .split(',')
+                .map(|index| index.trim())
+                .collect::<Vec<_>>();
+
+            actions.push(json!({ "remove": { "indices": indices, "alias": alias } }));
+        }
+
+        if let Some(options) = self.add {
+            let action = AddAliasAction {
+                index: self.index.as_ref(),
+                alias,
+                options,
+            };
+
+            actions.push(json!({ "add": action }));
+        }
+
+        IndicesUpdateAliasesRequest::new(json!({ "actions": actions }))
+    }
+
+    fn with_add(mut self, f: impl FnOnce(&mut AddAlias)) -> Self {
+        if let Some(ref mut add) = self.add {
+            f(add);
+        }
+
+        self
+    }
+}
+
+/**
+# Builder methods
+
+Configure an `IndexUpdateAliasesRequestBuilder` before sending it.
+
+These methods only apply when an alias is being added to an index.
+They're ignored when an alias is only being removed.
+*/
+impl<TSender> IndexUpdateAliasesRequestBuilder<TSender>
+where
+    TSender: Sender,
+{
+    /** Set a query that filters the documents visible through the alias. */
+    pub fn filter(mut self, filter: impl Into<Value>) -> Self {
+        let filter = filter.into();
+        self.inner = self.inner.with_add(move |add| add.filter = Some(filter));
+        self
+    }
+
+    /** Set the routing value used for both indexing and searching through the alias. */
+    pub fn routing(mut self, routing: impl Into<String>) -> Self {
+        let routing = routing.into();
+        self.inner = self.inner.with_add(move |add| add.routing = Some(routing));
+        self
+    }
+
+    /** Set the routing value used when indexing documents through the alias. */
+    pub fn index_routing(mut self, routing: impl Into<String>) -> Self {
+        let routing = routing.into();
+        self.inner = self.inner.with_add(move |add| add.index_routing = Some(routing));
+        self
+    }
+
+    /** Set the routing values used when searching documents through the alias. */
+    pub fn search_routing(mut self, routing: impl Into<String>) -> Self {
+        let routing = routing.into();
+        self.inner = self.inner.with_add(move |add| add.search_routing = Some(routing));
+        self
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl IndexUpdateAliasesRequestBuilder<SyncSender> {
+    /**
+    Send an `IndexUpdateAliasesRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    Add an alias called `myalias` to an index called `myindex`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index("myindex").add_alias("myalias").send()?;
+
+    assert!(response.acknowledged());
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<CommandResponse, Error> {
+        let req = self.inner.into_request();
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl IndexUpdateAliasesRequestBuilder<AsyncSender> {
+    /**
+    Send an `IndexUpdateAliasesRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised command response.
+
+    # Examples
+
+    Add an alias called `myalias` to an index called `myindex`:
+
+    ```no_run
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.index("myindex").add_alias("myalias").send();
+
+    future.and_then(|response| {
+        assert!(response.acknowledged());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending {
+        let req = self.inner.into_request();
+
+        let res_future =
+            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response());
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<CommandResponse>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+    }
+
+    #[test]
+    fn add_alias_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .index("testindex")
+            .add_alias("testalias")
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "actions": [
+                { "add": { "index": "testindex", "alias": "testalias" } }
+            ]
+        });
+
+        assert_eq!("/_aliases", req.url.as_ref());
+        assert_eq!(expected, req.body);
+    }
+
+    #[test]
+    fn add_alias_with_filter_and_routing() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .index("testindex")
+            .add_alias("testalias")
+            .filter(json!({ "term": { "level": "error" } }))
+            .index_routing("1")
+            .search_routing("1,2")
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "actions": [
+                {
+                    "add": {
+                        "index": "testindex",
+                        "alias": "testalias",
+                        "filter": { "term": { "level": "error" } },
+                        "index_routing": "1",
+                        "search_routing": "1,2"
+                    }
+                }
+            ]
+        });
+
+        assert_eq!(expected, req.body);
+    }
+
+    #[test]
+    fn remove_alias_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .index("testindex")
+            .remove_alias("testalias")
+            .routing("1")
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "actions": [
+                { "remove": { "indices": ["testindex"], "alias": "testalias" } }
+            ]
+        });
+
+        assert_eq!(expected, req.body);
+    }
+
+    #[test]
+    fn swap_alias_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .index("testindex_v2")
+            .swap_alias("testalias", "testindex_v0,testindex_v1")
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "actions": [
+                {
+                    "remove": {
+                        "indices": ["testindex_v0", "testindex_v1"],
+                        "alias": "testalias"
+                    }
+                },
+                { "add": { "index": "testindex_v2", "alias": "testalias" } }
+            ]
+        });
+
+        assert_eq!(expected, req.body);
+    }
+}
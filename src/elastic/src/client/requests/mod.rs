@@ -6,10 +6,18 @@ This module contains implementation details that are useful if you want to custo
 
 use std::{
     marker::PhantomData,
-    sync::Arc,
+    mem,
+    sync::{
+        Arc,
+        Mutex,
+    },
+    time::Duration,
 };
 
-use fluent_builder::SharedFluentBuilder;
+use fluent_builder::{
+    SharedFluentBuilder,
+    Stack,
+};
 use futures::{
     Future,
     Poll,
@@ -46,44 +54,78 @@ pub use self::sql::SqlRequestBuilder;
 
 // Document requests
 pub mod document_delete;
+pub mod document_delete_by_query;
 pub mod document_get;
+pub mod document_get_many;
 pub mod document_index;
+pub mod document_mapping_diff;
 pub mod document_put_mapping;
 pub mod document_update;
+pub mod document_update_by_query;
 
 #[doc(inline)]
 pub use self::{
     document_delete::DeleteRequestBuilder,
+    document_delete_by_query::DeleteByQueryRequestBuilder,
     document_get::GetRequestBuilder,
+    document_get_many::GetManyRequestBuilder,
     document_index::IndexRequestBuilder,
+    document_mapping_diff::MappingDiffRequestBuilder,
     document_put_mapping::PutMappingRequestBuilder,
     document_update::UpdateRequestBuilder,
+    document_update_by_query::UpdateByQueryRequestBuilder,
 };
 
 // Index requests
+pub mod index_alias_exists;
 pub mod index_close;
 pub mod index_create;
 pub mod index_delete;
 pub mod index_exists;
+pub mod index_get_aliases;
+pub mod index_get_settings;
+pub mod index_migrate;
 pub mod index_open;
+pub mod index_put_settings;
+pub mod index_template_delete;
+pub mod index_template_exists;
+pub mod index_template_get;
+pub mod index_template_put;
+pub mod index_update_aliases;
 
 #[doc(inline)]
 pub use self::{
+    index_alias_exists::IndexAliasExistsRequestBuilder,
     index_close::IndexCloseRequestBuilder,
     index_create::IndexCreateRequestBuilder,
     index_delete::IndexDeleteRequestBuilder,
     index_exists::IndexExistsRequestBuilder,
+    index_get_aliases::IndexGetAliasesRequestBuilder,
+    index_get_settings::IndexGetSettingsRequestBuilder,
+    index_migrate::IndexMigrateBuilder,
     index_open::IndexOpenRequestBuilder,
+    index_put_settings::IndexPutSettingsRequestBuilder,
+    index_template_delete::IndexTemplateDeleteRequestBuilder,
+    index_template_exists::IndexTemplateExistsRequestBuilder,
+    index_template_get::IndexTemplateGetRequestBuilder,
+    index_template_put::IndexTemplatePutRequestBuilder,
+    index_update_aliases::IndexUpdateAliasesRequestBuilder,
 };
 
 // Misc requests
 pub mod bulk;
+pub mod msearch;
 pub mod ping;
+pub mod reindex;
+pub mod task;
 
 #[doc(inline)]
 pub use self::{
     bulk::BulkRequestBuilder,
+    msearch::MsearchRequestBuilder,
     ping::PingRequestBuilder,
+    reindex::ReindexRequestBuilder,
+    task::TaskHandle,
 };
 
 pub mod common;
@@ -104,7 +146,7 @@ where
     TSender: Sender,
 {
     client: Client<TSender>,
-    params_builder: SharedFluentBuilder<RequestParams>,
+    params_builder: SharedFluentBuilder<RequestParams, Stack>,
     inner: TRequest,
 }
 
@@ -127,7 +169,7 @@ where
 
     fn new(
         client: Client<TSender>,
-        builder: SharedFluentBuilder<RequestParams>,
+        builder: SharedFluentBuilder<RequestParams, Stack>,
         req: TRequest,
     ) -> Self {
         RequestBuilder {
@@ -137,6 +179,22 @@ where
         }
     }
 
+    /**
+    Share the parameters for this request with another request sent along with it.
+
+    The parameters are built once, for whichever request is sent first, and the same parameters are used for the other.
+    */
+    fn share_params(&mut self) -> SharedFluentBuilder<RequestParams, Stack> {
+        let builder = mem::replace(&mut self.params_builder, SharedFluentBuilder::new());
+        let shared = Arc::new(Mutex::new(SharedParams {
+            builder: Some(builder),
+            built: None,
+        }));
+
+        self.params_builder = shared_params_builder(shared.clone());
+        shared_params_builder(shared)
+    }
+
     /**
     Override the parameters for this request.
 
@@ -207,6 +265,69 @@ where
 
         self
     }
+
+    /**
+    Override the most time to wait for a connection to a node to be established for this request.
+
+    If the connection isn't established in time then the request fails with an [`Error::Timeout`][Error::Timeout].
+
+    [Error::Timeout]: ../../error/enum.Error.html#variant.Timeout
+    */
+    pub fn connect_timeout(self, timeout: Duration) -> Self {
+        self.params_fluent(move |p| p.connect_timeout(timeout))
+    }
+
+    /**
+    Override the most time to wait for a node to respond to each attempt of this request, and for each read of its response body.
+
+    If the node doesn't respond in time then the request fails with an [`Error::Timeout`][Error::Timeout].
+
+    [Error::Timeout]: ../../error/enum.Error.html#variant.Timeout
+    */
+    pub fn read_timeout(self, timeout: Duration) -> Self {
+        self.params_fluent(move |p| p.read_timeout(timeout))
+    }
+
+    /**
+    Override the most time this request can take, across all attempts and backoffs, from connecting to a node until its response body is read.
+
+    If the request doesn't complete in time then it fails with an [`Error::Timeout`][Error::Timeout].
+
+    # Examples
+
+    Time out a search that takes longer than 5 seconds:
+
+    ```no_run
+    # use std::time::Duration;
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # use elastic::Error;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.search::<Value>()
+                         .total_timeout(Duration::from_secs(5))
+                         .send();
+
+    match response {
+        Ok(response) => {
+            // do something with the response
+        }
+        Err(Error::Timeout(e)) => {
+            // handle a request that timed out
+        }
+        Err(e) => {
+            // handle any other error
+        }
+    }
+    # Ok(())
+    # }
+    ```
+
+    [Error::Timeout]: ../../error/enum.Error.html#variant.Timeout
+    */
+    pub fn total_timeout(self, timeout: Duration) -> Self {
+        self.params_fluent(move |p| p.total_timeout(timeout))
+    }
 }
 
 /**
@@ -259,6 +380,28 @@ impl<TRequest> RequestBuilder<AsyncSender, TRequest> {
     }
 }
 
+/** The parameters for requests that are sent together. */
+struct SharedParams {
+    builder: Option<SharedFluentBuilder<RequestParams, Stack>>,
+    built: Option<RequestParams>,
+}
+
+fn shared_params_builder(
+    shared: Arc<Mutex<SharedParams>>,
+) -> SharedFluentBuilder<RequestParams, Stack> {
+    SharedFluentBuilder::<RequestParams, Stack>::new()
+        .fluent(move |params| {
+            let mut shared = shared.lock().expect("lock poisoned");
+
+            if let Some(builder) = shared.builder.take() {
+                shared.built = Some(builder.into_value(move || params));
+            }
+
+            shared.built.clone().expect("params are built")
+        })
+        .shared()
+}
+
 /** A future returned by calling `send`. */
 pub struct Pending<T> {
     inner: Box<dyn Future<Item = T, Error = Error> + Send>,
@@ -295,19 +438,46 @@ pub mod prelude {
         BulkOperation,
     };
 
+    pub use super::msearch::{
+        msearch,
+        MsearchOperation,
+    };
+
+    pub use super::common::{
+        Analysis,
+        IndexSettings,
+    };
+
     pub use super::{
+        DeleteByQueryRequestBuilder,
         DeleteRequestBuilder,
+        GetManyRequestBuilder,
         GetRequestBuilder,
+        IndexAliasExistsRequestBuilder,
         IndexCloseRequestBuilder,
         IndexCreateRequestBuilder,
         IndexDeleteRequestBuilder,
+        IndexGetAliasesRequestBuilder,
+        IndexGetSettingsRequestBuilder,
+        IndexMigrateBuilder,
         IndexOpenRequestBuilder,
+        IndexPutSettingsRequestBuilder,
         IndexRequestBuilder,
+        IndexTemplateDeleteRequestBuilder,
+        IndexTemplateExistsRequestBuilder,
+        IndexTemplateGetRequestBuilder,
+        IndexTemplatePutRequestBuilder,
+        IndexUpdateAliasesRequestBuilder,
+        MappingDiffRequestBuilder,
+        MsearchRequestBuilder,
         PingRequestBuilder,
         PutMappingRequestBuilder,
         RawRequestBuilder,
+        ReindexRequestBuilder,
         SearchRequestBuilder,
         SqlRequestBuilder,
+        TaskHandle,
+        UpdateByQueryRequestBuilder,
         UpdateRequestBuilder,
     };
 }
@@ -0,0 +1,587 @@
+/*!
+Builders for [multi search requests][docs-msearch].
+
+[docs-msearch]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-multi-search.html
+*/
+
+use futures::{
+    future,
+    Future,
+};
+use serde::{
+    de::DeserializeOwned,
+    ser::Serialize,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+use std::{
+    error::Error as StdError,
+    fmt,
+    io::Write,
+    marker::PhantomData,
+};
+
+use crate::{
+    client::{
+        requests::{
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::MsearchResponse,
+        Client,
+    },
+    endpoints::MsearchRequest,
+    error::{
+        self,
+        Error,
+    },
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::{
+        Index,
+        Type,
+    },
+};
+
+/**
+A [multi search request][docs-msearch] builder that can be configured before sending.
+
+Call [`Client.msearch`][Client.msearch] to get a `MsearchRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-msearch]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-multi-search.html
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.msearch]: ../../struct.Client.html#multi-search-request
+*/
+pub type MsearchRequestBuilder<TSender, TDocument> =
+    RequestBuilder<TSender, MsearchRequestInner<TDocument>>;
+
+#[doc(hidden)]
+pub struct MsearchRequestInner<TDocument> {
+    index: Option<Index<'static>>,
+    ty: Option<Type<'static>>,
+    body: Vec<u8>,
+    errs: Vec<Error>,
+    _marker: PhantomData<TDocument>,
+}
+
+/**
+# Multi search request
+*/
+impl<TSender> Client<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create a [`MsearchRequestBuilder`][MsearchRequestBuilder] with this `Client` that can be configured before sending.
+
+    Searches are added to the request with the [`push`][builder-push] or [`extend`][builder-extend] methods.
+    Each search can target its own index and type.
+
+    For more details, see:
+
+    - [builder methods][builder-methods]
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Run a few queries for a [`DocumentType`][documents-mod] called `MyType` in a single request:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # #[macro_use] extern crate serde_json;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Debug, Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.msearch::<MyType>()
+                         .push(msearch().index("myindex").body(json!({
+                             "query": {
+                                 "match_all": {}
+                             }
+                         })))
+                         .push(msearch().index("myotherindex").body(json!({
+                             "query": {
+                                 "query_string": {
+                                     "query": "a query string"
+                                 }
+                             }
+                         })))
+                         .send()?;
+
+    // Iterate through the searches in the order they were added
+    for search in response {
+        match search {
+            Ok(search) => println!("hits: {:?}", search.hits().count()),
+            Err(err) => println!("err: {:?}", err),
+        }
+    }
+    # Ok(())
+    # }
+    ```
+
+    [MsearchRequestBuilder]: requests/msearch/type.MsearchRequestBuilder.html
+    [builder-methods]: requests/msearch/type.MsearchRequestBuilder.html#builder-methods
+    [builder-push]: requests/msearch/type.MsearchRequestBuilder.html#method.push
+    [builder-extend]: requests/msearch/type.MsearchRequestBuilder.html#method.extend
+    [send-sync]: requests/msearch/type.MsearchRequestBuilder.html#send-synchronously
+    [send-async]: requests/msearch/type.MsearchRequestBuilder.html#send-asynchronously
+    [documents-mod]: ../types/document/index.html
+    */
+    pub fn msearch<TDocument>(&self) -> MsearchRequestBuilder<TSender, TDocument>
+    where
+        TDocument: DeserializeOwned,
+    {
+        RequestBuilder::initial(self.clone(), MsearchRequestInner::new())
+    }
+}
+
+impl<TDocument> MsearchRequestInner<TDocument> {
+    fn new() -> Self {
+        MsearchRequestInner {
+            index: None,
+            ty: None,
+            body: Vec::new(),
+            errs: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn push<TBody>(&mut self, search: MsearchOperation<TBody>)
+    where
+        TBody: Serialize,
+    {
+        if let Err(e) = search.write(&mut self.body) {
+            self.errs.push(error::request(e));
+        }
+    }
+
+    fn into_request(self) -> Result<MsearchRequest<'static, Vec<u8>>, Error> {
+        if !self.errs.is_empty() {
+            return Err(error::request(MsearchBodyError(self.errs)));
+        }
+
+        match (self.index, self.ty) {
+            (Some(index), Some(ty)) => Ok(MsearchRequest::for_index_ty(index, ty, self.body)),
+            (Some(index), None) => Ok(MsearchRequest::for_index(index, self.body)),
+            (None, None) => Ok(MsearchRequest::new(self.body)),
+            (None, Some(_)) => Err(error::request(MsearchRequestError(
+                "missing `index` parameter".to_owned(),
+            ))),
+        }
+    }
+}
+
+/**
+# Builder methods
+
+Configure a `MsearchRequestBuilder` before sending it.
+*/
+impl<TSender, TDocument> MsearchRequestBuilder<TSender, TDocument>
+where
+    TSender: Sender,
+{
+    /**
+    Set the default index for searches in the request.
+
+    Searches that specify their own index will override this value.
+    */
+    pub fn index(mut self, index: impl Into<Index<'static>>) -> Self {
+        self.inner.index = Some(index.into());
+        self
+    }
+
+    /**
+    Set the default type for searches in the request.
+
+    Searches that specify their own type will override this value.
+    If a type is given then an index must also be given.
+    */
+    pub fn ty(mut self, ty: impl Into<Type<'static>>) -> Self {
+        self.inner.ty = Some(ty.into());
+        self
+    }
+
+    /**
+    Push a search onto the multi search request.
+
+    # Deferred errors
+
+    If the search body can't be serialized then sending the request will return an error.
+    */
+    pub fn push<TBody>(mut self, search: MsearchOperation<TBody>) -> Self
+    where
+        TBody: Serialize,
+    {
+        self.inner.push(search);
+        self
+    }
+
+    /**
+    Push a collection of searches onto the multi search request.
+
+    # Deferred errors
+
+    If any search bodies can't be serialized then sending the request will return an error.
+    */
+    pub fn extend<TIter, TBody>(mut self, iter: TIter) -> Self
+    where
+        TIter: IntoIterator<Item = MsearchOperation<TBody>>,
+        TBody: Serialize,
+    {
+        for search in iter.into_iter() {
+            self.inner.push(search);
+        }
+        self
+    }
+}
+
+impl<TSender, TDocument, TBody> Extend<MsearchOperation<TBody>>
+    for MsearchRequestBuilder<TSender, TDocument>
+where
+    TSender: Sender,
+    TBody: Serialize,
+{
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = MsearchOperation<TBody>>,
+    {
+        for search in iter.into_iter() {
+            self.inner.push(search);
+        }
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl<TDocument> MsearchRequestBuilder<SyncSender, TDocument>
+where
+    TDocument: DeserializeOwned,
+{
+    /**
+    Send a `MsearchRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    Run a couple of queries for a [`DocumentType`][documents-mod] called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Debug, Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.msearch::<MyType>()
+                         .push(msearch().index("myindex"))
+                         .push(msearch().index("myotherindex"))
+                         .send()?;
+
+    for search in response {
+        println!("{:?}", search);
+    }
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    [documents-mod]: ../../../types/document/index.html
+    */
+    pub fn send(self) -> Result<MsearchResponse<TDocument>, Error> {
+        let req = self.inner.into_request()?;
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl<TDocument> MsearchRequestBuilder<AsyncSender, TDocument>
+where
+    TDocument: DeserializeOwned + Send + 'static,
+{
+    /**
+    Send a `MsearchRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised multi search response.
+
+    # Examples
+
+    Run a couple of queries for a [`DocumentType`][documents-mod] called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Debug, Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.msearch::<MyType>()
+                       .push(msearch().index("myindex"))
+                       .push(msearch().index("myotherindex"))
+                       .send();
+
+    future.and_then(|response| {
+        for search in response {
+            println!("{:?}", search);
+        }
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    [documents-mod]: ../../../types/document/index.html
+    */
+    pub fn send(self) -> Pending<TDocument> {
+        let req = match self.inner.into_request() {
+            Ok(req) => req,
+            Err(e) => return Pending::new(future::err(e)),
+        };
+
+        let res_future =
+            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response());
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending<TDocument> = BasePending<MsearchResponse<TDocument>>;
+
+/**
+A single search in a multi search request.
+
+Call [`msearch`][msearch] to get a `MsearchOperation`.
+
+[msearch]: fn.msearch.html
+*/
+pub struct MsearchOperation<TBody> {
+    index: Option<Index<'static>>,
+    ty: Option<Type<'static>>,
+    body: TBody,
+}
+
+/**
+Create a search to push onto a [multi search request][MsearchRequestBuilder].
+
+If no body is specified then an empty query will be used.
+If no index is specified then the index of the multi search request will be used.
+
+# Examples
+
+```
+# #[macro_use] extern crate serde_json;
+# use elastic::prelude::*;
+let search = msearch()
+    .index("myindex")
+    .body(json!({
+        "query": {
+            "match_all": {}
+        }
+    }));
+```
+
+[MsearchRequestBuilder]: type.MsearchRequestBuilder.html
+*/
+pub fn msearch() -> MsearchOperation<Value> {
+    MsearchOperation {
+        index: None,
+        ty: None,
+        body: Value::Object(Map::new()),
+    }
+}
+
+impl<TBody> MsearchOperation<TBody> {
+    /** Set the indices for the search. */
+    pub fn index(mut self, index: impl Into<Index<'static>>) -> Self {
+        self.index = Some(index.into());
+        self
+    }
+
+    /** Set the types for the search. */
+    pub fn ty(mut self, ty: impl Into<Type<'static>>) -> Self {
+        self.ty = Some(ty.into());
+        self
+    }
+
+    /** Set the body for the search. */
+    pub fn body<TNewBody>(self, body: TNewBody) -> MsearchOperation<TNewBody>
+    where
+        TNewBody: Serialize,
+    {
+        MsearchOperation {
+            index: self.index,
+            ty: self.ty,
+            body,
+        }
+    }
+}
+
+impl<TBody> MsearchOperation<TBody>
+where
+    TBody: Serialize,
+{
+    /** Write the search header and body as newline-delimited json. */
+    fn write(&self, mut writer: impl Write) -> Result<(), serde_json::Error> {
+        #[derive(Serialize)]
+        struct Header<'a> {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            index: Option<&'a str>,
+            #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+            ty: Option<&'a str>,
+        }
+
+        let header = Header {
+            index: self.index.as_ref().map(|index| index.as_ref()),
+            ty: self.ty.as_ref().map(|ty| ty.as_ref()),
+        };
+
+        // Write to an intermediate buffer so a failed search doesn't leave a partial line in the body
+        let mut buf = serde_json::to_vec(&header)?;
+        buf.push(b'\n');
+        serde_json::to_writer(&mut buf, &self.body)?;
+        buf.push(b'\n');
+
+        writer.write_all(&buf).map_err(serde_json::Error::io)
+    }
+}
+
+#[derive(Debug)]
+struct MsearchBodyError(Vec<Error>);
+
+impl fmt::Display for MsearchBodyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "errors ({}) writing multi search request body:", self.0.len())?;
+
+        for err in &self.0 {
+            writeln!(f, "{}", err)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl StdError for MsearchBodyError {
+    fn description(&self) -> &str {
+        "errors writing multi search request body"
+    }
+}
+
+#[derive(Debug)]
+struct MsearchRequestError(String);
+
+impl fmt::Display for MsearchRequestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.0)
+    }
+}
+
+impl StdError for MsearchRequestError {
+    fn description(&self) -> &str {
+        "error building multi search request"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending<Value>>();
+    }
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client.msearch::<Value>().inner.into_request().unwrap();
+
+        assert_eq!("/_msearch", req.url.as_ref());
+        assert!(req.body.is_empty());
+    }
+
+    #[test]
+    fn specify_index() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .msearch::<Value>()
+            .index("new-idx")
+            .inner
+            .into_request()
+            .unwrap();
+
+        assert_eq!("/new-idx/_msearch", req.url.as_ref());
+    }
+
+    #[test]
+    fn specify_ty_without_index() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client.msearch::<Value>().ty("new-ty").inner.into_request();
+
+        assert!(req.is_err());
+    }
+
+    #[test]
+    fn specify_searches() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .msearch::<Value>()
+            .push(msearch())
+            .push(
+                msearch()
+                    .index("idx-a")
+                    .ty("ty-a")
+                    .body(json!({ "query": { "match_all": {} } })),
+            )
+            .extend(vec![msearch().index("idx-b")])
+            .inner
+            .into_request()
+            .unwrap();
+
+        let expected = concat!(
+            "{}\n{}\n",
+            r#"{"index":"idx-a","type":"ty-a"}"#,
+            "\n",
+            r#"{"query":{"match_all":{}}}"#,
+            "\n",
+            r#"{"index":"idx-b"}"#,
+            "\n{}\n"
+        );
+
+        assert_eq!(expected, String::from_utf8(req.body).unwrap());
+    }
+}
@@ -102,7 +102,7 @@ where
     TSender: Sender,
     TEndpoint: Into<Endpoint<'static, TBody>>,
     TBody: Into<<TSender>::Body> + Send + 'static,
-    NodeAddresses<TSender>: NextParams,
+    NodeAddresses<TSender>: NextParams + Send + 'static,
     <NodeAddresses<TSender> as NextParams>::Params: Into<TSender::Params> + Send + 'static,
 {
     /**
@@ -173,10 +173,17 @@ where
         // Only try fetch a next address if an explicit `RequestParams` hasn't been given
         let params = match self.params_builder.try_into_value() {
             TryIntoValue::Value(value) => SendableRequestParams::Value(value),
-            TryIntoValue::Builder(builder) => SendableRequestParams::Builder {
-                params: client.addresses.next(),
-                builder,
-            },
+            TryIntoValue::Builder(builder) => {
+                // Retries are sent to the next address
+                let addresses = client.addresses.clone();
+
+                SendableRequestParams::Builder {
+                    params: client.addresses.next(),
+                    next: Box::new(move || addresses.next()),
+                    feedback: client.addresses.feedback(),
+                    builder,
+                }
+            }
         };
 
         let req = SendableRequest::new(endpoint, params);
@@ -208,7 +215,7 @@ mod tests {
     ) -> thread::JoinHandle<()> {
         let req = req.into();
         thread::spawn(move || {
-            assert_eq!("/test_index/test_ty/_search", *req.url);
+            assert_eq!("/test_index/test_ty/_search", **req.url());
         })
     }
 
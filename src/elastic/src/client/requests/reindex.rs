@@ -0,0 +1,667 @@
+/*!
+Builders for [reindex requests][docs-reindex].
+
+[docs-reindex]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-reindex.html
+*/
+
+use futures::Future;
+use serde::ser::Serialize;
+use serde_json::Value;
+
+use crate::{
+    client::{
+        requests::{
+            common::{
+                DefaultParams,
+                Script,
+                ScriptBuilder,
+            },
+            raw::RawRequestInner,
+            task::{
+                TaskHandle,
+                TaskKind,
+            },
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::ReindexResponse,
+        Client,
+    },
+    endpoints::ReindexRequest,
+    error::{
+        self,
+        Error,
+    },
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::Index,
+};
+
+/**
+A [reindex request][docs-reindex] builder that can be configured before sending.
+
+Call [`Client.reindex`][Client.reindex] to get a `ReindexRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-reindex]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-reindex.html
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.reindex]: ../../struct.Client.html#reindex-request
+*/
+pub type ReindexRequestBuilder<TSender, TParams> =
+    RequestBuilder<TSender, ReindexRequestInner<TParams>>;
+
+#[doc(hidden)]
+pub struct ReindexRequestInner<TParams> {
+    body: ReindexBody<TParams>,
+}
+
+#[derive(Serialize)]
+struct ReindexBody<TParams> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    conflicts: Option<&'static str>,
+    source: ReindexSource,
+    dest: ReindexDest,
+    #[serde(flatten)]
+    script: Option<Script<TParams>>,
+}
+
+#[derive(Serialize)]
+struct ReindexSource {
+    index: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remote: Option<RemoteSource>,
+}
+
+#[derive(Serialize)]
+struct ReindexDest {
+    index: String,
+}
+
+/**
+A remote Elasticsearch cluster to reindex documents from.
+
+The remote host needs to be whitelisted in the `reindex.remote.whitelist` setting of the destination cluster.
+
+# Examples
+
+Create a remote source with credentials:
+
+```
+# use elastic::client::requests::reindex::RemoteSource;
+let remote = RemoteSource::new("http://otherhost:9200").credentials("user", "pass");
+```
+*/
+#[derive(Serialize, Debug, Clone)]
+pub struct RemoteSource {
+    host: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    password: Option<String>,
+}
+
+impl RemoteSource {
+    /** Create a new remote source for the given host, like `http://otherhost:9200`. */
+    pub fn new(host: impl Into<String>) -> Self {
+        RemoteSource {
+            host: host.into(),
+            username: None,
+            password: None,
+        }
+    }
+
+    /** Set the credentials to use for basic authentication with the remote host. */
+    pub fn credentials(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self.password = Some(password.into());
+        self
+    }
+}
+
+impl From<String> for RemoteSource {
+    fn from(host: String) -> Self {
+        RemoteSource::new(host)
+    }
+}
+
+impl<'a> From<&'a str> for RemoteSource {
+    fn from(host: &'a str) -> Self {
+        RemoteSource::new(host)
+    }
+}
+
+/**
+# Reindex request
+*/
+impl<TSender> Client<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create a [`ReindexRequestBuilder`][ReindexRequestBuilder] with this `Client` that can be configured before sending.
+
+    Documents are copied from the `source` index into the `dest` index.
+    The `dest` index should be created with the appropriate mapping before reindexing.
+
+    For more details, see:
+
+    - [builder methods][builder-methods]
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Copy all documents from `myindex` into `mynewindex`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.reindex("myindex", "mynewindex")
+                         .send()?;
+
+    println!("created {} documents", response.created());
+    # Ok(())
+    # }
+    ```
+
+    Copy some documents and update them using a script:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.reindex("myindex", "mynewindex")
+                         .query(json!({
+                             "term": {
+                                 "user": "kimchy"
+                             }
+                         }))
+                         .script("ctx._source.likes = 0")
+                         .send()?;
+    # Ok(())
+    # }
+    ```
+
+    [ReindexRequestBuilder]: requests/reindex/type.ReindexRequestBuilder.html
+    [builder-methods]: requests/reindex/type.ReindexRequestBuilder.html#builder-methods
+    [send-sync]: requests/reindex/type.ReindexRequestBuilder.html#send-synchronously
+    [send-async]: requests/reindex/type.ReindexRequestBuilder.html#send-asynchronously
+    */
+    pub fn reindex(
+        &self,
+        source: impl Into<Index<'static>>,
+        dest: impl Into<Index<'static>>,
+    ) -> ReindexRequestBuilder<TSender, DefaultParams> {
+        RequestBuilder::initial(
+            self.clone(),
+            ReindexRequestInner::new(source.into(), dest.into()),
+        )
+    }
+}
+
+impl ReindexRequestInner<DefaultParams> {
+    fn new(source: Index<'static>, dest: Index<'static>) -> Self {
+        ReindexRequestInner {
+            body: ReindexBody {
+                conflicts: None,
+                source: ReindexSource {
+                    index: source.to_string(),
+                    query: None,
+                    remote: None,
+                },
+                dest: ReindexDest {
+                    index: dest.to_string(),
+                },
+                script: None,
+            },
+        }
+    }
+}
+
+impl<TParams> ReindexRequestInner<TParams>
+where
+    TParams: Serialize,
+{
+    fn into_request(self) -> Result<ReindexRequest<'static, Vec<u8>>, Error> {
+        let body = serde_json::to_vec(&self.body).map_err(error::request)?;
+
+        Ok(ReindexRequest::new(body))
+    }
+}
+
+/**
+# Builder methods
+
+Configure a `ReindexRequestBuilder` before sending it.
+*/
+impl<TSender, TParams> ReindexRequestBuilder<TSender, TParams>
+where
+    TSender: Sender,
+{
+    /** Only reindex documents in the source index that match the given query. */
+    pub fn query(mut self, query: impl Into<Value>) -> Self {
+        self.inner.body.source.query = Some(query.into());
+        self
+    }
+
+    /**
+    Reindex documents from a remote cluster.
+
+    The source index is read from the remote cluster and documents are indexed into the destination index on this cluster.
+    */
+    pub fn remote(mut self, remote: impl Into<RemoteSource>) -> Self {
+        self.inner.body.source.remote = Some(remote.into());
+        self
+    }
+
+    /**
+    Update the source of each document using [an inline script][painless-lang] before it's indexed into the destination.
+
+    [painless-lang]: https://www.elastic.co/guide/en/elasticsearch/reference/current/modules-scripting-painless.html
+    */
+    pub fn script<TScript, TNewParams>(
+        self,
+        builder: TScript,
+    ) -> ReindexRequestBuilder<TSender, TNewParams>
+    where
+        TScript: Into<ScriptBuilder<TNewParams>>,
+    {
+        let body = self.inner.body;
+
+        RequestBuilder::new(
+            self.client,
+            self.params_builder,
+            ReindexRequestInner {
+                body: ReindexBody {
+                    conflicts: body.conflicts,
+                    source: body.source,
+                    dest: body.dest,
+                    script: Some(builder.into().build()),
+                },
+            },
+        )
+    }
+
+    /**
+    Update the source of each document using [a script][painless-lang] configured by a fluent closure API.
+
+    [painless-lang]: https://www.elastic.co/guide/en/elasticsearch/reference/current/modules-scripting-painless.html
+    */
+    pub fn script_fluent<TScript, TNewParams>(
+        self,
+        source: TScript,
+        builder: impl FnOnce(ScriptBuilder<DefaultParams>) -> ScriptBuilder<TNewParams>,
+    ) -> ReindexRequestBuilder<TSender, TNewParams>
+    where
+        TScript: ToString,
+    {
+        let builder = builder(ScriptBuilder::new(source));
+
+        self.script(builder)
+    }
+
+    /**
+    Continue reindexing documents when there are version conflicts.
+
+    By default the request is aborted on the first version conflict.
+    */
+    pub fn proceed_on_conflicts(mut self) -> Self {
+        self.inner.body.conflicts = Some("proceed");
+        self
+    }
+
+    /** Throttle the request to the given number of requests per second. */
+    pub fn requests_per_second(mut self, requests_per_second: f32) -> Self {
+        self.params_builder = self
+            .params_builder
+            .fluent(move |params| params.url_param("requests_per_second", requests_per_second))
+            .shared();
+        self
+    }
+
+    /**
+    Split the request into the given number of slices that run in parallel.
+
+    Use `0` to let Elasticsearch choose the number of slices.
+    Slicing isn't supported when reindexing from a remote cluster.
+    */
+    pub fn slices(mut self, slices: u32) -> Self {
+        self.params_builder = self
+            .params_builder
+            .fluent(move |params| match slices {
+                0 => params.url_param("slices", "auto"),
+                slices => params.url_param("slices", slices),
+            })
+            .shared();
+        self
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl<TParams> ReindexRequestBuilder<SyncSender, TParams>
+where
+    TParams: Serialize,
+{
+    /**
+    Send a `ReindexRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until all documents have been reindexed.
+
+    # Examples
+
+    Copy all documents from `myindex` into `mynewindex`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.reindex("myindex", "mynewindex")
+                         .send()?;
+
+    assert!(response.is_ok());
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<ReindexResponse, Error> {
+        let req = self.inner.into_request()?;
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+
+    /**
+    Send a `ReindexRequestBuilder` synchronously without waiting for it to complete.
+
+    The request is sent with `wait_for_completion=false` and runs as a task in the background.
+    The returned [`TaskHandle`][TaskHandle] can be used to check the progress of the task, change its throttling, or wait for it to complete.
+
+    # Examples
+
+    Copy all documents from `myindex` into `mynewindex` in the background:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let task = client.reindex("myindex", "mynewindex")
+                     .requests_per_second(500.0)
+                     .send_task()?;
+
+    // Remove the throttling
+    task.rethrottle(None)?;
+
+    let response = task.wait()?;
+
+    assert!(response.is_ok());
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    [TaskHandle]: ../task/struct.TaskHandle.html
+    */
+    pub fn send_task(self) -> Result<TaskHandle<SyncSender, ReindexResponse>, Error> {
+        let req = self.inner.into_request()?;
+        let client = self.client.clone();
+
+        let params_builder = self
+            .params_builder
+            .fluent(|params| params.url_param("wait_for_completion", false))
+            .shared();
+
+        let started = RequestBuilder::new(self.client, params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()?;
+
+        Ok(TaskHandle::new(client, started, TaskKind::Reindex))
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl<TParams> ReindexRequestBuilder<AsyncSender, TParams>
+where
+    TParams: Serialize + Send + 'static,
+{
+    /**
+    Send a `ReindexRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised reindex response once all documents have been reindexed.
+
+    # Examples
+
+    Copy all documents from `myindex` into `mynewindex`:
+
+    ```no_run
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.reindex("myindex", "mynewindex")
+                       .send();
+
+    future.and_then(|response| {
+        assert!(response.is_ok());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending {
+        let (client, params_builder, inner) = (self.client, self.params_builder, self.inner);
+
+        let req_future = client.sender.maybe_async(move || inner.into_request());
+
+        let res_future = req_future.and_then(move |req| {
+            RequestBuilder::new(client, params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response())
+        });
+
+        Pending::new(res_future)
+    }
+
+    /**
+    Send a `ReindexRequestBuilder` asynchronously without waiting for it to complete.
+
+    The request is sent with `wait_for_completion=false` and runs as a task in the background.
+    The returned future resolves to a [`TaskHandle`][TaskHandle] that can be used to report the progress of the task, change its throttling, or wait for it to complete.
+
+    # Examples
+
+    Copy all documents from `myindex` into `mynewindex` in the background and print the progress:
+
+    ```no_run
+    # use futures::{Future, Stream};
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.reindex("myindex", "mynewindex")
+                       .send_task()
+                       .and_then(|task| {
+                           task.progress().for_each(|status| {
+                               println!("created {} of {}", status.created(), status.total());
+
+                               Ok(())
+                           })
+                       });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    [TaskHandle]: ../task/struct.TaskHandle.html
+    */
+    pub fn send_task(self) -> PendingTask {
+        let (client, inner) = (self.client, self.inner);
+
+        let params_builder = self
+            .params_builder
+            .fluent(|params| params.url_param("wait_for_completion", false))
+            .shared();
+
+        let req_future = client.sender.maybe_async(move || inner.into_request());
+
+        let res_future = req_future.and_then(move |req| {
+            RequestBuilder::new(client.clone(), params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response())
+                .map(move |started| TaskHandle::new(client, started, TaskKind::Reindex))
+        });
+
+        PendingTask::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<ReindexResponse>;
+
+/** A future returned by calling `send_task`. */
+pub type PendingTask = BasePending<TaskHandle<AsyncSender, ReindexResponse>>;
+
+#[cfg(test)]
+mod tests {
+    use super::RemoteSource;
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+    use serde_json::{
+        self,
+        Value,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+        assert_send::<super::PendingTask>();
+    }
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .reindex("old-idx", "new-idx")
+            .inner
+            .into_request()
+            .unwrap();
+
+        let expected_body = json!({
+            "source": {
+                "index": "old-idx"
+            },
+            "dest": {
+                "index": "new-idx"
+            }
+        });
+
+        let actual_body: Value = serde_json::from_slice(&req.body).unwrap();
+
+        assert_eq!("/_reindex", req.url.as_ref());
+        assert_eq!(expected_body, actual_body);
+    }
+
+    #[test]
+    fn specify_query_and_script() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .reindex("old-idx", "new-idx")
+            .query(json!({ "term": { "user": "kimchy" } }))
+            .script_fluent("ctx._source.a = params.str", |script| {
+                script.param("str", "some value")
+            })
+            .proceed_on_conflicts()
+            .inner
+            .into_request()
+            .unwrap();
+
+        let expected_body = json!({
+            "conflicts": "proceed",
+            "source": {
+                "index": "old-idx",
+                "query": {
+                    "term": {
+                        "user": "kimchy"
+                    }
+                }
+            },
+            "dest": {
+                "index": "new-idx"
+            },
+            "script": {
+                "inline": "ctx._source.a = params.str",
+                "params": {
+                    "str": "some value"
+                }
+            }
+        });
+
+        let actual_body: Value = serde_json::from_slice(&req.body).unwrap();
+
+        assert_eq!(expected_body, actual_body);
+    }
+
+    #[test]
+    fn specify_remote() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .reindex("old-idx", "new-idx")
+            .remote(RemoteSource::new("http://otherhost:9200").credentials("user", "pass"))
+            .inner
+            .into_request()
+            .unwrap();
+
+        let expected_body = json!({
+            "source": {
+                "index": "old-idx",
+                "remote": {
+                    "host": "http://otherhost:9200",
+                    "username": "user",
+                    "password": "pass"
+                }
+            },
+            "dest": {
+                "index": "new-idx"
+            }
+        });
+
+        let actual_body: Value = serde_json::from_slice(&req.body).unwrap();
+
+        assert_eq!(expected_body, actual_body);
+    }
+
+    #[test]
+    fn specify_slices() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let params = client
+            .reindex("old-idx", "new-idx")
+            .slices(5)
+            .params_builder
+            .into_value(|| RequestParams::new("http://eshost:9200"));
+
+        assert_eq!(Some(String::from("?slices=5")), params.get_url_qry().1);
+    }
+}
@@ -4,7 +4,16 @@ Builders for [search requests][docs-search].
 [docs-search]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-search.html
 */
 
-use futures::Future;
+use fluent_builder::{
+    SharedFluentBuilder,
+    Stack,
+    TryIntoValue,
+};
+use futures::{
+    future::Either,
+    Future,
+    IntoFuture,
+};
 use serde::de::DeserializeOwned;
 use std::marker::PhantomData;
 
@@ -19,16 +28,24 @@ use crate::{
         Client,
         DocumentClient,
     },
-    endpoints::SearchRequest,
+    endpoints::{
+        Endpoint,
+        SearchRequest,
+    },
     error::Error,
     http::{
         empty_body,
+        receiver::IsOk,
         sender::{
             AsyncSender,
+            NextParams,
+            RequestParams,
             Sender,
             SyncSender,
         },
+        AsyncBody,
         DefaultBody,
+        SyncBody,
     },
     params::{
         Index,
@@ -37,6 +54,22 @@ use crate::{
     types::document::DocumentType,
 };
 
+pub mod scroll;
+pub mod search_after;
+
+#[doc(inline)]
+pub use self::{
+    scroll::{
+        ScrollIter,
+        ScrollStream,
+    },
+    search_after::{
+        SearchAfterBody,
+        SearchAfterIter,
+        SearchAfterStream,
+    },
+};
+
 /**
 A [search request][docs-search] builder that can be configured before sending.
 
@@ -408,6 +441,85 @@ where
 /** A future returned by calling `send`. */
 pub type Pending<TDocument> = BasePending<SearchResponse<TDocument>>;
 
+/**
+Resolve the parameters for a sequence of synchronous requests.
+
+The node address is only fetched once so all requests in the sequence are sent to the same node.
+*/
+fn sync_params(
+    client: &Client<SyncSender>,
+    builder: SharedFluentBuilder<RequestParams, Stack>,
+) -> Result<RequestParams, Error> {
+    match builder.try_into_value() {
+        TryIntoValue::Value(params) => Ok(params),
+        TryIntoValue::Builder(builder) => {
+            let params = client.addresses.next().into_inner()?;
+
+            Ok(builder.into_value(move || params))
+        }
+    }
+}
+
+/**
+Resolve the parameters for a sequence of asynchronous requests.
+
+The node address is only fetched once so all requests in the sequence are sent to the same node.
+*/
+fn async_params(
+    client: &Client<AsyncSender>,
+    builder: SharedFluentBuilder<RequestParams, Stack>,
+) -> impl Future<Item = RequestParams, Error = Error> + Send {
+    match builder.try_into_value() {
+        TryIntoValue::Value(params) => Either::A(Ok(params).into_future()),
+        TryIntoValue::Builder(builder) => Either::B(
+            client
+                .addresses
+                .next()
+                .map(move |params| builder.into_value(move || params)),
+        ),
+    }
+}
+
+/** Send a request in a sequence of search requests using already resolved parameters. */
+fn send_sync<TEndpoint, TBody, TResponse>(
+    client: &Client<SyncSender>,
+    params: RequestParams,
+    req: TEndpoint,
+) -> Result<TResponse, Error>
+where
+    TEndpoint: Into<Endpoint<'static, TBody>>,
+    TBody: Into<SyncBody> + Send + 'static,
+    TResponse: IsOk + DeserializeOwned,
+{
+    RequestBuilder::new(
+        client.clone(),
+        SharedFluentBuilder::new().value(params),
+        RawRequestInner::new(req),
+    )
+    .send()?
+    .into_response()
+}
+
+/** Send a request in a sequence of search requests using already resolved parameters. */
+fn send_async<TEndpoint, TBody, TResponse>(
+    client: &Client<AsyncSender>,
+    params: RequestParams,
+    req: TEndpoint,
+) -> impl Future<Item = TResponse, Error = Error> + Send
+where
+    TEndpoint: Into<Endpoint<'static, TBody>>,
+    TBody: Into<AsyncBody> + Send + 'static,
+    TResponse: IsOk + DeserializeOwned + Send + 'static,
+{
+    RequestBuilder::new(
+        client.clone(),
+        SharedFluentBuilder::new().value(params),
+        RawRequestInner::new(req),
+    )
+    .send()
+    .and_then(|res| res.into_response())
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::Value;
@@ -0,0 +1,544 @@
+/*!
+Types for [scrolling][docs-scroll] through the results of a search request.
+
+[docs-scroll]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-body.html#request-body-search-scroll
+*/
+
+use std::{
+    mem,
+    time::Duration,
+};
+
+use fluent_builder::{
+    SharedFluentBuilder,
+    Stack,
+};
+use futures::{
+    Async,
+    Future,
+    Poll,
+    Stream,
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tokio::executor::{
+    DefaultExecutor,
+    Executor,
+};
+
+use super::{
+    async_params,
+    send_async,
+    send_sync,
+    sync_params,
+    SearchRequestBuilder,
+};
+use crate::{
+    client::{
+        responses::{
+            search::{
+                Hit,
+                IntoHits,
+            },
+            SearchResponse,
+        },
+        Client,
+    },
+    endpoints::{
+        ClearScrollRequest,
+        ScrollRequest,
+        SearchRequest,
+    },
+    error::Error,
+    http::{
+        sender::{
+            AsyncSender,
+            RequestParams,
+            Sender,
+            SyncSender,
+        },
+        AsyncBody,
+        SyncBody,
+    },
+};
+
+/**
+# Scroll synchronously
+*/
+impl<TDocument, TBody> SearchRequestBuilder<SyncSender, TDocument, TBody>
+where
+    TDocument: DeserializeOwned,
+    TBody: Into<<SyncSender as Sender>::Body> + Send + 'static,
+{
+    /**
+    Scroll through all hits matched by a `SearchRequestBuilder` using a [`SyncClient`][SyncClient].
+
+    The `keep_alive` duration is how long Elasticsearch should keep the search context alive between pages.
+    It only needs to be long enough to process a single page of hits.
+
+    No requests are sent until the returned iterator is polled.
+    Pages of hits are then fetched lazily as the iterator is consumed.
+    The scroll context is cleared when the iterator is exhausted or dropped.
+    If any request fails then the error is returned and the iterator finishes.
+
+    # Examples
+
+    Scroll through all documents of a [`DocumentType`][documents-mod] called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # #[macro_use] extern crate serde_json;
+    # use std::time::Duration;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Debug, Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = SyncClientBuilder::new().build()?;
+    let hits = client.document::<MyType>()
+                     .search()
+                     .body(json!({
+                         "size": 1000,
+                         "query": {
+                             "match_all": {}
+                         }
+                     }))
+                     .scroll(Duration::from_secs(60));
+
+    for hit in hits {
+        let hit = hit?;
+
+        println!("{:?}", hit.document());
+    }
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    [documents-mod]: ../../../types/document/index.html
+    */
+    pub fn scroll(self, keep_alive: Duration) -> ScrollIter<TDocument> {
+        let SearchRequest { url, body } = self.inner.into_request();
+
+        ScrollIter {
+            client: self.client,
+            keep_alive: keep_alive_param(keep_alive),
+            search: Some((
+                SearchRequest {
+                    url,
+                    body: body.into(),
+                },
+                self.params_builder,
+            )),
+            params: None,
+            scroll_id: None,
+            hits: None,
+            done: false,
+        }
+    }
+}
+
+/**
+# Scroll asynchronously
+*/
+impl<TDocument, TBody> SearchRequestBuilder<AsyncSender, TDocument, TBody>
+where
+    TDocument: DeserializeOwned + Send + 'static,
+    TBody: Into<<AsyncSender as Sender>::Body> + Send + 'static,
+{
+    /**
+    Scroll through all hits matched by a `SearchRequestBuilder` using an [`AsyncClient`][AsyncClient].
+
+    The `keep_alive` duration is how long Elasticsearch should keep the search context alive between pages.
+    It only needs to be long enough to process a single page of hits.
+
+    No requests are sent until the returned stream is polled.
+    Pages of hits are then fetched lazily as the stream is consumed.
+    The scroll context is cleared when the stream is exhausted or dropped.
+    If any request fails then the error is returned and the stream finishes.
+
+    # Examples
+
+    Scroll through all documents of a [`DocumentType`][documents-mod] called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use std::time::Duration;
+    # use futures::{Future, Stream};
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Debug, Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = AsyncClientBuilder::new().build()?;
+    let hits = client.document::<MyType>()
+                     .search()
+                     .scroll(Duration::from_secs(60));
+
+    let future = hits.for_each(|hit| {
+        println!("{:?}", hit.document());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    [documents-mod]: ../../../types/document/index.html
+    */
+    pub fn scroll(self, keep_alive: Duration) -> ScrollStream<TDocument> {
+        let keep_alive = keep_alive_param(keep_alive);
+        let SearchRequest { url, body } = self.inner.into_request();
+        let req = SearchRequest::<AsyncBody> {
+            url,
+            body: body.into(),
+        };
+
+        let client = self.client.clone();
+        let search_keep_alive = keep_alive.clone();
+        let page = async_params(&self.client, self.params_builder).and_then(move |params| {
+            let search_params = params.clone().url_param("scroll", search_keep_alive);
+
+            send_async(&client, search_params, req)
+                .map(move |res| (params.without_url_params(), res))
+        });
+
+        ScrollStream {
+            client: self.client,
+            keep_alive,
+            params: None,
+            scroll_id: None,
+            hits: None,
+            state: ScrollStreamState::Page(Box::new(page)),
+        }
+    }
+}
+
+/**
+A synchronous iterator over the hits of a scrolled search.
+
+Call [`SearchRequestBuilder.scroll`][SearchRequestBuilder.scroll] with a [`SyncClient`][SyncClient] to get a `ScrollIter`.
+
+[SearchRequestBuilder.scroll]: type.SearchRequestBuilder.html#scroll-synchronously
+[SyncClient]: ../../type.SyncClient.html
+*/
+pub struct ScrollIter<TDocument> {
+    client: Client<SyncSender>,
+    keep_alive: String,
+    search: Option<(
+        SearchRequest<'static, SyncBody>,
+        SharedFluentBuilder<RequestParams, Stack>,
+    )>,
+    params: Option<RequestParams>,
+    scroll_id: Option<String>,
+    hits: Option<IntoHits<TDocument>>,
+    done: bool,
+}
+
+impl<TDocument> ScrollIter<TDocument>
+where
+    TDocument: DeserializeOwned,
+{
+    /**
+    Fetch the next page of hits.
+
+    Returns `false` if there are no more hits to fetch.
+    */
+    fn next_page(&mut self) -> Result<bool, Error> {
+        let res: SearchResponse<TDocument> = match self.search.take() {
+            Some((req, builder)) => {
+                let params = sync_params(&self.client, builder)?;
+                let search_params = params.clone().url_param("scroll", &self.keep_alive);
+
+                self.params = Some(params.without_url_params());
+
+                send_sync(&self.client, search_params, req)?
+            }
+            None => match (&self.params, &self.scroll_id) {
+                (Some(params), Some(scroll_id)) => {
+                    let req = ScrollRequest::new(scroll_body(&self.keep_alive, scroll_id));
+
+                    send_sync(&self.client, params.clone(), req)?
+                }
+                _ => return Ok(false),
+            },
+        };
+
+        if let Some(scroll_id) = res.scroll_id() {
+            self.scroll_id = Some(scroll_id.to_owned());
+        }
+
+        if res.hits().next().is_none() {
+            return Ok(false);
+        }
+
+        self.hits = Some(res.into_hits());
+
+        Ok(true)
+    }
+}
+
+impl<TDocument> ScrollIter<TDocument> {
+    /** Stop iterating and clear the scroll context if there is one. */
+    fn finish(&mut self) {
+        self.done = true;
+        self.hits = None;
+
+        if let (Some(params), Some(scroll_id)) = (self.params.clone(), self.scroll_id.take()) {
+            let req = ClearScrollRequest::new(clear_scroll_body(scroll_id));
+
+            if let Err(e) = send_sync::<_, _, Value>(&self.client, params, req) {
+                warn!("Elasticsearch Clear Scroll: error: '{:?}'", e);
+            }
+        }
+    }
+}
+
+impl<TDocument> Iterator for ScrollIter<TDocument>
+where
+    TDocument: DeserializeOwned,
+{
+    type Item = Result<Hit<TDocument>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(hit) = self.hits.as_mut().and_then(Iterator::next) {
+                return Some(Ok(hit));
+            }
+
+            if self.done {
+                return None;
+            }
+
+            match self.next_page() {
+                Ok(true) => continue,
+                Ok(false) => {
+                    self.finish();
+                    return None;
+                }
+                Err(e) => {
+                    self.finish();
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+impl<TDocument> Drop for ScrollIter<TDocument> {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+type PendingPage<TDocument> =
+    Box<dyn Future<Item = (RequestParams, SearchResponse<TDocument>), Error = Error> + Send>;
+
+type PendingClear = Box<dyn Future<Item = (), Error = ()> + Send>;
+
+/**
+An asynchronous stream of the hits of a scrolled search.
+
+Call [`SearchRequestBuilder.scroll`][SearchRequestBuilder.scroll] with an [`AsyncClient`][AsyncClient] to get a `ScrollStream`.
+
+[SearchRequestBuilder.scroll]: type.SearchRequestBuilder.html#scroll-asynchronously
+[AsyncClient]: ../../type.AsyncClient.html
+*/
+pub struct ScrollStream<TDocument> {
+    client: Client<AsyncSender>,
+    keep_alive: String,
+    params: Option<RequestParams>,
+    scroll_id: Option<String>,
+    hits: Option<IntoHits<TDocument>>,
+    state: ScrollStreamState<TDocument>,
+}
+
+enum ScrollStreamState<TDocument> {
+    Page(PendingPage<TDocument>),
+    Idle,
+    Clearing(PendingClear),
+    Done,
+}
+
+impl<TDocument> ScrollStream<TDocument>
+where
+    TDocument: DeserializeOwned + Send + 'static,
+{
+    fn next_page(&mut self) -> ScrollStreamState<TDocument> {
+        match (&self.params, &self.scroll_id) {
+            (Some(params), Some(scroll_id)) => {
+                let params = params.clone();
+                let req = ScrollRequest::new(scroll_body(&self.keep_alive, scroll_id));
+
+                let page = send_async(&self.client, params.clone(), req).map(|res| (params, res));
+
+                ScrollStreamState::Page(Box::new(page))
+            }
+            _ => ScrollStreamState::Done,
+        }
+    }
+}
+
+impl<TDocument> ScrollStream<TDocument> {
+    fn clear(&mut self) -> Option<PendingClear> {
+        self.hits = None;
+
+        match (self.params.clone(), self.scroll_id.take()) {
+            (Some(params), Some(scroll_id)) => {
+                let req = ClearScrollRequest::new(clear_scroll_body(scroll_id));
+
+                let clear = send_async::<_, _, Value>(&self.client, params, req)
+                    .map(|_| ())
+                    .map_err(|e| warn!("Elasticsearch Clear Scroll: error: '{:?}'", e));
+
+                Some(Box::new(clear))
+            }
+            _ => None,
+        }
+    }
+
+    fn finish(&mut self) -> ScrollStreamState<TDocument> {
+        match self.clear() {
+            Some(clear) => ScrollStreamState::Clearing(clear),
+            None => ScrollStreamState::Done,
+        }
+    }
+}
+
+impl<TDocument> Stream for ScrollStream<TDocument>
+where
+    TDocument: DeserializeOwned + Send + 'static,
+{
+    type Item = Hit<TDocument>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        loop {
+            if let Some(hit) = self.hits.as_mut().and_then(Iterator::next) {
+                return Ok(Async::Ready(Some(hit)));
+            }
+
+            match mem::replace(&mut self.state, ScrollStreamState::Done) {
+                ScrollStreamState::Page(mut page) => match page.poll() {
+                    Ok(Async::NotReady) => {
+                        self.state = ScrollStreamState::Page(page);
+                        return Ok(Async::NotReady);
+                    }
+                    Ok(Async::Ready((params, res))) => {
+                        self.params = Some(params);
+
+                        if let Some(scroll_id) = res.scroll_id() {
+                            self.scroll_id = Some(scroll_id.to_owned());
+                        }
+
+                        if res.hits().next().is_none() {
+                            self.state = self.finish();
+                        } else {
+                            self.hits = Some(res.into_hits());
+                            self.state = ScrollStreamState::Idle;
+                        }
+                    }
+                    Err(e) => {
+                        self.state = self.finish();
+                        return Err(e);
+                    }
+                },
+                ScrollStreamState::Idle => {
+                    self.state = self.next_page();
+                }
+                ScrollStreamState::Clearing(mut clear) => {
+                    if let Ok(Async::NotReady) = clear.poll() {
+                        self.state = ScrollStreamState::Clearing(clear);
+                        return Ok(Async::NotReady);
+                    }
+                }
+                ScrollStreamState::Done => return Ok(Async::Ready(None)),
+            }
+        }
+    }
+}
+
+impl<TDocument> Drop for ScrollStream<TDocument> {
+    fn drop(&mut self) {
+        let clear = match mem::replace(&mut self.state, ScrollStreamState::Done) {
+            ScrollStreamState::Clearing(clear) => Some(clear),
+            _ => self.clear(),
+        };
+
+        // The stream may be dropped outside of a runtime, in which case the scroll context is left to expire
+        if let Some(clear) = clear {
+            if let Err(e) = DefaultExecutor::current().spawn(clear) {
+                warn!("Elasticsearch Clear Scroll: error: '{:?}'", e);
+            }
+        }
+    }
+}
+
+/** Format a keep alive duration as an Elasticsearch time unit. */
+fn keep_alive_param(keep_alive: Duration) -> String {
+    format!("{}ms", keep_alive.as_millis())
+}
+
+fn scroll_body(keep_alive: &str, scroll_id: &str) -> Value {
+    json!({
+        "scroll": keep_alive,
+        "scroll_id": scroll_id
+    })
+}
+
+fn clear_scroll_body(scroll_id: String) -> Value {
+    json!({
+        "scroll_id": [scroll_id]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::Value;
+
+    use super::*;
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<ScrollStream<Value>>();
+    }
+
+    #[test]
+    fn keep_alive_is_millis() {
+        assert_eq!("90000ms", keep_alive_param(Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn scroll_body_has_keep_alive_and_id() {
+        let body = scroll_body("60000ms", "abc");
+
+        assert_eq!(json!({ "scroll": "60000ms", "scroll_id": "abc" }), body);
+    }
+
+    #[test]
+    fn clear_scroll_body_has_id() {
+        let body = clear_scroll_body("abc".to_owned());
+
+        assert_eq!(json!({ "scroll_id": ["abc"] }), body);
+    }
+
+    #[test]
+    fn unpolled_scroll_sends_nothing() {
+        let client = SyncClientBuilder::new()
+            .static_node("http://not-a-real-host:9200")
+            .build()
+            .unwrap();
+
+        let scroll = client.search::<Value>().scroll(Duration::from_secs(60));
+
+        assert!(scroll.params.is_none());
+        assert!(scroll.scroll_id.is_none());
+    }
+}
@@ -0,0 +1,705 @@
+/*!
+Types for paginating through the results of a search request using [`search_after`][docs-search-after].
+
+[docs-search-after]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-body.html#request-body-search-search-after
+*/
+
+use std::{
+    mem,
+    time::Duration,
+};
+
+use fluent_builder::{
+    SharedFluentBuilder,
+    Stack,
+};
+use futures::{
+    Async,
+    Future,
+    Poll,
+    Stream,
+};
+use serde::de::DeserializeOwned;
+use serde_json::{
+    Map,
+    Value,
+};
+
+use super::{
+    async_params,
+    send_async,
+    send_sync,
+    sync_params,
+    SearchRequestBuilder,
+};
+use crate::{
+    client::{
+        responses::{
+            search::{
+                Hit,
+                IntoHits,
+            },
+            SearchResponse,
+        },
+        Client,
+    },
+    endpoints::SearchRequest,
+    error::{
+        self,
+        Error,
+    },
+    http::{
+        sender::{
+            AsyncSender,
+            RequestParams,
+            Sender,
+            SyncSender,
+        },
+        UrlPath,
+    },
+};
+
+/**
+A search request body that can be paginated using `search_after`.
+
+The body needs to be a JSON object so the `search_after` and `pit` fields can be set on it for each page.
+*/
+pub trait SearchAfterBody {
+    /** Convert the body into a JSON object. */
+    fn into_search_after_body(self) -> Result<Map<String, Value>, Error>;
+}
+
+impl SearchAfterBody for Value {
+    fn into_search_after_body(self) -> Result<Map<String, Value>, Error> {
+        match self {
+            Value::Object(body) => Ok(body),
+            Value::Null => Ok(Map::new()),
+            _ => Err(error::request(error::message(
+                "the search request body must be a JSON object",
+            ))),
+        }
+    }
+}
+
+impl SearchAfterBody for &'static [u8] {
+    fn into_search_after_body(self) -> Result<Map<String, Value>, Error> {
+        if self.is_empty() {
+            return Ok(Map::new());
+        }
+
+        serde_json::from_slice::<Value>(self)
+            .map_err(error::request)?
+            .into_search_after_body()
+    }
+}
+
+impl SearchAfterBody for &'static str {
+    fn into_search_after_body(self) -> Result<Map<String, Value>, Error> {
+        self.as_bytes().into_search_after_body()
+    }
+}
+
+impl SearchAfterBody for Vec<u8> {
+    fn into_search_after_body(self) -> Result<Map<String, Value>, Error> {
+        if self.is_empty() {
+            return Ok(Map::new());
+        }
+
+        serde_json::from_slice::<Value>(&self)
+            .map_err(error::request)?
+            .into_search_after_body()
+    }
+}
+
+impl SearchAfterBody for String {
+    fn into_search_after_body(self) -> Result<Map<String, Value>, Error> {
+        self.into_bytes().into_search_after_body()
+    }
+}
+
+/**
+# Paginate synchronously
+*/
+impl<TDocument, TBody> SearchRequestBuilder<SyncSender, TDocument, TBody>
+where
+    TDocument: DeserializeOwned,
+    TBody: Into<<SyncSender as Sender>::Body> + SearchAfterBody + Send + 'static,
+{
+    /**
+    Paginate through all hits matched by a `SearchRequestBuilder` using a [`SyncClient`][SyncClient].
+
+    The query is re-sent for each page with the [`search_after`][docs-search-after] parameter set to the sort values of the last hit on the previous page.
+    The query must be sorted by a unique field, or combination of fields, for pages to be consistent.
+    The `size` set in the body is used as the page size.
+
+    The search can be pinned to a [point in time][docs-pit] by calling [`point_in_time`][SearchAfterIter.point_in_time] on the returned iterator.
+
+    No requests are sent until the returned iterator is polled.
+    Pages of hits are then fetched lazily as the iterator is consumed.
+    If any request fails then the error is returned and the iterator finishes.
+
+    # Examples
+
+    Paginate through all documents of a [`DocumentType`][documents-mod] called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # #[macro_use] extern crate serde_json;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Debug, Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = SyncClientBuilder::new().build()?;
+    let hits = client.document::<MyType>()
+                     .search()
+                     .body(json!({
+                         "size": 100,
+                         "query": {
+                             "match_all": {}
+                         },
+                         "sort": [
+                             { "timestamp": "asc" },
+                             { "id": "asc" }
+                         ]
+                     }))
+                     .search_after();
+
+    for hit in hits {
+        let hit = hit?;
+
+        println!("{:?}", hit.document());
+    }
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    [SearchAfterIter.point_in_time]: struct.SearchAfterIter.html#method.point_in_time
+    [documents-mod]: ../../../types/document/index.html
+    [docs-search-after]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-body.html#request-body-search-search-after
+    [docs-pit]: https://www.elastic.co/guide/en/elasticsearch/reference/current/point-in-time-api.html
+    */
+    pub fn search_after(self) -> SearchAfterIter<TDocument> {
+        SearchAfterIter {
+            client: self.client,
+            builder: Some(self.params_builder),
+            params: None,
+            pages: Pages::new(self.inner.into_request()),
+            hits: None,
+        }
+    }
+}
+
+/**
+# Paginate asynchronously
+*/
+impl<TDocument, TBody> SearchRequestBuilder<AsyncSender, TDocument, TBody>
+where
+    TDocument: DeserializeOwned + Send + 'static,
+    TBody: Into<<AsyncSender as Sender>::Body> + SearchAfterBody + Send + 'static,
+{
+    /**
+    Paginate through all hits matched by a `SearchRequestBuilder` using an [`AsyncClient`][AsyncClient].
+
+    The query is re-sent for each page with the [`search_after`][docs-search-after] parameter set to the sort values of the last hit on the previous page.
+    The query must be sorted by a unique field, or combination of fields, for pages to be consistent.
+    The `size` set in the body is used as the page size.
+
+    The search can be pinned to a [point in time][docs-pit] by calling [`point_in_time`][SearchAfterStream.point_in_time] on the returned stream.
+
+    No requests are sent until the returned stream is polled.
+    Pages of hits are then fetched lazily as the stream is consumed.
+    If any request fails then the error is returned and the stream finishes.
+
+    # Examples
+
+    Paginate through all documents of a [`DocumentType`][documents-mod] called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # #[macro_use] extern crate serde_json;
+    # use futures::{Future, Stream};
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Debug, Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = AsyncClientBuilder::new().build()?;
+    let hits = client.document::<MyType>()
+                     .search()
+                     .body(json!({
+                         "size": 100,
+                         "sort": [
+                             { "id": "asc" }
+                         ]
+                     }))
+                     .search_after();
+
+    let future = hits.for_each(|hit| {
+        println!("{:?}", hit.document());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    [SearchAfterStream.point_in_time]: struct.SearchAfterStream.html#method.point_in_time
+    [documents-mod]: ../../../types/document/index.html
+    [docs-search-after]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-body.html#request-body-search-search-after
+    [docs-pit]: https://www.elastic.co/guide/en/elasticsearch/reference/current/point-in-time-api.html
+    */
+    pub fn search_after(self) -> SearchAfterStream<TDocument> {
+        SearchAfterStream {
+            client: self.client,
+            builder: Some(self.params_builder),
+            params: None,
+            pages: Pages::new(self.inner.into_request()),
+            hits: None,
+            state: SearchAfterStreamState::Idle,
+        }
+    }
+}
+
+/** A point in time to pin a paginated search to. */
+struct PointInTime {
+    id: String,
+    keep_alive: String,
+}
+
+/**
+The state needed to build the request for each page.
+
+This is shared by the synchronous and asynchronous paginators.
+*/
+struct Pages {
+    url: UrlPath<'static>,
+    body: Result<Map<String, Value>, Option<Error>>,
+    pit: Option<PointInTime>,
+    search_after: Option<Value>,
+    done: bool,
+}
+
+impl Pages {
+    fn new<TBody>(req: SearchRequest<'static, TBody>) -> Self
+    where
+        TBody: SearchAfterBody,
+    {
+        let SearchRequest { url, body } = req;
+
+        Pages {
+            url,
+            body: body.into_search_after_body().map_err(Some),
+            pit: None,
+            search_after: None,
+            done: false,
+        }
+    }
+
+    fn point_in_time(&mut self, id: String, keep_alive: Duration) {
+        self.pit = Some(PointInTime {
+            id,
+            keep_alive: format!("{}ms", keep_alive.as_millis()),
+        });
+    }
+
+    /**
+    Get the request for the next page.
+
+    Returns `None` if there are no more pages.
+    */
+    fn next_request(&mut self) -> Option<Result<SearchRequest<'static, Value>, Error>> {
+        if self.done {
+            return None;
+        }
+
+        let mut body = match self.body {
+            Ok(ref body) => body.clone(),
+            Err(ref mut e) => {
+                self.done = true;
+                return e.take().map(Err);
+            }
+        };
+
+        if let Some(ref search_after) = self.search_after {
+            body.insert("search_after".to_owned(), search_after.clone());
+        }
+
+        match self.pit {
+            // Searches against a point in time can't specify an index
+            Some(ref pit) => {
+                body.insert(
+                    "pit".to_owned(),
+                    json!({
+                        "id": pit.id,
+                        "keep_alive": pit.keep_alive
+                    }),
+                );
+
+                Some(Ok(SearchRequest::new(Value::Object(body))))
+            }
+            None => Some(Ok(SearchRequest {
+                url: self.url.clone(),
+                body: Value::Object(body),
+            })),
+        }
+    }
+
+    /**
+    Update the state from the response for a page.
+
+    Returns `None` if the page had no hits.
+    */
+    fn next_hits<TDocument>(
+        &mut self,
+        res: SearchResponse<TDocument>,
+    ) -> Result<Option<IntoHits<TDocument>>, Error> {
+        if let (Some(pit), Some(pit_id)) = (self.pit.as_mut(), res.pit_id()) {
+            pit.id = pit_id.to_owned();
+        }
+
+        let search_after = match res.hits().last() {
+            Some(hit) => hit.sort().cloned(),
+            None => {
+                self.done = true;
+                return Ok(None);
+            }
+        };
+
+        match search_after {
+            Some(search_after) => {
+                self.search_after = Some(search_after);
+
+                Ok(Some(res.into_hits()))
+            }
+            None => {
+                self.done = true;
+
+                Err(error::request(error::message(
+                    "hits have no sort values so `search_after` can't be used. Add a `sort` to the search request body",
+                )))
+            }
+        }
+    }
+}
+
+/**
+A synchronous iterator over the hits of a search paginated with `search_after`.
+
+Call [`SearchRequestBuilder.search_after`][SearchRequestBuilder.search_after] with a [`SyncClient`][SyncClient] to get a `SearchAfterIter`.
+
+[SearchRequestBuilder.search_after]: type.SearchRequestBuilder.html#paginate-synchronously
+[SyncClient]: ../../type.SyncClient.html
+*/
+pub struct SearchAfterIter<TDocument> {
+    client: Client<SyncSender>,
+    builder: Option<SharedFluentBuilder<RequestParams, Stack>>,
+    params: Option<RequestParams>,
+    pages: Pages,
+    hits: Option<IntoHits<TDocument>>,
+}
+
+impl<TDocument> SearchAfterIter<TDocument> {
+    /**
+    Pin the search to a [point in time][docs-pit].
+
+    The point in time must already be open.
+    Its id is updated from each response, and `keep_alive` is sent with each request to keep it open.
+    This method should be called before the iterator is polled.
+
+    [docs-pit]: https://www.elastic.co/guide/en/elasticsearch/reference/current/point-in-time-api.html
+    */
+    pub fn point_in_time(mut self, id: impl Into<String>, keep_alive: Duration) -> Self {
+        self.pages.point_in_time(id.into(), keep_alive);
+        self
+    }
+
+    /**
+    The id of the point in time the search is pinned to.
+
+    The id is updated from each response so it may differ from the one the search was pinned to.
+    */
+    pub fn pit_id(&self) -> Option<&str> {
+        self.pages.pit.as_ref().map(|pit| pit.id.as_str())
+    }
+}
+
+impl<TDocument> SearchAfterIter<TDocument>
+where
+    TDocument: DeserializeOwned,
+{
+    fn next_page(&mut self) -> Result<Option<IntoHits<TDocument>>, Error> {
+        let req = match self.pages.next_request() {
+            Some(req) => req?,
+            None => return Ok(None),
+        };
+
+        let params = match self.builder.take() {
+            Some(builder) => {
+                let params = sync_params(&self.client, builder)?;
+                self.params = Some(params.clone());
+
+                params
+            }
+            None => self.params.clone().expect("missing request params"),
+        };
+
+        let res = send_sync(&self.client, params, req)?;
+
+        self.pages.next_hits(res)
+    }
+}
+
+impl<TDocument> Iterator for SearchAfterIter<TDocument>
+where
+    TDocument: DeserializeOwned,
+{
+    type Item = Result<Hit<TDocument>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(hit) = self.hits.as_mut().and_then(Iterator::next) {
+                return Some(Ok(hit));
+            }
+
+            match self.next_page() {
+                Ok(Some(hits)) => self.hits = Some(hits),
+                Ok(None) => return None,
+                Err(e) => {
+                    self.pages.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+type PendingPage<TDocument> =
+    Box<dyn Future<Item = (RequestParams, SearchResponse<TDocument>), Error = Error> + Send>;
+
+/**
+An asynchronous stream of the hits of a search paginated with `search_after`.
+
+Call [`SearchRequestBuilder.search_after`][SearchRequestBuilder.search_after] with an [`AsyncClient`][AsyncClient] to get a `SearchAfterStream`.
+
+[SearchRequestBuilder.search_after]: type.SearchRequestBuilder.html#paginate-asynchronously
+[AsyncClient]: ../../type.AsyncClient.html
+*/
+pub struct SearchAfterStream<TDocument> {
+    client: Client<AsyncSender>,
+    builder: Option<SharedFluentBuilder<RequestParams, Stack>>,
+    params: Option<RequestParams>,
+    pages: Pages,
+    hits: Option<IntoHits<TDocument>>,
+    state: SearchAfterStreamState<TDocument>,
+}
+
+enum SearchAfterStreamState<TDocument> {
+    Page(PendingPage<TDocument>),
+    Idle,
+}
+
+impl<TDocument> SearchAfterStream<TDocument> {
+    /**
+    Pin the search to a [point in time][docs-pit].
+
+    The point in time must already be open.
+    Its id is updated from each response, and `keep_alive` is sent with each request to keep it open.
+    This method should be called before the stream is polled.
+
+    [docs-pit]: https://www.elastic.co/guide/en/elasticsearch/reference/current/point-in-time-api.html
+    */
+    pub fn point_in_time(mut self, id: impl Into<String>, keep_alive: Duration) -> Self {
+        self.pages.point_in_time(id.into(), keep_alive);
+        self
+    }
+
+    /**
+    The id of the point in time the search is pinned to.
+
+    The id is updated from each response so it may differ from the one the search was pinned to.
+    */
+    pub fn pit_id(&self) -> Option<&str> {
+        self.pages.pit.as_ref().map(|pit| pit.id.as_str())
+    }
+}
+
+impl<TDocument> SearchAfterStream<TDocument>
+where
+    TDocument: DeserializeOwned + Send + 'static,
+{
+    fn next_page(&mut self, req: SearchRequest<'static, Value>) -> PendingPage<TDocument> {
+        let client = self.client.clone();
+
+        match self.builder.take() {
+            Some(builder) => Box::new(async_params(&self.client, builder).and_then(
+                move |params| send_async(&client, params.clone(), req).map(|res| (params, res)),
+            )),
+            None => {
+                let params = self.params.clone().expect("missing request params");
+
+                Box::new(send_async(&client, params.clone(), req).map(|res| (params, res)))
+            }
+        }
+    }
+}
+
+impl<TDocument> Stream for SearchAfterStream<TDocument>
+where
+    TDocument: DeserializeOwned + Send + 'static,
+{
+    type Item = Hit<TDocument>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        loop {
+            if let Some(hit) = self.hits.as_mut().and_then(Iterator::next) {
+                return Ok(Async::Ready(Some(hit)));
+            }
+
+            match mem::replace(&mut self.state, SearchAfterStreamState::Idle) {
+                SearchAfterStreamState::Page(mut page) => match page.poll() {
+                    Ok(Async::NotReady) => {
+                        self.state = SearchAfterStreamState::Page(page);
+                        return Ok(Async::NotReady);
+                    }
+                    Ok(Async::Ready((params, res))) => {
+                        self.params = Some(params);
+
+                        match self.pages.next_hits(res)? {
+                            Some(hits) => self.hits = Some(hits),
+                            None => return Ok(Async::Ready(None)),
+                        }
+                    }
+                    Err(e) => {
+                        self.pages.done = true;
+                        return Err(e);
+                    }
+                },
+                SearchAfterStreamState::Idle => match self.pages.next_request() {
+                    Some(req) => self.state = SearchAfterStreamState::Page(self.next_page(req?)),
+                    None => return Ok(Async::Ready(None)),
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::Value;
+
+    use super::*;
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<SearchAfterStream<Value>>();
+    }
+
+    fn pages(body: Value) -> Pages {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        Pages::new(client.search::<Value>().index("myindex").body(body).inner.into_request())
+    }
+
+    fn hit_response(sort: Value) -> SearchResponse<Value> {
+        serde_json::from_value(json!({
+            "took": 1,
+            "timed_out": false,
+            "_shards": { "total": 1, "successful": 1, "failed": 0 },
+            "pit_id": "updated",
+            "hits": {
+                "total": { "value": 1, "relation": "eq" },
+                "max_score": null,
+                "hits": [{
+                    "_index": "myindex",
+                    "_type": "_doc",
+                    "_id": "1",
+                    "_source": {},
+                    "sort": sort
+                }]
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn first_request_has_no_search_after() {
+        let mut pages = pages(json!({ "sort": ["id"] }));
+
+        let req = pages.next_request().unwrap().unwrap();
+
+        assert_eq!("/myindex/_search", req.url.as_ref());
+        assert_eq!(json!({ "sort": ["id"] }), req.body);
+    }
+
+    #[test]
+    fn next_request_has_search_after_from_last_hit() {
+        let mut pages = pages(json!({ "sort": ["id"] }));
+
+        pages.next_request().unwrap().unwrap();
+        pages.next_hits(hit_response(json!(["1"]))).unwrap().unwrap();
+
+        let req = pages.next_request().unwrap().unwrap();
+
+        assert_eq!(json!({ "sort": ["id"], "search_after": ["1"] }), req.body);
+    }
+
+    #[test]
+    fn point_in_time_request_has_no_index() {
+        let mut pages = pages(json!({ "sort": ["id"] }));
+        pages.point_in_time("abc".to_owned(), Duration::from_secs(60));
+
+        let req = pages.next_request().unwrap().unwrap();
+
+        assert_eq!("/_search", req.url.as_ref());
+        assert_eq!(
+            json!({ "sort": ["id"], "pit": { "id": "abc", "keep_alive": "60000ms" } }),
+            req.body
+        );
+    }
+
+    #[test]
+    fn point_in_time_id_is_updated_from_response() {
+        let mut pages = pages(json!({ "sort": ["id"] }));
+        pages.point_in_time("abc".to_owned(), Duration::from_secs(60));
+
+        pages.next_hits(hit_response(json!(["1"]))).unwrap().unwrap();
+
+        assert_eq!("updated", pages.pit.unwrap().id);
+    }
+
+    #[test]
+    fn hits_without_sort_are_an_error() {
+        let mut pages = pages(json!({}));
+
+        assert!(pages.next_hits(hit_response(Value::Null)).is_err());
+        assert!(pages.next_request().is_none());
+    }
+
+    #[test]
+    fn invalid_body_is_an_error() {
+        let mut pages = pages(json!([]));
+
+        assert!(pages.next_request().unwrap().is_err());
+        assert!(pages.next_request().is_none());
+    }
+
+    #[test]
+    fn empty_body_is_an_empty_object() {
+        let client = SyncClientBuilder::new().build().unwrap();
+        let mut pages = Pages::new(client.search::<Value>().inner.into_request());
+
+        let req = pages.next_request().unwrap().unwrap();
+
+        assert_eq!(json!({}), req.body);
+    }
+}
@@ -0,0 +1,607 @@
+/*!
+Handles for long-running requests that run as a [task][docs-tasks] in the background.
+
+[docs-tasks]: https://www.elastic.co/guide/en/elasticsearch/reference/current/tasks.html
+*/
+
+use std::{
+    marker::PhantomData,
+    mem,
+    thread,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use futures::{
+    future::{
+        self,
+        Either,
+        Loop,
+    },
+    Async,
+    Future,
+    Poll,
+    Stream,
+};
+use serde::de::DeserializeOwned;
+use serde_json::{
+    Map,
+    Value,
+};
+use tokio::timer::Delay;
+
+use super::Pending as BasePending;
+use crate::{
+    client::{
+        responses::{
+            bulk_by_scroll::BulkByScrollStatus,
+            TaskResponse,
+        },
+        Client,
+    },
+    endpoints::{
+        DeleteByQueryRethrottleRequest,
+        Endpoint,
+        ReindexRethrottleRequest,
+        TasksGetRequest,
+        UpdateByQueryRethrottleRequest,
+    },
+    error::{
+        self,
+        ApiError,
+        Error,
+    },
+    http::{
+        empty_body,
+        receiver::{
+            IsOkOnSuccess,
+            ParsedApiError,
+        },
+        sender::{
+            AsyncSender,
+            Sender,
+            SyncSender,
+        },
+        DefaultBody,
+    },
+};
+
+const DEFAULT_POLL_INTERVAL_MILLIS: u64 = 1000;
+
+/**
+A handle to a request that's running as a [task][docs-tasks] in the background.
+
+A `TaskHandle` is returned by sending a request without waiting for it to complete, like [`DeleteByQueryRequestBuilder.send_task`][DeleteByQueryRequestBuilder.send_task].
+The handle can be used to check the progress of the task, change its throttling, or wait for it to complete.
+Methods on the handle will either run [synchronously][sync] or [asynchronously][async], depending on the `Client` it was created from.
+
+[docs-tasks]: https://www.elastic.co/guide/en/elasticsearch/reference/current/tasks.html
+[DeleteByQueryRequestBuilder.send_task]: ../document_delete_by_query/type.DeleteByQueryRequestBuilder.html#method.send_task
+[sync]: #synchronously
+[async]: #asynchronously
+*/
+pub struct TaskHandle<TSender, TResponse>
+where
+    TSender: Sender,
+{
+    client: Client<TSender>,
+    task_id: String,
+    kind: TaskKind,
+    poll_interval: Duration,
+    _marker: PhantomData<TResponse>,
+}
+
+/** The kind of request a task is running. */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum TaskKind {
+    DeleteByQuery,
+    UpdateByQuery,
+    Reindex,
+}
+
+/** The response returned by sending a request with `wait_for_completion=false`. */
+#[derive(Deserialize, Debug)]
+pub(crate) struct TaskStartedResponse {
+    task: String,
+}
+
+impl IsOkOnSuccess for TaskStartedResponse {}
+
+#[derive(Deserialize, Debug)]
+struct RethrottleResponse {
+    #[serde(default)]
+    node_failures: Vec<Map<String, Value>>,
+    #[serde(default)]
+    task_failures: Vec<Map<String, Value>>,
+}
+
+impl IsOkOnSuccess for RethrottleResponse {}
+
+impl RethrottleResponse {
+    fn into_result(self) -> Result<(), Error> {
+        // Task failures wrap the error in their `reason`, node failures are the error
+        let failure = self
+            .task_failures
+            .into_iter()
+            .map(task_failure_reason)
+            .chain(self.node_failures)
+            .next();
+
+        match failure {
+            Some(failure) => Err(Error::Api(failure_into_api_error(failure))),
+            None => Ok(()),
+        }
+    }
+}
+
+fn task_failure_reason(mut failure: Map<String, Value>) -> Map<String, Value> {
+    match failure.remove("reason") {
+        Some(Value::Object(reason)) => reason,
+        _ => failure,
+    }
+}
+
+fn failure_into_api_error(failure: Map<String, Value>) -> ApiError {
+    let mut item = Map::new();
+    item.insert("error".to_owned(), Value::Object(failure));
+
+    ParsedApiError::from(item).into_api_error()
+}
+
+impl<TSender, TResponse> TaskHandle<TSender, TResponse>
+where
+    TSender: Sender,
+{
+    pub(crate) fn new(
+        client: Client<TSender>,
+        started: TaskStartedResponse,
+        kind: TaskKind,
+    ) -> Self {
+        TaskHandle {
+            client,
+            task_id: started.task,
+            kind,
+            poll_interval: Duration::from_millis(DEFAULT_POLL_INTERVAL_MILLIS),
+            _marker: PhantomData,
+        }
+    }
+
+    /** The id of the task in the form `node:id`. */
+    pub fn task_id(&self) -> &str {
+        &self.task_id
+    }
+
+    /**
+    Set how long to wait between checks on the status of the task while waiting for it to complete.
+
+    The default interval is 1 second.
+    */
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    fn status_request(&self) -> TasksGetRequest<'static> {
+        TasksGetRequest::for_task_id(self.task_id.clone())
+    }
+
+    fn rethrottle_request(&self) -> Endpoint<'static, DefaultBody> {
+        let task_id = self.task_id.clone();
+
+        match self.kind {
+            TaskKind::DeleteByQuery => {
+                DeleteByQueryRethrottleRequest::for_task_id(task_id, empty_body()).into()
+            }
+            TaskKind::UpdateByQuery => {
+                UpdateByQueryRethrottleRequest::for_task_id(task_id, empty_body()).into()
+            }
+            TaskKind::Reindex => {
+                ReindexRethrottleRequest::for_task_id(task_id, empty_body()).into()
+            }
+        }
+    }
+}
+
+impl<TSender, TResponse> Clone for TaskHandle<TSender, TResponse>
+where
+    TSender: Sender + Clone,
+{
+    fn clone(&self) -> Self {
+        TaskHandle {
+            client: self.client.clone(),
+            task_id: self.task_id.clone(),
+            kind: self.kind,
+            poll_interval: self.poll_interval,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/**
+Get the final response of a task, or `None` if the task is still running.
+*/
+fn into_completed<TResponse>(res: TaskResponse<TResponse>) -> Result<Option<TResponse>, Error> {
+    if !res.completed() {
+        return Ok(None);
+    }
+
+    match res.into_result() {
+        Some(Ok(response)) => Ok(Some(response)),
+        Some(Err(err)) => Err(Error::Api(err)),
+        None => Err(error::request(error::message(
+            "the task completed without a response",
+        ))),
+    }
+}
+
+fn requests_per_second_param(requests_per_second: Option<f32>) -> String {
+    requests_per_second.unwrap_or(-1.0).to_string()
+}
+
+/**
+# Synchronously
+*/
+impl<TResponse> TaskHandle<SyncSender, TResponse>
+where
+    TResponse: DeserializeOwned,
+{
+    /**
+    Get the current status of the task synchronously.
+
+    # Examples
+
+    Check how many documents a delete by query task has deleted so far:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let task = client.document::<MyType>()
+                     .delete_by_query(json!({ "match_all": {} }))
+                     .send_task()?;
+
+    let response = task.status()?;
+
+    if let Some(status) = response.status() {
+        println!("deleted {} of {}", status.deleted(), status.total());
+    }
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn status(&self) -> Result<TaskResponse<TResponse>, Error> {
+        self.client
+            .request(self.status_request())
+            .send()?
+            .into_response()
+    }
+
+    /**
+    Block the current thread until the task has completed, polling its status at the [`poll_interval`](#method.poll_interval).
+
+    If the task fails then the error is returned as an `Error::Api`.
+    */
+    pub fn wait(self) -> Result<TResponse, Error> {
+        loop {
+            if let Some(response) = into_completed(self.status()?)? {
+                return Ok(response);
+            }
+
+            thread::sleep(self.poll_interval);
+        }
+    }
+
+    /**
+    Change the number of requests per second the task is throttled to.
+
+    Passing `None` will remove any throttling.
+    */
+    pub fn rethrottle(&self, requests_per_second: impl Into<Option<f32>>) -> Result<(), Error> {
+        let requests_per_second = requests_per_second_param(requests_per_second.into());
+
+        self.client
+            .request(self.rethrottle_request())
+            .params_fluent(move |params| {
+                params.url_param("requests_per_second", requests_per_second.clone())
+            })
+            .send()?
+            .into_response::<RethrottleResponse>()?
+            .into_result()
+    }
+}
+
+/**
+# Asynchronously
+*/
+impl<TResponse> TaskHandle<AsyncSender, TResponse>
+where
+    TResponse: DeserializeOwned + Send + 'static,
+{
+    /**
+    Get the current status of the task asynchronously.
+
+    # Examples
+
+    Check how many documents a delete by query task has deleted so far:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.document::<MyType>()
+                       .delete_by_query(json!({ "match_all": {} }))
+                       .send_task()
+                       .and_then(|task| task.status());
+
+    future.and_then(|response| {
+        if let Some(status) = response.status() {
+            println!("deleted {} of {}", status.deleted(), status.total());
+        }
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn status(&self) -> Pending<TaskResponse<TResponse>> {
+        let res_future = self
+            .client
+            .request(self.status_request())
+            .send()
+            .and_then(|res| res.into_response());
+
+        Pending::new(res_future)
+    }
+
+    /**
+    Return a future that resolves when the task has completed, polling its status at the [`poll_interval`](#method.poll_interval).
+
+    If the task fails then the error is returned as an `Error::Api`.
+    */
+    pub fn wait(self) -> Pending<TResponse> {
+        let poll_interval = self.poll_interval;
+
+        let res_future = future::loop_fn(self, move |handle| {
+            handle
+                .status()
+                .and_then(into_completed)
+                .and_then(move |response| match response {
+                    Some(response) => Either::A(future::ok(Loop::Break(response))),
+                    None => Either::B(
+                        Delay::new(Instant::now() + poll_interval)
+                            .map_err(error::request)
+                            .map(move |_| Loop::Continue(handle)),
+                    ),
+                })
+        });
+
+        Pending::new(res_future)
+    }
+
+    /**
+    Get a stream that reports the progress of the task, polling its status at the [`poll_interval`](#method.poll_interval).
+
+    The stream yields the counts of documents the task has processed so far, and ends once the task has completed.
+    If the task fails then the stream will return the error as an `Error::Api`.
+    The handle can still be used to change the throttling of the task while the progress is being reported.
+
+    # Examples
+
+    Print the progress of a reindex task:
+
+    ```no_run
+    # use futures::{Future, Stream};
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.reindex("myindex", "mynewindex")
+                       .send_task()
+                       .and_then(|task| {
+                           task.progress().for_each(|status| {
+                               println!("created {} of {}", status.created(), status.total());
+
+                               Ok(())
+                           })
+                       });
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn progress(&self) -> TaskProgress<TResponse> {
+        TaskProgress {
+            state: ProgressState::Polling(self.status()),
+            handle: self.clone(),
+        }
+    }
+
+    /**
+    Change the number of requests per second the task is throttled to.
+
+    Passing `None` will remove any throttling.
+    */
+    pub fn rethrottle(&self, requests_per_second: impl Into<Option<f32>>) -> Pending<()> {
+        let requests_per_second = requests_per_second_param(requests_per_second.into());
+
+        let res_future = self
+            .client
+            .request(self.rethrottle_request())
+            .params_fluent(move |params| {
+                params.url_param("requests_per_second", requests_per_second.clone())
+            })
+            .send()
+            .and_then(|res| res.into_response::<RethrottleResponse>())
+            .and_then(|res| res.into_result());
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling methods on an asynchronous `TaskHandle`. */
+pub type Pending<T> = BasePending<T>;
+
+/**
+A stream that reports the progress of a task.
+
+Call [`TaskHandle.progress`](struct.TaskHandle.html#method.progress) to get a `TaskProgress`.
+*/
+pub struct TaskProgress<TResponse> {
+    handle: TaskHandle<AsyncSender, TResponse>,
+    state: ProgressState<TResponse>,
+}
+
+enum ProgressState<TResponse> {
+    Polling(Pending<TaskResponse<TResponse>>),
+    Waiting(Delay),
+    Done,
+}
+
+impl<TResponse> Stream for TaskProgress<TResponse>
+where
+    TResponse: DeserializeOwned + Send + 'static,
+{
+    type Item = BulkByScrollStatus;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        loop {
+            match mem::replace(&mut self.state, ProgressState::Done) {
+                ProgressState::Polling(mut status) => {
+                    let res = match status.poll() {
+                        Ok(Async::Ready(res)) => res,
+                        Ok(Async::NotReady) => {
+                            self.state = ProgressState::Polling(status);
+                            return Ok(Async::NotReady);
+                        }
+                        Err(err) => return Err(err),
+                    };
+
+                    let progress = res.status().cloned().unwrap_or_default();
+
+                    if into_completed(res)?.is_none() {
+                        let next = Instant::now() + self.handle.poll_interval;
+                        self.state = ProgressState::Waiting(Delay::new(next));
+                    }
+
+                    return Ok(Async::Ready(Some(progress)));
+                }
+                ProgressState::Waiting(mut delay) => match delay.poll() {
+                    Ok(Async::Ready(())) => {
+                        self.state = ProgressState::Polling(self.handle.status());
+                    }
+                    Ok(Async::NotReady) => {
+                        self.state = ProgressState::Waiting(delay);
+                        return Ok(Async::NotReady);
+                    }
+                    Err(err) => return Err(error::request(err)),
+                },
+                ProgressState::Done => return Ok(Async::Ready(None)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<TaskHandle<AsyncSender, DeleteByQueryResponse>>();
+        assert_send::<super::Pending<DeleteByQueryResponse>>();
+        assert_send::<TaskProgress<ReindexResponse>>();
+    }
+
+    fn handle(kind: TaskKind) -> TaskHandle<SyncSender, DeleteByQueryResponse> {
+        let client = SyncClientBuilder::new().build().unwrap();
+        let started = TaskStartedResponse {
+            task: "node:1".to_owned(),
+        };
+
+        TaskHandle::new(client, started, kind)
+    }
+
+    #[test]
+    fn status_request() {
+        let req = handle(TaskKind::DeleteByQuery).status_request();
+
+        assert_eq!("/_tasks/node:1", req.url.as_ref());
+    }
+
+    #[test]
+    fn rethrottle_request() {
+        let req = handle(TaskKind::DeleteByQuery).rethrottle_request();
+        assert_eq!("/_delete_by_query/node:1/_rethrottle", req.url.as_ref());
+
+        let req = handle(TaskKind::UpdateByQuery).rethrottle_request();
+        assert_eq!("/_update_by_query/node:1/_rethrottle", req.url.as_ref());
+
+        let req = handle(TaskKind::Reindex).rethrottle_request();
+        assert_eq!("/_reindex/node:1/_rethrottle", req.url.as_ref());
+    }
+
+    #[test]
+    fn unthrottled_param() {
+        assert_eq!("-1", requests_per_second_param(None));
+        assert_eq!("500", requests_per_second_param(Some(500.0)));
+    }
+
+    #[test]
+    fn rethrottle_failure_is_api_error() {
+        let res: RethrottleResponse = serde_json::from_value(json!({
+            "nodes": {},
+            "task_failures": [{
+                "task_id": 1,
+                "node_id": "node",
+                "status": "NOT_FOUND",
+                "reason": {
+                    "type": "resource_not_found_exception",
+                    "reason": "task [node:1] is missing"
+                }
+            }]
+        }))
+        .unwrap();
+
+        match res.into_result() {
+            Err(Error::Api(ApiError::Other { ty, .. })) => {
+                assert_eq!("resource_not_found_exception", ty)
+            }
+            res => panic!("expected an `ApiError::Other`, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn rethrottle_node_failure_is_api_error() {
+        let res: RethrottleResponse = serde_json::from_value(json!({
+            "nodes": {},
+            "node_failures": [{
+                "type": "failed_node_exception",
+                "reason": "Failed node [node]",
+                "node_id": "node"
+            }]
+        }))
+        .unwrap();
+
+        match res.into_result() {
+            Err(Error::Api(ApiError::Other { ty, .. })) => assert_eq!("failed_node_exception", ty),
+            res => panic!("expected an `ApiError::Other`, got {:?}", res),
+        }
+    }
+}
@@ -0,0 +1,190 @@
+/*!
+Response types for requests that operate on documents matching a query, like [delete by query][docs-delete-by-query], [update by query][docs-update-by-query] and [reindex][docs-reindex].
+
+[docs-delete-by-query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-delete-by-query.html
+[docs-update-by-query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-update-by-query.html
+[docs-reindex]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-reindex.html
+*/
+
+use serde_json::Value;
+
+use crate::http::receiver::IsOkOnSuccess;
+
+/**
+Response for a [delete by query][docs-delete-by-query], [update by query][docs-update-by-query] or [reindex][docs-reindex] request.
+
+A request can partially succeed, so check the `failures` even if the request itself succeeded.
+
+[docs-delete-by-query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-delete-by-query.html
+[docs-update-by-query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-update-by-query.html
+[docs-reindex]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-reindex.html
+*/
+#[derive(Deserialize, Debug, Clone)]
+pub struct BulkByScrollResponse {
+    took: u64,
+    timed_out: bool,
+    #[serde(flatten)]
+    status: BulkByScrollStatus,
+    #[serde(default)]
+    failures: Vec<Value>,
+}
+
+/** Response for a [delete by query](https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-delete-by-query.html) request. */
+pub type DeleteByQueryResponse = BulkByScrollResponse;
+
+/** Response for an [update by query](https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-update-by-query.html) request. */
+pub type UpdateByQueryResponse = BulkByScrollResponse;
+
+/** Response for a [reindex](https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-reindex.html) request. */
+pub type ReindexResponse = BulkByScrollResponse;
+
+impl BulkByScrollResponse {
+    /** Time in milliseconds it took for Elasticsearch to process the request. */
+    pub fn took(&self) -> u64 {
+        self.took
+    }
+
+    /** Whether or not any requests timed out before completing. */
+    pub fn timed_out(&self) -> bool {
+        self.timed_out
+    }
+
+    /** The number of documents that were processed. */
+    pub fn total(&self) -> u64 {
+        self.status.total
+    }
+
+    /** The number of documents that were created. */
+    pub fn created(&self) -> u64 {
+        self.status.created
+    }
+
+    /** The number of documents that were updated. */
+    pub fn updated(&self) -> u64 {
+        self.status.updated
+    }
+
+    /** The number of documents that were deleted. */
+    pub fn deleted(&self) -> u64 {
+        self.status.deleted
+    }
+
+    /** The number of scroll responses that were pulled back. */
+    pub fn batches(&self) -> u64 {
+        self.status.batches
+    }
+
+    /** The number of version conflicts that were hit. */
+    pub fn version_conflicts(&self) -> u64 {
+        self.status.version_conflicts
+    }
+
+    /** The number of documents that were ignored because a script returned `noop`. */
+    pub fn noops(&self) -> u64 {
+        self.status.noops
+    }
+
+    /** The counts of the request, like the number of documents updated. */
+    pub fn status(&self) -> &BulkByScrollStatus {
+        &self.status
+    }
+
+    /**
+    Any documents that failed during the request.
+
+    If any documents fail then the request is aborted, but documents that were processed before the failure aren't rolled back.
+    */
+    pub fn failures(&self) -> &[Value] {
+        &self.failures
+    }
+
+    /** Returns `true` if the request completed without any failures. */
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty() && !self.timed_out
+    }
+}
+
+impl IsOkOnSuccess for BulkByScrollResponse {}
+
+/**
+The counts of a request that operates on documents matching a query.
+
+For a request that's still running, the counts are a snapshot of its progress so far.
+*/
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct BulkByScrollStatus {
+    #[serde(default)]
+    total: u64,
+    #[serde(default)]
+    created: u64,
+    #[serde(default)]
+    updated: u64,
+    #[serde(default)]
+    deleted: u64,
+    #[serde(default)]
+    batches: u64,
+    #[serde(default)]
+    version_conflicts: u64,
+    #[serde(default)]
+    noops: u64,
+    #[serde(default)]
+    throttled_millis: u64,
+    requests_per_second: Option<f32>,
+}
+
+impl BulkByScrollStatus {
+    /** The number of documents to process. */
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /** The number of documents that were created. */
+    pub fn created(&self) -> u64 {
+        self.created
+    }
+
+    /** The number of documents that were updated. */
+    pub fn updated(&self) -> u64 {
+        self.updated
+    }
+
+    /** The number of documents that were deleted. */
+    pub fn deleted(&self) -> u64 {
+        self.deleted
+    }
+
+    /** The number of scroll responses that were pulled back. */
+    pub fn batches(&self) -> u64 {
+        self.batches
+    }
+
+    /** The number of version conflicts that were hit. */
+    pub fn version_conflicts(&self) -> u64 {
+        self.version_conflicts
+    }
+
+    /** The number of documents that were ignored because a script returned `noop`. */
+    pub fn noops(&self) -> u64 {
+        self.noops
+    }
+
+    /** The number of milliseconds the request slept to conform to `requests_per_second`. */
+    pub fn throttled_millis(&self) -> u64 {
+        self.throttled_millis
+    }
+
+    /**
+    The number of requests per second the request is throttled to.
+
+    This value is `None` if the request isn't throttled.
+    */
+    pub fn requests_per_second(&self) -> Option<f32> {
+        self.requests_per_second
+            .filter(|requests_per_second| *requests_per_second >= 0.0)
+    }
+
+    /** The number of documents that have been processed so far. */
+    pub fn processed(&self) -> u64 {
+        self.created + self.updated + self.deleted + self.noops + self.version_conflicts
+    }
+}
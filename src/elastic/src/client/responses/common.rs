@@ -2,6 +2,22 @@
 Types that are common to many responses.
 */
 
+use serde::de::{
+    Deserialize,
+    DeserializeOwned,
+    Deserializer,
+    Error as DeError,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+
+use crate::http::receiver::{
+    ApiError,
+    ParsedApiError,
+};
+
 /** A default type for allocated fields in responses. */
 pub(crate) type DefaultAllocatedField = String;
 
@@ -43,3 +59,31 @@ pub(crate) enum DocumentResult {
     #[serde(rename = "created")]
     Created,
 }
+
+/**
+Deserialize an array of response items that may individually fail.
+
+Items with an `error` field are converted into an `ApiError`.
+*/
+pub(crate) fn deserialize_item_results<'de, D, T>(
+    deserializer: D,
+) -> Result<Vec<Result<T, ApiError>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let items = Vec::<Map<String, Value>>::deserialize(deserializer)?;
+
+    items
+        .into_iter()
+        .map(|item| {
+            if item.contains_key("error") {
+                Ok(Err(ParsedApiError::from(item).into_api_error()))
+            } else {
+                serde_json::from_value(Value::Object(item))
+                    .map(Ok)
+                    .map_err(D::Error::custom)
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,130 @@
+/*!
+Response types for a [multi get document request](https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-multi-get.html).
+*/
+
+use serde::de::DeserializeOwned;
+
+use super::{
+    common::deserialize_item_results,
+    GetResponse,
+};
+use crate::http::receiver::{
+    ApiError,
+    IsOkOnSuccess,
+};
+
+use std::{
+    slice::Iter,
+    vec::IntoIter,
+};
+
+/**
+Response for a [multi get document request](https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-multi-get.html).
+
+Individual documents are a `Result` of [`GetResponse`](struct.GetResponse.html) or [`ApiError`](../../error/enum.ApiError.html) and can be iterated over.
+They're returned in the same order as the ids in the request.
+A document that doesn't exist is still an `Ok(GetResponse)`, so check its `found` method.
+A document that couldn't be fetched at all, for example because its index doesn't exist, is an `Err(ApiError)`.
+
+# Examples
+
+Send a multi get request and iterate through the results:
+
+```no_run
+# use serde_json::Value;
+# use elastic::prelude::*;
+# fn do_request() -> GetManyResponse<Value> { unimplemented!() }
+let response: GetManyResponse<Value> = do_request();
+
+for doc in response {
+    match doc {
+        Ok(ref doc) if doc.found() => println!("found: {:?}", doc.document()),
+        Ok(doc) => println!("not found: {}", doc.id()),
+        Err(err) => println!("err: {:?}", err),
+    }
+}
+```
+*/
+#[derive(Deserialize, Debug)]
+#[serde(bound(deserialize = "T: DeserializeOwned"))]
+pub struct GetManyResponse<T> {
+    #[serde(deserialize_with = "deserialize_item_results")]
+    docs: Vec<GetManyItem<T>>,
+}
+
+/** The result of an individual document in a multi get response. */
+pub type GetManyItem<T> = Result<GetResponse<T>, ApiError>;
+
+/** A borrowed result of an individual document in a multi get response. */
+pub type GetManyItemBrw<'a, T> = Result<&'a GetResponse<T>, &'a ApiError>;
+
+impl<T> GetManyResponse<T> {
+    /** The number of documents in the response. */
+    pub fn len(&self) -> usize {
+        self.docs.len()
+    }
+
+    /** Returns `true` if the response doesn't contain any documents. */
+    pub fn is_empty(&self) -> bool {
+        self.docs.is_empty()
+    }
+
+    /**
+    Iterate through the documents.
+
+    The items in this iterator are a standard `Result` where `Ok` means the document was fetched
+    and `Err` means it failed.
+
+    To move out of the items in a `GetManyResponse` instead of borrowing them, call `into_iter`.
+    */
+    pub fn iter(&self) -> GetManyIter<'_, T> {
+        GetManyIter(self.docs.iter())
+    }
+
+    /** Iterate through the source of the documents that were found. */
+    pub fn documents(&self) -> impl Iterator<Item = &T> {
+        self.docs
+            .iter()
+            .filter_map(|doc| doc.as_ref().ok().and_then(GetResponse::document))
+    }
+
+    /** Convert the response into the source of the documents that were found. */
+    pub fn into_documents(self) -> impl Iterator<Item = T> {
+        self.docs
+            .into_iter()
+            .filter_map(|doc| doc.ok().and_then(GetResponse::into_document))
+    }
+}
+
+impl<T> IntoIterator for GetManyResponse<T> {
+    type Item = <Self::IntoIter as Iterator>::Item;
+    type IntoIter = GetManyIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        GetManyIntoIter(self.docs.into_iter())
+    }
+}
+
+/** An owning iterator for a document that may have been fetched or failed. */
+pub struct GetManyIntoIter<T>(IntoIter<GetManyItem<T>>);
+
+impl<T> Iterator for GetManyIntoIter<T> {
+    type Item = GetManyItem<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+/** A borrowing iterator for a document that may have been fetched or failed. */
+pub struct GetManyIter<'a, T: 'a>(Iter<'a, GetManyItem<T>>);
+
+impl<'a, T: 'a> Iterator for GetManyIter<'a, T> {
+    type Item = GetManyItemBrw<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|item| item.as_ref())
+    }
+}
+
+impl<T: DeserializeOwned> IsOkOnSuccess for GetManyResponse<T> {}
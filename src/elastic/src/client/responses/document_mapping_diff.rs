@@ -0,0 +1,345 @@
+/*!
+Response types for a mapping diff request.
+*/
+
+use serde_json::{
+    Map,
+    Value,
+};
+use std::collections::BTreeMap;
+
+/**
+Response for a mapping diff request.
+
+The response contains a diff for each index that matched the request.
+An index that doesn't exist won't be included in the response.
+
+# Examples
+
+Check whether the mapping for a document type can be applied to the live index:
+
+```no_run
+# use elastic::prelude::*;
+# fn do_request() -> MappingDiffResponse { unimplemented!() }
+let response: MappingDiffResponse = do_request();
+
+for (index, diff) in response.iter() {
+    for change in diff.type_changed() {
+        println!("{}: {:?}", index, change);
+    }
+}
+
+assert!(response.is_compatible());
+```
+*/
+#[derive(Debug, Clone, Default)]
+pub struct MappingDiffResponse {
+    indices: BTreeMap<String, MappingDiff>,
+}
+
+impl MappingDiffResponse {
+    pub(crate) fn new(indices: BTreeMap<String, MappingDiff>) -> Self {
+        MappingDiffResponse { indices }
+    }
+
+    /** Get the diff for an index by name. */
+    pub fn get(&self, index: &str) -> Option<&MappingDiff> {
+        self.indices.get(index)
+    }
+
+    /** Iterate through the diff for each index by name. */
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &MappingDiff)> {
+        self.indices.iter().map(|(index, diff)| (index.as_ref(), diff))
+    }
+
+    /** Whether or not any indices matched the request. */
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /**
+    Whether or not the mapping is compatible with every index that matched the request.
+
+    See [`MappingDiff::is_compatible`](struct.MappingDiff.html#method.is_compatible) for details.
+    */
+    pub fn is_compatible(&self) -> bool {
+        self.indices.values().all(MappingDiff::is_compatible)
+    }
+
+    /**
+    The mapping containing only the fields that can be added to every index.
+
+    Returns `None` if no indices matched the request.
+    */
+    pub(crate) fn additive_mapping(&self) -> Option<Value> {
+        let mut diffs = self.indices.values();
+
+        let mut mapping = diffs.next()?.additive_mapping().clone();
+        for diff in diffs {
+            merge(&mut mapping, diff.additive_mapping());
+        }
+
+        Some(mapping)
+    }
+}
+
+/**
+The differences between the mapping for a document type and the live mapping of a single index.
+
+Fields are identified by their dotted path, like `address.street`.
+Multi-fields are included as children of the field they belong to, like `title.raw`.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct MappingDiff {
+    changes: Vec<FieldChange>,
+    additive_mapping: Value,
+}
+
+impl MappingDiff {
+    pub(crate) fn new(live: &Value, local: &Value) -> Self {
+        let live_fields = fields(live);
+        let local_fields = fields(local);
+
+        let mut changes = Vec::new();
+
+        for (path, local_ty) in &local_fields {
+            match live_fields.get(path) {
+                None => changes.push(FieldChange::Added {
+                    path: path.clone(),
+                    ty: local_ty.clone(),
+                }),
+                Some(live_ty) if live_ty != local_ty => changes.push(FieldChange::TypeChanged {
+                    path: path.clone(),
+                    from: live_ty.clone(),
+                    to: local_ty.clone(),
+                }),
+                Some(_) => (),
+            }
+        }
+
+        for (path, live_ty) in &live_fields {
+            if !local_fields.contains_key(path) {
+                changes.push(FieldChange::Removed {
+                    path: path.clone(),
+                    ty: live_ty.clone(),
+                });
+            }
+        }
+
+        changes.sort_by(|a, b| a.path().cmp(b.path()));
+
+        let mut additive_mapping = Map::new();
+        additive_mapping.insert(
+            "properties".to_owned(),
+            Value::Object(additive_properties(live, local, "properties")),
+        );
+
+        MappingDiff {
+            changes,
+            additive_mapping: Value::Object(additive_mapping),
+        }
+    }
+
+    /** All changes, ordered by field path. */
+    pub fn changes(&self) -> &[FieldChange] {
+        &self.changes
+    }
+
+    /** Fields that are in the document mapping but not the live index. */
+    pub fn added(&self) -> impl Iterator<Item = &FieldChange> {
+        self.changes
+            .iter()
+            .filter(|change| matches!(change, FieldChange::Added { .. }))
+    }
+
+    /** Fields that are in the live index but not the document mapping. */
+    pub fn removed(&self) -> impl Iterator<Item = &FieldChange> {
+        self.changes
+            .iter()
+            .filter(|change| matches!(change, FieldChange::Removed { .. }))
+    }
+
+    /** Fields that have a different type in the document mapping than in the live index. */
+    pub fn type_changed(&self) -> impl Iterator<Item = &FieldChange> {
+        self.changes
+            .iter()
+            .filter(|change| matches!(change, FieldChange::TypeChanged { .. }))
+    }
+
+    /** Whether or not the document mapping is the same as the live index. */
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /**
+    Whether or not the document mapping is compatible with the live index.
+
+    The mapping is compatible if no fields have changed type.
+    Removed fields are still compatible because they'll remain in the index without being used.
+    */
+    pub fn is_compatible(&self) -> bool {
+        self.type_changed().next().is_none()
+    }
+
+    /**
+    The document mapping containing only the fields that can be added to the live index.
+
+    Fields that have been removed or changed type aren't included.
+    */
+    pub fn additive_mapping(&self) -> &Value {
+        &self.additive_mapping
+    }
+}
+
+/** A change to a single field. */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldChange {
+    /** A field that is in the document mapping but not the live index. */
+    Added {
+        /** The dotted path to the field. */
+        path: String,
+        /** The type of the field in the document mapping. */
+        ty: String,
+    },
+    /** A field that is in the live index but not the document mapping. */
+    Removed {
+        /** The dotted path to the field. */
+        path: String,
+        /** The type of the field in the live index. */
+        ty: String,
+    },
+    /** A field that has a different type in the document mapping than in the live index. */
+    TypeChanged {
+        /** The dotted path to the field. */
+        path: String,
+        /** The type of the field in the live index. */
+        from: String,
+        /** The type of the field in the document mapping. */
+        to: String,
+    },
+}
+
+impl FieldChange {
+    /** The dotted path to the field. */
+    pub fn path(&self) -> &str {
+        match self {
+            FieldChange::Added { path, .. }
+            | FieldChange::Removed { path, .. }
+            | FieldChange::TypeChanged { path, .. } => path,
+        }
+    }
+
+    /**
+    Whether or not the change can be applied to the live index in place.
+
+    Only new fields can be added to an existing index.
+    Fields can't be removed and their type can't be changed without reindexing.
+    */
+    pub fn can_apply_in_place(&self) -> bool {
+        matches!(self, FieldChange::Added { .. })
+    }
+}
+
+/** Get the type of each field in a mapping by its dotted path. */
+fn fields(mapping: &Value) -> BTreeMap<String, String> {
+    let mut fields = BTreeMap::new();
+    collect_fields(mapping, "", &mut fields);
+
+    fields
+}
+
+fn collect_fields(mapping: &Value, prefix: &str, fields: &mut BTreeMap<String, String>) {
+    for key in &["properties", "fields"] {
+        if let Some(Value::Object(properties)) = mapping.get(key) {
+            for (name, field) in properties {
+                let path = format!("{}{}", prefix, name);
+
+                collect_fields(field, &format!("{}.", path), fields);
+                fields.insert(path, field_type(field).to_owned());
+            }
+        }
+    }
+}
+
+/**
+Get the type of a field.
+
+Object fields don't need an explicit type.
+*/
+fn field_type(field: &Value) -> &str {
+    field.get("type").and_then(Value::as_str).unwrap_or("object")
+}
+
+/**
+Get the fields under `key` in the local mapping that can be added to the live mapping.
+
+New fields are included as-is.
+Existing fields with new children are included with just those children.
+*/
+fn additive_properties(live: &Value, local: &Value, key: &str) -> Map<String, Value> {
+    let mut additions = Map::new();
+
+    let local_properties = match local.get(key) {
+        Some(Value::Object(properties)) => properties,
+        _ => return additions,
+    };
+
+    for (name, local_field) in local_properties {
+        let live_field = match live.get(key).and_then(|properties| properties.get(name)) {
+            Some(live_field) => live_field,
+            None => {
+                additions.insert(name.clone(), local_field.clone());
+                continue;
+            }
+        };
+
+        if field_type(live_field) != field_type(local_field) {
+            continue;
+        }
+
+        let properties = additive_properties(live_field, local_field, "properties");
+        let fields = additive_properties(live_field, local_field, "fields");
+
+        if properties.is_empty() && fields.is_empty() {
+            continue;
+        }
+
+        // Send the existing field definition with only its new children
+        let mut field = match live_field {
+            Value::Object(field) => field.clone(),
+            _ => Map::new(),
+        };
+
+        field.remove("properties");
+        field.remove("fields");
+
+        if !properties.is_empty() {
+            field.insert("properties".to_owned(), Value::Object(properties));
+        }
+
+        if !fields.is_empty() {
+            field.insert("fields".to_owned(), Value::Object(fields));
+        }
+
+        additions.insert(name.clone(), Value::Object(field));
+    }
+
+    additions
+}
+
+/** Recursively merge the objects in `from` into `into`. */
+fn merge(into: &mut Value, from: &Value) {
+    match (into, from) {
+        (Value::Object(into), Value::Object(from)) => {
+            for (key, value) in from {
+                match into.get_mut(key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        into.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (into, from) => *into = from.clone(),
+    }
+}
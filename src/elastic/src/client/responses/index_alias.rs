@@ -0,0 +1,146 @@
+/*!
+Response types for [index alias requests](https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-aliases.html).
+*/
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::http::{
+    receiver::{
+        HttpResponseHead,
+        IsOk,
+        IsOkOnSuccess,
+        MaybeOkResponse,
+        ParseError,
+        ResponseBody,
+        Unbuffered,
+    },
+    StatusCode,
+};
+
+/**
+Response for a [get alias request](https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-aliases.html#alias-retrieving).
+
+The response contains the aliases for each index that matched the request.
+
+# Examples
+
+Print the aliases of an index:
+
+```no_run
+# use elastic::prelude::*;
+# fn do_request() -> AliasesResponse { unimplemented!() }
+let response: AliasesResponse = do_request();
+
+for alias in response.aliases() {
+    println!("{} -> {} (filter: {:?})", alias.name(), alias.index(), alias.filter());
+}
+```
+*/
+#[derive(Deserialize, Debug, Default)]
+pub struct AliasesResponse(BTreeMap<String, IndexAliases>);
+
+#[derive(Deserialize, Debug)]
+struct IndexAliases {
+    #[serde(default)]
+    aliases: BTreeMap<String, AliasOptions>,
+}
+
+#[derive(Deserialize, Debug)]
+struct AliasOptions {
+    filter: Option<Value>,
+    index_routing: Option<String>,
+    search_routing: Option<String>,
+    is_write_index: Option<bool>,
+}
+
+impl AliasesResponse {
+    /** The indices that matched the request. */
+    pub fn indices(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(|index| index.as_ref())
+    }
+
+    /** Iterate through each alias for each index. */
+    pub fn aliases(&self) -> impl Iterator<Item = Alias<'_>> {
+        self.0.iter().flat_map(|(index, aliases)| {
+            aliases.aliases.iter().map(move |(name, options)| Alias {
+                index,
+                name,
+                options,
+            })
+        })
+    }
+
+    /** Whether or not any indices matched the request. */
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl IsOkOnSuccess for AliasesResponse {}
+
+/** A single alias on an index. */
+#[derive(Debug, Clone, Copy)]
+pub struct Alias<'a> {
+    index: &'a str,
+    name: &'a str,
+    options: &'a AliasOptions,
+}
+
+impl<'a> Alias<'a> {
+    /** The index the alias points to. */
+    pub fn index(&self) -> &'a str {
+        self.index
+    }
+
+    /** The name of the alias. */
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /** The query used to filter documents visible through the alias. */
+    pub fn filter(&self) -> Option<&'a Value> {
+        self.options.filter.as_ref()
+    }
+
+    /** The routing value used when indexing documents through the alias. */
+    pub fn index_routing(&self) -> Option<&'a str> {
+        self.options.index_routing.as_deref()
+    }
+
+    /** The routing value used when searching documents through the alias. */
+    pub fn search_routing(&self) -> Option<&'a str> {
+        self.options.search_routing.as_deref()
+    }
+
+    /** Whether or not the index is the write index for the alias. */
+    pub fn is_write_index(&self) -> Option<bool> {
+        self.options.is_write_index
+    }
+}
+
+/** Response for an [alias exists request](https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-aliases.html#alias-retrieving). */
+#[derive(Deserialize, Debug)]
+pub struct AliasExistsResponse {
+    exists: bool,
+}
+
+impl AliasExistsResponse {
+    /** Whether or not the alias exists. */
+    pub fn exists(&self) -> bool {
+        self.exists
+    }
+}
+
+impl IsOk for AliasExistsResponse {
+    fn is_ok<B: ResponseBody>(
+        head: HttpResponseHead,
+        body: Unbuffered<B>,
+    ) -> Result<MaybeOkResponse<B>, ParseError> {
+        match head.status() {
+            status if status.is_success() => Ok(MaybeOkResponse::ok(json!({ "exists": true }))),
+            StatusCode::NOT_FOUND => Ok(MaybeOkResponse::ok(json!({ "exists": false }))),
+            _ => Ok(MaybeOkResponse::err(body)),
+        }
+    }
+}
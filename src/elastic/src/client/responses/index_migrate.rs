@@ -0,0 +1,71 @@
+/*!
+Response types for an index migration.
+*/
+
+use super::ReindexResponse;
+
+/**
+The result of migrating an alias to a new index.
+
+A migration is made up of several requests, so this response summarises them rather than being deserialised from a single Elasticsearch response.
+*/
+#[derive(Debug)]
+pub struct IndexMigrateResponse {
+    pub(crate) alias: String,
+    pub(crate) new_index: String,
+    pub(crate) old_indices: Vec<String>,
+    pub(crate) reindex: Option<ReindexResponse>,
+    pub(crate) doc_count: u64,
+    pub(crate) deleted_old_indices: bool,
+    pub(crate) undeleted_old_indices: Vec<String>,
+}
+
+impl IndexMigrateResponse {
+    /** The alias that was migrated. */
+    pub fn alias(&self) -> &str {
+        &self.alias
+    }
+
+    /** The index the alias now points to. */
+    pub fn new_index(&self) -> &str {
+        &self.new_index
+    }
+
+    /** The indices the alias pointed to before the migration. */
+    pub fn old_indices(&self) -> &[String] {
+        &self.old_indices
+    }
+
+    /**
+    The response of reindexing documents from the old indices into the new one.
+
+    This value is `None` if the alias didn't point to any indices so there was nothing to reindex.
+    */
+    pub fn reindex(&self) -> Option<&ReindexResponse> {
+        self.reindex.as_ref()
+    }
+
+    /** The number of documents in the new index. */
+    pub fn doc_count(&self) -> u64 {
+        self.doc_count
+    }
+
+    /**
+    Whether or not the old indices were deleted after the alias was swapped.
+
+    Some of the old indices may have failed to delete.
+    They're available from [`undeleted_old_indices`](#method.undeleted_old_indices).
+    */
+    pub fn deleted_old_indices(&self) -> bool {
+        self.deleted_old_indices
+    }
+
+    /**
+    The old indices that couldn't be deleted after the alias was swapped.
+
+    The migration still succeeded, but these indices will need to be deleted separately.
+    */
+    pub fn undeleted_old_indices(&self) -> &[String] {
+        &self.undeleted_old_indices
+    }
+}
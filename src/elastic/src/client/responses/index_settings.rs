@@ -0,0 +1,59 @@
+/*!
+Response types for a [get index settings request](https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-get-settings.html).
+*/
+
+use std::collections::BTreeMap;
+
+use crate::{
+    client::requests::common::IndexSettings,
+    http::receiver::IsOkOnSuccess,
+};
+
+/**
+Response for a [get index settings request](https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-get-settings.html).
+
+The response contains the settings for each index that matched the request.
+
+# Examples
+
+Print the number of replicas for an index:
+
+```no_run
+# use elastic::prelude::*;
+# fn do_request() -> IndexSettingsResponse { unimplemented!() }
+let response: IndexSettingsResponse = do_request();
+
+if let Some(settings) = response.get("myindex") {
+    println!("{:?}", settings.number_of_replicas);
+}
+```
+*/
+#[derive(Deserialize, Debug)]
+pub struct IndexSettingsResponse(BTreeMap<String, IndexSettingsEntry>);
+
+#[derive(Deserialize, Debug)]
+struct IndexSettingsEntry {
+    settings: IndexSettingsWrapper,
+}
+
+#[derive(Deserialize, Debug)]
+struct IndexSettingsWrapper {
+    #[serde(default)]
+    index: IndexSettings,
+}
+
+impl IndexSettingsResponse {
+    /** Get the settings for an index by name. */
+    pub fn get(&self, index: &str) -> Option<&IndexSettings> {
+        self.0.get(index).map(|entry| &entry.settings.index)
+    }
+
+    /** Iterate through the settings for each index by name. */
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &IndexSettings)> {
+        self.0
+            .iter()
+            .map(|(index, entry)| (index.as_ref(), &entry.settings.index))
+    }
+}
+
+impl IsOkOnSuccess for IndexSettingsResponse {}
@@ -0,0 +1,145 @@
+/*!
+Response types for [index template requests](https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-templates.html).
+*/
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::http::{
+    receiver::{
+        HttpResponseHead,
+        IsOk,
+        MaybeOkResponse,
+        ParseError,
+        ResponseBody,
+        Unbuffered,
+    },
+    StatusCode,
+};
+
+/**
+Response for a [get index template request](https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-templates.html#getting).
+
+The response contains each template that matched the requested name.
+A template that doesn't exist will produce an empty response rather than an error.
+
+# Examples
+
+Print the index patterns of a template:
+
+```no_run
+# use elastic::prelude::*;
+# fn do_request() -> IndexTemplatesResponse { unimplemented!() }
+let response: IndexTemplatesResponse = do_request();
+
+if let Some(template) = response.get("logs") {
+    println!("{:?}", template.index_patterns());
+}
+```
+*/
+#[derive(Deserialize, Debug)]
+pub struct IndexTemplatesResponse(BTreeMap<String, IndexTemplate>);
+
+impl IndexTemplatesResponse {
+    /** Get a template by name. */
+    pub fn get(&self, name: &str) -> Option<&IndexTemplate> {
+        self.0.get(name)
+    }
+
+    /** Iterate through the templates by name. */
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &IndexTemplate)> {
+        self.0.iter().map(|(name, template)| (name.as_ref(), template))
+    }
+
+    /** Whether or not any templates matched the request. */
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl IsOk for IndexTemplatesResponse {
+    fn is_ok<B: ResponseBody>(
+        head: HttpResponseHead,
+        body: Unbuffered<B>,
+    ) -> Result<MaybeOkResponse<B>, ParseError> {
+        match head.status() {
+            status if status.is_success() => Ok(MaybeOkResponse::ok(body)),
+            StatusCode::NOT_FOUND => Ok(MaybeOkResponse::ok(json!({}))),
+            _ => Ok(MaybeOkResponse::err(body)),
+        }
+    }
+}
+
+/** A single index template. */
+#[derive(Deserialize, Debug)]
+pub struct IndexTemplate {
+    #[serde(default)]
+    order: i64,
+    version: Option<i64>,
+    #[serde(default)]
+    index_patterns: Vec<String>,
+    #[serde(default)]
+    settings: Value,
+    #[serde(default)]
+    mappings: Value,
+    #[serde(default)]
+    aliases: Value,
+}
+
+impl IndexTemplate {
+    /** The order the template is applied in when multiple templates match an index. */
+    pub fn order(&self) -> i64 {
+        self.order
+    }
+
+    /** The version of the template, if one was set. */
+    pub fn version(&self) -> Option<i64> {
+        self.version
+    }
+
+    /** The index name patterns the template applies to. */
+    pub fn index_patterns(&self) -> &[String] {
+        &self.index_patterns
+    }
+
+    /** The settings applied to new indices. */
+    pub fn settings(&self) -> &Value {
+        &self.settings
+    }
+
+    /** The mappings applied to new indices. */
+    pub fn mappings(&self) -> &Value {
+        &self.mappings
+    }
+
+    /** The aliases added to new indices. */
+    pub fn aliases(&self) -> &Value {
+        &self.aliases
+    }
+}
+
+/** Response for an [index template exists request](https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-templates.html#indices-templates-exists). */
+#[derive(Deserialize, Debug)]
+pub struct IndexTemplateExistsResponse {
+    exists: bool,
+}
+
+impl IndexTemplateExistsResponse {
+    /** Whether or not the template exists. */
+    pub fn exists(&self) -> bool {
+        self.exists
+    }
+}
+
+impl IsOk for IndexTemplateExistsResponse {
+    fn is_ok<B: ResponseBody>(
+        head: HttpResponseHead,
+        body: Unbuffered<B>,
+    ) -> Result<MaybeOkResponse<B>, ParseError> {
+        match head.status() {
+            status if status.is_success() => Ok(MaybeOkResponse::ok(json!({ "exists": true }))),
+            StatusCode::NOT_FOUND => Ok(MaybeOkResponse::ok(json!({ "exists": false }))),
+            _ => Ok(MaybeOkResponse::err(body)),
+        }
+    }
+}
@@ -5,16 +5,25 @@ This module contains implementation details that are useful if you want to custo
 */
 
 pub mod bulk;
+pub mod bulk_by_scroll;
 mod command;
 pub mod common;
 mod document_delete;
 mod document_get;
+pub mod document_get_many;
 mod document_index;
+pub mod document_mapping_diff;
 mod document_update;
+pub mod index_alias;
+pub mod index_migrate;
+pub mod index_settings;
+pub mod index_template;
+pub mod msearch;
 pub mod nodes_info;
 mod ping;
 pub mod search;
 mod sql;
+pub mod task;
 
 mod index_exists;
 
@@ -27,15 +36,42 @@ pub use self::{
         BulkErrorsResponse,
         BulkResponse,
     },
+    bulk_by_scroll::{
+        BulkByScrollResponse,
+        DeleteByQueryResponse,
+        ReindexResponse,
+        UpdateByQueryResponse,
+    },
     command::*,
     document_delete::*,
     document_get::*,
+    document_get_many::GetManyResponse,
     document_index::*,
+    document_mapping_diff::{
+        FieldChange,
+        MappingDiff,
+        MappingDiffResponse,
+    },
     document_update::*,
-    nodes_info::NodesInfoResponse,
+    index_alias::{
+        AliasExistsResponse,
+        AliasesResponse,
+    },
+    index_migrate::IndexMigrateResponse,
+    index_settings::IndexSettingsResponse,
+    index_template::{
+        IndexTemplateExistsResponse,
+        IndexTemplatesResponse,
+    },
+    msearch::MsearchResponse,
+    nodes_info::{
+        NodeInfo,
+        NodesInfoResponse,
+    },
     ping::*,
     search::SearchResponse,
     sql::*,
+    task::TaskResponse,
 };
 
 pub use self::index_exists::*;
@@ -45,17 +81,30 @@ pub mod prelude {
 
     pub use super::{
         bulk::Action as BulkAction,
+        AliasExistsResponse,
+        AliasesResponse,
         BulkErrorsResponse,
         BulkResponse,
         CommandResponse,
+        DeleteByQueryResponse,
         DeleteResponse,
+        GetManyResponse,
         GetResponse,
+        IndexMigrateResponse,
         IndexResponse,
+        IndexSettingsResponse,
+        IndexTemplateExistsResponse,
+        IndexTemplatesResponse,
         IndicesExistsResponse,
+        MappingDiffResponse,
+        MsearchResponse,
         NodesInfoResponse,
         PingResponse,
+        ReindexResponse,
         SearchResponse,
         SqlQueryResponse,
+        TaskResponse,
+        UpdateByQueryResponse,
         UpdateResponse,
     };
 }
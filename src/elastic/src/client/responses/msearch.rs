@@ -0,0 +1,146 @@
+/*!
+Response types for a [multi search request](https://www.elastic.co/guide/en/elasticsearch/reference/current/search-multi-search.html).
+*/
+
+use serde::de::DeserializeOwned;
+
+use super::{
+    common::deserialize_item_results,
+    SearchResponse,
+};
+use crate::http::receiver::{
+    ApiError,
+    IsOkOnSuccess,
+};
+
+use std::{
+    slice::Iter,
+    vec::IntoIter,
+};
+
+/**
+Response for a [multi search request](https://www.elastic.co/guide/en/elasticsearch/reference/current/search-multi-search.html).
+
+Individual searches are a `Result` of [`SearchResponse`](struct.SearchResponse.html) or [`ApiError`](../../error/enum.ApiError.html) and can be iterated over.
+They're returned in the same order they were added to the request.
+Any individual search may be an `Err(ApiError)`, so it's important to check them.
+
+# Examples
+
+Send a multi search request and iterate through the results:
+
+```no_run
+# use serde_json::Value;
+# use elastic::prelude::*;
+# fn do_request() -> MsearchResponse<Value> { unimplemented!() }
+let response: MsearchResponse<Value> = do_request();
+
+// Check if the response contains any errors
+if response.is_err() {
+    println!("some searches failed");
+}
+
+// Iterate through all searches
+for search in response {
+    match search {
+        Ok(search) => {
+            // Do something with the `SearchResponse`s
+            println!("ok: {:?}", search)
+        },
+        Err(err) => {
+            // Do something with the `ApiError`s
+            println!("err: {:?}", err)
+        }
+    }
+}
+```
+*/
+#[derive(Deserialize, Debug)]
+#[serde(bound(deserialize = "T: DeserializeOwned"))]
+pub struct MsearchResponse<T> {
+    took: Option<u64>,
+    #[serde(deserialize_with = "deserialize_item_results")]
+    responses: Vec<MsearchItem<T>>,
+}
+
+/** The result of an individual search in a multi search response. */
+pub type MsearchItem<T> = Result<SearchResponse<T>, ApiError>;
+
+/** A borrowed result of an individual search in a multi search response. */
+pub type MsearchItemBrw<'a, T> = Result<&'a SearchResponse<T>, &'a ApiError>;
+
+impl<T> MsearchResponse<T> {
+    /**
+    Time in milliseconds it took for Elasticsearch to process the request.
+
+    Older versions of Elasticsearch don't return this value.
+    */
+    pub fn took(&self) -> Option<u64> {
+        self.took
+    }
+
+    /** The number of searches in the response. */
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    /** Returns `true` if the response doesn't contain any searches. */
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+
+    /** Returns `true` if all searches succeeded. */
+    pub fn is_ok(&self) -> bool {
+        self.responses.iter().all(Result::is_ok)
+    }
+
+    /** Returns `true` if any searches failed. */
+    pub fn is_err(&self) -> bool {
+        !self.is_ok()
+    }
+
+    /**
+    Iterate through the searches.
+
+    The items in this iterator are a standard `Result` where `Ok` means the search succeeded
+    and `Err` means it failed.
+
+    To move out of the items in a `MsearchResponse` instead of borrowing them, call `into_iter`.
+    */
+    pub fn iter(&self) -> MsearchIter<'_, T> {
+        MsearchIter(self.responses.iter())
+    }
+}
+
+impl<T> IntoIterator for MsearchResponse<T> {
+    type Item = <Self::IntoIter as Iterator>::Item;
+    type IntoIter = MsearchIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        MsearchIntoIter(self.responses.into_iter())
+    }
+}
+
+/** An owning iterator for a search that may have succeeded or failed. */
+pub struct MsearchIntoIter<T>(IntoIter<MsearchItem<T>>);
+
+impl<T> Iterator for MsearchIntoIter<T> {
+    type Item = MsearchItem<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+/** A borrowing iterator for a search that may have succeeded or failed. */
+pub struct MsearchIter<'a, T: 'a>(Iter<'a, MsearchItem<T>>);
+
+impl<'a, T: 'a> Iterator for MsearchIter<'a, T> {
+    type Item = MsearchItemBrw<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|item| item.as_ref())
+    }
+}
+
+impl<T: DeserializeOwned> IsOkOnSuccess for MsearchResponse<T> {}
@@ -9,6 +9,7 @@ use serde::de::{
 };
 
 use std::{
+    collections::BTreeMap,
     fmt,
     iter::IntoIterator,
     slice::Iter,
@@ -19,11 +20,25 @@ use std::{
 #[derive(Debug, PartialEq, Deserialize)]
 pub struct NodesInfoResponse {
     #[serde(deserialize_with = "deserialize_nodes")]
-    nodes: Vec<SniffedNode>,
+    nodes: Vec<NodeInfo>,
 }
 
+/**
+The details of a single node in the cluster.
+
+Nodes are passed to the filters on a [`SniffedNodesBuilder`][SniffedNodesBuilder] to decide which ones requests can be sent to.
+
+[SniffedNodesBuilder]: ../../../http/sender/sniffed_nodes/struct.SniffedNodesBuilder.html
+*/
 #[derive(Debug, PartialEq, Deserialize)]
-struct SniffedNode {
+pub struct NodeInfo {
+    #[serde(skip)]
+    id: String,
+    name: Option<String>,
+    #[serde(default)]
+    roles: Vec<String>,
+    #[serde(default)]
+    attributes: BTreeMap<String, String>,
     http: Option<SniffedNodeHttp>,
 }
 
@@ -32,7 +47,61 @@ struct SniffedNodeHttp {
     publish_address: Option<String>,
 }
 
+impl NodeInfo {
+    /** The unique id of the node. */
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /** The name of the node. */
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(|name| name.as_ref())
+    }
+
+    /** The address the node can be reached at for HTTP requests. */
+    pub fn publish_address(&self) -> Option<&str> {
+        self.http
+            .as_ref()
+            .and_then(|http| http.publish_address.as_ref())
+            .map(|addr| addr.as_ref())
+    }
+
+    /**
+    The roles of the node, like `master`, `data` or `ingest`.
+
+    Versions of Elasticsearch before `5.0` don't return the roles of nodes.
+    */
+    pub fn roles(&self) -> &[String] {
+        &self.roles
+    }
+
+    /** Whether or not the node has the given role. */
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|node_role| node_role == role)
+    }
+
+    /** Whether or not the node is a dedicated master node that has no other roles. */
+    pub fn is_master_only(&self) -> bool {
+        !self.roles.is_empty() && self.roles.iter().all(|role| role == "master")
+    }
+
+    /** The custom attributes of the node, like `zone` or `rack`. */
+    pub fn attributes(&self) -> &BTreeMap<String, String> {
+        &self.attributes
+    }
+
+    /** Get the value of a custom attribute by name. */
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(|value| value.as_ref())
+    }
+}
+
 impl NodesInfoResponse {
+    /** Iterate over the nodes in the cluster. */
+    pub fn iter_nodes(&self) -> Iter<'_, NodeInfo> {
+        self.nodes.iter()
+    }
+
     /** Iterate over borrowed publish addresses in the cluster. */
     pub fn iter_addrs(&self) -> IterAddrs {
         IterAddrs(self.nodes.iter())
@@ -49,18 +118,14 @@ An iterator over node publish addresses.
 
 This is the result of calling [`NodesInfoResponse.iter_addrs()`](structNodesInfoResponse.html#method.iter_addrs).
 */
-pub struct IterAddrs<'a>(Iter<'a, SniffedNode>);
+pub struct IterAddrs<'a>(Iter<'a, NodeInfo>);
 
 impl<'a> Iterator for IterAddrs<'a> {
     type Item = &'a str;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(node) = self.0.next() {
-            if let Some(addr) = node
-                .http
-                .as_ref()
-                .and_then(|http| http.publish_address.as_ref())
-            {
+        for node in self.0.by_ref() {
+            if let Some(addr) = node.publish_address() {
                 return Some(addr);
             }
         }
@@ -74,13 +139,13 @@ An iterator over node publish addresses.
 
 This is the result of calling [`NodesInfoResponse.innto_iter_addrs()`](structNodesInfoResponse.html#method.innto_iter_addrs).
 */
-pub struct IntoIterAddrs(IntoIter<SniffedNode>);
+pub struct IntoIterAddrs(IntoIter<NodeInfo>);
 
 impl Iterator for IntoIterAddrs {
     type Item = String;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(node) = self.0.next() {
+        for node in self.0.by_ref() {
             if let Some(addr) = node.http.and_then(|http| http.publish_address) {
                 return Some(addr);
             }
@@ -92,19 +157,18 @@ impl Iterator for IntoIterAddrs {
 
 impl IsOkOnSuccess for NodesInfoResponse {}
 
-fn deserialize_nodes<'de, D>(deserializer: D) -> Result<Vec<SniffedNode>, D::Error>
+fn deserialize_nodes<'de, D>(deserializer: D) -> Result<Vec<NodeInfo>, D::Error>
 where
     D: Deserializer<'de>,
 {
     #[derive(Debug, PartialEq)]
-    struct SniffedNodeSet(Vec<SniffedNode>);
+    struct SniffedNodeSet(Vec<NodeInfo>);
 
     impl<'de> Deserialize<'de> for SniffedNodeSet {
         fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where
             D: Deserializer<'de>,
         {
-            #[derive(Default)]
             struct SniffedNodeSetVisitor;
 
             impl<'de> Visitor<'de> for SniffedNodeSetVisitor {
@@ -120,7 +184,8 @@ where
                 {
                     let mut nodes = Vec::with_capacity(access.size_hint().unwrap_or(0));
 
-                    while let Some((_, node)) = access.next_entry::<String, _>()? {
+                    while let Some((id, mut node)) = access.next_entry::<String, NodeInfo>()? {
+                        node.id = id;
                         nodes.push(node);
                     }
 
@@ -128,7 +193,7 @@ where
                 }
             }
 
-            deserializer.deserialize_map(SniffedNodeSetVisitor::default())
+            deserializer.deserialize_map(SniffedNodeSetVisitor)
         }
     }
 
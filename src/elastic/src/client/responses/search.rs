@@ -71,6 +71,9 @@ pub struct SearchResponse<T> {
     hits: HitsWrapper<T>,
     aggregations: Option<AggsWrapper>,
     status: Option<u16>,
+    #[serde(rename = "_scroll_id")]
+    scroll_id: Option<String>,
+    pit_id: Option<String>,
 }
 
 /** Struct to hold the search's Hits, serializable to type `T` or `serde_json::Value`. */
@@ -109,6 +112,29 @@ impl<T> SearchResponse<T> {
         self.status
     }
 
+    /**
+    The id of the [scroll] context for the request.
+
+    This is only returned for requests that specify a `scroll` parameter.
+
+    [scroll]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-body.html#request-body-search-scroll
+    */
+    pub fn scroll_id(&self) -> Option<&str> {
+        self.scroll_id.as_deref()
+    }
+
+    /**
+    The id of the [point in time][pit] for the request.
+
+    This is only returned for requests that search a point in time.
+    The id may change between requests, so the latest one should be used for the next request.
+
+    [pit]: https://www.elastic.co/guide/en/elasticsearch/reference/current/point-in-time-api.html
+    */
+    pub fn pit_id(&self) -> Option<&str> {
+        self.pit_id.as_deref()
+    }
+
     /** The total number of documents that matched the search query. */
     pub fn total(&self) -> u64 {
         self.hits.total.value
@@ -0,0 +1,136 @@
+/*!
+Response types for a [get task request](https://www.elastic.co/guide/en/elasticsearch/reference/current/tasks.html).
+*/
+
+use serde::de::{
+    Deserialize,
+    DeserializeOwned,
+    Deserializer,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+
+use super::bulk_by_scroll::BulkByScrollStatus;
+use crate::http::receiver::{
+    ApiError,
+    IsOkOnSuccess,
+    ParsedApiError,
+};
+
+/**
+Response for a [get task request](https://www.elastic.co/guide/en/elasticsearch/reference/current/tasks.html).
+
+A task that's still running will report its current `status`.
+A task that has completed will contain either its final `response` or the `error` it failed with.
+
+# Examples
+
+Check whether a delete by query task has completed:
+
+```no_run
+# use elastic::prelude::*;
+# fn do_request() -> TaskResponse<DeleteByQueryResponse> { unimplemented!() }
+let response: TaskResponse<DeleteByQueryResponse> = do_request();
+
+if response.completed() {
+    println!("deleted: {:?}", response.response().map(|res| res.deleted()));
+} else {
+    println!("progress: {:?}", response.status());
+}
+```
+*/
+#[derive(Deserialize, Debug)]
+#[serde(bound(deserialize = "T: DeserializeOwned"))]
+pub struct TaskResponse<T> {
+    #[serde(default)]
+    completed: bool,
+    task: TaskInfo,
+    response: Option<T>,
+    #[serde(default, deserialize_with = "deserialize_task_error")]
+    error: Option<ApiError>,
+}
+
+#[derive(Deserialize, Debug)]
+struct TaskInfo {
+    node: String,
+    id: u64,
+    action: String,
+    description: Option<String>,
+    status: Option<BulkByScrollStatus>,
+}
+
+impl<T> TaskResponse<T> {
+    /** Whether or not the task has finished running. */
+    pub fn completed(&self) -> bool {
+        self.completed
+    }
+
+    /** The id of the task in the form `node:id`. */
+    pub fn task_id(&self) -> String {
+        format!("{}:{}", self.task.node, self.task.id)
+    }
+
+    /** The name of the action the task is running, like `indices:data/write/delete/byquery`. */
+    pub fn action(&self) -> &str {
+        &self.task.action
+    }
+
+    /** A description of what the task is doing. */
+    pub fn description(&self) -> Option<&str> {
+        self.task.description.as_deref()
+    }
+
+    /** The counts of documents the task has processed so far. */
+    pub fn status(&self) -> Option<&BulkByScrollStatus> {
+        self.task.status.as_ref()
+    }
+
+    /**
+    The response of the task if it completed successfully.
+
+    A response may still contain individual failures.
+    */
+    pub fn response(&self) -> Option<&T> {
+        self.response.as_ref()
+    }
+
+    /** The error the task failed with, if it completed unsuccessfully. */
+    pub fn error(&self) -> Option<&ApiError> {
+        self.error.as_ref()
+    }
+
+    /**
+    Convert the task into its result.
+
+    This method returns `None` if the task hasn't completed yet.
+    */
+    pub fn into_result(self) -> Option<Result<T, ApiError>> {
+        if !self.completed {
+            return None;
+        }
+
+        match (self.response, self.error) {
+            (_, Some(err)) => Some(Err(err)),
+            (Some(response), None) => Some(Ok(response)),
+            (None, None) => None,
+        }
+    }
+}
+
+impl<T: DeserializeOwned> IsOkOnSuccess for TaskResponse<T> {}
+
+fn deserialize_task_error<'de, D>(deserializer: D) -> Result<Option<ApiError>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let error = Option::<Map<String, Value>>::deserialize(deserializer)?;
+
+    Ok(error.map(|error| {
+        let mut item = Map::new();
+        item.insert("error".to_owned(), Value::Object(error));
+
+        ParsedApiError::from(item).into_api_error()
+    }))
+}
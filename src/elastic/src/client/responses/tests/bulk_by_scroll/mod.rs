@@ -0,0 +1,41 @@
+use crate::{
+    client::responses::*,
+    http::{
+        receiver::parse,
+        StatusCode,
+    },
+};
+
+#[test]
+fn success_parse_response() {
+    let f = include_bytes!("delete_by_query.json");
+    let deserialized = parse::<DeleteByQueryResponse>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert_eq!(147, deserialized.took());
+    assert!(!deserialized.timed_out());
+    assert_eq!(119, deserialized.total());
+    assert_eq!(119, deserialized.deleted());
+    assert_eq!(0, deserialized.updated());
+    assert_eq!(1, deserialized.batches());
+    assert_eq!(None, deserialized.status().requests_per_second());
+
+    assert!(deserialized.is_ok());
+}
+
+#[test]
+fn success_parse_response_with_failures() {
+    let f = include_bytes!("update_by_query_failures.json");
+    let deserialized = parse::<UpdateByQueryResponse>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert_eq!(3, deserialized.updated());
+    assert_eq!(2, deserialized.version_conflicts());
+    assert_eq!(5, deserialized.status().processed());
+    assert_eq!(Some(50.0), deserialized.status().requests_per_second());
+    assert_eq!(1, deserialized.failures().len());
+
+    assert!(!deserialized.is_ok());
+}
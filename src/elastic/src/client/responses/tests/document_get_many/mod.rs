@@ -0,0 +1,40 @@
+use crate::{
+    client::responses::*,
+    error::*,
+    http::{
+        receiver::parse,
+        StatusCode,
+    },
+};
+use serde_json::Value;
+
+#[test]
+fn success_parse_get_many() {
+    let f = include_bytes!("get_many.json");
+    let deserialized = parse::<GetManyResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert_eq!(3, deserialized.len());
+    assert_eq!(1, deserialized.documents().count());
+
+    let mut docs = deserialized.into_iter();
+
+    let found = docs.next().unwrap().unwrap();
+    assert!(found.found());
+    assert_eq!("1", found.id());
+    assert_eq!(Some(8), found.version());
+
+    let not_found = docs.next().unwrap().unwrap();
+    assert!(!not_found.found());
+    assert_eq!("2", not_found.id());
+    assert!(not_found.document().is_none());
+
+    let err = docs.next().unwrap().unwrap_err();
+    assert_eq!(
+        ApiError::IndexNotFound {
+            index: "missingindex".to_owned()
+        },
+        err
+    );
+}
@@ -0,0 +1,122 @@
+use serde_json::{
+    self,
+    Value,
+};
+use std::collections::BTreeMap;
+
+use crate::client::responses::document_mapping_diff::*;
+
+fn live_mapping() -> Value {
+    serde_json::from_slice(include_bytes!("live_mapping.json")).unwrap()
+}
+
+fn local_mapping() -> Value {
+    json!({
+        "properties": {
+            "title": {
+                "type": "text",
+                "fields": {
+                    "raw": { "type": "keyword" },
+                    "english": { "type": "text", "analyzer": "english" }
+                }
+            },
+            "status": { "type": "keyword" },
+            "author": {
+                "properties": {
+                    "name": { "type": "keyword" },
+                    "email": { "type": "keyword" }
+                }
+            },
+            "created": { "type": "date" }
+        }
+    })
+}
+
+#[test]
+fn diff_fields() {
+    let diff = MappingDiff::new(&live_mapping(), &local_mapping());
+
+    let expected = [
+        FieldChange::Added {
+            path: "author.email".to_owned(),
+            ty: "keyword".to_owned(),
+        },
+        FieldChange::Added {
+            path: "created".to_owned(),
+            ty: "date".to_owned(),
+        },
+        FieldChange::Removed {
+            path: "legacy".to_owned(),
+            ty: "long".to_owned(),
+        },
+        FieldChange::TypeChanged {
+            path: "status".to_owned(),
+            from: "text".to_owned(),
+            to: "keyword".to_owned(),
+        },
+        FieldChange::Added {
+            path: "title.english".to_owned(),
+            ty: "text".to_owned(),
+        },
+    ];
+
+    assert_eq!(&expected[..], diff.changes());
+    assert_eq!(3, diff.added().count());
+    assert!(!diff.is_compatible());
+    assert!(!diff.changes()[3].can_apply_in_place());
+}
+
+#[test]
+fn diff_additive_mapping() {
+    let diff = MappingDiff::new(&live_mapping(), &local_mapping());
+
+    let expected = json!({
+        "properties": {
+            "author": {
+                "properties": {
+                    "email": { "type": "keyword" }
+                }
+            },
+            "created": { "type": "date" },
+            "title": {
+                "type": "text",
+                "fields": {
+                    "english": { "type": "text", "analyzer": "english" }
+                }
+            }
+        }
+    });
+
+    assert_eq!(&expected, diff.additive_mapping());
+}
+
+#[test]
+fn diff_same_mapping() {
+    let diff = MappingDiff::new(&live_mapping(), &live_mapping());
+
+    assert!(diff.is_empty());
+    assert!(diff.is_compatible());
+    assert_eq!(&json!({ "properties": {} }), diff.additive_mapping());
+}
+
+#[test]
+fn diff_many_indices() {
+    let mut indices = BTreeMap::new();
+    indices.insert(
+        "index_a".to_owned(),
+        MappingDiff::new(&json!({}), &local_mapping()),
+    );
+    indices.insert(
+        "index_b".to_owned(),
+        MappingDiff::new(&live_mapping(), &local_mapping()),
+    );
+
+    let response = MappingDiffResponse::new(indices);
+
+    assert!(!response.is_compatible());
+    assert!(response.get("index_a").unwrap().is_compatible());
+    assert_eq!(
+        Some(local_mapping()["properties"]["status"].clone()),
+        response.additive_mapping().map(|mapping| mapping["properties"]["status"].clone())
+    );
+}
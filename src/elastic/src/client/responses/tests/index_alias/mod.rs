@@ -0,0 +1,72 @@
+use crate::{
+    client::responses::*,
+    http::{
+        receiver::parse,
+        StatusCode,
+    },
+};
+
+#[test]
+fn success_parse_aliases() {
+    let f = include_bytes!("aliases.json");
+    let deserialized = parse::<AliasesResponse>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert_eq!(
+        vec!["logs_v1", "logs_v2"],
+        deserialized.indices().collect::<Vec<_>>()
+    );
+
+    let aliases = deserialized.aliases().collect::<Vec<_>>();
+    assert_eq!(3, aliases.len());
+
+    let errors = aliases
+        .iter()
+        .find(|alias| alias.name() == "logs_errors")
+        .unwrap();
+
+    assert_eq!("logs_v1", errors.index());
+    assert_eq!(
+        Some(&json!({ "term": { "level": "error" } })),
+        errors.filter()
+    );
+    assert_eq!(Some("1"), errors.index_routing());
+    assert_eq!(Some("1,2"), errors.search_routing());
+
+    let write = aliases
+        .iter()
+        .find(|alias| alias.index() == "logs_v2")
+        .unwrap();
+
+    assert_eq!("logs", write.name());
+    assert_eq!(Some(true), write.is_write_index());
+}
+
+#[test]
+fn success_parse_no_aliases() {
+    let deserialized = parse::<AliasesResponse>()
+        .from_slice(StatusCode::OK, b"{}" as &[_])
+        .unwrap();
+
+    assert!(deserialized.is_empty());
+    assert_eq!(0, deserialized.aliases().count());
+}
+
+#[test]
+fn success_parse_response_alias_exists() {
+    let deserialized = parse::<AliasExistsResponse>()
+        .from_slice(StatusCode::OK, b"")
+        .unwrap();
+
+    assert!(deserialized.exists());
+}
+
+#[test]
+fn success_parse_response_alias_not_exists() {
+    let deserialized = parse::<AliasExistsResponse>()
+        .from_slice(StatusCode::NOT_FOUND, b"")
+        .unwrap();
+
+    assert!(!deserialized.exists());
+}
@@ -0,0 +1,28 @@
+use crate::{
+    client::responses::*,
+    http::{
+        receiver::parse,
+        StatusCode,
+    },
+};
+
+#[test]
+fn success_parse_settings() {
+    let f = include_bytes!("settings.json");
+    let deserialized = parse::<IndexSettingsResponse>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    let settings = deserialized.get("myindex").unwrap();
+
+    assert_eq!(Some(3), settings.number_of_shards);
+    assert_eq!(Some(1), settings.number_of_replicas);
+    assert_eq!(Some("30s"), settings.refresh_interval.as_ref().map(|s| s.as_ref()));
+    assert_eq!(Some(20000), settings.max_result_window);
+    assert!(settings.analysis.analyzer.contains_key("lowercase_keyword"));
+    assert!(settings.analysis.normalizer.contains_key("lowercase"));
+    assert!(settings.analysis.tokenizer.is_empty());
+    assert_eq!(Some(&json!("9Jp1CwVHRfaU0oQ4a3b8xw")), settings.other.get("uuid"));
+
+    assert_eq!(1, deserialized.iter().count());
+}
@@ -0,0 +1,52 @@
+use crate::{
+    client::responses::*,
+    http::{
+        receiver::parse,
+        StatusCode,
+    },
+};
+
+#[test]
+fn success_parse_template() {
+    let f = include_bytes!("template.json");
+    let deserialized = parse::<IndexTemplatesResponse>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    let template = deserialized.get("logs").unwrap();
+
+    assert_eq!(1, template.order());
+    assert_eq!(Some(3), template.version());
+    assert_eq!(&["logs-*".to_owned()], template.index_patterns());
+    assert_eq!(
+        &json!({ "properties": { "message": { "type": "text" } } }),
+        template.mappings()
+    );
+}
+
+#[test]
+fn success_parse_template_not_found() {
+    let deserialized = parse::<IndexTemplatesResponse>()
+        .from_slice(StatusCode::NOT_FOUND, b"{}" as &[_])
+        .unwrap();
+
+    assert!(deserialized.is_empty());
+}
+
+#[test]
+fn success_parse_response_template_exists() {
+    let deserialized = parse::<IndexTemplateExistsResponse>()
+        .from_slice(StatusCode::OK, b"")
+        .unwrap();
+
+    assert!(deserialized.exists());
+}
+
+#[test]
+fn success_parse_response_template_not_exists() {
+    let deserialized = parse::<IndexTemplateExistsResponse>()
+        .from_slice(StatusCode::NOT_FOUND, b"")
+        .unwrap();
+
+    assert!(!deserialized.exists());
+}
@@ -1,10 +1,18 @@
 mod bulk;
+mod bulk_by_scroll;
 mod command;
 mod document_delete;
 mod document_get;
+mod document_get_many;
 mod document_index;
+mod document_mapping_diff;
 mod document_update;
+mod index_alias;
 mod index_exists;
+mod index_settings;
+mod index_template;
+mod msearch;
 mod nodes_info;
 mod ping;
 mod search;
+mod task;
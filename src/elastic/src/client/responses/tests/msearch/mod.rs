@@ -0,0 +1,57 @@
+use crate::{
+    client::responses::*,
+    error::*,
+    http::{
+        receiver::parse,
+        StatusCode,
+    },
+};
+use serde_json::Value;
+
+#[test]
+fn success_parse_mixed() {
+    let f = include_bytes!("msearch_mixed.json");
+    let deserialized = parse::<MsearchResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert!(deserialized.is_err());
+    assert_eq!(Some(4), deserialized.took());
+    assert_eq!(3, deserialized.len());
+
+    let mut items = deserialized.into_iter();
+
+    let search = items.next().unwrap().unwrap();
+    assert_eq!(1, search.total());
+
+    let err = items.next().unwrap().unwrap_err();
+    assert_eq!(
+        ApiError::IndexNotFound {
+            index: "missing".to_owned()
+        },
+        err
+    );
+
+    let err = items.next().unwrap().unwrap_err();
+    assert_eq!(
+        ApiError::Other {
+            ty: "search_phase_execution_exception".to_owned(),
+            reason: "all shards failed".to_owned()
+        },
+        err
+    );
+
+    assert!(items.next().is_none());
+}
+
+#[test]
+fn success_parse_empty() {
+    let f = br#"{ "responses": [] }"#;
+    let deserialized = parse::<MsearchResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert!(deserialized.is_ok());
+    assert!(deserialized.is_empty());
+    assert_eq!(None, deserialized.took());
+}
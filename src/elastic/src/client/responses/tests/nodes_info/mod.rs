@@ -27,3 +27,25 @@ fn deserialise_nodes_empty() {
 
     assert_eq!(0, deserialized.iter_addrs().count());
 }
+
+#[test]
+fn deserialise_nodes_roles_and_attributes() {
+    let f = include_bytes!("nodes_info_roles.json");
+    let deserialized = parse::<NodesInfoResponse>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    let nodes: Vec<_> = deserialized.iter_nodes().collect();
+
+    assert_eq!("node1", nodes[0].id());
+    assert_eq!(Some("master-1"), nodes[0].name());
+    assert!(nodes[0].is_master_only());
+    assert_eq!(Some("a"), nodes[0].attribute("zone"));
+
+    assert_eq!("node2", nodes[1].id());
+    assert!(nodes[1].has_role("data"));
+    assert!(!nodes[1].has_role("master"));
+    assert!(!nodes[1].is_master_only());
+    assert_eq!(Some("b"), nodes[1].attribute("zone"));
+    assert_eq!(Some("1.1.1.2:9200"), nodes[1].publish_address());
+}
@@ -214,6 +214,43 @@ fn success_parse_hits_simple_as_value() {
     assert_eq!(deserialized["_shards"]["total"].as_u64().unwrap(), 5);
 }
 
+#[test]
+fn success_parse_scroll_id() {
+    let f = include_bytes!("search_scroll.json");
+    let deserialized = parse::<SearchResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert_eq!(
+        Some("DXF1ZXJ5QW5kRmV0Y2gBAAAAAAAAAD4WYm9laVYtZndUQlNsdDcwakFMNjU1QQ=="),
+        deserialized.scroll_id()
+    );
+}
+
+#[test]
+fn success_parse_no_scroll_id() {
+    let f = include_bytes!("search_hits_only.json");
+    let deserialized = parse::<SearchResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert_eq!(None, deserialized.scroll_id());
+}
+
+#[test]
+fn success_parse_pit_id() {
+    let f = include_bytes!("search_pit.json");
+    let deserialized = parse::<SearchResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert!(deserialized.pit_id().is_some());
+    assert_eq!(
+        Some(&json!(["1", 4294967298u64])),
+        deserialized.hits().next().unwrap().sort()
+    );
+}
+
 #[test]
 fn error_parse_index_not_found() {
     let f = include_bytes!("../error/error_index_not_found.json");
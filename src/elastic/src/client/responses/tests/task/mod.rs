@@ -0,0 +1,63 @@
+use crate::{
+    client::responses::*,
+    error::ApiError,
+    http::{
+        receiver::parse,
+        StatusCode,
+    },
+};
+
+#[test]
+fn success_parse_running_task() {
+    let f = include_bytes!("task_running.json");
+    let deserialized = parse::<TaskResponse<DeleteByQueryResponse>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert!(!deserialized.completed());
+    assert_eq!("r1A2WoRbTwKZ516z6NEs5A:36619", deserialized.task_id());
+    assert_eq!("indices:data/write/delete/byquery", deserialized.action());
+    assert_eq!(
+        Some("delete-by-query [testindex]"),
+        deserialized.description()
+    );
+
+    let status = deserialized.status().unwrap();
+    assert_eq!(6154, status.total());
+    assert_eq!(3500, status.deleted());
+
+    assert!(deserialized.response().is_none());
+    assert!(deserialized.into_result().is_none());
+}
+
+#[test]
+fn success_parse_completed_task() {
+    let f = include_bytes!("task_completed.json");
+    let deserialized = parse::<TaskResponse<DeleteByQueryResponse>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert!(deserialized.completed());
+
+    let response = deserialized.into_result().unwrap().unwrap();
+    assert_eq!(6154, response.deleted());
+    assert_eq!(62, response.batches());
+}
+
+#[test]
+fn success_parse_failed_task() {
+    let f = include_bytes!("task_error.json");
+    let deserialized = parse::<TaskResponse<UpdateByQueryResponse>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert!(deserialized.completed());
+
+    match deserialized.into_result() {
+        Some(Err(ApiError::Other { ref ty, ref reason })) => {
+            assert_eq!("script_exception", ty);
+            assert_eq!("runtime error", reason);
+        }
+        res => panic!("expected an `ApiError::Other`, got {:?}", res),
+    }
+}
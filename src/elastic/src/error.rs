@@ -56,6 +56,8 @@ quick_error! {
 
     API errors can be easily matched and destructured whereas client errors
     can be formatted, but not destructured.
+    Requests that time out return a timeout error so they can be handled
+    separately from other client errors.
 
     If the `RUST_BACKTRACE` environment variable is `1` then client errors will
     also contain a backtrace.
@@ -67,6 +69,11 @@ quick_error! {
             cause(err)
             display("API error returned from Elasticsearch. Caused by: {}", err)
         }
+        /** A request timed out before its response arrived. */
+        Timeout(err: ClientError) {
+            cause(err)
+            display("request timed out. Caused by: {}", err)
+        }
         /** Any other kind of error. */
         Client(err: ClientError) {
             cause(err)
@@ -146,6 +153,15 @@ where
     })
 }
 
+pub(crate) fn timeout<E>(err: E) -> Error
+where
+    E: StdError + Send + 'static,
+{
+    Error::Timeout(ClientError {
+        inner: inner::Error::with_chain(err, inner::ErrorKind::Timeout),
+    })
+}
+
 pub(crate) fn response<E>(status: StatusCode, err: E) -> Error
 where
     E: Into<MaybeApiError<E>> + StdError + Send + 'static,
@@ -218,6 +234,9 @@ mod inner {
             Request {
                 display("error sending a request")
             }
+            Timeout {
+                display("timed out sending a request or waiting for a response")
+            }
             Response(status: StatusCode) {
                 display("error receiving a response. Status code: {}", status)
             }
@@ -78,6 +78,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for BulkRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "bulk",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -127,6 +128,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatAliasesRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.aliases",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -176,6 +178,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatAllocationRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.allocation",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -225,6 +228,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatCountRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.count",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -274,6 +278,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatFielddataRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.fielddata",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -307,6 +312,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatHealthRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.health",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -340,6 +346,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatHelpRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.help",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -389,6 +396,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatIndicesRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.indices",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -422,6 +430,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatMasterRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.master",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -455,6 +464,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatNodeattrsRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.nodeattrs",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -488,6 +498,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatNodesRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.nodes",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -521,6 +532,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatPendingTasksRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.pending_tasks",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -554,6 +566,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatPluginsRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.plugins",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -603,6 +616,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatRecoveryRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.recovery",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -636,6 +650,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatRepositoriesRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.repositories",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -685,6 +700,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatSegmentsRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.segments",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -734,6 +750,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatShardsRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.shards",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -783,6 +800,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatSnapshotsRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.snapshots",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -816,6 +834,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatTasksRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.tasks",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -865,6 +884,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatTemplatesRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.templates",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -916,6 +936,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for CatThreadPoolRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cat.thread_pool",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -968,6 +989,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for ClearScrollRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "clear_scroll",
                 url: self.url,
                 method: Method::DELETE,
                 body: Some(self.body),
@@ -1005,6 +1027,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for ClusterAllocationExplainRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "cluster.allocation_explain",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -1038,6 +1061,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for ClusterGetSettingsRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cluster.get_settings",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -1087,6 +1111,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for ClusterHealthRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cluster.health",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -1120,6 +1145,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for ClusterPendingTasksRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cluster.pending_tasks",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -1155,6 +1181,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for ClusterPutSettingsRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "cluster.put_settings",
                 url: self.url,
                 method: Method::PUT,
                 body: Some(self.body),
@@ -1188,6 +1215,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for ClusterRemoteInfoRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cluster.remote_info",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -1223,6 +1251,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for ClusterRerouteRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "cluster.reroute",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -1291,6 +1320,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for ClusterStateRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cluster.state",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -1340,6 +1370,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for ClusterStatsRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "cluster.stats",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -1414,6 +1445,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for CountRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "count",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -1490,6 +1522,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for CreateRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "create",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -1556,6 +1589,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for DeleteRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "delete",
                 url: self.url,
                 method: Method::DELETE,
                 body: None,
@@ -1621,6 +1655,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for DeleteByQueryRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "delete_by_query",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -1665,6 +1700,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for DeleteByQueryRethrottleRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "delete_by_query_rethrottle",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -1706,6 +1742,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for DeleteScriptRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "delete_script",
                 url: self.url,
                 method: Method::DELETE,
                 body: None,
@@ -1772,6 +1809,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for ExistsRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "exists",
                 url: self.url,
                 method: Method::HEAD,
                 body: None,
@@ -1840,6 +1878,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for ExistsSourceRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "exists_source",
                 url: self.url,
                 method: Method::HEAD,
                 body: None,
@@ -1916,6 +1955,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for ExplainRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "explain",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -1969,6 +2009,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for FieldCapsRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "field_caps",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -2035,6 +2076,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for GetRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "get",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -2076,6 +2118,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for GetScriptRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "get_script",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -2144,6 +2187,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for GetSourceRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "get_source",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -2256,6 +2300,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndexRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "index",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -2309,6 +2354,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesAnalyzeRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.analyze",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -2362,6 +2408,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesClearCacheRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.clear_cache",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -2406,6 +2453,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesCloseRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.close",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -2449,6 +2497,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesCreateRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.create",
                 url: self.url,
                 method: Method::PUT,
                 body: Some(self.body),
@@ -2490,6 +2539,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IndicesDeleteRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "indices.delete",
                 url: self.url,
                 method: Method::DELETE,
                 body: None,
@@ -2534,6 +2584,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IndicesDeleteAliasRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "indices.delete_alias",
                 url: self.url,
                 method: Method::DELETE,
                 body: None,
@@ -2575,6 +2626,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IndicesDeleteTemplateRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "indices.delete_template",
                 url: self.url,
                 method: Method::DELETE,
                 body: None,
@@ -2616,6 +2668,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IndicesExistsRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "indices.exists",
                 url: self.url,
                 method: Method::HEAD,
                 body: None,
@@ -2676,6 +2729,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IndicesExistsAliasRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "indices.exists_alias",
                 url: self.url,
                 method: Method::HEAD,
                 body: None,
@@ -2717,6 +2771,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IndicesExistsTemplateRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "indices.exists_template",
                 url: self.url,
                 method: Method::HEAD,
                 body: None,
@@ -2761,6 +2816,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IndicesExistsTypeRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "indices.exists_type",
                 url: self.url,
                 method: Method::HEAD,
                 body: None,
@@ -2814,6 +2870,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesFlushRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.flush",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -2867,6 +2924,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesFlushSyncedRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.flush_synced",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -2920,6 +2978,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesForcemergeRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.forcemerge",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -2961,6 +3020,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IndicesGetRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "indices.get",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -3046,6 +3106,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IndicesGetAliasRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "indices.get_alias",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -3158,6 +3219,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IndicesGetFieldMappingRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "indices.get_field_mapping",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -3243,6 +3305,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IndicesGetMappingRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "indices.get_mapping",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -3328,6 +3391,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IndicesGetSettingsRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "indices.get_settings",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -3377,6 +3441,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IndicesGetTemplateRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "indices.get_template",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -3427,6 +3492,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IndicesGetUpgradeRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "indices.get_upgrade",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -3471,6 +3537,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesOpenRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.open",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -3517,6 +3584,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesPutAliasRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.put_alias",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -3598,6 +3666,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesPutMappingRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.put_mapping",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -3651,6 +3720,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesPutSettingsRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.put_settings",
                 url: self.url,
                 method: Method::PUT,
                 body: Some(self.body),
@@ -3694,6 +3764,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesPutTemplateRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.put_template",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -3744,6 +3815,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IndicesRecoveryRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "indices.recovery",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -3797,6 +3869,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesRefreshRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.refresh",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -3865,6 +3938,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesRolloverRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.rollover",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -3915,6 +3989,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IndicesSegmentsRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "indices.segments",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -3965,6 +4040,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IndicesShardStoresRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "indices.shard_stores",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -4011,6 +4087,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesShrinkRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.shrink",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -4057,6 +4134,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesSplitRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.split",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -4142,6 +4220,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IndicesStatsRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "indices.stats",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -4177,6 +4256,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesUpdateAliasesRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.update_aliases",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -4230,6 +4310,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesUpgradeRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.upgrade",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -4304,6 +4385,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IndicesValidateQueryRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "indices.validate_query",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -4337,6 +4419,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for InfoRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "info",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -4378,6 +4461,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IngestDeletePipelineRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "ingest.delete_pipeline",
                 url: self.url,
                 method: Method::DELETE,
                 body: None,
@@ -4427,6 +4511,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IngestGetPipelineRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "ingest.get_pipeline",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -4460,6 +4545,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for IngestProcessorGrokRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "ingest.processor_grok",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -4503,6 +4589,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IngestPutPipelineRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "ingest.put_pipeline",
                 url: self.url,
                 method: Method::PUT,
                 body: Some(self.body),
@@ -4556,6 +4643,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for IngestSimulateRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "ingest.simulate",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -4630,6 +4718,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for MgetRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "mget",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -4704,6 +4793,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for MsearchRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "msearch",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -4778,6 +4868,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for MsearchTemplateRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "msearch_template",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -4852,6 +4943,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for MtermvectorsRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "mtermvectors",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -4902,6 +4994,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for NodesHotThreadsRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "nodes.hot_threads",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -4986,6 +5079,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for NodesInfoRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "nodes.info",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -5041,6 +5135,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for NodesReloadSecureSettingsRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "nodes.reload_secure_settings",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -5187,6 +5282,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for NodesStatsRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "nodes.stats",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -5272,6 +5368,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for NodesUsageRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "nodes.usage",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -5305,6 +5402,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for PingRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "ping",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -5338,6 +5436,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for PingHeadRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "ping_head",
                 url: self.url,
                 method: Method::HEAD,
                 body: None,
@@ -5401,6 +5500,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for PutScriptRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "put_script",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -5454,6 +5554,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for RankEvalRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "rank_eval",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -5489,6 +5590,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for ReindexRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "reindex",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -5533,6 +5635,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for ReindexRethrottleRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "reindex_rethrottle",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -5585,6 +5688,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for RenderSearchTemplateRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "render_search_template",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -5622,6 +5726,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for ScriptsPainlessExecuteRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "scripts_painless_execute",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -5674,6 +5779,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for ScrollRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "scroll",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -5744,6 +5850,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for SimpleSearchRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "simple_search",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -5818,6 +5925,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for SearchRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "search",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -5871,6 +5979,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for SearchShardsRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "search_shards",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -5945,6 +6054,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for SearchTemplateRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "search_template",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -6000,6 +6110,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for SnapshotCreateRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "snapshot.create",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -6043,6 +6154,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for SnapshotCreateRepositoryRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "snapshot.create_repository",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -6095,6 +6207,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for SnapshotDeleteRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "snapshot.delete",
                 url: self.url,
                 method: Method::DELETE,
                 body: None,
@@ -6136,6 +6249,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for SnapshotDeleteRepositoryRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "snapshot.delete_repository",
                 url: self.url,
                 method: Method::DELETE,
                 body: None,
@@ -6185,6 +6299,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for SnapshotGetRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "snapshot.get",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -6234,6 +6349,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for SnapshotGetRepositoryRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "snapshot.get_repository",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -6290,6 +6406,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for SnapshotRestoreRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "snapshot.restore",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -6368,6 +6485,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for SnapshotStatusRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "snapshot.status",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -6412,6 +6530,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for SnapshotVerifyRepositoryRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "snapshot.verify_repository",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -6447,6 +6566,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for SqlQueryRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "sql.query",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -6500,6 +6620,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for TasksCancelRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "tasks.cancel",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -6541,6 +6662,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for TasksGetRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "tasks.get",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -6574,6 +6696,7 @@ pub mod endpoints {
     impl<'a> Into<Endpoint<'a, DefaultBody>> for TasksListRequest<'a> {
         fn into(self) -> Endpoint<'a, DefaultBody> {
             Endpoint {
+                name: "tasks.list",
                 url: self.url,
                 method: Method::GET,
                 body: None,
@@ -6689,6 +6812,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for TermvectorsRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "termvectors",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -6765,6 +6889,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for UpdateRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "update",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -6830,6 +6955,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for UpdateByQueryRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "update_by_query",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -6874,6 +7000,7 @@ pub mod endpoints {
     impl<'a, B> Into<Endpoint<'a, B>> for UpdateByQueryRethrottleRequest<'a, B> {
         fn into(self) -> Endpoint<'a, B> {
             Endpoint {
+                name: "update_by_query_rethrottle",
                 url: self.url,
                 method: Method::POST,
                 body: Some(self.body),
@@ -6913,9 +7040,44 @@ pub mod http {
     #[doc = r" A general request type that all endpoints can be converted into."]
     #[derive(Debug, Clone, PartialEq)]
     pub struct Endpoint<'a, B> {
-        pub url: UrlPath<'a>,
-        pub method: Method,
-        pub body: Option<B>,
+        pub(crate) name: &'static str,
+        pub(crate) url: UrlPath<'a>,
+        pub(crate) method: Method,
+        pub(crate) body: Option<B>,
+    }
+    impl<'a, B> Endpoint<'a, B> {
+        #[doc = r" Create an endpoint."]
+        #[doc = r""]
+        #[doc = r" The name identifies requests to the endpoint in metrics and traces."]
+        pub fn new(
+            name: &'static str,
+            url: impl Into<UrlPath<'a>>,
+            method: Method,
+            body: Option<B>,
+        ) -> Self {
+            Endpoint {
+                name,
+                url: url.into(),
+                method,
+                body,
+            }
+        }
+        #[doc = r" The name of the endpoint."]
+        pub fn name(&self) -> &'static str {
+            self.name
+        }
+        #[doc = r" The url path of the endpoint."]
+        pub fn url(&self) -> &UrlPath<'a> {
+            &self.url
+        }
+        #[doc = r" The HTTP method of the endpoint."]
+        pub fn method(&self) -> &Method {
+            &self.method
+        }
+        #[doc = r" The body of the endpoint."]
+        pub fn body(&self) -> Option<&B> {
+            self.body.as_ref()
+        }
     }
     #[doc = r" A default body type."]
     pub type DefaultBody = &'static [u8];
@@ -13,18 +13,13 @@ use futures::{
     Poll,
     Stream,
 };
-use reqwest::r#async::{
-    Body,
-    Response as RawResponse,
-};
+use reqwest::r#async::Body;
 use tokio::io::AsyncRead;
 
 use crate::{
-    error::{
-        self,
-        Error,
-    },
+    error::Error,
     http::{
+        gzip,
         HttpRequest,
         StatusCode,
     },
@@ -36,8 +31,10 @@ pub use reqwest::r#async::Chunk as AsyncChunk;
 pub type AsyncHttpRequest = HttpRequest<AsyncBody>;
 
 /** A type that can be converted into a request body. */
+#[derive(Clone)]
 pub struct AsyncBody(AsyncBodyInner);
 
+#[derive(Clone)]
 enum AsyncBodyInner {
     Shared(Bytes),
     Bytes(Cow<'static, [u8]>),
@@ -85,6 +82,16 @@ impl AsyncBody {
         }
     }
 
+    /** Compress the body with gzip. */
+    pub(crate) fn gzip(&self) -> Result<AsyncBody, io::Error> {
+        Ok(gzip(self.0.as_ref())?.into())
+    }
+
+    /** Get the length of the body in bytes. */
+    pub(crate) fn buffered_len(&self) -> Option<u64> {
+        Some(self.0.as_ref().len() as u64)
+    }
+
     /**
     Get a reader over the asynchronous body.
     */
@@ -131,12 +138,15 @@ impl From<&'static str> for AsyncBody {
     }
 }
 
+/** The body of an asynchronous response. */
+pub(crate) type AsyncResponseBody = Box<dyn Stream<Item = AsyncChunk, Error = Error> + Send>;
+
 /** A raw HTTP response that can be buffered using `Read`. */
-pub struct AsyncHttpResponse(StatusCode, RawResponse);
+pub struct AsyncHttpResponse(StatusCode, AsyncResponseBody);
 
 impl AsyncHttpResponse {
-    pub(crate) fn from_raw(status: StatusCode, response: RawResponse) -> Self {
-        AsyncHttpResponse(status, response)
+    pub(crate) fn from_raw(status: StatusCode, body: AsyncResponseBody) -> Self {
+        AsyncHttpResponse(status, body)
     }
 }
 
@@ -145,10 +155,7 @@ impl Stream for AsyncHttpResponse {
     type Error = Error;
 
     fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
-        self.1
-            .body_mut()
-            .poll()
-            .map_err(|e| error::response(self.0, e))
+        self.1.poll()
     }
 }
 
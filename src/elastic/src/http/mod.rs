@@ -35,12 +35,29 @@ pub use crate::genned::http::{
     UrlPath,
 };
 
+use flate2::{
+    write::GzEncoder,
+    Compression,
+};
 use std::{
     fmt,
+    io::{
+        self,
+        Write,
+    },
     sync::Arc,
 };
+use uuid::Uuid;
+
+use self::header::{
+    HeaderMap,
+    HeaderName,
+    HeaderValue,
+    CONTENT_ENCODING,
+};
 
-use self::header::HeaderMap;
+/** The header Elasticsearch uses to identify requests in its slow logs and tasks. */
+const X_OPAQUE_ID: &str = "x-opaque-id";
 
 /**
 A request just before being sent.
@@ -54,6 +71,34 @@ pub struct HttpRequest<TBody> {
 }
 
 impl<TBody> HttpRequest<TBody> {
+    /**
+    Get a reference to the request url.
+    */
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /**
+    Get a reference to the request method.
+    */
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /**
+    Get a reference to the request headers.
+    */
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /**
+    Get a reference to the request body.
+    */
+    pub fn body(&self) -> Option<&TBody> {
+        self.body.as_ref()
+    }
+
     /**
     Get a mutable reference to the request url.
     */
@@ -81,6 +126,35 @@ impl<TBody> HttpRequest<TBody> {
     pub fn body_mut(&mut self) -> Option<&mut TBody> {
         self.body.as_mut()
     }
+
+    /**
+    Set the headers for a request whose body has been compressed with gzip.
+
+    Compressed responses are requested and decoded by the `reqwest` client itself.
+    */
+    pub(crate) fn set_gzip_headers(&mut self) {
+        if self.body.is_some() {
+            self.headers_mut()
+                .insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+        }
+    }
+
+    /**
+    Set the `X-Opaque-Id` header to the given correlation id.
+
+    An id that's already been set on the request, like one from the request parameters, is kept.
+    */
+    pub(crate) fn set_opaque_id(&mut self, correlation_id: Uuid) {
+        if self.headers.contains_key(X_OPAQUE_ID) {
+            return;
+        }
+
+        let value = HeaderValue::from_str(&correlation_id.to_string())
+            .expect("a uuid is always a valid header value");
+
+        self.headers_mut()
+            .insert(HeaderName::from_static(X_OPAQUE_ID), value);
+    }
 }
 
 impl<TBody> fmt::Debug for HttpRequest<TBody> {
@@ -93,3 +167,11 @@ impl<TBody> fmt::Debug for HttpRequest<TBody> {
             .finish()
     }
 }
+
+/** Compress a buffer with gzip. */
+pub(crate) fn gzip(bytes: &[u8]) -> Result<Vec<u8>, io::Error> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(bytes)?;
+
+    encoder.finish()
+}
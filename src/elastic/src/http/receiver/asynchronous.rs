@@ -1,3 +1,4 @@
+use bytes::Bytes;
 use std::sync::Arc;
 
 use futures::{
@@ -6,7 +7,10 @@ use futures::{
     Poll,
     Stream,
 };
-use reqwest::r#async::Response as RawResponse;
+use reqwest::r#async::{
+    Body,
+    Response as RawResponse,
+};
 use serde::de::DeserializeOwned;
 use tokio_threadpool::ThreadPool;
 
@@ -22,6 +26,7 @@ use crate::{
         },
         AsyncChunk,
         AsyncHttpResponse,
+        AsyncResponseBody,
         StatusCode,
     },
 };
@@ -33,7 +38,7 @@ This structure wraps the completed HTTP response but gives you options for conve
 You can also `Read` directly from the response body.
 */
 pub struct AsyncResponseBuilder {
-    inner: RawResponse,
+    inner: AsyncResponseBody,
     status: StatusCode,
     de_pool: Option<Arc<ThreadPool>>,
 }
@@ -44,12 +49,28 @@ pub(crate) fn async_response(
 ) -> Result<AsyncResponseBuilder, Error> {
     let status = StatusCode::from_u16(res.status().into()).map_err(error::request)?;
     Ok(AsyncResponseBuilder {
-        inner: res,
+        inner: Box::new(
+            res.into_body()
+                .map_err(move |e| error::response(status, e)),
+        ),
         status,
         de_pool,
     })
 }
 
+/** Get a response builder for a response whose body is already in memory. */
+pub(crate) fn async_buffered_response(
+    status: StatusCode,
+    body: Bytes,
+    de_pool: Option<Arc<ThreadPool>>,
+) -> AsyncResponseBuilder {
+    AsyncResponseBuilder {
+        inner: Box::new(Body::from(body).map_err(move |e| error::response(status, e))),
+        status,
+        de_pool,
+    }
+}
+
 impl AsyncResponseBuilder {
     /** Get the HTTP status for the response. */
     pub fn status(&self) -> StatusCode {
@@ -65,6 +86,14 @@ impl AsyncResponseBuilder {
         AsyncHttpResponse::from_raw(self.status, self.inner)
     }
 
+    /** Wrap the body of the response, like to observe it as it's read. */
+    pub(crate) fn map_body(self, f: impl FnOnce(AsyncResponseBody) -> AsyncResponseBody) -> Self {
+        AsyncResponseBuilder {
+            inner: f(self.inner),
+            ..self
+        }
+    }
+
     /**
     Parse an API response type from the HTTP body.
 
@@ -125,7 +154,7 @@ impl AsyncResponseBuilder {
         T: IsOk + DeserializeOwned + Send + 'static,
     {
         let status = self.status;
-        let body = self.inner.into_body();
+        let body = self.inner;
 
         let de_fn = move |body: AsyncChunk| {
             parse()
@@ -133,7 +162,7 @@ impl AsyncResponseBuilder {
                 .map_err(move |e| error::response(status, e))
         };
 
-        let body_future = body.concat2().map_err(move |e| error::response(status, e));
+        let body_future = body.concat2();
 
         if let Some(de_pool) = self.de_pool {
             IntoResponse::new(
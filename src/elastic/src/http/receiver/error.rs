@@ -175,6 +175,16 @@ quick_error! {
             description("verification exception")
             display("verification error: '{}", reason)
         }
+        /**
+        Some other error that doesn't have a specific variant.
+
+        This error is only returned for responses that contain multiple individual errors, like multi search or multi get,
+        or for tasks that failed while running in the background.
+        */
+        Other { ty: String, reason: String } {
+            description("other api error")
+            display("{}: '{}'", ty, reason)
+        }
         #[doc(hidden)]
         __NonExhaustive {}
     }
@@ -266,3 +276,31 @@ impl From<Map<String, Value>> for ParsedApiError {
         }
     }
 }
+
+impl ParsedApiError {
+    /**
+    Convert into an `ApiError`, even if the error isn't a known kind.
+
+    Unknown errors are converted into an `ApiError::Other` with their `type` and `reason`.
+    */
+    pub(crate) fn into_api_error(self) -> ApiError {
+        match self {
+            ParsedApiError::Known(err) => err,
+            ParsedApiError::Unknown(obj) => {
+                let ty = obj
+                    .get("type")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown")
+                    .to_owned();
+
+                let reason = obj
+                    .get("reason")
+                    .and_then(|v| v.as_str())
+                    .map(|v| v.to_owned())
+                    .unwrap_or_else(|| Value::Object(obj).to_string());
+
+                ApiError::Other { ty, reason }
+            }
+        }
+    }
+}
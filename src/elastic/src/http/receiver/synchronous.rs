@@ -1,5 +1,7 @@
+use bytes::Bytes;
 use reqwest::Response as RawResponse;
 use serde::de::DeserializeOwned;
+use std::io::Cursor;
 
 use crate::{
     error::{
@@ -13,6 +15,7 @@ use crate::{
         },
         StatusCode,
         SyncHttpResponse,
+        SyncResponseBody,
     },
 };
 
@@ -22,11 +25,16 @@ A builder for a response.
 This structure wraps the completed HTTP response but gives you options for converting it into a concrete type.
 You can also `Read` directly from the response body.
 */
-pub struct SyncResponseBuilder(StatusCode, RawResponse);
+pub struct SyncResponseBuilder(StatusCode, SyncResponseBody);
 
 pub(crate) fn sync_response(res: RawResponse) -> Result<SyncResponseBuilder, Error> {
     let status = StatusCode::from_u16(res.status().into()).map_err(error::request)?;
-    Ok(SyncResponseBuilder(status, res))
+    Ok(SyncResponseBuilder(status, Box::new(res)))
+}
+
+/** Get a response builder for a response whose body is already in memory. */
+pub(crate) fn sync_buffered_response(status: StatusCode, body: Bytes) -> SyncResponseBuilder {
+    SyncResponseBuilder(status, Box::new(Cursor::new(body)))
 }
 
 impl SyncResponseBuilder {
@@ -44,6 +52,11 @@ impl SyncResponseBuilder {
         SyncHttpResponse::from_raw(self.0, self.1)
     }
 
+    /** Wrap the body of the response, like to observe it as it's read. */
+    pub(crate) fn map_body(self, f: impl FnOnce(SyncResponseBody) -> SyncResponseBody) -> Self {
+        SyncResponseBuilder(self.0, f(self.1))
+    }
+
     /**
     Parse an API response type from the HTTP body.
 
@@ -1,25 +1,49 @@
 use futures::{
     future::{
+        join_all,
         lazy,
+        loop_fn,
         Either,
         FutureResult,
+        Loop,
     },
     Future,
     IntoFuture,
     Poll,
 };
-use reqwest::r#async::{
-    Client as AsyncHttpClient,
-    RequestBuilder as AsyncHttpRequestBuilder,
+use reqwest::{
+    r#async::{
+        Client as AsyncHttpClient,
+        Request as RawRequest,
+        RequestBuilder as AsyncHttpRequestBuilder,
+    },
+    Error as ReqwestError,
 };
 use std::{
     error::Error as StdError,
     sync::Arc,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+use tokio::{
+    executor::{
+        DefaultExecutor,
+        Executor,
+    },
+    timer::{
+        timeout::Error as TimeoutError,
+        Delay,
+        Timeout,
+    },
 };
 use tokio_threadpool::{
     SpawnHandle,
     ThreadPool,
 };
+use tracing_futures::Instrument;
+use uuid::Uuid;
 
 use crate::{
     endpoints::Endpoint,
@@ -33,19 +57,61 @@ use crate::{
             AsyncResponseBuilder,
         },
         sender::{
+            attempt::{
+                attempt_span,
+                build_request,
+                prepare_request,
+                request_span,
+                Attempt,
+                AttemptOutcome,
+                AttemptTimer,
+                PreparedRequest,
+                Transport,
+            },
             build_reqwest_method,
-            build_url,
+            health::{
+                build_ping,
+                PING_TIMEOUT,
+            },
+            middleware::{
+                after_receive,
+                before_send_async,
+                BeforeSendResult,
+                MiddlewareChain,
+            },
+            retry::retry_params,
+            timeouts::{
+                read_deadline,
+                send_error,
+                TimeoutBody,
+            },
+            BeforeSend,
+            BufferedResponse,
+            ClientTimeouts,
+            CredentialsProvider,
+            FixtureRecorder,
+            HttpClients,
+            MetricsObserver,
+            MetricsRequest,
+            Middleware,
+            MockTransport,
             NextParams,
+            NodeAddress,
             NodeAddresses,
             NodeAddressesInner,
+            NodeFeedback,
             RequestParams,
+            RetryPolicy,
+            RetryableError,
             SendableRequest,
             SendableRequestParams,
             Sender,
         },
         AsyncBody,
         AsyncHttpRequest,
-        Url,
+        Method,
+        StatusCode,
+        UrlPath,
     },
     private,
 };
@@ -59,9 +125,15 @@ pub(crate) type AsyncPreSend = dyn Fn(
 /** An asynchronous request sender. */
 #[derive(Clone)]
 pub struct AsyncSender {
-    pub(crate) http: AsyncHttpClient,
+    pub(crate) http: HttpClients<AsyncHttpClient>,
     pub(crate) serde_pool: Option<Arc<ThreadPool>>,
-    pub(crate) pre_send: Option<Arc<AsyncPreSend>>,
+    pub(crate) middleware: MiddlewareChain<AsyncBody>,
+    pub(crate) metrics: Option<Arc<dyn MetricsObserver>>,
+    pub(crate) credentials: Option<Arc<dyn CredentialsProvider>>,
+    pub(crate) gzip: bool,
+    pub(crate) mock: Option<MockTransport>,
+    pub(crate) recorder: Option<FixtureRecorder>,
+    pub(crate) retry_policy: Arc<RetryPolicy>,
 }
 
 impl private::Sealed for AsyncSender {}
@@ -81,6 +153,38 @@ impl AsyncSender {
             Either::B(f().into_future())
         }
     }
+
+    /**
+    Ping a node to see whether it's alive.
+
+    The ping is sent straight to the node with a short timeout.
+    It isn't retried and doesn't go through middleware, metrics or fixture recording.
+    */
+    fn ping(&self, params: &RequestParams) -> impl Future<Item = StatusCode, Error = Error> {
+        let req = match build_ping(params, self.credentials.as_deref()) {
+            Ok(req) => req,
+            Err(e) => return Either::A(Err(e).into_future()),
+        };
+
+        if let Some(ref mock) = self.mock {
+            return Either::A(mock.send_async(req, None).map(|res| res.status()).into_future());
+        }
+
+        let ping_future = self
+            .http
+            .get(PING_TIMEOUTS)
+            .and_then(|http| {
+                let req = build_reqwest(&http, req).build().map_err(error::request)?;
+
+                Ok(Timeout::new(http.execute(req), PING_TIMEOUT))
+            })
+            .into_future()
+            .and_then(|ping| ping.map_err(|e| timeout_error(e).1))
+            .and_then(|res| async_response(res, None))
+            .map(|res| res.status());
+
+        Either::B(ping_future)
+    }
 }
 
 impl Sender for AsyncSender {
@@ -98,21 +202,34 @@ impl Sender for AsyncSender {
         TParams: Into<Self::Params> + Send + 'static,
     {
         let correlation_id = request.correlation_id;
-        let serde_pool = self.serde_pool.clone();
-        let params = request.params;
         let Endpoint {
-            url, method, body, ..
+            name: endpoint,
+            url,
+            method,
+            body,
+            ..
         } = request.inner.into();
 
+        let span = request_span(correlation_id, endpoint, &method);
+        let _span = span.enter();
+        let request_start = Instant::now();
+
         info!(
             "Elasticsearch Request: correlation_id: '{}', path: '{}'",
             correlation_id,
             url.as_ref()
         );
 
-        let params_future = match params {
-            SendableRequestParams::Value(params) => Either::A(Ok(params).into_future()),
-            SendableRequestParams::Builder { params, builder } => {
+        let (params_future, next_params, feedback) = match request.params {
+            SendableRequestParams::Value(params) => {
+                (Either::A(Ok(params).into_future()), None, None)
+            }
+            SendableRequestParams::Builder {
+                params,
+                next,
+                feedback,
+                builder,
+            } => {
                 let params = params.into().log_err(move |e| {
                     error!(
                         "Elasticsearch Node Selection: correlation_id: '{}', error: '{:?}'",
@@ -120,75 +237,313 @@ impl Sender for AsyncSender {
                     )
                 });
 
-                Either::B(params.and_then(|params| Ok(builder.into_value(move || params))))
+                let params = params.and_then(|params| Ok(builder.into_value(move || params)));
+                let next: Box<NextPendingParams> = Box::new(move || next().into());
+
+                (Either::B(params), Some(next), Some(feedback))
             }
         };
 
-        let build_req_future = params_future
-            .and_then(move |params| {
-                Url::parse(&build_url(&url, &params))
-                    .map_err(error::request)
-                    .map(|url| (params, url))
-            })
-            .and_then(move |(params, url)| {
-                Ok(AsyncHttpRequest {
-                    url,
-                    method,
-                    headers: params.get_headers(),
-                    body: body.map(|body| body.into()),
-                })
-            });
-
-        let pre_send = self.pre_send.clone();
-        let pre_send_future = build_req_future.and_then(move |mut req| {
-            if let Some(pre_send) = pre_send {
-                Either::A(
-                    pre_send(&mut req)
-                        .map_err(error::wrapped)
-                        .map_err(error::request)
-                        .and_then(move |_| Ok(req).into_future()),
+        let body: Option<AsyncBody> = body.map(|body| body.into());
+
+        // Compress the body once on the serde pool so it can be reused for retries
+        let body_future = match body {
+            Some(body) if self.gzip => Either::A(
+                self.maybe_async(move || body.gzip().map(Some).map_err(error::request))
+                    .log_err(move |e| {
+                        error!(
+                            "Elasticsearch Request: correlation_id: '{}', error: '{:?}'",
+                            correlation_id, e
+                        )
+                    }),
+            ),
+            body => Either::B(Ok(body).into_future()),
+        };
+
+        let sender = self.clone();
+        let req_future = params_future.join(body_future).and_then(move |(params, body)| {
+            let attempt = AsyncAttempt {
+                attempt: 1,
+                deadline: params.get_timeouts().deadline(request_start),
+                params,
+                body,
+                next_params,
+                feedback,
+            };
+
+            loop_fn(attempt, move |attempt| {
+                sender.send_attempt(
+                    correlation_id,
+                    endpoint,
+                    url.clone(),
+                    method.clone(),
+                    attempt,
                 )
-            } else {
-                Either::B(Ok(req).into_future())
-            }
+            })
         });
 
-        let pre_send_http = self.http.clone();
-        let pre_send_future = pre_send_future
-            .and_then(move |req| {
-                build_reqwest(&pre_send_http, req)
-                    .build()
-                    .map_err(error::request)
+        let record_span = span.clone();
+        let req_future = req_future
+            .then(move |res| {
+                if let Ok(ref res) = res {
+                    record_span.record("status", res.status().as_u16());
+                }
+
+                record_span.record("duration_ms", request_start.elapsed().as_millis() as u64);
+
+                res
             })
-            .log_err(move |e| {
+            .instrument(span.clone());
+
+        PendingResponse::new(req_future)
+    }
+}
+
+type NextPendingParams = dyn Fn() -> PendingParams + Send;
+
+/** The timeouts for pinging a dead node. The response is timed separately. */
+const PING_TIMEOUTS: ClientTimeouts = ClientTimeouts {
+    connect: Some(PING_TIMEOUT),
+    timeout: None,
+};
+
+/** Run a function given to `pre_send_raw` as the last middleware. */
+pub(crate) struct AsyncPreSendMiddleware(pub(crate) Arc<AsyncPreSend>);
+
+impl Middleware<AsyncBody> for AsyncPreSendMiddleware {
+    fn before_send(
+        &self,
+        req: &mut AsyncHttpRequest,
+    ) -> Result<BeforeSend, Box<dyn StdError + Send + Sync>> {
+        Ok(BeforeSend::Pending(Box::new((self.0)(req).map(|_| None))))
+    }
+}
+
+/** A request that's been through middleware and is ready to be sent asynchronously. */
+type AsyncPreparedRequest = PreparedRequest<AsyncBody, AsyncHttpClient, RawRequest>;
+
+/** The state for a single attempt at sending a request. */
+struct AsyncAttempt {
+    attempt: u32,
+    deadline: Option<Instant>,
+    params: RequestParams,
+    body: Option<AsyncBody>,
+    next_params: Option<Box<NextPendingParams>>,
+    feedback: Option<NodeFeedback>,
+}
+
+impl AsyncSender {
+    /**
+    Send a single attempt of a request.
+
+    If the attempt fails with a transient error then the future will resolve to the next attempt after waiting for its backoff.
+    */
+    fn send_attempt(
+        &self,
+        correlation_id: Uuid,
+        endpoint: &'static str,
+        url: UrlPath<'static>,
+        method: Method,
+        attempt: AsyncAttempt,
+    ) -> impl Future<Item = Loop<AsyncResponseBuilder, AsyncAttempt>, Error = Error> {
+        let AsyncAttempt {
+            attempt,
+            deadline,
+            params,
+            body,
+            next_params,
+            feedback,
+        } = attempt;
+
+        let attempt_span = attempt_span(attempt, &params);
+
+        // Keep a copy of the body in case the request needs to be retried
+        let retry_body = if self.retry_policy.can_retry_after(&method, attempt) {
+            Some(body.clone())
+        } else {
+            None
+        };
+
+        let req = build_request(
+            correlation_id,
+            &url,
+            method.clone(),
+            body,
+            &params,
+            self.gzip,
+            self.credentials.as_deref(),
+        );
+
+        let middleware = self.middleware.clone();
+        let before_send_future = req.into_future().and_then(move |req| {
+            before_send_async(middleware, req).log_err(move |e| {
                 error!(
-                    "Elasticsearch Request: correlation_id: '{}', error: '{:?}'",
+                    "Elasticsearch Request Middleware: correlation_id: '{}', error: '{:?}'",
                     correlation_id, e
                 )
-            });
-
-        let req_http = self.http.clone();
-        let req_future = pre_send_future.and_then(move |req| {
-            req_http
-                .execute(req)
-                .map_err(error::request)
-                .and_then(move |res| {
-                    info!(
-                        "Elasticsearch Response: correlation_id: '{}', status: '{}'",
-                        correlation_id,
-                        res.status()
-                    );
-                    async_response(res, serde_pool).into_future()
-                })
-                .log_err(move |e| {
-                    error!(
-                        "Elasticsearch Response: correlation_id: '{}', error: '{:?}'",
-                        correlation_id, e
-                    )
-                })
+            })
         });
 
-        PendingResponse::new(req_future)
+        // Other timeouts are enforced on the request future
+        let timeouts = params.get_timeouts();
+        let client_timeouts = ClientTimeouts {
+            connect: timeouts.connect,
+            timeout: None,
+        };
+
+        let sender = self.clone();
+        let prepare_future = before_send_future.and_then(move |before_send| {
+            sender.prepare(correlation_id, before_send, client_timeouts)
+        });
+
+        let sender = self.clone();
+        let span = attempt_span.clone();
+        let attempt_future = prepare_future.and_then(move |prepared| {
+            // The attempt is finished with when its future is dropped, even if it hasn't resolved
+            let node = params.node_address().clone();
+            let sent = Attempt::start(
+                correlation_id,
+                span,
+                MetricsRequest::new(endpoint, method, node, attempt),
+                prepared.transport.is_sent(),
+                prepared.body_len,
+                sender.metrics.as_ref(),
+                feedback.as_ref(),
+            );
+
+            let res_future = sender.send_prepared(prepared, sent.timer(), timeouts.read, deadline);
+
+            res_future.then(move |res| {
+                let retryable = retry_body.is_some();
+                let backoff = match sent.finish(res, &sender.retry_policy, retryable, deadline) {
+                    AttemptOutcome::Done(res) => {
+                        return Either::A(res.map(Loop::Break).into_future());
+                    }
+                    AttemptOutcome::Retry(backoff) => backoff,
+                };
+
+                let params_future = match next_params {
+                    Some(ref next_params) => Either::A(next_params().then(move |next| {
+                        Ok(retry_params(params, next, correlation_id))
+                    })),
+                    None => Either::B(Ok(params).into_future()),
+                };
+
+                let next_attempt_future = Delay::new(Instant::now() + backoff)
+                    .map_err(error::request)
+                    .and_then(move |_| params_future)
+                    .map(move |params| {
+                        Loop::Continue(AsyncAttempt {
+                            attempt: attempt + 1,
+                            deadline,
+                            params,
+                            body: retry_body.flatten(),
+                            next_params,
+                            feedback,
+                        })
+                    });
+
+                Either::B(next_attempt_future)
+            })
+        });
+
+        attempt_future.instrument(attempt_span)
+    }
+
+    /** Pick the transport to send a single attempt of a request with after it's been through middleware. */
+    fn prepare(
+        &self,
+        correlation_id: Uuid,
+        before_send: BeforeSendResult<AsyncBody>,
+        timeouts: ClientTimeouts,
+    ) -> Result<AsyncPreparedRequest, Error> {
+        prepare_request(
+            correlation_id,
+            before_send,
+            &self.middleware,
+            self.mock.as_ref(),
+            self.recorder.is_some(),
+            |req| {
+                let http = self.http.get(timeouts)?;
+                let req = build_reqwest(&http, req).build().map_err(error::request)?;
+
+                Ok((http, req))
+            },
+        )
+    }
+
+    /** Send a single attempt of a request and read its response, passing it to any recorder and middleware. */
+    fn send_prepared(
+        &self,
+        prepared: AsyncPreparedRequest,
+        timer: &AttemptTimer,
+        read: Option<Duration>,
+        deadline: Option<Instant>,
+    ) -> impl Future<Item = AsyncResponseBuilder, Error = (Option<RetryableError>, Error)> {
+        let PreparedRequest {
+            transport,
+            recorded,
+            receivers,
+            ..
+        } = prepared;
+
+        let serde_pool = self.serde_pool.clone();
+        let record_pool = serde_pool.clone();
+        let receive_pool = serde_pool.clone();
+        let res_future = match transport {
+            Transport::Respond(res) => Either::A(Ok(res.into_async(serde_pool)).into_future()),
+            Transport::Mock(mock, req) => Either::A(
+                mock.send_async(req, serde_pool)
+                    .map_err(|e| (None, e))
+                    .into_future(),
+            ),
+            Transport::Http(http, req) => {
+                let res_future = match read_deadline(read, deadline) {
+                    Some(at) => {
+                        Either::A(Timeout::new_at(http.execute(req), at).map_err(timeout_error))
+                    }
+                    None => Either::B(http.execute(req).map_err(send_error)),
+                };
+
+                // The response body is streamed so it's timed separately as it's read
+                Either::B(res_future.and_then(move |res| {
+                    let res = async_response(res, serde_pool).map_err(|e| (None, e))?;
+
+                    Ok(match (read, deadline) {
+                        (None, None) => res,
+                        (read, total) => {
+                            res.map_body(|body| Box::new(TimeoutBody::new(body, read, total)))
+                        }
+                    })
+                }))
+            }
+        };
+
+        let timer = timer.clone();
+        let res_future = res_future.then(move |res| timer.received(res));
+
+        let res_future = match (self.recorder.clone(), recorded) {
+            (Some(recorder), Some(req)) => Either::A(res_future.and_then(move |res| {
+                recorder
+                    .record_async(req, res, record_pool)
+                    .map_err(|e| (None, e))
+            })),
+            _ => Either::B(res_future),
+        };
+
+        let middleware = self.middleware.clone();
+        match receivers {
+            Some((req, called)) => Either::A(res_future.and_then(move |res| {
+                BufferedResponse::from_async(res)
+                    .and_then(move |mut res| {
+                        after_receive(&middleware, called, &req, &mut res)?;
+
+                        Ok(res.into_async(receive_pool))
+                    })
+                    .map_err(|e| (None, e))
+            })),
+            None => Either::B(res_future),
+        }
     }
 }
 
@@ -196,11 +551,62 @@ impl NextParams for NodeAddresses<AsyncSender> {
     type Params = PendingParams;
 
     fn next(&self) -> Self::Params {
+        let resurrect_params = self.resurrect_params();
+
+        if resurrect_params.is_empty() {
+            return self.next_inner();
+        }
+
+        let addresses = self.clone();
+
+        // Wait until the request is polled so the pings are spawned on its runtime
+        PendingParams::new(lazy(move || {
+            addresses.resurrect(resurrect_params);
+            addresses.next_inner()
+        }))
+    }
+}
+
+impl NodeAddresses<AsyncSender> {
+    fn next_inner(&self) -> PendingParams {
         match self.inner {
             NodeAddressesInner::Static(ref nodes) => PendingParams::new(nodes.next().into_future()),
             NodeAddressesInner::Sniffed(ref sniffer) => PendingParams::new(sniffer.next()),
         }
     }
+
+    /**
+    Ping the given dead nodes on a task and mark them alive if they respond.
+
+    The task is spawned on the current runtime.
+    If there's no runtime then the nodes are pinged when a request is polled on one.
+    */
+    fn resurrect(&self, resurrect_params: Vec<(NodeAddress, RequestParams)>) {
+        let health = self.health();
+        let nodes: Vec<_> = resurrect_params.iter().map(|(node, _)| node.clone()).collect();
+
+        let pings: Vec<_> = resurrect_params
+            .into_iter()
+            .map(|(node, params)| {
+                let health = health.clone();
+
+                self.sender.ping(&params).then(move |res| {
+                    health.finish_resurrect(&node, res);
+
+                    Ok(())
+                })
+            })
+            .collect();
+
+        if DefaultExecutor::current()
+            .spawn(Box::new(join_all(pings).map(|_| ())))
+            .is_err()
+        {
+            for node in &nodes {
+                health.stop_resurrect(node);
+            }
+        }
+    }
 }
 
 /** A future returned by calling `next` on an async set of `NodeAddresses`. */
@@ -234,6 +640,23 @@ impl From<RequestParams> for PendingParams {
     }
 }
 
+/**
+Get the kind of error for a request that failed or didn't get a response in time.
+
+Requests that timed out are returned as an `Error::Timeout`.
+*/
+fn timeout_error(err: TimeoutError<ReqwestError>) -> (Option<RetryableError>, Error) {
+    if err.is_elapsed() {
+        let err = error::message("no response was received before the timeout elapsed");
+
+        (Some(RetryableError::Timeout), error::timeout(err))
+    } else if err.is_inner() {
+        send_error(err.into_inner().expect("missing inner error"))
+    } else {
+        (None, error::request(err))
+    }
+}
+
 /** Build an asynchronous `reqwest::RequestBuilder` from an Elasticsearch request. */
 fn build_reqwest(client: &AsyncHttpClient, req: AsyncHttpRequest) -> AsyncHttpRequestBuilder {
     let AsyncHttpRequest {
@@ -334,11 +757,321 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::tests::*;
+    use futures::{
+        future::{
+            self,
+            Either,
+        },
+        Future,
+    };
+    use serde_json::Value;
+    use std::{
+        error::Error as StdError,
+        io::{
+            Read,
+            Write,
+        },
+        net::TcpListener,
+        sync::Mutex,
+        thread,
+        time::{
+            Duration,
+            Instant,
+        },
+    };
+    use tokio::{
+        runtime::current_thread::block_on_all,
+        timer::Delay,
+    };
+
+    use crate::{
+        error::Error,
+        http::{
+            sender::{
+                health::PING_TIMEOUT,
+                static_nodes::LeastInFlight,
+                BeforeSend,
+                BufferedResponse,
+                InMemoryMetrics,
+                Middleware,
+                MockRequest,
+                MockResponse,
+                MockTransport,
+                RetryPolicy,
+            },
+            HttpRequest,
+            Method,
+            StatusCode,
+        },
+        prelude::*,
+        tests::*,
+    };
 
     #[test]
     fn is_send() {
         assert_send::<super::PendingParams>();
         assert_send::<super::PendingResponse>();
     }
+
+    #[test]
+    fn mock_typed_requests() {
+        let mock = MockTransport::new().respond(
+            MockRequest::new(Method::GET, "/myindex/_doc/1"),
+            MockResponse::new(StatusCode::OK).body(json!({
+                "_index": "myindex",
+                "_type": "_doc",
+                "_id": "1",
+                "found": true,
+                "_source": { "title": "A document" }
+            })),
+        );
+
+        let client = AsyncClientBuilder::new().mock(mock.clone()).build().unwrap();
+
+        let res = client
+            .document::<Value>()
+            .get_raw("myindex", 1)
+            .send()
+            .wait()
+            .unwrap();
+
+        assert_eq!(Some(json!({ "title": "A document" })), res.into_document());
+        assert_eq!("/myindex/_doc/1", mock.requests()[0].url().path());
+    }
+
+    /** Cache the last response and return it for every request after the first. */
+    #[derive(Default)]
+    struct Cache(Mutex<Option<BufferedResponse>>);
+
+    impl<TBody> Middleware<TBody> for Cache {
+        fn before_send(
+            &self,
+            _: &mut HttpRequest<TBody>,
+        ) -> Result<BeforeSend, Box<dyn StdError + Send + Sync>> {
+            let cached = self.0.lock().unwrap().clone();
+
+            Ok(BeforeSend::Pending(Box::new(future::ok(cached))))
+        }
+
+        fn after_receive(
+            &self,
+            _: &HttpRequest<()>,
+            res: &mut BufferedResponse,
+        ) -> Result<(), Box<dyn StdError + Send + Sync>> {
+            *self.0.lock().unwrap() = Some(res.clone());
+
+            Ok(())
+        }
+
+        fn receives_responses(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn middleware_responds_without_sending() {
+        let mock = MockTransport::new().respond(
+            MockRequest::new(Method::GET, "/"),
+            MockResponse::new(StatusCode::OK).body(json!({ "cached": true })),
+        );
+
+        let client = AsyncClientBuilder::new()
+            .mock(mock.clone())
+            .middleware(Cache::default())
+            .build()
+            .unwrap();
+
+        for _ in 0..2 {
+            let res = client
+                .request(PingRequest::new())
+                .send()
+                .and_then(|res| res.into_response::<Value>())
+                .wait()
+                .unwrap();
+
+            assert_eq!(json!({ "cached": true }), res);
+        }
+
+        assert_eq!(1, mock.requests().len());
+    }
+
+    #[test]
+    fn metrics_count_bytes_received() {
+        let mock = MockTransport::new().respond(
+            MockRequest::new(Method::GET, "/"),
+            MockResponse::new(StatusCode::OK).body(r#"{"name":"a node"}"#),
+        );
+
+        let metrics = InMemoryMetrics::new();
+        let client = AsyncClientBuilder::new()
+            .mock(mock)
+            .metrics(metrics.clone())
+            .build()
+            .unwrap();
+
+        client
+            .request(PingRequest::new())
+            .send()
+            .and_then(|res| res.into_response::<Value>())
+            .wait()
+            .unwrap();
+
+        let ping = &metrics.snapshot().endpoints["ping"];
+
+        assert_eq!(1, ping.requests);
+        assert_eq!(17, ping.bytes_received);
+        assert_eq!(Some(&1), ping.statuses.get(&200));
+    }
+
+    /** Serve a response whose body is written one byte at a time with a delay between each. */
+    fn serve_trickle(delay: Duration) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+
+        thread::spawn(move || {
+            let mut stream = listener.incoming().next().unwrap().unwrap();
+
+            let mut buf = [0; 4096];
+            let _ = stream.read(&mut buf);
+
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\ncontent-length: 100\r\nconnection: close\r\n\r\n"
+            );
+
+            for _ in 0..100 {
+                thread::sleep(delay);
+
+                if stream.write_all(b" ").is_err() {
+                    break;
+                }
+            }
+        });
+
+        address
+    }
+
+    #[test]
+    fn total_timeout_covers_reading_body() {
+        let client = AsyncClientBuilder::new()
+            .static_node(serve_trickle(Duration::from_millis(50)))
+            .params_fluent(|p| {
+                p.read_timeout(Duration::from_secs(1))
+                    .total_timeout(Duration::from_millis(300))
+            })
+            .build()
+            .unwrap();
+
+        let start = Instant::now();
+        let res = block_on_all(
+            client
+                .request(PingRequest::new())
+                .send()
+                .and_then(|res| res.into_response::<Value>()),
+        );
+
+        assert!(matches!(res, Err(Error::Timeout(_))));
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn read_timeout_covers_reading_body() {
+        let client = AsyncClientBuilder::new()
+            .static_node(serve_trickle(Duration::from_millis(300)))
+            .params_fluent(|p| p.read_timeout(Duration::from_millis(100)))
+            .build()
+            .unwrap();
+
+        let res = block_on_all(
+            client
+                .request(PingRequest::new())
+                .send()
+                .and_then(|res| res.into_response::<Value>()),
+        );
+
+        assert!(matches!(res, Err(Error::Timeout(_))));
+    }
+
+    /** Serve a successful response to the given number of requests. */
+    fn serve_ok(requests: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+
+        thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+
+                let mut buf = [0; 4096];
+                let _ = stream.read(&mut buf);
+
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\n{{}}"
+                );
+            }
+        });
+
+        address
+    }
+
+    #[test]
+    fn slow_dead_node_doesnt_delay_requests() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let client = AsyncClientBuilder::new()
+            .static_nodes(vec![format!("http://{}", local_addr), serve_ok(2)])
+            .dead_node_timeout(Duration::from_millis(0), Duration::from_millis(0))
+            .retry_policy(RetryPolicy::new().jitter(false))
+            .build()
+            .unwrap();
+
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+
+        // The first node refuses the connection so it's marked dead
+        runtime.block_on(client.request(PingRequest::new()).send()).unwrap();
+
+        // The dead node accepts the ping but never responds to it
+        let _slow = TcpListener::bind(local_addr).unwrap();
+
+        let start = Instant::now();
+        let res = runtime.block_on(client.request(PingRequest::new()).send()).unwrap();
+
+        assert_eq!(StatusCode::OK, res.status());
+        assert!(start.elapsed() < PING_TIMEOUT);
+    }
+
+    #[test]
+    fn dropped_requests_arent_in_flight() {
+        // The node accepts the request but never responds to it
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+
+        let strategy = LeastInFlight::default();
+        let client = AsyncClientBuilder::new()
+            .static_node(address.clone())
+            .strategy(strategy.clone())
+            .build()
+            .unwrap();
+
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+
+        let pending = client.request(PingRequest::new()).send();
+        let wait = {
+            let strategy = strategy.clone();
+            let address = address.clone();
+
+            Delay::new(Instant::now() + Duration::from_millis(100))
+                .map(move |_| strategy.in_flight(&address))
+        };
+
+        // The request is still in flight when it's dropped
+        match runtime.block_on(pending.select2(wait)) {
+            Ok(Either::B((in_flight, _))) => assert_eq!(1, in_flight),
+            _ => panic!("expected the request to still be pending"),
+        }
+
+        assert_eq!(0, strategy.in_flight(&address));
+    }
 }
@@ -0,0 +1,539 @@
+/*!
+The stages of sending a request that are shared by synchronous and asynchronous senders.
+
+Each attempt at sending a request builds it for the selected node, runs it through middleware, picks a transport to send it with, and then classifies the result to decide whether to retry it.
+The senders only differ in how they wait on the transport, middleware and the backoff between attempts.
+*/
+
+use std::{
+    sync::Arc,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+use tracing::Span;
+use uuid::Uuid;
+
+use crate::{
+    error::{
+        self,
+        Error,
+    },
+    http::{
+        receiver::{
+            AsyncResponseBuilder,
+            SyncResponseBuilder,
+        },
+        sender::{
+            build_url,
+            credentials::authorize,
+            metrics::AttemptMetrics,
+            middleware::{
+                receives_responses,
+                request_head,
+                BeforeSendResult,
+            },
+            mock::{
+                copy_async_request,
+                copy_sync_request,
+            },
+            timeouts::has_time_for,
+            BufferedResponse,
+            CredentialsProvider,
+            InFlightRequest,
+            MetricsObserver,
+            MetricsRequest,
+            Middleware,
+            MockHttpRequest,
+            MockTransport,
+            NodeFeedback,
+            RequestParams,
+            RetryPolicy,
+            RetryableError,
+        },
+        AsyncBody,
+        HttpRequest,
+        Method,
+        StatusCode,
+        SyncBody,
+        Url,
+    },
+};
+
+/** The result of an attempt, along with the kind of error it failed with if it can be retried. */
+pub(crate) type AttemptResult<TResponse> = Result<TResponse, (Option<RetryableError>, Error)>;
+
+/** The request given to middleware along with its response, and the number of middleware to call. */
+pub(crate) type Receivers = Option<(HttpRequest<()>, usize)>;
+
+/** Get the span for a request. */
+pub(crate) fn request_span(correlation_id: Uuid, endpoint: &'static str, method: &Method) -> Span {
+    tracing::info_span!(
+        "elasticsearch.request",
+        %correlation_id,
+        endpoint,
+        %method,
+        status = tracing::field::Empty,
+        duration_ms = tracing::field::Empty,
+    )
+}
+
+/** Get the span for a single attempt at sending a request. */
+pub(crate) fn attempt_span(attempt: u32, params: &RequestParams) -> Span {
+    let node: &str = params.node_address().as_ref();
+
+    tracing::info_span!(
+        "elasticsearch.attempt",
+        attempt,
+        node,
+        status = tracing::field::Empty,
+        duration_ms = tracing::field::Empty,
+    )
+}
+
+/** Build an Elasticsearch request for an attempt, before it's given to middleware. */
+pub(crate) fn build_request<TBody>(
+    correlation_id: Uuid,
+    url: &str,
+    method: Method,
+    body: Option<TBody>,
+    params: &RequestParams,
+    gzip: bool,
+    credentials: Option<&dyn CredentialsProvider>,
+) -> Result<HttpRequest<TBody>, Error> {
+    let url = Url::parse(&build_url(url, params))
+        .map_err(error::request)
+        .log_err(|e| {
+            error!(
+                "Elasticsearch Request: correlation_id: '{}', error: '{:?}'",
+                correlation_id, e
+            )
+        })?;
+
+    let mut req = HttpRequest {
+        url,
+        method,
+        headers: params.get_headers(),
+        body,
+    };
+
+    req.set_opaque_id(correlation_id);
+
+    if gzip {
+        req.set_gzip_headers();
+    }
+
+    if let Some(credentials) = credentials {
+        authorize(&mut req, credentials).log_err(|e| {
+            error!(
+                "Elasticsearch Request Credentials: correlation_id: '{}', error: '{:?}'",
+                correlation_id, e
+            )
+        })?;
+    }
+
+    Ok(req)
+}
+
+/** The body of a request that can be measured and recorded to a fixture file. */
+pub(crate) trait AttemptBody: Sized {
+    /** The length of the body if it's already in memory. */
+    fn buffered_len(&self) -> Option<u64>;
+
+    /** Copy a request so it can be recorded along with its response. */
+    fn copy_request(req: &HttpRequest<Self>) -> Result<MockHttpRequest, Error>;
+}
+
+impl AttemptBody for SyncBody {
+    fn buffered_len(&self) -> Option<u64> {
+        SyncBody::buffered_len(self)
+    }
+
+    fn copy_request(req: &HttpRequest<Self>) -> Result<MockHttpRequest, Error> {
+        copy_sync_request(req)
+    }
+}
+
+impl AttemptBody for AsyncBody {
+    fn buffered_len(&self) -> Option<u64> {
+        AsyncBody::buffered_len(self)
+    }
+
+    fn copy_request(req: &HttpRequest<Self>) -> Result<MockHttpRequest, Error> {
+        copy_async_request(req)
+    }
+}
+
+/** The transport to send a single attempt of a request with. */
+pub(crate) enum Transport<TBody, TClient, TRequest> {
+    Respond(BufferedResponse),
+    Mock(MockTransport, HttpRequest<TBody>),
+    Http(TClient, TRequest),
+}
+
+impl<TBody, TClient, TRequest> Transport<TBody, TClient, TRequest> {
+    /**
+    Whether the request is sent to a node.
+
+    Responses from middleware don't affect the health of the node.
+    */
+    pub(crate) fn is_sent(&self) -> bool {
+        !matches!(self, Transport::Respond(_))
+    }
+}
+
+/** A request that's been through middleware and is ready to be sent. */
+pub(crate) struct PreparedRequest<TBody, TClient, TRequest> {
+    pub(crate) transport: Transport<TBody, TClient, TRequest>,
+    pub(crate) recorded: Option<MockHttpRequest>,
+    pub(crate) receivers: Receivers,
+    pub(crate) body_len: Option<u64>,
+}
+
+/**
+Pick the transport to send a request with after it's been through middleware.
+
+Requests that middleware responded to aren't sent or recorded.
+Otherwise the request is sent with the mock transport if there is one, or built into a `reqwest` request with the given function.
+*/
+pub(crate) fn prepare_request<TBody, TClient, TRequest>(
+    correlation_id: Uuid,
+    before_send: BeforeSendResult<TBody>,
+    middleware: &[Box<dyn Middleware<TBody>>],
+    mock: Option<&MockTransport>,
+    is_recording: bool,
+    build_http: impl FnOnce(HttpRequest<TBody>) -> Result<(TClient, TRequest), Error>,
+) -> Result<PreparedRequest<TBody, TClient, TRequest>, Error>
+where
+    TBody: AttemptBody,
+{
+    let BeforeSendResult {
+        req,
+        called,
+        response,
+    } = before_send;
+
+    let body_len = req.body.as_ref().map_or(Some(0), AttemptBody::buffered_len);
+
+    // Keep the parts of the request that middleware are given along with the response
+    let receivers = if receives_responses(middleware, called) {
+        Some((request_head(&req), called))
+    } else {
+        None
+    };
+
+    let prepared = |transport, recorded| PreparedRequest {
+        transport,
+        recorded,
+        receivers,
+        body_len,
+    };
+
+    if let Some(res) = response {
+        return Ok(prepared(Transport::Respond(res), None));
+    }
+
+    let prepare = move || {
+        let recorded = if is_recording {
+            Some(TBody::copy_request(&req)?)
+        } else {
+            None
+        };
+
+        let transport = match mock {
+            Some(mock) => Transport::Mock(mock.clone(), req),
+            None => {
+                let (http, req) = build_http(req)?;
+
+                Transport::Http(http, req)
+            }
+        };
+
+        Ok(prepared(transport, recorded))
+    };
+
+    prepare().log_err(|e| {
+        error!(
+            "Elasticsearch Request: correlation_id: '{}', error: '{:?}'",
+            correlation_id, e
+        )
+    })
+}
+
+/** A response to an attempt. */
+pub(crate) trait AttemptResponse: Sized {
+    /** Get the HTTP status for the response. */
+    fn status(&self) -> StatusCode;
+
+    /** Count the bytes read from the response body. */
+    fn read_metrics(self, metrics: &AttemptMetrics) -> Self;
+}
+
+impl AttemptResponse for SyncResponseBuilder {
+    fn status(&self) -> StatusCode {
+        SyncResponseBuilder::status(self)
+    }
+
+    fn read_metrics(self, metrics: &AttemptMetrics) -> Self {
+        self.map_body(|body| Box::new(metrics.read(body)))
+    }
+}
+
+impl AttemptResponse for AsyncResponseBuilder {
+    fn status(&self) -> StatusCode {
+        AsyncResponseBuilder::status(self)
+    }
+
+    fn read_metrics(self, metrics: &AttemptMetrics) -> Self {
+        self.map_body(|body| Box::new(metrics.read(body)))
+    }
+}
+
+/** What to do after an attempt at sending a request. */
+pub(crate) enum AttemptOutcome<TResponse> {
+    /** Return the response or error to the caller. */
+    Done(Result<TResponse, Error>),
+    /** Send the request again after waiting for the backoff. */
+    Retry(Duration),
+}
+
+/** The time an attempt was sent and its metrics. */
+#[derive(Clone)]
+pub(crate) struct AttemptTimer {
+    start: Instant,
+    metrics: Option<AttemptMetrics>,
+}
+
+impl AttemptTimer {
+    /** Report the response or failure of an attempt to its metrics. */
+    pub(crate) fn received<TResponse>(
+        &self,
+        res: AttemptResult<TResponse>,
+    ) -> AttemptResult<TResponse>
+    where
+        TResponse: AttemptResponse,
+    {
+        let metrics = match self.metrics {
+            Some(ref metrics) => metrics,
+            None => return res,
+        };
+
+        match res {
+            Ok(res) => {
+                metrics.response_received(res.status(), self.start.elapsed());
+
+                Ok(res.read_metrics(metrics))
+            }
+            Err(e) => {
+                metrics.request_failed(self.start.elapsed());
+
+                Err(e)
+            }
+        }
+    }
+}
+
+/**
+A single attempt at sending a request that's about to be sent.
+
+The node the attempt is sent to is told when it finishes.
+If the attempt is dropped before then, like when an asynchronous request is cancelled, the node is told the request was cancelled.
+*/
+pub(crate) struct Attempt {
+    correlation_id: Uuid,
+    attempt: u32,
+    span: Span,
+    timer: AttemptTimer,
+    in_flight: Option<InFlightRequest>,
+}
+
+impl Attempt {
+    /** Start an attempt, reporting it to any metrics and the node's feedback. */
+    pub(crate) fn start(
+        correlation_id: Uuid,
+        span: Span,
+        req: MetricsRequest,
+        is_sent: bool,
+        body_len: Option<u64>,
+        observer: Option<&Arc<dyn MetricsObserver>>,
+        feedback: Option<&NodeFeedback>,
+    ) -> Self {
+        let in_flight = feedback
+            .filter(|_| is_sent)
+            .map(|feedback| feedback.request_started(req.node()));
+
+        let attempt = req.attempt();
+        let metrics = observer.map(|observer| AttemptMetrics::new(observer, req, is_sent));
+
+        if let Some(ref metrics) = metrics {
+            metrics.request_sent(body_len);
+        }
+
+        Attempt {
+            correlation_id,
+            attempt,
+            span,
+            timer: AttemptTimer {
+                start: Instant::now(),
+                metrics,
+            },
+            in_flight,
+        }
+    }
+
+    /** Get the timer for reporting the response of the attempt to its metrics. */
+    pub(crate) fn timer(&self) -> &AttemptTimer {
+        &self.timer
+    }
+
+    /**
+    Classify the result of the attempt and decide whether to retry it.
+
+    An attempt is only retried if it's `retryable` and there's time left before the `deadline` to wait for its backoff.
+    */
+    pub(crate) fn finish<TResponse>(
+        mut self,
+        res: AttemptResult<TResponse>,
+        retry_policy: &RetryPolicy,
+        retryable: bool,
+        deadline: Option<Instant>,
+    ) -> AttemptOutcome<TResponse>
+    where
+        TResponse: AttemptResponse,
+    {
+        let correlation_id = self.correlation_id;
+
+        self.span.record("duration_ms", self.timer.start.elapsed().as_millis() as u64);
+
+        // Don't retry if the total timeout would elapse before the next attempt
+        let backoff = retry_policy.backoff_after(self.attempt);
+        let can_retry = retryable && has_time_for(backoff, deadline);
+
+        let retry_reason = match res {
+            Ok(res) => {
+                info!(
+                    "Elasticsearch Response: correlation_id: '{}', status: '{}'",
+                    correlation_id,
+                    res.status()
+                );
+
+                self.span.record("status", res.status().as_u16());
+
+                if let Some(in_flight) = self.in_flight.take() {
+                    in_flight.finished();
+                }
+
+                if !can_retry || !retry_policy.is_retryable_status(res.status()) {
+                    return AttemptOutcome::Done(Ok(res));
+                }
+
+                format!("status: '{}'", res.status())
+            }
+            Err((kind, e)) => {
+                if let Some(in_flight) = self.in_flight.take() {
+                    in_flight.failed(kind);
+                }
+
+                if !can_retry || !retry_policy.is_retryable_error(kind) {
+                    error!(
+                        "Elasticsearch Response: correlation_id: '{}', error: '{:?}'",
+                        correlation_id, e
+                    );
+
+                    return AttemptOutcome::Done(Err(e));
+                }
+
+                format!("error: '{:?}'", e)
+            }
+        };
+
+        if let Some(ref metrics) = self.timer.metrics {
+            metrics.request_retried();
+        }
+
+        warn!(
+            "Elasticsearch Retry: correlation_id: '{}', attempt: '{}', backoff: '{:?}', {}",
+            correlation_id, self.attempt, backoff, retry_reason
+        );
+
+        AttemptOutcome::Retry(backoff)
+    }
+}
+
+pub(crate) trait LogErr<E> {
+    fn log_err<F>(self, log: F) -> Self
+    where
+        F: FnOnce(&E);
+}
+
+impl<T, E> LogErr<E> for Result<T, E> {
+    fn log_err<F>(self, log: F) -> Self
+    where
+        F: FnOnce(&E),
+    {
+        if let Err(ref e) = self {
+            log(e);
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::receiver::sync_buffered_response;
+
+    fn attempt() -> Attempt {
+        let req = MetricsRequest::new("search", Method::GET, "http://localhost:9200".into(), 1);
+
+        Attempt::start(Uuid::new_v4(), Span::none(), req, true, None, None, None)
+    }
+
+    fn response(status: StatusCode) -> AttemptResult<SyncResponseBuilder> {
+        Ok(sync_buffered_response(status, "{}".into()))
+    }
+
+    fn is_retried<TResponse>(outcome: AttemptOutcome<TResponse>) -> bool {
+        matches!(outcome, AttemptOutcome::Retry(_))
+    }
+
+    #[test]
+    fn retry_retryable_status() {
+        let policy = RetryPolicy::new().jitter(false);
+
+        let res = response(StatusCode::SERVICE_UNAVAILABLE);
+        assert!(is_retried(attempt().finish(res, &policy, true, None)));
+
+        let res = response(StatusCode::NOT_FOUND);
+        assert!(!is_retried(attempt().finish(res, &policy, true, None)));
+    }
+
+    #[test]
+    fn retry_retryable_error() {
+        let policy = RetryPolicy::new().jitter(false);
+
+        let res: AttemptResult<SyncResponseBuilder> = Err((
+            Some(RetryableError::Connection),
+            error::request(error::message("connection refused")),
+        ));
+        assert!(is_retried(attempt().finish(res, &policy, true, None)));
+
+        let res: AttemptResult<SyncResponseBuilder> =
+            Err((None, error::request(error::message("invalid request"))));
+        assert!(!is_retried(attempt().finish(res, &policy, true, None)));
+    }
+
+    #[test]
+    fn dont_retry_without_attempts_or_time_left() {
+        let policy = RetryPolicy::new().jitter(false);
+
+        let res = response(StatusCode::SERVICE_UNAVAILABLE);
+        assert!(!is_retried(attempt().finish(res, &policy, false, None)));
+
+        let res = response(StatusCode::SERVICE_UNAVAILABLE);
+        let deadline = Some(Instant::now());
+        assert!(!is_retried(attempt().finish(res, &policy, true, deadline)));
+    }
+}
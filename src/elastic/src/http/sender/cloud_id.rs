@@ -0,0 +1,124 @@
+/*!
+Node addresses for clusters hosted on Elastic Cloud.
+*/
+
+use std::str;
+
+use crate::{
+    error::{
+        self,
+        Error,
+    },
+    http::sender::NodeAddress,
+};
+
+const DEFAULT_CLOUD_PORT: u16 = 443;
+
+impl NodeAddress {
+    /**
+    Get the address of an Elasticsearch cluster hosted on Elastic Cloud from its cloud id.
+
+    The cloud id is shown in the Elastic Cloud console as a name followed by a `:` and a base64 encoded string.
+    The encoded string contains the host and the ids of the Elasticsearch and Kibana instances separated by a `$`.
+    The address always uses `https`, and port `443` unless the cloud id includes a different one.
+
+    # Examples
+
+    ```
+    # use elastic::http::sender::NodeAddress;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    let address = NodeAddress::from_cloud_id(
+        "my-cluster:dXMtZWFzdC0xLmF3cy5mb3VuZC5pbyRjZWM2ZjI2MWE3NGJmMjRjZTMzYmI4ODExYjg0Mjk0ZiRjNmMyY2E2ZDA0MjI0OWFmMGNjN2Q3YTllOTYyNTc0Mw==",
+    )?;
+
+    assert_eq!(
+        "https://cec6f261a74bf24ce33bb8811b84294f.us-east-1.aws.found.io:443",
+        address.as_ref()
+    );
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn from_cloud_id(cloud_id: &str) -> Result<Self, Error> {
+        // The name is optional and can't be confused with the encoded part, which never contains a `:`
+        let encoded = cloud_id.rsplit(':').next().unwrap_or_default();
+
+        let decoded = base64::decode(encoded).map_err(error::build)?;
+        let decoded = str::from_utf8(&decoded).map_err(error::build)?;
+
+        let mut parts = decoded.split('$');
+        let host = parts.next().unwrap_or_default();
+        let es_id = parts.next().unwrap_or_default();
+
+        let (host, host_port) = split_port(host)?;
+        let (es_id, es_port) = split_port(es_id)?;
+
+        if host.is_empty() || es_id.is_empty() {
+            return Err(error::build(error::message(format!(
+                "the cloud id '{}' doesn't contain a host and Elasticsearch id",
+                cloud_id
+            ))));
+        }
+
+        let port = es_port.or(host_port).unwrap_or(DEFAULT_CLOUD_PORT);
+
+        Ok(NodeAddress::from(format!("https://{}.{}:{}", es_id, host, port)))
+    }
+}
+
+/** Split an optional port from the end of a part of a cloud id. */
+fn split_port(part: &str) -> Result<(&str, Option<u16>), Error> {
+    match part.rfind(':') {
+        Some(idx) => {
+            let port = part[idx + 1..].parse().map_err(error::build)?;
+
+            Ok((&part[..idx], Some(port)))
+        }
+        None => Ok((part, None)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cloud_id(decoded: &str) -> String {
+        format!("name:{}", base64::encode(decoded))
+    }
+
+    #[test]
+    fn parse_cloud_id() {
+        let address = NodeAddress::from_cloud_id(&cloud_id("cloud.es.io$es-id$kibana-id")).unwrap();
+
+        assert_eq!("https://es-id.cloud.es.io:443", address.as_ref());
+    }
+
+    #[test]
+    fn parse_cloud_id_without_name() {
+        let address = NodeAddress::from_cloud_id(&base64::encode("cloud.es.io$es-id")).unwrap();
+
+        assert_eq!("https://es-id.cloud.es.io:443", address.as_ref());
+    }
+
+    #[test]
+    fn parse_cloud_id_with_host_port() {
+        let address = NodeAddress::from_cloud_id(&cloud_id("cloud.es.io:9243$es-id$kibana-id"));
+
+        assert_eq!("https://es-id.cloud.es.io:9243", address.unwrap().as_ref());
+    }
+
+    #[test]
+    fn parse_cloud_id_with_es_port() {
+        let cloud_id = cloud_id("cloud.es.io:9243$es-id:9200$kibana-id");
+        let address = NodeAddress::from_cloud_id(&cloud_id).unwrap();
+
+        assert_eq!("https://es-id.cloud.es.io:9200", address.as_ref());
+    }
+
+    #[test]
+    fn invalid_cloud_id() {
+        assert!(NodeAddress::from_cloud_id("name:not base64").is_err());
+        assert!(NodeAddress::from_cloud_id(&cloud_id("cloud.es.io")).is_err());
+        assert!(NodeAddress::from_cloud_id(&cloud_id("cloud.es.io:port$es-id")).is_err());
+    }
+}
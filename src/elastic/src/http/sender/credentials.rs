@@ -0,0 +1,278 @@
+/*!
+Credentials for authenticating requests.
+*/
+
+use std::{
+    error::Error as StdError,
+    fmt,
+};
+
+use crate::{
+    error::{
+        self,
+        Error,
+    },
+    http::{
+        header::{
+            HeaderValue,
+            InvalidHeaderValue,
+            AUTHORIZATION,
+        },
+        HttpRequest,
+    },
+};
+
+/**
+Credentials for authenticating a request with Elasticsearch.
+
+Credentials are sent in the `Authorization` header.
+
+# Examples
+
+Authenticate with an [API key][docs-api-key]:
+
+```
+# use elastic::prelude::*;
+let credentials = Credentials::api_key("VuaCfGcBCdbkQm-e5aOx", "ui2lp2axTNmsyakw9tvNnw");
+```
+
+Authenticate with an OAuth bearer token:
+
+```
+# use elastic::prelude::*;
+let credentials = Credentials::bearer_token("dGhpcyBpcyBub3QgYSByZWFsIHRva2Vu");
+```
+
+[docs-api-key]: https://www.elastic.co/guide/en/elasticsearch/reference/current/security-api-create-api-key.html
+*/
+#[derive(Clone, PartialEq, Eq)]
+pub enum Credentials {
+    /** HTTP basic authentication with a username and optional password. */
+    Basic {
+        /** The name of the user. */
+        username: String,
+        /** The password for the user. */
+        password: Option<String>,
+    },
+    /** An Elasticsearch API key. */
+    ApiKey {
+        /** The id of the API key. */
+        id: String,
+        /** The secret part of the API key. */
+        key: String,
+    },
+    /** An OAuth bearer token. */
+    Bearer(String),
+}
+
+impl Credentials {
+    /** Create credentials for HTTP basic authentication. */
+    pub fn basic(username: impl ToString, password: Option<impl ToString>) -> Self {
+        Credentials::Basic {
+            username: username.to_string(),
+            password: password.map(|password| password.to_string()),
+        }
+    }
+
+    /** Create credentials for an Elasticsearch API key from its id and key. */
+    pub fn api_key(id: impl ToString, key: impl ToString) -> Self {
+        Credentials::ApiKey {
+            id: id.to_string(),
+            key: key.to_string(),
+        }
+    }
+
+    /** Create credentials for an OAuth bearer token. */
+    pub fn bearer_token(token: impl ToString) -> Self {
+        Credentials::Bearer(token.to_string())
+    }
+
+    /**
+    Get the value of the `Authorization` header for these credentials.
+
+    The header value is marked as sensitive so it isn't logged.
+    */
+    pub fn to_header_value(&self) -> Result<HeaderValue, InvalidHeaderValue> {
+        let value = match self {
+            Credentials::Basic { username, password } => {
+                let auth = match password {
+                    Some(password) => format!("{}:{}", username, password),
+                    None => format!("{}:", username),
+                };
+
+                format!("Basic {}", base64::encode(&auth))
+            }
+            Credentials::ApiKey { id, key } => {
+                format!("ApiKey {}", base64::encode(&format!("{}:{}", id, key)))
+            }
+            Credentials::Bearer(token) => format!("Bearer {}", token),
+        };
+
+        let mut value = HeaderValue::from_str(&value)?;
+        value.set_sensitive(true);
+
+        Ok(value)
+    }
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Don't leak secrets into logs
+        match self {
+            Credentials::Basic { username, .. } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .finish(),
+            Credentials::ApiKey { id, .. } => f.debug_struct("ApiKey").field("id", id).finish(),
+            Credentials::Bearer(_) => f.debug_tuple("Bearer").finish(),
+        }
+    }
+}
+
+/**
+A source of credentials that's asked for them before each request is sent.
+
+A provider can be used when credentials change over time, like API keys or OAuth tokens that are rotated.
+It's called before every request, including the requests used to sniff nodes, so the client never needs to be rebuilt when the credentials change.
+The provider is called on the thread sending the request, so it should return quickly, for example by returning cached credentials that are refreshed elsewhere.
+
+Returning `Ok(None)` sends the request without any credentials from the provider.
+Returning an error fails the request without sending it.
+
+A provider is implemented for closures and for fixed `Credentials`.
+
+# Examples
+
+Read the latest token each time a request is sent:
+
+```
+# use std::sync::{Arc, RwLock};
+# use elastic::prelude::*;
+let token = Arc::new(RwLock::new(String::from("a token")));
+
+let builder = SyncClientBuilder::new()
+    .credentials_provider(move || {
+        let token = token.read().expect("lock poisoned");
+
+        Ok(Some(Credentials::bearer_token(&*token)))
+    });
+```
+*/
+pub trait CredentialsProvider: Send + Sync {
+    /** Get the credentials to send a request with. */
+    fn credentials(&self) -> Result<Option<Credentials>, Box<dyn StdError + Send + Sync>>;
+}
+
+impl<F> CredentialsProvider for F
+where
+    F: Fn() -> Result<Option<Credentials>, Box<dyn StdError + Send + Sync>> + Send + Sync,
+{
+    fn credentials(&self) -> Result<Option<Credentials>, Box<dyn StdError + Send + Sync>> {
+        self()
+    }
+}
+
+impl CredentialsProvider for Credentials {
+    fn credentials(&self) -> Result<Option<Credentials>, Box<dyn StdError + Send + Sync>> {
+        Ok(Some(self.clone()))
+    }
+}
+
+/**
+Set the `Authorization` header on a request using the credentials from a provider.
+
+If the provider doesn't return any credentials then the request is left unchanged.
+*/
+pub(crate) fn authorize<TBody>(
+    req: &mut HttpRequest<TBody>,
+    provider: &dyn CredentialsProvider,
+) -> Result<(), Error> {
+    let credentials = provider
+        .credentials()
+        .map_err(error::wrapped)
+        .map_err(error::request)?;
+
+    if let Some(credentials) = credentials {
+        let header_value = credentials.to_header_value().map_err(error::request)?;
+        req.headers_mut().insert(AUTHORIZATION, header_value);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{
+        header::HeaderMap,
+        Method,
+        Url,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn basic_header() {
+        let value = Credentials::basic("user", Some("pass")).to_header_value().unwrap();
+
+        assert_eq!("Basic dXNlcjpwYXNz", value.to_str().unwrap());
+        assert!(value.is_sensitive());
+    }
+
+    #[test]
+    fn api_key_header() {
+        let value = Credentials::api_key("id", "key").to_header_value().unwrap();
+
+        assert_eq!("ApiKey aWQ6a2V5", value.to_str().unwrap());
+    }
+
+    #[test]
+    fn bearer_header() {
+        let value = Credentials::bearer_token("token").to_header_value().unwrap();
+
+        assert_eq!("Bearer token", value.to_str().unwrap());
+    }
+
+    #[test]
+    fn debug_hides_secrets() {
+        let credentials = Credentials::api_key("id", "secret");
+
+        assert!(!format!("{:?}", credentials).contains("secret"));
+    }
+
+    fn request() -> HttpRequest<()> {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer old"));
+
+        HttpRequest {
+            url: Url::parse("http://localhost:9200").unwrap(),
+            method: Method::GET,
+            headers: Arc::new(headers),
+            body: None,
+        }
+    }
+
+    #[test]
+    fn provider_sets_authorization() {
+        let mut req = request();
+
+        authorize(&mut req, &Credentials::bearer_token("new")).unwrap();
+
+        assert_eq!("Bearer new", req.headers[AUTHORIZATION].to_str().unwrap());
+    }
+
+    #[test]
+    fn provider_without_credentials_keeps_authorization() {
+        let mut req = request();
+
+        authorize(&mut req, &|| Ok(None)).unwrap();
+
+        assert_eq!("Bearer old", req.headers[AUTHORIZATION].to_str().unwrap());
+    }
+
+    #[test]
+    fn provider_error_fails_request() {
+        let mut req = request();
+
+        assert!(authorize(&mut req, &|| Err("no credentials".into())).is_err());
+    }
+}
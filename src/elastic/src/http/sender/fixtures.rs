@@ -0,0 +1,422 @@
+/*!
+Fixtures for recording requests sent to a cluster and replaying them without one.
+*/
+
+use futures::Future;
+use serde_json::Value;
+use std::{
+    fs::File,
+    io::{
+        BufRead,
+        BufReader,
+        Write,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::{
+        atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+        Arc,
+        Mutex,
+    },
+};
+use tokio_threadpool::ThreadPool;
+
+use crate::{
+    error::{
+        self,
+        Error,
+    },
+    http::{
+        receiver::{
+            AsyncResponseBuilder,
+            SyncResponseBuilder,
+        },
+        sender::{
+            mock::path_and_query,
+            BufferedResponse,
+            MockBody,
+            MockHttpRequest,
+            MockRequest,
+            MockResponse,
+            MockTransport,
+        },
+        Method,
+        StatusCode,
+    },
+};
+
+/**
+A file of requests and responses that are either recorded from a cluster or replayed without one.
+
+Fixtures are given to a client builder.
+When recording, every request the client sends is still sent to the cluster, and the request along with its response is written to the fixture file.
+When replaying, no requests are sent over the network.
+Instead, each request is answered with the response that was recorded for the same method, path, query string and body.
+If the same request was recorded more than once then its responses are replayed in the order they were recorded, with the last one being repeated.
+Requests that weren't recorded fail with an error.
+
+Fixture files contain a JSON object on each line with the `method`, `url` and `body` of a request along with the `status` and `response` body it received.
+Bodies that aren't JSON are stored as strings.
+Request headers aren't part of a fixture, so values that change on every request, like correlation ids, don't affect replaying.
+
+Fields in request and response bodies that change between runs, like `took`, can be normalized to a fixed value.
+Normalized fields are replaced wherever they appear in a JSON body before it's recorded, and in the bodies of requests before they're matched while replaying.
+
+# Examples
+
+Record requests when the `RECORD_FIXTURES` environment variable is set and replay them otherwise:
+
+```no_run
+# use std::env;
+# use elastic::prelude::*;
+# use elastic::http::sender::Fixtures;
+# fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+let fixtures = if env::var("RECORD_FIXTURES").is_ok() {
+    Fixtures::record("tests/fixtures/search.ndjson")
+} else {
+    Fixtures::replay("tests/fixtures/search.ndjson")
+};
+
+let client = SyncClientBuilder::new()
+    .fixtures(fixtures.normalize("took", 0))
+    .build()?;
+# Ok(())
+# }
+```
+*/
+#[derive(Debug, Clone)]
+pub struct Fixtures {
+    path: PathBuf,
+    mode: FixtureMode,
+    normalize: Normalize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FixtureMode {
+    Record,
+    Replay,
+}
+
+impl Fixtures {
+    /**
+    Record requests and their responses to the given file.
+
+    The file is created when the client is built, replacing any fixtures that were previously recorded.
+    */
+    pub fn record(path: impl AsRef<Path>) -> Self {
+        Fixtures::new(path, FixtureMode::Record)
+    }
+
+    /**
+    Replay responses from the given file.
+
+    The file is read when the client is built.
+    */
+    pub fn replay(path: impl AsRef<Path>) -> Self {
+        Fixtures::new(path, FixtureMode::Replay)
+    }
+
+    fn new(path: impl AsRef<Path>, mode: FixtureMode) -> Self {
+        Fixtures {
+            path: path.as_ref().to_owned(),
+            mode,
+            normalize: Normalize::default(),
+        }
+    }
+
+    /** Replace a field in request and response bodies with a fixed value. */
+    pub fn normalize(mut self, field: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.normalize.fields.push((field.into(), value.into()));
+
+        self
+    }
+
+    /** Open the fixture file for recording or replaying. */
+    pub(crate) fn open(self) -> Result<FixtureTransport, Error> {
+        let normalize = Arc::new(self.normalize);
+
+        match self.mode {
+            FixtureMode::Record => {
+                let file = File::create(&self.path).map_err(error::build)?;
+
+                Ok(FixtureTransport::Record(FixtureRecorder {
+                    file: Arc::new(Mutex::new(file)),
+                    normalize,
+                }))
+            }
+            FixtureMode::Replay => {
+                let file = File::open(&self.path).map_err(error::build)?;
+
+                Ok(FixtureTransport::Replay(replay(file, normalize)?))
+            }
+        }
+    }
+}
+
+/** The transport to use for a set of fixtures. */
+pub(crate) enum FixtureTransport {
+    Record(FixtureRecorder),
+    Replay(MockTransport),
+}
+
+/** Replacements for fields whose values change between requests. */
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Normalize {
+    fields: Vec<(String, Value)>,
+}
+
+impl Normalize {
+    /** Replace any normalized fields in the given value. */
+    pub(crate) fn apply(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    match self.fields.iter().find(|(field, _)| field == key) {
+                        Some((_, replacement)) => *value = replacement.clone(),
+                        None => self.apply(value),
+                    }
+                }
+            }
+            Value::Array(values) => {
+                for value in values {
+                    self.apply(value);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /** Get the value of a body to store in a fixture. */
+    fn body(&self, body: &[u8]) -> Value {
+        match serde_json::from_slice(body) {
+            Ok(mut value) => {
+                self.apply(&mut value);
+                value
+            }
+            Err(_) => Value::String(String::from_utf8_lossy(body).into_owned()),
+        }
+    }
+}
+
+/** A single recorded request and its response. */
+#[derive(Serialize, Deserialize)]
+struct Fixture {
+    method: String,
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<Value>,
+    status: u16,
+    response: Value,
+}
+
+impl Fixture {
+    fn key(&self) -> (&str, &str, Option<&Value>) {
+        (&self.method, &self.url, self.body.as_ref())
+    }
+}
+
+/** Get the body of a request or response from a fixture. */
+fn fixture_body(body: Value) -> MockBody {
+    match body {
+        Value::String(body) => MockBody::from(body),
+        body => MockBody::from(body),
+    }
+}
+
+/** Read recorded fixtures into a mock transport that replays them. */
+fn replay(file: File, normalize: Arc<Normalize>) -> Result<MockTransport, Error> {
+    let mut fixtures: Vec<(Fixture, Vec<MockResponse>)> = Vec::new();
+
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(error::build)?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let fixture: Fixture = serde_json::from_str(&line).map_err(error::build)?;
+
+        let status = StatusCode::from_u16(fixture.status).map_err(error::build)?;
+        let response = MockResponse::new(status).body(fixture_body(fixture.response.clone()));
+
+        match fixtures
+            .iter_mut()
+            .find(|(recorded, _)| recorded.key() == fixture.key())
+        {
+            Some((_, responses)) => responses.push(response),
+            None => fixtures.push((fixture, vec![response])),
+        }
+    }
+
+    let mut mock = MockTransport::new();
+
+    for (fixture, responses) in fixtures {
+        let method = Method::from_bytes(fixture.method.as_bytes()).map_err(error::build)?;
+
+        let mut request = MockRequest::new(method, fixture.url).normalize(normalize.clone());
+        if let Some(body) = fixture.body {
+            request = request.body(fixture_body(body));
+        }
+
+        let next = AtomicUsize::new(0);
+        mock = mock.respond_with(request, move |_| {
+            let next = next.fetch_add(1, Ordering::SeqCst);
+
+            responses[next.min(responses.len() - 1)].clone()
+        });
+    }
+
+    Ok(mock)
+}
+
+/** Records requests and their responses to a fixture file. */
+#[derive(Clone)]
+pub(crate) struct FixtureRecorder {
+    file: Arc<Mutex<File>>,
+    normalize: Arc<Normalize>,
+}
+
+impl FixtureRecorder {
+    /** Record a synchronous response to the given request. */
+    pub(crate) fn record_sync(
+        &self,
+        req: &MockHttpRequest,
+        res: SyncResponseBuilder,
+    ) -> Result<SyncResponseBuilder, Error> {
+        let res = BufferedResponse::from_sync(res)?;
+        self.write(req, &res)?;
+
+        Ok(res.into_sync())
+    }
+
+    /** Record an asynchronous response to the given request. */
+    pub(crate) fn record_async(
+        &self,
+        req: MockHttpRequest,
+        res: AsyncResponseBuilder,
+        de_pool: Option<Arc<ThreadPool>>,
+    ) -> impl Future<Item = AsyncResponseBuilder, Error = Error> {
+        let recorder = self.clone();
+
+        BufferedResponse::from_async(res).and_then(move |res| {
+            recorder.write(&req, &res)?;
+
+            Ok(res.into_async(de_pool))
+        })
+    }
+
+    fn write(&self, req: &MockHttpRequest, res: &BufferedResponse) -> Result<(), Error> {
+        let fixture = Fixture {
+            method: req.method.to_string(),
+            url: path_and_query(&req.url),
+            body: req.body.as_ref().map(|body| self.normalize.body(body.as_ref())),
+            status: res.status().as_u16(),
+            response: self.normalize.body(res.body()),
+        };
+
+        let mut line = serde_json::to_string(&fixture).map_err(error::request)?;
+        line.push('\n');
+
+        self.file
+            .lock()
+            .expect("lock poisoned")
+            .write_all(line.as_bytes())
+            .map_err(error::request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{
+        header::HeaderMap,
+        SyncHttpRequest,
+        Url,
+    };
+    use std::{
+        env,
+        fs,
+        process,
+    };
+
+    fn fixture_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("elastic-fixtures-{}-{}.ndjson", process::id(), name))
+    }
+
+    #[test]
+    fn normalize_nested_fields() {
+        let normalize = Fixtures::replay("").normalize("took", 0).normalize;
+
+        let mut value = json!({
+            "took": 12,
+            "responses": [{ "took": 3, "hits": { "total": 1 } }]
+        });
+        normalize.apply(&mut value);
+
+        assert_eq!(
+            json!({
+                "took": 0,
+                "responses": [{ "took": 0, "hits": { "total": 1 } }]
+            }),
+            value
+        );
+    }
+
+    #[test]
+    fn fixture_bodies() {
+        let normalize = Normalize::default();
+
+        assert_eq!(json!({ "size": 1 }), normalize.body(b"{\"size\":1}"));
+        assert_eq!(json!("a\nb\n"), normalize.body(b"a\nb\n"));
+        assert_eq!(MockBody::from("a\nb\n"), fixture_body(json!("a\nb\n")));
+    }
+
+    #[test]
+    fn replay_in_recorded_order() {
+        let path = fixture_path("replay_in_recorded_order");
+        fs::write(
+            &path,
+            concat!(
+                r#"{"method":"GET","url":"/","status":503,"response":{}}"#,
+                "\n",
+                r#"{"method":"GET","url":"/","status":200,"response":{}}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+
+        let mock = match Fixtures::replay(&path).open().unwrap() {
+            FixtureTransport::Replay(mock) => mock,
+            FixtureTransport::Record(_) => panic!("expected a replay transport"),
+        };
+
+        fs::remove_file(&path).unwrap();
+
+        let statuses: Vec<_> = (0..3)
+            .map(|_| {
+                let req = SyncHttpRequest {
+                    url: Url::parse("http://localhost:9200/").unwrap(),
+                    method: Method::GET,
+                    headers: Arc::new(HeaderMap::new()),
+                    body: None,
+                };
+
+                mock.send_sync(req).unwrap().status()
+            })
+            .collect();
+
+        assert_eq!(
+            vec![
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::OK,
+                StatusCode::OK
+            ],
+            statuses
+        );
+    }
+}
@@ -0,0 +1,398 @@
+/*!
+Health tracking for a pool of nodes.
+
+A node is marked dead when a request to it fails to connect.
+Dead nodes are skipped when selecting a node for a request until their timeout expires.
+After that, the node is pinged in the background and marked alive again if it responds.
+Each time a node is marked dead in a row its timeout doubles, up to a maximum.
+*/
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    sync::{
+        Arc,
+        RwLock,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use crate::{
+    error::{
+        self,
+        Error,
+    },
+    http::{
+        sender::{
+            build_url,
+            credentials::authorize,
+            retry::exponential_backoff,
+            CredentialsProvider,
+            NodeAddress,
+            RequestParams,
+            RetryableError,
+        },
+        HttpRequest,
+        Method,
+        StatusCode,
+        Url,
+    },
+};
+
+pub(crate) const DEFAULT_DEAD_TIMEOUT: Duration = Duration::from_secs(60);
+pub(crate) const DEFAULT_MAX_DEAD_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/** The most time to wait for a dead node to respond to a ping. */
+pub(crate) const PING_TIMEOUT: Duration = Duration::from_secs(3);
+
+/**
+Build a request to ping a dead node with.
+
+The request only has the headers from the parameters and the credentials from the provider.
+*/
+pub(crate) fn build_ping<TBody>(
+    params: &RequestParams,
+    credentials: Option<&dyn CredentialsProvider>,
+) -> Result<HttpRequest<TBody>, Error> {
+    let mut req = HttpRequest {
+        url: Url::parse(&build_url("/", params)).map_err(error::request)?,
+        method: Method::HEAD,
+        headers: params.get_headers(),
+        body: None,
+    };
+
+    if let Some(credentials) = credentials {
+        authorize(&mut req, credentials)?;
+    }
+
+    Ok(req)
+}
+
+/** Tracks nodes that have failed and when they can be tried again. */
+#[derive(Clone)]
+pub(crate) struct NodeHealth {
+    dead: Arc<RwLock<HashMap<NodeAddress, DeadNode>>>,
+    dead_timeout: Duration,
+    max_dead_timeout: Duration,
+}
+
+struct DeadNode {
+    failures: u32,
+    dead_until: Instant,
+    resurrecting: bool,
+}
+
+impl NodeHealth {
+    pub(crate) fn new(dead_timeout: Duration, max_dead_timeout: Duration) -> Self {
+        NodeHealth {
+            dead: Arc::new(RwLock::new(HashMap::new())),
+            dead_timeout,
+            max_dead_timeout: max_dead_timeout.max(dead_timeout),
+        }
+    }
+
+    /**
+    Mark a node as dead.
+
+    The node won't be selected for requests until its timeout expires.
+    */
+    pub(crate) fn mark_dead(&self, node: &NodeAddress) {
+        let mut dead = self.dead.write().expect("lock poisoned");
+
+        let failures = dead.get(node).map_or(0, |node| node.failures) + 1;
+        let timeout = self.timeout_after(failures);
+
+        warn!(
+            "Elasticsearch Node Health: node: '{}', dead for: '{:?}', failures: '{}'",
+            node.as_ref(),
+            timeout,
+            failures
+        );
+
+        dead.insert(
+            node.clone(),
+            DeadNode {
+                failures,
+                dead_until: Instant::now() + timeout,
+                resurrecting: false,
+            },
+        );
+    }
+
+    /**
+    Mark a node as dead if a request to it failed to connect.
+
+    Returns `true` if the node was marked dead.
+    */
+    pub(crate) fn mark_dead_on_error(
+        &self,
+        node: &NodeAddress,
+        err: Option<RetryableError>,
+    ) -> bool {
+        let is_connection_error = err == Some(RetryableError::Connection);
+
+        if is_connection_error {
+            self.mark_dead(node);
+        }
+
+        is_connection_error
+    }
+
+    /** Mark a node as alive if it was previously dead. */
+    pub(crate) fn mark_alive(&self, node: &str) {
+        // Most nodes are alive most of the time so avoid taking a write lock
+        if !self.dead.read().expect("lock poisoned").contains_key(node) {
+            return;
+        }
+
+        if self.dead.write().expect("lock poisoned").remove(node).is_some() {
+            info!("Elasticsearch Node Health: node: '{}', alive", node);
+        }
+    }
+
+    /**
+    Get the nodes that are alive.
+
+    If every node is dead then all of them are returned so requests can still be attempted.
+    */
+    pub(crate) fn alive<'a>(&self, nodes: &'a [NodeAddress]) -> Cow<'a, [NodeAddress]> {
+        let dead = self.dead.read().expect("lock poisoned");
+
+        if dead.is_empty() {
+            return Cow::Borrowed(nodes);
+        }
+
+        let alive: Vec<_> = nodes
+            .iter()
+            .filter(|node| !dead.contains_key(*node))
+            .cloned()
+            .collect();
+
+        if alive.is_empty() {
+            Cow::Borrowed(nodes)
+        } else {
+            Cow::Owned(alive)
+        }
+    }
+
+    /**
+    Get the dead nodes whose timeout has expired so they can be pinged.
+
+    Each node is only returned once until it's marked dead or alive again.
+    */
+    pub(crate) fn start_resurrect(&self, nodes: &[NodeAddress]) -> Vec<NodeAddress> {
+        let now = Instant::now();
+        let is_resurrectable =
+            |node: &DeadNode| !node.resurrecting && node.dead_until <= now;
+
+        {
+            let dead = self.dead.read().expect("lock poisoned");
+
+            if !dead.values().any(is_resurrectable) {
+                return Vec::new();
+            }
+        }
+
+        let mut dead = self.dead.write().expect("lock poisoned");
+
+        nodes
+            .iter()
+            .filter(|node| match dead.get_mut(*node) {
+                Some(ref mut dead_node) if is_resurrectable(dead_node) => {
+                    dead_node.resurrecting = true;
+                    true
+                }
+                _ => false,
+            })
+            .cloned()
+            .collect()
+    }
+
+    /**
+    Mark a node alive or dead depending on how it responded to a ping.
+
+    A node that responds with anything other than a server error is alive.
+    */
+    pub(crate) fn finish_resurrect(&self, node: &NodeAddress, res: Result<StatusCode, Error>) {
+        match res {
+            Ok(status) if !status.is_server_error() => self.mark_alive(node.as_ref()),
+            Ok(status) => {
+                debug!(
+                    "Elasticsearch Node Health: node: '{}', ping status: '{}'",
+                    node.as_ref(),
+                    status
+                );
+
+                self.mark_dead(node)
+            }
+            Err(e) => {
+                debug!(
+                    "Elasticsearch Node Health: node: '{}', ping error: '{:?}'",
+                    node.as_ref(),
+                    e
+                );
+
+                self.mark_dead(node)
+            }
+        }
+    }
+
+    /**
+    Stop resurrecting a node without pinging it.
+
+    The node is returned by `start_resurrect` again so another ping can be attempted.
+    */
+    pub(crate) fn stop_resurrect(&self, node: &NodeAddress) {
+        if let Some(dead_node) = self.dead.write().expect("lock poisoned").get_mut(node) {
+            dead_node.resurrecting = false;
+        }
+    }
+
+    /** Forget about any nodes that aren't in the given set. */
+    pub(crate) fn retain(&self, nodes: &[NodeAddress]) {
+        self.dead
+            .write()
+            .expect("lock poisoned")
+            .retain(|node, _| nodes.contains(node));
+    }
+
+    fn timeout_after(&self, failures: u32) -> Duration {
+        exponential_backoff(self.dead_timeout, self.max_dead_timeout, failures)
+    }
+}
+
+impl Default for NodeHealth {
+    fn default() -> Self {
+        NodeHealth::new(DEFAULT_DEAD_TIMEOUT, DEFAULT_MAX_DEAD_TIMEOUT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nodes() -> Vec<NodeAddress> {
+        vec!["http://a:9200".into(), "http://b:9200".into()]
+    }
+
+    fn addresses(nodes: &[NodeAddress]) -> Vec<&str> {
+        nodes.iter().map(|node| node.as_ref()).collect()
+    }
+
+    #[test]
+    fn all_nodes_alive_initially() {
+        let health = NodeHealth::default();
+        let nodes = nodes();
+
+        assert_eq!(vec!["http://a:9200", "http://b:9200"], addresses(&health.alive(&nodes)));
+    }
+
+    #[test]
+    fn dead_nodes_are_skipped() {
+        let health = NodeHealth::default();
+        let nodes = nodes();
+
+        health.mark_dead(&nodes[0]);
+
+        assert_eq!(vec!["http://b:9200"], addresses(&health.alive(&nodes)));
+    }
+
+    #[test]
+    fn all_nodes_returned_when_all_dead() {
+        let health = NodeHealth::default();
+        let nodes = nodes();
+
+        health.mark_dead(&nodes[0]);
+        health.mark_dead(&nodes[1]);
+
+        assert_eq!(vec!["http://a:9200", "http://b:9200"], addresses(&health.alive(&nodes)));
+    }
+
+    #[test]
+    fn alive_nodes_are_not_skipped() {
+        let health = NodeHealth::default();
+        let nodes = nodes();
+
+        health.mark_dead(&nodes[0]);
+        health.mark_alive("http://a:9200");
+
+        assert_eq!(vec!["http://a:9200", "http://b:9200"], addresses(&health.alive(&nodes)));
+    }
+
+    #[test]
+    fn resurrect_after_timeout() {
+        let health = NodeHealth::new(Duration::from_millis(0), Duration::from_millis(0));
+        let nodes = nodes();
+
+        health.mark_dead(&nodes[0]);
+
+        assert_eq!(vec!["http://a:9200"], addresses(&health.start_resurrect(&nodes)));
+
+        // The node is only resurrected once
+        assert!(health.start_resurrect(&nodes).is_empty());
+
+        // The node is still dead until it's marked alive
+        assert_eq!(vec!["http://b:9200"], addresses(&health.alive(&nodes)));
+    }
+
+    #[test]
+    fn dont_resurrect_before_timeout() {
+        let health = NodeHealth::default();
+        let nodes = nodes();
+
+        health.mark_dead(&nodes[0]);
+
+        assert!(health.start_resurrect(&nodes).is_empty());
+    }
+
+    #[test]
+    fn timeout_doubles_up_to_max() {
+        let health = NodeHealth::new(Duration::from_secs(1), Duration::from_secs(3));
+
+        assert_eq!(Duration::from_secs(1), health.timeout_after(1));
+        assert_eq!(Duration::from_secs(2), health.timeout_after(2));
+        assert_eq!(Duration::from_secs(3), health.timeout_after(3));
+        assert_eq!(Duration::from_secs(3), health.timeout_after(50));
+    }
+
+    #[test]
+    fn retain_forgets_removed_nodes() {
+        let health = NodeHealth::default();
+        let nodes = nodes();
+
+        health.mark_dead(&nodes[0]);
+        health.retain(&nodes[1..]);
+
+        assert_eq!(vec!["http://a:9200", "http://b:9200"], addresses(&health.alive(&nodes)));
+    }
+
+    #[test]
+    fn finish_resurrect_marks_responding_nodes_alive() {
+        let health = NodeHealth::new(Duration::from_millis(0), Duration::from_millis(0));
+        let nodes = nodes();
+
+        health.mark_dead(&nodes[0]);
+        health.mark_dead(&nodes[1]);
+        health.start_resurrect(&nodes);
+
+        health.finish_resurrect(&nodes[0], Ok(StatusCode::UNAUTHORIZED));
+        health.finish_resurrect(&nodes[1], Ok(StatusCode::SERVICE_UNAVAILABLE));
+
+        assert_eq!(vec!["http://a:9200"], addresses(&health.alive(&nodes)));
+    }
+
+    #[test]
+    fn stop_resurrect_allows_another_ping() {
+        let health = NodeHealth::new(Duration::from_millis(0), Duration::from_millis(0));
+        let nodes = nodes();
+
+        health.mark_dead(&nodes[0]);
+        health.start_resurrect(&nodes);
+        health.stop_resurrect(&nodes[0]);
+
+        assert_eq!(vec!["http://a:9200"], addresses(&health.start_resurrect(&nodes)));
+    }
+}
@@ -0,0 +1,511 @@
+/*!
+Metrics for requests sent by a client.
+*/
+
+use futures::{
+    Async,
+    Poll,
+    Stream,
+};
+use std::{
+    collections::BTreeMap,
+    io::{
+        self,
+        Read,
+    },
+    sync::{
+        Arc,
+        Mutex,
+    },
+    time::Duration,
+};
+
+use crate::http::{
+    sender::NodeAddress,
+    AsyncChunk,
+    Method,
+    StatusCode,
+};
+
+/**
+An observer that's told about each attempt at sending a request.
+
+The observer is called by the client on the thread sending a request, so it should return quickly.
+Every method has a default implementation that does nothing, so observers only need to implement the events they care about.
+
+Each event is given a [`MetricsRequest`][MetricsRequest] with the name of the endpoint, like `search` or `indices.create`, and the node the attempt was sent to.
+Requests that are retried produce a set of events for each attempt.
+Responses returned by middleware aren't sent to a node, so they only produce `request_retried` events.
+
+See [`InMemoryMetrics`][InMemoryMetrics] for an observer that aggregates metrics in memory.
+
+# Examples
+
+Count the requests that fail without a response:
+
+```
+# use std::sync::atomic::{AtomicUsize, Ordering};
+# use std::time::Duration;
+# use elastic::prelude::*;
+# use elastic::http::sender::{MetricsObserver, MetricsRequest};
+#[derive(Default)]
+struct Failures(AtomicUsize);
+
+impl MetricsObserver for Failures {
+    fn request_failed(&self, req: &MetricsRequest, duration: Duration) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+let builder = SyncClientBuilder::new().metrics(Failures::default());
+```
+
+[MetricsRequest]: struct.MetricsRequest.html
+[InMemoryMetrics]: struct.InMemoryMetrics.html
+*/
+pub trait MetricsObserver: Send + Sync {
+    /**
+    Called when a node has been selected and an attempt at a request is about to be sent to it.
+
+    The length of the request body is given in bytes if it's known.
+    Bodies that can only be read once, like files, don't have a known length.
+    */
+    fn request_sent(&self, req: &MetricsRequest, body_len: Option<u64>) {
+        let _ = (req, body_len);
+    }
+
+    /** Called when a response is received, with the time taken to receive its status. */
+    fn response_received(&self, req: &MetricsRequest, status: StatusCode, duration: Duration) {
+        let _ = (req, status, duration);
+    }
+
+    /** Called when an attempt at a request fails without a response. */
+    fn request_failed(&self, req: &MetricsRequest, duration: Duration) {
+        let _ = (req, duration);
+    }
+
+    /**
+    Called when the body of a response has been read, with the number of bytes read.
+
+    If a response is dropped before its body is completely read then the number of bytes read so far is given.
+    */
+    fn response_read(&self, req: &MetricsRequest, body_len: u64) {
+        let _ = (req, body_len);
+    }
+
+    /** Called when an attempt at a request is going to be retried. */
+    fn request_retried(&self, req: &MetricsRequest) {
+        let _ = req;
+    }
+}
+
+/** The attempt at a request that a metrics event is for. */
+#[derive(Debug, Clone)]
+pub struct MetricsRequest {
+    endpoint: &'static str,
+    method: Method,
+    node: NodeAddress,
+    attempt: u32,
+}
+
+impl MetricsRequest {
+    pub(crate) fn new(
+        endpoint: &'static str,
+        method: Method,
+        node: NodeAddress,
+        attempt: u32,
+    ) -> Self {
+        MetricsRequest {
+            endpoint,
+            method,
+            node,
+            attempt,
+        }
+    }
+
+    /** The name of the endpoint the request was sent to, like `search` or `indices.create`. */
+    pub fn endpoint(&self) -> &'static str {
+        self.endpoint
+    }
+
+    /** The HTTP method of the request. */
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /** The node the request was sent to. */
+    pub fn node(&self) -> &NodeAddress {
+        &self.node
+    }
+
+    /** The attempt at sending the request, starting from `1`. */
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+}
+
+/** The upper bounds of the buckets in a latency histogram, in milliseconds. */
+const LATENCY_BUCKETS_MS: [u64; 12] = [1, 2, 5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000];
+
+/**
+A metrics observer that aggregates metrics in memory.
+
+Metrics are kept for each endpoint and node.
+Clones of an `InMemoryMetrics` share the same metrics, so a clone can be given to a client builder while the original is used to take snapshots.
+
+# Examples
+
+Take a snapshot of the metrics for a client:
+
+```no_run
+# use elastic::prelude::*;
+# use elastic::http::sender::InMemoryMetrics;
+# fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+let metrics = InMemoryMetrics::new();
+
+let client = SyncClientBuilder::new()
+    .metrics(metrics.clone())
+    .build()?;
+
+client.request(PingRequest::new()).send()?;
+
+let snapshot = metrics.snapshot();
+let ping = &snapshot.endpoints["ping"];
+
+println!("{} requests, {} bytes received", ping.requests, ping.bytes_received);
+# Ok(())
+# }
+```
+
+Snapshots can be serialized, so they can be served to a metrics scraper as JSON.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryMetrics {
+    inner: Arc<Mutex<MetricsSnapshot>>,
+}
+
+impl InMemoryMetrics {
+    /** Create an empty set of metrics. */
+    pub fn new() -> Self {
+        InMemoryMetrics::default()
+    }
+
+    /** Take a snapshot of the metrics collected so far. */
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        self.inner.lock().expect("lock poisoned").clone()
+    }
+
+    /** Take a snapshot of the metrics collected so far and reset them. */
+    pub fn take_snapshot(&self) -> MetricsSnapshot {
+        let mut inner = self.inner.lock().expect("lock poisoned");
+
+        std::mem::take(&mut *inner)
+    }
+
+    fn endpoint<F>(&self, req: &MetricsRequest, f: F)
+    where
+        F: FnOnce(&mut EndpointMetrics),
+    {
+        let mut inner = self.inner.lock().expect("lock poisoned");
+        let metrics = inner.endpoints.entry(req.endpoint.to_owned()).or_default();
+
+        f(metrics)
+    }
+}
+
+impl MetricsObserver for InMemoryMetrics {
+    fn request_sent(&self, req: &MetricsRequest, body_len: Option<u64>) {
+        {
+            let mut inner = self.inner.lock().expect("lock poisoned");
+            *inner.nodes.entry(req.node.as_ref().to_owned()).or_default() += 1;
+        }
+
+        self.endpoint(req, |metrics| {
+            metrics.requests += 1;
+            metrics.bytes_sent += body_len.unwrap_or(0);
+        })
+    }
+
+    fn response_received(&self, req: &MetricsRequest, status: StatusCode, duration: Duration) {
+        self.endpoint(req, |metrics| {
+            *metrics.statuses.entry(status.as_u16()).or_default() += 1;
+            metrics.latency.observe(duration);
+        })
+    }
+
+    fn request_failed(&self, req: &MetricsRequest, duration: Duration) {
+        self.endpoint(req, |metrics| {
+            metrics.failures += 1;
+            metrics.latency.observe(duration);
+        })
+    }
+
+    fn response_read(&self, req: &MetricsRequest, body_len: u64) {
+        self.endpoint(req, |metrics| metrics.bytes_received += body_len)
+    }
+
+    fn request_retried(&self, req: &MetricsRequest) {
+        self.endpoint(req, |metrics| metrics.retries += 1)
+    }
+}
+
+/** A snapshot of the metrics collected by an `InMemoryMetrics`. */
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MetricsSnapshot {
+    /** Metrics for each endpoint, keyed by the name of the endpoint. */
+    pub endpoints: BTreeMap<String, EndpointMetrics>,
+    /** The number of attempts at requests sent to each node, keyed by the address of the node. */
+    pub nodes: BTreeMap<String, u64>,
+}
+
+/** Metrics for a single endpoint. */
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct EndpointMetrics {
+    /** The number of attempts at requests sent to the endpoint. */
+    pub requests: u64,
+    /** The number of attempts that were retried. */
+    pub retries: u64,
+    /** The number of attempts that failed without a response. */
+    pub failures: u64,
+    /** The number of responses received with each status code. */
+    pub statuses: BTreeMap<u16, u64>,
+    /** The number of bytes sent in request bodies whose length was known. */
+    pub bytes_sent: u64,
+    /** The number of bytes read from response bodies. */
+    pub bytes_received: u64,
+    /** The time taken to receive a response or fail. */
+    pub latency: LatencyHistogram,
+}
+
+/** A histogram of latencies. */
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LatencyHistogram {
+    /**
+    The number of latencies in each bucket along with the bucket's upper bound in milliseconds.
+
+    Each latency is counted in the first bucket whose upper bound it doesn't exceed.
+    The last bucket has no upper bound.
+    */
+    pub buckets: Vec<(Option<u64>, u64)>,
+    /** The number of latencies observed. */
+    pub count: u64,
+    /** The sum of the latencies observed in milliseconds. */
+    pub sum_ms: f64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        let buckets = LATENCY_BUCKETS_MS
+            .iter()
+            .map(|&bound| Some(bound))
+            .chain(Some(None))
+            .map(|bound| (bound, 0))
+            .collect();
+
+        LatencyHistogram {
+            buckets,
+            count: 0,
+            sum_ms: 0.0,
+        }
+    }
+}
+
+impl LatencyHistogram {
+    fn observe(&mut self, duration: Duration) {
+        let ms = duration.as_secs_f64() * 1000.0;
+
+        let bucket = self
+            .buckets
+            .iter_mut()
+            .find(|(bound, _)| match *bound {
+                Some(bound) => ms <= bound as f64,
+                None => true,
+            })
+            .expect("the last bucket has no upper bound");
+
+        bucket.1 += 1;
+        self.count += 1;
+        self.sum_ms += ms;
+    }
+}
+
+/** The metrics for a single attempt at sending a request. */
+#[derive(Clone)]
+pub(crate) struct AttemptMetrics {
+    observer: Arc<dyn MetricsObserver>,
+    req: MetricsRequest,
+    is_sent: bool,
+}
+
+impl AttemptMetrics {
+    /**
+    Get the metrics for an attempt.
+
+    Events for requests that aren't sent to a node, like those responded to by middleware, are only reported when they're retried.
+    */
+    pub(crate) fn new(
+        observer: &Arc<dyn MetricsObserver>,
+        req: MetricsRequest,
+        is_sent: bool,
+    ) -> Self {
+        AttemptMetrics {
+            observer: observer.clone(),
+            req,
+            is_sent,
+        }
+    }
+
+    pub(crate) fn request_sent(&self, body_len: Option<u64>) {
+        if self.is_sent {
+            self.observer.request_sent(&self.req, body_len);
+        }
+    }
+
+    pub(crate) fn response_received(&self, status: StatusCode, duration: Duration) {
+        if self.is_sent {
+            self.observer.response_received(&self.req, status, duration);
+        }
+    }
+
+    pub(crate) fn request_failed(&self, duration: Duration) {
+        if self.is_sent {
+            self.observer.request_failed(&self.req, duration);
+        }
+    }
+
+    pub(crate) fn request_retried(&self) {
+        self.observer.request_retried(&self.req);
+    }
+
+    /** Count the bytes read from a response body. */
+    pub(crate) fn read<T>(&self, body: T) -> ReadMetrics<T> {
+        ReadMetrics::new(body, self.observer.clone(), self.req.clone())
+    }
+}
+
+/** Counts the bytes read from a response body and reports them to an observer. */
+pub(crate) struct ReadMetrics<T> {
+    inner: T,
+    observer: Arc<dyn MetricsObserver>,
+    req: Option<MetricsRequest>,
+    body_len: u64,
+}
+
+impl<T> ReadMetrics<T> {
+    pub(crate) fn new(inner: T, observer: Arc<dyn MetricsObserver>, req: MetricsRequest) -> Self {
+        ReadMetrics {
+            inner,
+            observer,
+            req: Some(req),
+            body_len: 0,
+        }
+    }
+
+    fn finish(&mut self) {
+        if let Some(req) = self.req.take() {
+            self.observer.response_read(&req, self.body_len);
+        }
+    }
+}
+
+impl<T> Drop for ReadMetrics<T> {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+impl<T> Read for ReadMetrics<T>
+where
+    T: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+
+        self.body_len += len as u64;
+        if len == 0 && !buf.is_empty() {
+            self.finish();
+        }
+
+        Ok(len)
+    }
+}
+
+impl<T> Stream for ReadMetrics<T>
+where
+    T: Stream<Item = AsyncChunk>,
+{
+    type Item = AsyncChunk;
+    type Error = T::Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        let chunk = futures::try_ready!(self.inner.poll());
+
+        match chunk {
+            Some(ref chunk) => self.body_len += chunk.len() as u64,
+            None => self.finish(),
+        }
+
+        Ok(Async::Ready(chunk))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn request(endpoint: &'static str) -> MetricsRequest {
+        MetricsRequest::new(endpoint, Method::GET, "http://localhost:9200".into(), 1)
+    }
+
+    #[test]
+    fn aggregate_by_endpoint_and_node() {
+        let metrics = InMemoryMetrics::new();
+
+        metrics.request_sent(&request("search"), Some(10));
+        metrics.response_received(&request("search"), StatusCode::OK, Duration::from_millis(3));
+        metrics.request_retried(&request("search"));
+        metrics.request_sent(&request("search"), None);
+        metrics.request_failed(&request("search"), Duration::from_secs(10));
+        metrics.request_sent(&request("ping"), Some(0));
+
+        let snapshot = metrics.snapshot();
+        let search = &snapshot.endpoints["search"];
+
+        assert_eq!(2, search.requests);
+        assert_eq!(1, search.retries);
+        assert_eq!(1, search.failures);
+        assert_eq!(10, search.bytes_sent);
+        assert_eq!(Some(&1), search.statuses.get(&200));
+        assert_eq!(2, search.latency.count);
+        assert_eq!((Some(5), 1), search.latency.buckets[2]);
+        assert_eq!((None, 1), *search.latency.buckets.last().unwrap());
+        assert_eq!(1, snapshot.endpoints["ping"].requests);
+        assert_eq!(Some(&3), snapshot.nodes.get("http://localhost:9200"));
+    }
+
+    #[test]
+    fn take_snapshot_resets_metrics() {
+        let metrics = InMemoryMetrics::new();
+        metrics.request_sent(&request("ping"), Some(0));
+
+        assert_eq!(1, metrics.take_snapshot().endpoints.len());
+        assert_eq!(MetricsSnapshot::default(), metrics.snapshot());
+    }
+
+    #[test]
+    fn count_bytes_read_once() {
+        let metrics = InMemoryMetrics::new();
+
+        let mut body = String::new();
+        {
+            let observer: Arc<dyn MetricsObserver> = Arc::new(metrics.clone());
+            let mut reader = ReadMetrics::new(Cursor::new("a body"), observer, request("ping"));
+
+            reader.read_to_string(&mut body).unwrap();
+        }
+
+        assert_eq!("a body", body);
+        assert_eq!(6, metrics.snapshot().endpoints["ping"].bytes_received);
+    }
+}
@@ -0,0 +1,504 @@
+/*!
+Middleware that can inspect and change requests before they're sent and responses after they're received.
+*/
+
+use bytes::Bytes;
+use futures::{
+    future::{
+        loop_fn,
+        Either,
+        Loop,
+    },
+    Future,
+    IntoFuture,
+    Stream,
+};
+use std::{
+    error::Error as StdError,
+    io::Read,
+    sync::Arc,
+};
+use tokio_threadpool::ThreadPool;
+
+use crate::{
+    error::{
+        self,
+        Error,
+    },
+    http::{
+        receiver::{
+            async_buffered_response,
+            sync_buffered_response,
+            AsyncResponseBuilder,
+            SyncResponseBuilder,
+        },
+        AsyncBody,
+        AsyncHttpRequest,
+        HttpRequest,
+        StatusCode,
+        SyncBody,
+        SyncHttpRequest,
+    },
+};
+
+/** A future returned by middleware that has to wait before a request can be sent. */
+pub type PendingBeforeSend =
+    Box<dyn Future<Item = Option<BufferedResponse>, Error = Box<dyn StdError + Send + Sync>> + Send>;
+
+/** What to do with a request after a middleware has seen it. */
+pub enum BeforeSend {
+    /** Pass the request on to the next middleware and then send it. */
+    Continue,
+    /** Don't send the request and use the given response instead. */
+    Respond(BufferedResponse),
+    /**
+    Wait for a future to complete before continuing.
+
+    If the future returns a response then the request isn't sent and that response is used instead.
+    Synchronous clients block the calling thread until the future completes.
+    */
+    Pending(PendingBeforeSend),
+}
+
+/**
+A middleware that's called before every request is sent and after every response is received.
+
+Middleware are called in the order they're added to a client builder before a request is sent, and in reverse order after its response is received.
+Any middleware can stop the request from being sent by responding to it instead.
+When a middleware responds to a request, the middleware after it aren't called and the response is given to the middleware before it.
+Responses returned by middleware are subject to the client's retry policy like any other response, but they don't affect node selection.
+
+Requests are given to middleware after any credentials have been added, and the function given to `pre_send_raw` runs as the last middleware.
+Each attempt at sending a request, including requests to sniff or ping nodes, goes through the middleware.
+If a request fails without a response then `after_receive` isn't called.
+
+Responses are streamed to the caller unless a middleware returns `true` from `receives_responses`.
+A middleware that implements `after_receive` must also return `true` from `receives_responses`, otherwise `after_receive` isn't called.
+Responses are then read into memory before they're given to middleware.
+
+The same middleware type can be used with both synchronous and asynchronous clients by implementing `Middleware<TBody>` for any `TBody`.
+
+# Examples
+
+Log the status of every response:
+
+```
+# use std::error::Error;
+# use elastic::prelude::*;
+# use elastic::http::HttpRequest;
+# use elastic::http::sender::{BeforeSend, BufferedResponse, Middleware};
+struct AuditLog;
+
+impl<TBody> Middleware<TBody> for AuditLog {
+    fn after_receive(
+        &self,
+        req: &HttpRequest<()>,
+        res: &mut BufferedResponse,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        println!("{} {}: {}", req.method(), req.url(), res.status());
+
+        Ok(())
+    }
+
+    fn receives_responses(&self) -> bool {
+        true
+    }
+}
+
+let builder = SyncClientBuilder::new().middleware(AuditLog);
+```
+
+Fail requests to a particular index without sending them:
+
+```
+# use std::error::Error;
+# use elastic::prelude::*;
+# use elastic::http::{HttpRequest, StatusCode};
+# use elastic::http::sender::{BeforeSend, BufferedResponse, Middleware};
+struct Unavailable;
+
+impl<TBody> Middleware<TBody> for Unavailable {
+    fn before_send(
+        &self,
+        req: &mut HttpRequest<TBody>,
+    ) -> Result<BeforeSend, Box<dyn Error + Send + Sync>> {
+        if req.url().path().starts_with("/unavailable") {
+            let res = BufferedResponse::new(StatusCode::SERVICE_UNAVAILABLE, "{}");
+
+            Ok(BeforeSend::Respond(res))
+        } else {
+            Ok(BeforeSend::Continue)
+        }
+    }
+}
+
+let builder = AsyncClientBuilder::new().middleware(Unavailable);
+```
+*/
+pub trait Middleware<TBody>: Send + Sync {
+    /**
+    Inspect or change a request before it's sent.
+
+    Returning an error fails the request without sending it.
+    */
+    fn before_send(
+        &self,
+        req: &mut HttpRequest<TBody>,
+    ) -> Result<BeforeSend, Box<dyn StdError + Send + Sync>> {
+        let _ = req;
+
+        Ok(BeforeSend::Continue)
+    }
+
+    /**
+    Inspect or change a response after it's received.
+
+    This is only called when `receives_responses` returns `true`.
+    The request is given without its body.
+    Returning an error fails the request.
+    */
+    fn after_receive(
+        &self,
+        req: &HttpRequest<()>,
+        res: &mut BufferedResponse,
+    ) -> Result<(), Box<dyn StdError + Send + Sync>> {
+        let _ = (req, res);
+
+        Ok(())
+    }
+
+    /**
+    Whether `after_receive` should be called for responses.
+
+    This is `false` by default so responses can be streamed without being read into memory first.
+    Middleware that implement `after_receive` need to return `true`.
+    */
+    fn receives_responses(&self) -> bool {
+        false
+    }
+}
+
+/** A response whose body has been read into memory. */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BufferedResponse {
+    status: StatusCode,
+    body: Bytes,
+}
+
+impl BufferedResponse {
+    /** Create a response with the given status and body. */
+    pub fn new(status: StatusCode, body: impl Into<Bytes>) -> Self {
+        BufferedResponse {
+            status,
+            body: body.into(),
+        }
+    }
+
+    /** Get the HTTP status for the response. */
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /** Get a mutable reference to the HTTP status for the response. */
+    pub fn status_mut(&mut self) -> &mut StatusCode {
+        &mut self.status
+    }
+
+    /** Get the body of the response. */
+    pub fn body(&self) -> &[u8] {
+        self.body.as_ref()
+    }
+
+    /** Set the body of the response. */
+    pub fn set_body(&mut self, body: impl Into<Bytes>) {
+        self.body = body.into();
+    }
+
+    /** Read a synchronous response into memory. */
+    pub(crate) fn from_sync(res: SyncResponseBuilder) -> Result<Self, Error> {
+        let mut res = res.into_raw();
+        let status = res.status();
+
+        let mut body = Vec::new();
+        res.read_to_end(&mut body)
+            .map_err(|e| error::response(status, e))?;
+
+        Ok(BufferedResponse::new(status, body))
+    }
+
+    /** Read an asynchronous response into memory. */
+    pub(crate) fn from_async(
+        res: AsyncResponseBuilder,
+    ) -> impl Future<Item = Self, Error = Error> {
+        let res = res.into_raw();
+        let status = res.status();
+
+        res.concat2()
+            .map(move |body| BufferedResponse::new(status, body.as_ref()))
+    }
+
+    pub(crate) fn into_sync(self) -> SyncResponseBuilder {
+        sync_buffered_response(self.status, self.body)
+    }
+
+    pub(crate) fn into_async(self, de_pool: Option<Arc<ThreadPool>>) -> AsyncResponseBuilder {
+        async_buffered_response(self.status, self.body, de_pool)
+    }
+}
+
+/** A chain of middleware for a sender. */
+pub(crate) type MiddlewareChain<TBody> = Arc<[Box<dyn Middleware<TBody>>]>;
+
+/**
+The result of calling `before_send` on a chain of middleware.
+
+`called` is the number of middleware that were called before the request was sent or responded to.
+*/
+pub(crate) struct BeforeSendResult<TBody> {
+    pub(crate) req: HttpRequest<TBody>,
+    pub(crate) called: usize,
+    pub(crate) response: Option<BufferedResponse>,
+}
+
+/** Call `before_send` on a chain of middleware for a synchronous request. */
+pub(crate) fn before_send_sync(
+    chain: &[Box<dyn Middleware<SyncBody>>],
+    mut req: SyncHttpRequest,
+) -> Result<BeforeSendResult<SyncBody>, Error> {
+    for (idx, middleware) in chain.iter().enumerate() {
+        let response = match middleware.before_send(&mut req).map_err(middleware_error)? {
+            BeforeSend::Continue => None,
+            BeforeSend::Respond(res) => Some(res),
+            BeforeSend::Pending(pending) => pending.wait().map_err(middleware_error)?,
+        };
+
+        if response.is_some() {
+            return Ok(BeforeSendResult {
+                req,
+                called: idx,
+                response,
+            });
+        }
+    }
+
+    Ok(BeforeSendResult {
+        req,
+        called: chain.len(),
+        response: None,
+    })
+}
+
+/** Call `before_send` on a chain of middleware for an asynchronous request. */
+pub(crate) fn before_send_async(
+    chain: MiddlewareChain<AsyncBody>,
+    req: AsyncHttpRequest,
+) -> impl Future<Item = BeforeSendResult<AsyncBody>, Error = Error> {
+    let start = BeforeSendResult {
+        req,
+        called: 0,
+        response: None,
+    };
+
+    loop_fn(start, move |mut state| {
+        let middleware = match chain.get(state.called) {
+            Some(middleware) => middleware,
+            None => return Either::A(Ok(Loop::Break(state)).into_future()),
+        };
+
+        let next = move |mut state: BeforeSendResult<AsyncBody>, response| match response {
+            Some(response) => {
+                state.response = Some(response);
+                Loop::Break(state)
+            }
+            None => {
+                state.called += 1;
+                Loop::Continue(state)
+            }
+        };
+
+        match middleware.before_send(&mut state.req) {
+            Ok(BeforeSend::Continue) => Either::A(Ok(next(state, None)).into_future()),
+            Ok(BeforeSend::Respond(res)) => Either::A(Ok(next(state, Some(res))).into_future()),
+            Ok(BeforeSend::Pending(pending)) => Either::B(
+                pending
+                    .map_err(middleware_error)
+                    .map(move |response| next(state, response)),
+            ),
+            Err(e) => Either::A(Err(middleware_error(e)).into_future()),
+        }
+    })
+}
+
+/** Whether any of the first `called` middleware in a chain receive responses. */
+pub(crate) fn receives_responses<TBody>(
+    chain: &[Box<dyn Middleware<TBody>>],
+    called: usize,
+) -> bool {
+    chain[..called]
+        .iter()
+        .any(|middleware| middleware.receives_responses())
+}
+
+/** Call `after_receive` on the first `called` middleware in a chain in reverse order. */
+pub(crate) fn after_receive<TBody>(
+    chain: &[Box<dyn Middleware<TBody>>],
+    called: usize,
+    req: &HttpRequest<()>,
+    res: &mut BufferedResponse,
+) -> Result<(), Error> {
+    for middleware in chain[..called].iter().rev() {
+        if middleware.receives_responses() {
+            middleware.after_receive(req, res).map_err(middleware_error)?;
+        }
+    }
+
+    Ok(())
+}
+
+/** Get a copy of a request without its body. */
+pub(crate) fn request_head<TBody>(req: &HttpRequest<TBody>) -> HttpRequest<()> {
+    HttpRequest {
+        url: req.url.clone(),
+        method: req.method.clone(),
+        headers: req.headers.clone(),
+        body: req.body.as_ref().map(|_| ()),
+    }
+}
+
+fn middleware_error(err: Box<dyn StdError + Send + Sync>) -> Error {
+    error::request(error::wrapped(err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{
+        header::HeaderMap,
+        Method,
+        Url,
+    };
+    use futures::future;
+    use std::sync::Mutex;
+
+    struct Record {
+        name: &'static str,
+        calls: Arc<Mutex<Vec<String>>>,
+        respond: bool,
+    }
+
+    impl<TBody> Middleware<TBody> for Record {
+        fn before_send(
+            &self,
+            _: &mut HttpRequest<TBody>,
+        ) -> Result<BeforeSend, Box<dyn StdError + Send + Sync>> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("before {}", self.name));
+
+            if self.respond {
+                let res = BufferedResponse::new(StatusCode::OK, self.name);
+
+                Ok(BeforeSend::Pending(Box::new(future::ok(Some(res)))))
+            } else {
+                Ok(BeforeSend::Continue)
+            }
+        }
+
+        fn after_receive(
+            &self,
+            _: &HttpRequest<()>,
+            _: &mut BufferedResponse,
+        ) -> Result<(), Box<dyn StdError + Send + Sync>> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("after {}", self.name));
+
+            Ok(())
+        }
+
+        fn receives_responses(&self) -> bool {
+            true
+        }
+    }
+
+    fn chain<TBody>(calls: &Arc<Mutex<Vec<String>>>) -> Vec<Box<dyn Middleware<TBody>>> {
+        ["a", "b", "c"]
+            .iter()
+            .map(|&name| {
+                Box::new(Record {
+                    name,
+                    calls: calls.clone(),
+                    respond: name == "b",
+                }) as Box<dyn Middleware<TBody>>
+            })
+            .collect()
+    }
+
+    fn request<TBody>() -> HttpRequest<TBody> {
+        HttpRequest {
+            url: Url::parse("http://localhost:9200/").unwrap(),
+            method: Method::GET,
+            headers: Arc::new(HeaderMap::new()),
+            body: None,
+        }
+    }
+
+    #[test]
+    fn sync_respond_skips_later_middleware() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let chain = chain::<SyncBody>(&calls);
+
+        let mut result = before_send_sync(&chain, request()).unwrap();
+        let mut res = result.response.take().unwrap();
+
+        after_receive(&chain, result.called, &request_head(&result.req), &mut res).unwrap();
+
+        assert_eq!(b"b", res.body());
+        assert_eq!(
+            vec!["before a", "before b", "after a"],
+            *calls.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn async_respond_skips_later_middleware() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let chain: MiddlewareChain<AsyncBody> = chain::<AsyncBody>(&calls).into();
+
+        let result = before_send_async(chain, request()).wait().unwrap();
+
+        assert_eq!(1, result.called);
+        assert_eq!(
+            Some(BufferedResponse::new(StatusCode::OK, "b")),
+            result.response
+        );
+        assert_eq!(vec!["before a", "before b"], *calls.lock().unwrap());
+    }
+
+    #[test]
+    fn before_only_middleware_doesnt_receive_responses() {
+        struct BeforeOnly;
+
+        impl<TBody> Middleware<TBody> for BeforeOnly {}
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let mut chain: Vec<Box<dyn Middleware<SyncBody>>> = vec![Box::new(BeforeOnly)];
+        chain.extend(self::chain(&calls));
+
+        assert!(!receives_responses(&chain, 0));
+        assert!(!receives_responses(&chain, 1));
+        assert!(receives_responses(&chain, 2));
+    }
+
+    #[test]
+    fn request_head_without_body() {
+        let mut req: SyncHttpRequest = request();
+        req.body = Some(SyncBody::from("{}"));
+
+        assert_eq!(Some(()), request_head(&req).body);
+        assert_eq!(None, request_head(&request::<SyncBody>()).body);
+    }
+}
@@ -0,0 +1,539 @@
+/*!
+An in-memory transport for testing code that uses a client without a running cluster.
+*/
+
+use bytes::Bytes;
+use flate2::read::GzDecoder;
+use serde_json::Value;
+use std::{
+    fmt,
+    io::{
+        self,
+        Read,
+    },
+    sync::{
+        Arc,
+        Mutex,
+    },
+};
+use tokio_threadpool::ThreadPool;
+
+use crate::{
+    error::{
+        self,
+        Error,
+    },
+    http::{
+        receiver::{
+            async_buffered_response,
+            sync_buffered_response,
+            AsyncResponseBuilder,
+            SyncResponseBuilder,
+        },
+        header::CONTENT_ENCODING,
+        sender::fixtures::Normalize,
+        AsyncHttpRequest,
+        HttpRequest,
+        Method,
+        StatusCode,
+        SyncBody,
+        SyncHttpRequest,
+        Url,
+    },
+};
+
+/** A request received by a `MockTransport`. */
+pub type MockHttpRequest = HttpRequest<MockBody>;
+
+type Respond = dyn Fn(&MockHttpRequest) -> MockResponse + Send + Sync;
+
+/**
+An in-memory transport that responds to requests without sending them over the network.
+
+A mock transport is given to a client builder in place of the nodes it would otherwise send requests to.
+Every request sent by the client is matched against the requests the mock expects, in the order they were added, and the response from the first match is returned.
+Requests that don't match any expected request fail with an error.
+
+The client still sends requests through its usual retry policy, credentials, and pre-send functions, so those can be tested too.
+Every request the mock receives is recorded, even if it doesn't match any expected request, and can be inspected using the `requests` method.
+
+# Examples
+
+Respond to a search request with a canned response:
+
+```
+# #[macro_use] extern crate serde_json;
+# use serde_json::Value;
+# use elastic::prelude::*;
+# use elastic::http::{Method, StatusCode};
+# use elastic::http::sender::{MockRequest, MockResponse, MockTransport};
+# fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+let mock = MockTransport::new().respond(
+    MockRequest::new(Method::POST, "/myindex/_search"),
+    MockResponse::new(StatusCode::OK).body(json!({
+        "took": 1,
+        "timed_out": false,
+        "_shards": { "total": 1, "successful": 1, "failed": 0 },
+        "hits": {
+            "total": { "value": 0, "relation": "eq" },
+            "max_score": null,
+            "hits": []
+        }
+    })),
+);
+
+let client = SyncClientBuilder::new().mock(mock.clone()).build()?;
+
+let response = client
+    .search::<Value>()
+    .index("myindex")
+    .send()?;
+
+assert_eq!(0, response.hits().count());
+assert_eq!("/myindex/_search", mock.requests()[0].url().path());
+# Ok(())
+# }
+```
+*/
+#[derive(Clone, Default)]
+pub struct MockTransport {
+    expected: Vec<Arc<MockExpected>>,
+    requests: Arc<Mutex<Vec<MockHttpRequest>>>,
+}
+
+struct MockExpected {
+    request: MockRequest,
+    respond: Box<Respond>,
+}
+
+impl MockTransport {
+    /** Create a mock transport that doesn't expect any requests. */
+    pub fn new() -> Self {
+        MockTransport::default()
+    }
+
+    /** Respond to a request with a canned response. */
+    pub fn respond(self, request: MockRequest, response: MockResponse) -> Self {
+        self.respond_with(request, move |_| response.clone())
+    }
+
+    /**
+    Respond to a request with a response produced by a closure.
+
+    The closure is given the request the mock received.
+    */
+    pub fn respond_with(
+        mut self,
+        request: MockRequest,
+        respond: impl Fn(&MockHttpRequest) -> MockResponse + Send + Sync + 'static,
+    ) -> Self {
+        self.expected.push(Arc::new(MockExpected {
+            request,
+            respond: Box::new(respond),
+        }));
+
+        self
+    }
+
+    /** Get all of the requests received by this transport in the order they were received. */
+    pub fn requests(&self) -> Vec<MockHttpRequest> {
+        self.requests.lock().expect("lock poisoned").clone()
+    }
+
+    /** Respond to a synchronous request. */
+    pub(crate) fn send_sync(&self, req: SyncHttpRequest) -> Result<SyncResponseBuilder, Error> {
+        let req = mock_request(req, |mut body| read_to_end(body.reader()))?;
+        let res = self.respond_to(req)?;
+
+        Ok(sync_buffered_response(res.status, res.body.0))
+    }
+
+    /** Respond to an asynchronous request. */
+    pub(crate) fn send_async(
+        &self,
+        req: AsyncHttpRequest,
+        de_pool: Option<Arc<ThreadPool>>,
+    ) -> Result<AsyncResponseBuilder, Error> {
+        let req = mock_request(req, |mut body| read_to_end(body.reader()))?;
+        let res = self.respond_to(req)?;
+
+        Ok(async_buffered_response(res.status, res.body.0, de_pool))
+    }
+
+    fn respond_to(&self, req: MockHttpRequest) -> Result<MockResponse, Error> {
+        self.requests
+            .lock()
+            .expect("lock poisoned")
+            .push(req.clone());
+
+        let expected = self
+            .expected
+            .iter()
+            .find(|expected| expected.request.matches(&req))
+            .ok_or_else(|| {
+                error::request(error::message(format!(
+                    "the mock transport doesn't expect a request for '{} {}'",
+                    req.method,
+                    req.url.path()
+                )))
+            })?;
+
+        Ok((expected.respond)(&req))
+    }
+}
+
+impl fmt::Debug for MockTransport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let expected: Vec<_> = self
+            .expected
+            .iter()
+            .map(|expected| &expected.request)
+            .collect();
+
+        f.debug_struct("MockTransport")
+            .field("expected", &expected)
+            .finish()
+    }
+}
+
+/**
+A request expected by a `MockTransport`.
+
+A request matches if it has the same method and path.
+The query string of the request is ignored unless the expected path includes one.
+If a body is given then the request must also have the same body.
+Bodies that are JSON are compared as JSON values, so differences in whitespace or the order of fields don't matter.
+*/
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    method: Method,
+    path: String,
+    body: Option<MockBody>,
+    normalize: Option<Arc<Normalize>>,
+}
+
+impl MockRequest {
+    /** Expect a request with the given method and path. */
+    pub fn new(method: Method, path: impl Into<String>) -> Self {
+        MockRequest {
+            method,
+            path: path.into(),
+            body: None,
+            normalize: None,
+        }
+    }
+
+    /** Expect the request to have the given body. */
+    pub fn body(mut self, body: impl Into<MockBody>) -> Self {
+        self.body = Some(body.into());
+
+        self
+    }
+
+    /** Normalize the body of requests before comparing it to the expected body. */
+    pub(crate) fn normalize(mut self, normalize: Arc<Normalize>) -> Self {
+        self.normalize = Some(normalize);
+
+        self
+    }
+
+    fn matches(&self, req: &MockHttpRequest) -> bool {
+        let path_matches = if self.path.contains('?') {
+            self.path == path_and_query(&req.url)
+        } else {
+            self.path == req.url.path()
+        };
+
+        if self.method != req.method || !path_matches {
+            return false;
+        }
+
+        match (&self.body, &req.body) {
+            (None, _) => true,
+            (Some(expected), Some(body)) => expected.matches(body, self.normalize.as_deref()),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/** A response returned by a `MockTransport`. */
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    status: StatusCode,
+    body: MockBody,
+}
+
+impl MockResponse {
+    /** Create a response with the given status and an empty JSON object for a body. */
+    pub fn new(status: StatusCode) -> Self {
+        MockResponse {
+            status,
+            body: MockBody::from("{}"),
+        }
+    }
+
+    /** Set the body of the response. */
+    pub fn body(mut self, body: impl Into<MockBody>) -> Self {
+        self.body = body.into();
+
+        self
+    }
+}
+
+/** The body of a request or response for a `MockTransport`. */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockBody(Bytes);
+
+impl MockBody {
+    fn matches(&self, other: &MockBody, normalize: Option<&Normalize>) -> bool {
+        if self == other {
+            return true;
+        }
+
+        match (
+            serde_json::from_slice::<Value>(self.as_ref()),
+            serde_json::from_slice::<Value>(other.as_ref()),
+        ) {
+            (Ok(expected), Ok(mut actual)) => {
+                if let Some(normalize) = normalize {
+                    normalize.apply(&mut actual);
+                }
+
+                expected == actual
+            }
+            _ => false,
+        }
+    }
+}
+
+impl AsRef<[u8]> for MockBody {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl From<Bytes> for MockBody {
+    fn from(body: Bytes) -> MockBody {
+        MockBody(body)
+    }
+}
+
+impl From<Vec<u8>> for MockBody {
+    fn from(body: Vec<u8>) -> MockBody {
+        MockBody(body.into())
+    }
+}
+
+impl From<String> for MockBody {
+    fn from(body: String) -> MockBody {
+        MockBody(body.into())
+    }
+}
+
+impl From<Value> for MockBody {
+    fn from(body: Value) -> MockBody {
+        MockBody(body.to_string().into())
+    }
+}
+
+impl From<&'static [u8]> for MockBody {
+    fn from(body: &'static [u8]) -> MockBody {
+        MockBody(Bytes::from_static(body))
+    }
+}
+
+impl From<&'static str> for MockBody {
+    fn from(body: &'static str) -> MockBody {
+        MockBody(Bytes::from_static(body.as_bytes()))
+    }
+}
+
+/** Get the path of a url along with its query string, if it has one. */
+pub(crate) fn path_and_query(url: &Url) -> String {
+    match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_owned(),
+    }
+}
+
+/**
+Copy a synchronous request so it can be recorded.
+
+Bodies that can only be read once aren't copied.
+*/
+pub(crate) fn copy_sync_request(req: &SyncHttpRequest) -> Result<MockHttpRequest, Error> {
+    let req = HttpRequest {
+        url: req.url.clone(),
+        method: req.method.clone(),
+        headers: req.headers.clone(),
+        body: req.body.as_ref().and_then(SyncBody::try_clone),
+    };
+
+    mock_request(req, |mut body| read_to_end(body.reader()))
+}
+
+/** Copy an asynchronous request so it can be recorded. */
+pub(crate) fn copy_async_request(req: &AsyncHttpRequest) -> Result<MockHttpRequest, Error> {
+    mock_request(req.clone(), |mut body| read_to_end(body.reader()))
+}
+
+/** Read the body of a request into memory, decompressing it if it was compressed with gzip. */
+fn mock_request<TBody>(
+    req: HttpRequest<TBody>,
+    read: impl FnOnce(TBody) -> Result<Vec<u8>, io::Error>,
+) -> Result<MockHttpRequest, Error> {
+    let HttpRequest {
+        url,
+        method,
+        headers,
+        body,
+    } = req;
+
+    let is_gzip = headers
+        .get(CONTENT_ENCODING)
+        .and_then(|encoding| encoding.to_str().ok())
+        == Some("gzip");
+
+    let body = match body {
+        Some(body) => {
+            let mut body = read(body).map_err(error::request)?;
+
+            if is_gzip {
+                body = read_to_end(GzDecoder::new(&body[..])).map_err(error::request)?;
+            }
+
+            Some(body.into())
+        }
+        None => None,
+    };
+
+    Ok(HttpRequest {
+        url,
+        method,
+        headers,
+        body,
+    })
+}
+
+fn read_to_end(mut reader: impl Read) -> Result<Vec<u8>, io::Error> {
+    let mut body = Vec::new();
+    reader.read_to_end(&mut body)?;
+
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{
+        header::HeaderMap,
+        Url,
+    };
+
+    fn request(method: Method, path: &str, body: Option<&'static str>) -> MockHttpRequest {
+        HttpRequest {
+            url: Url::parse(&format!("http://localhost:9200{}", path)).unwrap(),
+            method,
+            headers: Arc::new(HeaderMap::new()),
+            body: body.map(MockBody::from),
+        }
+    }
+
+    #[test]
+    fn match_method_and_path() {
+        let expected = MockRequest::new(Method::GET, "/myindex/_doc/1");
+
+        assert!(expected.matches(&request(Method::GET, "/myindex/_doc/1?refresh=true", None)));
+        assert!(!expected.matches(&request(Method::PUT, "/myindex/_doc/1", None)));
+        assert!(!expected.matches(&request(Method::GET, "/myindex/_doc/2", None)));
+    }
+
+    #[test]
+    fn match_query() {
+        let expected = MockRequest::new(Method::GET, "/myindex/_doc/1?refresh=true");
+
+        assert!(expected.matches(&request(Method::GET, "/myindex/_doc/1?refresh=true", None)));
+        assert!(!expected.matches(&request(Method::GET, "/myindex/_doc/1?refresh=false", None)));
+        assert!(!expected.matches(&request(Method::GET, "/myindex/_doc/1", None)));
+    }
+
+    #[test]
+    fn match_json_body() {
+        let expected = MockRequest::new(Method::POST, "/_search").body(json!({
+            "query": { "match_all": {} },
+            "size": 1
+        }));
+
+        let body = r#"{"size":1,"query":{"match_all":{}}}"#;
+        assert!(expected.matches(&request(Method::POST, "/_search", Some(body))));
+
+        let body = r#"{"size":2,"query":{"match_all":{}}}"#;
+        assert!(!expected.matches(&request(Method::POST, "/_search", Some(body))));
+
+        assert!(!expected.matches(&request(Method::POST, "/_search", None)));
+    }
+
+    #[test]
+    fn match_raw_body() {
+        let expected = MockRequest::new(Method::POST, "/_bulk").body("not json\n");
+
+        assert!(expected.matches(&request(Method::POST, "/_bulk", Some("not json\n"))));
+        assert!(!expected.matches(&request(Method::POST, "/_bulk", Some("not json"))));
+    }
+
+    #[test]
+    fn read_gzip_body() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_ENCODING, "gzip".parse().unwrap());
+
+        let req = HttpRequest {
+            url: Url::parse("http://localhost:9200/_search").unwrap(),
+            method: Method::POST,
+            headers: Arc::new(headers),
+            body: Some(SyncBody::from("{\"size\":1}").gzip().unwrap()),
+        };
+
+        let req = mock_request(req, |mut body| read_to_end(body.reader())).unwrap();
+
+        assert_eq!(Some(&MockBody::from("{\"size\":1}")), req.body());
+    }
+
+    #[test]
+    fn respond_with_first_match() {
+        let mock = MockTransport::new()
+            .respond(
+                MockRequest::new(Method::GET, "/"),
+                MockResponse::new(StatusCode::OK),
+            )
+            .respond(
+                MockRequest::new(Method::GET, "/"),
+                MockResponse::new(StatusCode::NOT_FOUND),
+            );
+
+        let res = mock.respond_to(request(Method::GET, "/", None)).unwrap();
+
+        assert_eq!(StatusCode::OK, res.status);
+    }
+
+    #[test]
+    fn respond_with_closure() {
+        let mock = MockTransport::new().respond_with(MockRequest::new(Method::PUT, "/doc"), |req| {
+            MockResponse::new(StatusCode::CREATED).body(req.body().cloned().unwrap())
+        });
+
+        let res = mock
+            .respond_to(request(Method::PUT, "/doc", Some("{\"id\":1}")))
+            .unwrap();
+
+        assert_eq!(StatusCode::CREATED, res.status);
+        assert_eq!(MockBody::from("{\"id\":1}"), res.body);
+    }
+
+    #[test]
+    fn record_unmatched_requests() {
+        let mock = MockTransport::new();
+
+        assert!(mock.respond_to(request(Method::GET, "/", None)).is_err());
+        assert_eq!(1, mock.requests().len());
+    }
+}
@@ -8,40 +8,91 @@ Some notable types include:
 - `Sender`: a generic trait that can send a http request and return a response
 - `NextParams`: a generic trait that can fetch a set of parameters to associate with a request
 - `SyncSender`: a synchronous http client
-- `AsyncSender`: an asynchronous http client.
-
+- `AsyncSender`: an asynchronous http client
+- `RetryPolicy`: a policy for retrying requests that fail with a transient error.
+- `CredentialsProvider`: a source of credentials that's asked for them before each request.
+- `MockTransport`: an in-memory transport for testing code that uses a client without a running cluster.
+- `Fixtures`: a file of requests and responses that are recorded from a cluster and replayed without one.
+- `Middleware`: a hook that can inspect, change or answer requests before they're sent, and inspect or change their responses.
+- `MetricsObserver`: a receiver for per-endpoint and per-node request metrics, like the in-memory `InMemoryMetrics`.
+
+Requests are traced with the [`tracing`][tracing] crate.
+Each request has an `elasticsearch.request` span with its `correlation_id`, `endpoint`, `method`, `status` and `duration_ms`.
+Each attempt at sending it has a child `elasticsearch.attempt` span with the `attempt` number, the `node` it was sent to, and its `status` and `duration_ms`.
+The correlation id is also sent in the `X-Opaque-Id` header, so requests can be found in Elasticsearch's slow logs and tasks.
+An `X-Opaque-Id` header set in the request parameters is sent instead.
+
+[tracing]: https://docs.rs/tracing
 [Client]: ../struct.Client.html
 */
 
 use fluent_builder::{
     SharedFluentBuilder,
     SharedStatefulFluentBuilder,
+    Stack,
 };
 
 pub mod sniffed_nodes;
 pub mod static_nodes;
 
 mod asynchronous;
+mod attempt;
+mod cloud_id;
+mod credentials;
+mod fixtures;
+mod health;
+mod metrics;
+mod middleware;
+mod mock;
 mod params;
+mod retry;
 mod synchronous;
+mod timeouts;
 pub use self::{
     asynchronous::*,
+    credentials::*,
+    fixtures::Fixtures,
+    metrics::*,
+    middleware::*,
+    mock::*,
     params::*,
+    retry::*,
     synchronous::*,
 };
 
+pub(crate) use self::{
+    fixtures::{
+        FixtureRecorder,
+        FixtureTransport,
+    },
+    health::NodeHealth,
+    timeouts::{
+        ClientTimeouts,
+        HttpClients,
+    },
+};
+
 use std::{
+    borrow::Borrow,
     marker::PhantomData,
     sync::Arc,
+    time::{
+        Duration,
+        Instant,
+    },
 };
 use uuid::Uuid;
 
 use self::{
     sniffed_nodes::{
+        SniffTrigger,
         SniffedNodes,
         SniffedNodesBuilder,
     },
-    static_nodes::StaticNodes,
+    static_nodes::{
+        StaticNodes,
+        Strategy,
+    },
 };
 use crate::{
     endpoints::Endpoint,
@@ -78,7 +129,9 @@ pub(crate) enum SendableRequestParams<TParams> {
     Value(RequestParams),
     Builder {
         params: TParams,
-        builder: SharedFluentBuilder<RequestParams>,
+        next: Box<dyn Fn() -> TParams + Send>,
+        feedback: NodeFeedback,
+        builder: SharedFluentBuilder<RequestParams, Stack>,
     },
 }
 
@@ -137,7 +190,7 @@ pub trait NextParams: private::Sealed + Clone {
 /**
 A single node address.
 */
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct NodeAddress(Arc<str>);
 
 impl AsRef<str> for NodeAddress {
@@ -146,6 +199,12 @@ impl AsRef<str> for NodeAddress {
     }
 }
 
+impl Borrow<str> for NodeAddress {
+    fn borrow(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
 impl<T> From<T> for NodeAddress
 where
     T: Into<Arc<str>>,
@@ -161,27 +220,164 @@ A common container for a source of node addresses.
 #[derive(Clone)]
 pub struct NodeAddresses<TSender> {
     inner: NodeAddressesInner<TSender>,
+    sender: TSender,
+    params: PreRequestParams,
 }
 
 impl<TSender> NodeAddresses<TSender> {
-    pub(crate) fn static_nodes(nodes: StaticNodes) -> Self {
+    pub(crate) fn static_nodes(
+        nodes: StaticNodes<Arc<dyn Strategy>>,
+        params: PreRequestParams,
+        sender: TSender,
+    ) -> Self {
         NodeAddresses {
             inner: NodeAddressesInner::Static(nodes),
+            sender,
+            params,
         }
     }
 
-    pub(crate) fn sniffed_nodes(nodes: SniffedNodes<TSender>) -> Self {
+    pub(crate) fn sniffed_nodes(
+        nodes: SniffedNodes<TSender>,
+        params: PreRequestParams,
+        sender: TSender,
+    ) -> Self {
         NodeAddresses {
             inner: NodeAddressesInner::Sniffed(nodes),
+            sender,
+            params,
         }
     }
+
+    /** Get the health of the nodes. */
+    fn health(&self) -> NodeHealth {
+        self.feedback().health
+    }
+
+    /** Get the feedback for the nodes so it can be updated when requests are sent. */
+    pub(crate) fn feedback(&self) -> NodeFeedback {
+        match self.inner {
+            NodeAddressesInner::Static(ref nodes) => {
+                NodeFeedback::new(nodes.health().clone(), nodes.strategy().clone())
+            }
+            NodeAddressesInner::Sniffed(ref sniffer) => sniffer.feedback().clone(),
+        }
+    }
+
+    /** Get the parameters to ping each dead node that's ready to be tried again with. */
+    fn resurrect_params(&self) -> Vec<(NodeAddress, RequestParams)> {
+        let nodes = match self.inner {
+            NodeAddressesInner::Static(ref nodes) => nodes.start_resurrect(),
+            NodeAddressesInner::Sniffed(ref sniffer) => sniffer.start_resurrect(),
+        };
+
+        nodes
+            .into_iter()
+            .map(|node| {
+                let params = RequestParams::from_parts(node.clone(), self.params.clone());
+
+                (node, params)
+            })
+            .collect()
+    }
 }
 
 impl<TSender> private::Sealed for NodeAddresses<TSender> {}
 
+/**
+Feedback about requests sent to a node.
+
+Requests that get a response mark the node alive and requests that fail to connect mark it dead.
+The strategy for selecting nodes is also told about each request.
+*/
+#[derive(Clone)]
+pub(crate) struct NodeFeedback {
+    health: NodeHealth,
+    strategy: Arc<dyn Strategy>,
+    sniff: Option<SniffTrigger>,
+}
+
+impl NodeFeedback {
+    pub(crate) fn new(health: NodeHealth, strategy: Arc<dyn Strategy>) -> Self {
+        NodeFeedback {
+            health,
+            strategy,
+            sniff: None,
+        }
+    }
+
+    /** Sniff the nodes again when a request to one of them fails to connect. */
+    pub(crate) fn with_sniff(mut self, sniff: SniffTrigger) -> Self {
+        self.sniff = Some(sniff);
+        self
+    }
+
+    /**
+    A request is about to be sent to the node.
+
+    The returned request should be told when the request finishes or fails.
+    If it's dropped first then the strategy is told the request was cancelled.
+    */
+    pub(crate) fn request_started(&self, node: &NodeAddress) -> InFlightRequest {
+        self.strategy.request_started(node);
+
+        InFlightRequest {
+            feedback: Some(self.clone()),
+            node: node.clone(),
+            start: Instant::now(),
+        }
+    }
+
+    fn request_finished(&self, node: &NodeAddress, elapsed: Duration) {
+        self.health.mark_alive(node.as_ref());
+        self.strategy.request_finished(node, elapsed);
+    }
+
+    fn request_failed(&self, node: &NodeAddress, elapsed: Duration, err: Option<RetryableError>) {
+        self.strategy.request_failed(node, elapsed);
+
+        if self.health.mark_dead_on_error(node, err) {
+            if let Some(ref sniff) = self.sniff {
+                sniff.sniff_now();
+            }
+        }
+    }
+}
+
+/** A request to a node that hasn't finished yet. */
+pub(crate) struct InFlightRequest {
+    feedback: Option<NodeFeedback>,
+    node: NodeAddress,
+    start: Instant,
+}
+
+impl InFlightRequest {
+    /** A response was received from the node. */
+    pub(crate) fn finished(mut self) {
+        if let Some(feedback) = self.feedback.take() {
+            feedback.request_finished(&self.node, self.start.elapsed());
+        }
+    }
+
+    /** The request to the node failed without a response. */
+    pub(crate) fn failed(mut self, err: Option<RetryableError>) {
+        if let Some(feedback) = self.feedback.take() {
+            feedback.request_failed(&self.node, self.start.elapsed(), err);
+        }
+    }
+}
+
+impl Drop for InFlightRequest {
+    fn drop(&mut self) {
+        if let Some(feedback) = self.feedback.take() {
+            feedback.strategy.request_cancelled(&self.node);
+        }
+    }
+}
+
 #[derive(Clone)]
 enum NodeAddressesInner<TSender> {
-    Static(StaticNodes),
+    Static(StaticNodes<Arc<dyn Strategy>>),
     Sniffed(SniffedNodes<TSender>),
 }
 
@@ -226,20 +422,28 @@ impl NodeAddressesBuilder {
     pub(crate) fn build<TSender>(
         self,
         params: PreRequestParams,
+        health: NodeHealth,
+        strategy: Arc<dyn Strategy>,
         sender: TSender,
-    ) -> NodeAddresses<TSender> {
+    ) -> NodeAddresses<TSender>
+    where
+        TSender: Clone,
+    {
         match self {
             NodeAddressesBuilder::Static(nodes) => {
-                let nodes = StaticNodes::round_robin(nodes, params);
+                let nodes = StaticNodes::new(nodes, strategy, params.clone()).with_health(health);
 
-                NodeAddresses::static_nodes(nodes)
+                NodeAddresses::static_nodes(nodes, params, sender)
             }
             NodeAddressesBuilder::Sniffed(builder) => {
-                let nodes = builder
-                    .into_value(SniffedNodesBuilder::new)
-                    .build(params, sender);
-
-                NodeAddresses::sniffed_nodes(nodes)
+                let nodes = builder.into_value(SniffedNodesBuilder::new).build(
+                    params.clone(),
+                    health,
+                    strategy,
+                    sender.clone(),
+                );
+
+                NodeAddresses::sniffed_nodes(nodes, params, sender)
             }
         }
     }
@@ -2,6 +2,10 @@ use std::{
     collections::HashMap,
     fmt,
     sync::Arc,
+    time::{
+        Duration,
+        Instant,
+    },
 };
 
 use reqwest::{
@@ -18,7 +22,10 @@ use reqwest::{
 use url::form_urlencoded::Serializer;
 
 use crate::http::{
-    sender::NodeAddress,
+    sender::{
+        Credentials,
+        NodeAddress,
+    },
     Method,
 };
 
@@ -38,6 +45,22 @@ pub struct PreRequestParams {
     url_params: Arc<HashMap<&'static str, String>>,
     // We should be able to replace this with `Arc<HeaderMapMap>` from the `http` crate
     headers: Arc<HeaderMap>,
+    timeouts: Timeouts,
+}
+
+/** Timeouts for sending a request and waiting for its response. */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(crate) struct Timeouts {
+    pub(crate) connect: Option<Duration>,
+    pub(crate) read: Option<Duration>,
+    pub(crate) total: Option<Duration>,
+}
+
+impl Timeouts {
+    /** Get the time a request that was started at the given time must complete by. */
+    pub(crate) fn deadline(&self, start: Instant) -> Option<Instant> {
+        self.total.map(|total| start + total)
+    }
 }
 
 /**
@@ -46,6 +69,7 @@ Parameters for a single REST API request.
 The `RequestParams` struct allows you to set headers and url parameters for a given request.
 By default, the `ContentType::json` header will always be added.
 Url parameters are added as simple key-value pairs, and serialised by [rust-url](http://servo.github.io/rust-url/url/index.html).
+Timeouts can also be set to limit how long a request may take.
 
 # Examples
 
@@ -87,6 +111,16 @@ let params = RequestParams::default()
     .url_param("pretty", true)
     .url_param("q", "*");
 ```
+
+With timeouts:
+
+```
+# use std::time::Duration;
+# use elastic::client::RequestParams;
+let params = RequestParams::default()
+    .connect_timeout(Duration::from_secs(1))
+    .total_timeout(Duration::from_secs(10));
+```
 */
 #[derive(Clone)]
 pub struct RequestParams {
@@ -110,6 +144,7 @@ impl PreRequestParams {
                 headers
             }),
             url_params: Arc::new(HashMap::new()),
+            timeouts: Timeouts::default(),
         }
     }
 
@@ -131,7 +166,7 @@ impl PreRequestParams {
 
     /** Enables HTTP basic authentication. */
     pub fn basic_auth<U, P>(
-        mut self,
+        self,
         username: U,
         password: Option<P>,
     ) -> Result<Self, InvalidHeaderValue>
@@ -139,14 +174,78 @@ impl PreRequestParams {
         U: fmt::Display,
         P: fmt::Display,
     {
-        let auth = match password {
-            Some(password) => format!("{}:{}", username, password),
-            None => format!("{}:", username),
-        };
-        let header_value = HeaderValue::from_str(&format!("Basic {}", base64::encode(&auth)))?;
+        self.authorization(Credentials::basic(username, password))
+    }
+
+    /**
+    Enables authentication with an Elasticsearch API key.
+
+    The key is sent in the `Authorization` header as `ApiKey` followed by the base64 encoded `id:key`.
+    */
+    pub fn api_key(
+        self,
+        id: impl ToString,
+        key: impl ToString,
+    ) -> Result<Self, InvalidHeaderValue> {
+        self.authorization(Credentials::api_key(id, key))
+    }
+
+    /**
+    Enables authentication with an OAuth bearer token.
+
+    Tokens that expire can be rotated without rebuilding the client by using a [`CredentialsProvider`][CredentialsProvider] instead.
+
+    [CredentialsProvider]: trait.CredentialsProvider.html
+    */
+    pub fn bearer_token(self, token: impl ToString) -> Result<Self, InvalidHeaderValue> {
+        self.authorization(Credentials::bearer_token(token))
+    }
+
+    fn authorization(mut self, credentials: Credentials) -> Result<Self, InvalidHeaderValue> {
+        let header_value = credentials.to_header_value()?;
         Arc::make_mut(&mut self.headers).insert(AUTHORIZATION, header_value);
         Ok(self)
     }
+
+    /**
+    Set the most time to wait for a connection to a node to be established.
+
+    If the connection isn't established in time then the request fails with an [`Error::Timeout`][Error::Timeout].
+
+    [Error::Timeout]: ../../error/enum.Error.html#variant.Timeout
+    */
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.connect = Some(timeout);
+        self
+    }
+
+    /**
+    Set the most time to wait for a node to respond to each attempt at a request, and for each read of its response body.
+
+    If the node doesn't respond in time then the attempt fails with an [`Error::Timeout`][Error::Timeout] and may be retried.
+
+    [Error::Timeout]: ../../error/enum.Error.html#variant.Timeout
+    */
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.read = Some(timeout);
+        self
+    }
+
+    /**
+    Set the most time a request can take, from connecting to a node until its response body has been read.
+
+    The timeout covers every attempt at the request and the time waited between them.
+    If there isn't time for another attempt before the timeout then the last response or error is returned instead of retrying.
+    If the request doesn't complete in time then it fails with an [`Error::Timeout`][Error::Timeout].
+
+    A synchronous client can't stop waiting part way through a single read, so each read is limited to the shorter of the read and total timeouts, and the total timeout is checked between them.
+
+    [Error::Timeout]: ../../error/enum.Error.html#variant.Timeout
+    */
+    pub fn total_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.total = Some(timeout);
+        self
+    }
 }
 
 impl Default for PreRequestParams {
@@ -196,15 +295,51 @@ impl RequestParams {
         self
     }
 
+    /** Set the most time to wait for a connection to a node to be established. */
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.connect_timeout(timeout);
+        self
+    }
+
+    /** Set the most time to wait for a node to respond to each attempt at a request, and for each read of its response body. */
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.read_timeout(timeout);
+        self
+    }
+
+    /** Set the most time a request can take, across all attempts, from connecting to a node until its response body has been read. */
+    pub fn total_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.total_timeout(timeout);
+        self
+    }
+
     /** Get the base url. */
     pub fn get_base_url(&self) -> &str {
         self.base_url.as_ref()
     }
 
+    pub(crate) fn node_address(&self) -> &NodeAddress {
+        &self.base_url
+    }
+
     pub(crate) fn get_headers(&self) -> Arc<HeaderMap> {
         self.inner.headers.clone()
     }
 
+    pub(crate) fn get_timeouts(&self) -> Timeouts {
+        self.inner.timeouts
+    }
+
+    /**
+    Remove all url params but keep the base url and headers.
+
+    Follow-up requests, like scrolling through a search, don't necessarily support the same url params as the request that started them.
+    */
+    pub(crate) fn without_url_params(mut self) -> Self {
+        self.inner.url_params = Arc::new(HashMap::new());
+        self
+    }
+
     /**
     Get the url query params as a formatted string.
 
@@ -330,6 +465,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pre_request_params_can_set_auth() {
+        let basic = PreRequestParams::new().basic_auth("user", Some("pass")).unwrap();
+        let api_key = PreRequestParams::new().api_key("id", "key").unwrap();
+        let bearer = PreRequestParams::new().bearer_token("token").unwrap();
+
+        let auth = |params: PreRequestParams| {
+            let headers = RequestParams::from_parts(DEFAULT_NODE_ADDRESS, params).get_headers();
+
+            headers[AUTHORIZATION].to_str().unwrap().to_owned()
+        };
+
+        assert_eq!("Basic dXNlcjpwYXNz", auth(basic));
+        assert_eq!("ApiKey aWQ6a2V5", auth(api_key));
+        assert_eq!("Bearer token", auth(bearer));
+    }
+
     #[test]
     fn request_params_has_default_base_url() {
         let req = RequestParams::default();
@@ -353,6 +505,42 @@ mod tests {
         assert_eq!((12, Some(String::from("?pretty=true"))), req.get_url_qry());
     }
 
+    #[test]
+    fn request_params_without_url_params_keeps_base_url_and_headers() {
+        let req = RequestParams::new("http://eshost:9200")
+            .url_param("pretty", true)
+            .header(AUTHORIZATION, HeaderValue::from_str("let me in").unwrap())
+            .without_url_params();
+
+        assert_eq!((0, None), req.get_url_qry());
+        assert_eq!("http://eshost:9200", req.get_base_url());
+        assert!(req.get_headers().contains_key(AUTHORIZATION));
+    }
+
+    #[test]
+    fn request_params_has_no_default_timeouts() {
+        let req = RequestParams::default();
+
+        assert_eq!(Timeouts::default(), req.get_timeouts());
+        assert_eq!(None, req.get_timeouts().deadline(Instant::now()));
+    }
+
+    #[test]
+    fn request_params_can_set_timeouts() {
+        let req = RequestParams::default()
+            .connect_timeout(Duration::from_secs(1))
+            .read_timeout(Duration::from_secs(5))
+            .total_timeout(Duration::from_secs(3));
+
+        let timeouts = req.get_timeouts();
+        let start = Instant::now();
+
+        assert_eq!(Some(Duration::from_secs(1)), timeouts.connect);
+        assert_eq!(Some(Duration::from_secs(5)), timeouts.read);
+        assert_eq!(Some(Duration::from_secs(3)), timeouts.total);
+        assert_eq!(Some(start + Duration::from_secs(3)), timeouts.deadline(start));
+    }
+
     #[test]
     fn empty_request_params_returns_empty_string() {
         let req = RequestParams::default();
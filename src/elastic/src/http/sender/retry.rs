@@ -0,0 +1,372 @@
+/*!
+Policies for retrying requests that fail with a transient error.
+*/
+
+use rand::{
+    self,
+    Rng,
+};
+use reqwest::Error as ReqwestError;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::{
+    error::Error,
+    http::{
+        sender::RequestParams,
+        Method,
+        StatusCode,
+    },
+};
+
+/**
+A kind of transport error that a request can be retried on.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryableError {
+    /** The connection to the node couldn't be established. */
+    Connection,
+    /** The node didn't respond in time. */
+    Timeout,
+}
+
+impl RetryableError {
+    pub(crate) fn from_reqwest(err: &ReqwestError) -> Option<Self> {
+        if err.is_timeout() {
+            Some(RetryableError::Timeout)
+        } else if is_connect(err) {
+            Some(RetryableError::Connection)
+        } else {
+            None
+        }
+    }
+}
+
+/** Whether or not the error was raised while connecting to the node. */
+fn is_connect(err: &ReqwestError) -> bool {
+    match err.get_ref().and_then(|err| err.downcast_ref::<hyper::Error>()) {
+        Some(err) => err.is_connect(),
+        None => false,
+    }
+}
+
+/**
+A policy for retrying requests that fail with a transient error.
+
+A request is retried when it fails with one of the retryable errors or when the response has one of the retryable status codes.
+Each retry is sent to the next node, so a request to a node that's down can still succeed on another one.
+The time to wait between attempts grows exponentially from the initial backoff up to the maximum backoff.
+
+By default, only idempotent requests are retried.
+Requests that use the `POST` method, like indexing a document with a generated id, could be applied twice if they're retried.
+Request bodies that can only be read once, like files, are never retried.
+
+# Examples
+
+Retry requests up to 5 times, waiting at least 50ms between attempts:
+
+```
+# use std::time::Duration;
+# use elastic::prelude::*;
+let policy = RetryPolicy::new()
+    .max_attempts(5)
+    .backoff(Duration::from_millis(50), Duration::from_secs(5));
+
+let builder = SyncClientBuilder::new().retry_policy(policy);
+```
+*/
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    statuses: Vec<StatusCode>,
+    errors: Vec<RetryableError>,
+    retry_non_idempotent: bool,
+}
+
+impl RetryPolicy {
+    /**
+    Create a new retry policy.
+
+    By default, a request will:
+
+    - Be attempted up to 3 times
+    - Wait between 100ms and 10s between attempts, with some random jitter
+    - Be retried on a `429`, `502`, `503` or `504` status code
+    - Be retried on a connection error or timeout
+    - Not be retried if its method isn't idempotent
+    */
+    pub fn new() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            jitter: true,
+            statuses: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            errors: vec![RetryableError::Connection, RetryableError::Timeout],
+            retry_non_idempotent: false,
+        }
+    }
+
+    /**
+    Create a retry policy that never retries requests.
+
+    This is the policy used by clients unless another one is given.
+    */
+    pub fn never() -> Self {
+        RetryPolicy::new().max_attempts(1)
+    }
+
+    /**
+    Set the maximum number of times to attempt a request, including the first attempt.
+
+    A value of `1` means requests are never retried.
+    */
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /** Set the time to wait before the first retry and the most time to wait between attempts. */
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /**
+    Set whether or not to randomise the time to wait between attempts.
+
+    Jitter stops clients that failed at the same time from retrying at the same time.
+    */
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /** Set the response status codes that a request will be retried on. */
+    pub fn retry_on_status(mut self, statuses: impl IntoIterator<Item = StatusCode>) -> Self {
+        self.statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /** Set the kinds of errors that a request will be retried on. */
+    pub fn retry_on_error(mut self, errors: impl IntoIterator<Item = RetryableError>) -> Self {
+        self.errors = errors.into_iter().collect();
+        self
+    }
+
+    /** Set whether or not requests with a method that isn't idempotent, like `POST`, may be retried. */
+    pub fn retry_non_idempotent(mut self, retry_non_idempotent: bool) -> Self {
+        self.retry_non_idempotent = retry_non_idempotent;
+        self
+    }
+
+    /** Whether or not a request may be retried at all. */
+    pub(crate) fn can_retry(&self, method: &Method) -> bool {
+        self.max_attempts > 1 && (self.retry_non_idempotent || method.is_idempotent())
+    }
+
+    /** Whether or not there are attempts left after the given attempt. */
+    pub(crate) fn has_attempts_after(&self, attempt: u32) -> bool {
+        attempt < self.max_attempts
+    }
+
+    /** Whether or not a request may be sent again after the given attempt. */
+    pub(crate) fn can_retry_after(&self, method: &Method, attempt: u32) -> bool {
+        self.can_retry(method) && self.has_attempts_after(attempt)
+    }
+
+    /** Whether or not a response status code can be retried. */
+    pub(crate) fn is_retryable_status(&self, status: StatusCode) -> bool {
+        self.statuses.contains(&status)
+    }
+
+    /** Whether or not an error sending a request can be retried. */
+    pub(crate) fn is_retryable_error(&self, err: Option<RetryableError>) -> bool {
+        err.is_some_and(|err| self.errors.contains(&err))
+    }
+
+    /**
+    Get the time to wait after the given attempt failed.
+
+    The backoff doubles after each attempt up to the maximum.
+    With jitter, the time to wait is a random value between half and all of the backoff.
+    */
+    pub(crate) fn backoff_after(&self, attempt: u32) -> Duration {
+        let backoff = exponential_backoff(self.initial_backoff, self.max_backoff, attempt);
+
+        if self.jitter {
+            let half = backoff / 2;
+            let jitter_millis = rand::thread_rng().gen_range(0, half.as_millis() as u64 + 1);
+
+            half + Duration::from_millis(jitter_millis)
+        } else {
+            backoff
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new()
+    }
+}
+
+/** Double the initial duration for each attempt after the first, up to the maximum. */
+pub(crate) fn exponential_backoff(initial: Duration, max: Duration, attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(31);
+
+    initial
+        .checked_mul(1 << exponent)
+        .map_or(max, |backoff| backoff.min(max))
+}
+
+/**
+Get the parameters to retry a request with on the next node.
+
+The rest of the parameters are kept so any changes made for the request are preserved.
+If the next node can't be selected then the request is retried on the same node.
+*/
+pub(crate) fn retry_params(
+    params: RequestParams,
+    next: Result<RequestParams, Error>,
+    correlation_id: Uuid,
+) -> RequestParams {
+    match next {
+        Ok(next) => params.base_url(next.split().0),
+        Err(e) => {
+            warn!(
+                "Elasticsearch Node Selection: correlation_id: '{}', error: '{:?}'",
+                correlation_id, e
+            );
+            params
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{
+            Read,
+            Write,
+        },
+        net::TcpListener,
+        thread,
+    };
+
+    #[test]
+    fn never_retries() {
+        let policy = RetryPolicy::never();
+
+        assert!(!policy.can_retry(&Method::GET));
+        assert!(!policy.has_attempts_after(1));
+    }
+
+    #[test]
+    fn default_attempts() {
+        let policy = RetryPolicy::new();
+
+        assert!(policy.has_attempts_after(1));
+        assert!(policy.has_attempts_after(2));
+        assert!(!policy.has_attempts_after(3));
+    }
+
+    #[test]
+    fn retry_idempotent_methods_only() {
+        let policy = RetryPolicy::new();
+
+        assert!(policy.can_retry(&Method::GET));
+        assert!(policy.can_retry(&Method::HEAD));
+        assert!(policy.can_retry(&Method::PUT));
+        assert!(policy.can_retry(&Method::DELETE));
+        assert!(!policy.can_retry(&Method::POST));
+
+        let policy = policy.retry_non_idempotent(true);
+
+        assert!(policy.can_retry(&Method::POST));
+    }
+
+    #[test]
+    fn retryable_statuses() {
+        let policy = RetryPolicy::new();
+
+        assert!(policy.is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(policy.is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!policy.is_retryable_status(StatusCode::NOT_FOUND));
+        assert!(!policy.is_retryable_status(StatusCode::INTERNAL_SERVER_ERROR));
+
+        let policy = policy.retry_on_status(vec![StatusCode::INTERNAL_SERVER_ERROR]);
+
+        assert!(!policy.is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(policy.is_retryable_status(StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    #[test]
+    fn exponential_backoff() {
+        let policy = RetryPolicy::new()
+            .jitter(false)
+            .backoff(Duration::from_millis(100), Duration::from_millis(350));
+
+        assert_eq!(Duration::from_millis(100), policy.backoff_after(1));
+        assert_eq!(Duration::from_millis(200), policy.backoff_after(2));
+        assert_eq!(Duration::from_millis(350), policy.backoff_after(3));
+        assert_eq!(Duration::from_millis(350), policy.backoff_after(40));
+    }
+
+    #[test]
+    fn backoff_with_jitter() {
+        let policy = RetryPolicy::new().backoff(Duration::from_millis(100), Duration::from_secs(1));
+
+        for _ in 0..100 {
+            let backoff = policy.backoff_after(2);
+
+            assert!(backoff >= Duration::from_millis(100));
+            assert!(backoff <= Duration::from_millis(200));
+        }
+    }
+
+    fn send_err(url: &str) -> ReqwestError {
+        reqwest::Client::new()
+            .get(url)
+            .send()
+            .expect_err("expected the request to fail")
+    }
+
+    #[test]
+    fn connect_errors_are_retryable() {
+        // Bind and drop a listener to find a local port nothing is listening on
+        let addr = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+
+        let err = send_err(&format!("http://{}/", addr));
+
+        assert_eq!(Some(RetryableError::Connection), RetryableError::from_reqwest(&err));
+    }
+
+    #[test]
+    fn other_http_errors_are_not_retryable() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream.read(&mut [0; 1024]);
+            stream.write_all(b"not a http response\r\n\r\n").unwrap();
+        });
+
+        let err = send_err(&format!("http://{}/", addr));
+        server.join().unwrap();
+
+        assert!(err.is_http());
+        assert_eq!(None, RetryableError::from_reqwest(&err));
+    }
+}
@@ -21,26 +21,56 @@ Synchronously:
 
 This means our `SniffedNodes` structure looks completely different in synchronous and asynchronous scenarios.
 It's effectively a rewrite.
+
+Refreshing in the background avoids both limitations without a rewrite:
+
+- A thread (sync) or task (async) refreshes the nodes on its own schedule
+- Requests never wait for a refresh and failed refreshes keep the last good set of nodes
+- The background refresher is started by the first request, so it can be spawned on the runtime that request is polled on
 */
 
 use futures::{
+    future::{
+        lazy,
+        loop_fn,
+        Either,
+        Loop,
+    },
+    sync::mpsc as async_mpsc,
     Future,
     IntoFuture,
+    Stream,
 };
 use std::{
     sync::{
+        mpsc::{
+            self,
+            RecvTimeoutError,
+        },
         Arc,
         RwLock,
+        Weak,
     },
+    thread,
     time::{
         Duration,
         Instant,
     },
 };
+use tokio::{
+    executor::{
+        DefaultExecutor,
+        Executor,
+    },
+    timer::Delay,
+};
 use url::Url;
 
 use crate::{
-    client::responses::NodesInfoResponse,
+    client::responses::{
+        NodeInfo,
+        NodesInfoResponse,
+    },
     endpoints::NodesInfoRequest,
     error::{
         self,
@@ -48,10 +78,15 @@ use crate::{
     },
     http::{
         sender::{
-            static_nodes::StaticNodes,
+            static_nodes::{
+                StaticNodes,
+                Strategy,
+            },
             AsyncSender,
             NextParams,
             NodeAddress,
+            NodeFeedback,
+            NodeHealth,
             PreRequestParams,
             RequestParams,
             SendableRequest,
@@ -67,18 +102,28 @@ use crate::{
 /**
 Periodically sniff nodes in a cluster.
 
-Requests are load balanced between the sniffed nodes using a round-robin strategy.
+Requests are load balanced between the sniffed nodes using the client's node selection strategy, which is round-robin by default.
 The base url for the node is obtained by the `http.publish_address` field on a [node info request].
 
 Nodes are refreshed on the next request after the specified timeout.
 If updating the nodes fails for some reason then the request itself will also fail.
 
+Nodes can also be [refreshed in the background][SniffedNodesBuilder.refresh_in_background] instead.
+Requests then never wait for a refresh, and a refresh that fails keeps the last good set of nodes.
+
+Sniffed nodes that fail to connect are skipped until they can be pinged again, the same as [`StaticNodes`][StaticNodes].
+Nodes can be excluded from the set entirely using the [`filter`][SniffedNodesBuilder.filter] method on the builder.
+
 [node info request]: https://www.elastic.co/guide/en/elasticsearch/reference/current/cluster-nodes-info.html
+[StaticNodes]: ../static_nodes/struct.StaticNodes.html
+[SniffedNodesBuilder.filter]: struct.SniffedNodesBuilder.html#method.filter
+[SniffedNodesBuilder.refresh_in_background]: struct.SniffedNodesBuilder.html#method.refresh_in_background
 */
 #[derive(Clone)]
 pub struct SniffedNodes<TSender> {
     sender: TSender,
     refresh_params: RequestParams,
+    feedback: NodeFeedback,
     inner: Arc<RwLock<SniffedNodesInner>>,
 }
 
@@ -88,16 +133,67 @@ A builder for a cluster sniffer.
 pub struct SniffedNodesBuilder {
     base_url: NodeAddress,
     wait: Option<Duration>,
+    background: bool,
+    filters: Vec<Arc<NodeFilter>>,
 }
 
+type NodeFilter = dyn Fn(&NodeInfo) -> bool + Send + Sync;
+
 struct SniffedNodesInner {
     last_update: Option<Instant>,
     wait: Duration,
     refreshing: bool,
-    nodes: StaticNodes,
+    background: bool,
+    waker: Option<BackgroundWaker>,
+    filters: Vec<Arc<NodeFilter>>,
+    nodes: StaticNodes<Arc<dyn Strategy>>,
+}
+
+/** Wakes a background refresher so it refreshes the nodes straight away. */
+enum BackgroundWaker {
+    Sync(mpsc::Sender<()>),
+    Async(async_mpsc::UnboundedSender<()>),
+}
+
+impl BackgroundWaker {
+    fn wake(&self) {
+        // The refresher has stopped if the receiver is gone so there's nothing to wake
+        let _ = match *self {
+            BackgroundWaker::Sync(ref waker) => waker.send(()).map_err(|_| ()),
+            BackgroundWaker::Async(ref waker) => waker.unbounded_send(()).map_err(|_| ()),
+        };
+    }
+}
+
+/**
+A handle to refresh sniffed nodes in the background straight away.
+
+This is used to refresh the nodes when a request to one of them fails to connect.
+It doesn't do anything if the nodes aren't being refreshed in the background.
+*/
+#[derive(Clone)]
+pub(crate) struct SniffTrigger {
+    inner: Arc<RwLock<SniffedNodesInner>>,
+}
+
+impl SniffTrigger {
+    pub(crate) fn sniff_now(&self) {
+        if let Some(ref waker) = self.inner.read().expect("lock poisoned").waker {
+            waker.wake();
+        }
+    }
 }
 
 impl<TSender> SniffedNodes<TSender> {
+    pub(crate) fn feedback(&self) -> &NodeFeedback {
+        &self.feedback
+    }
+
+    /** Get the dead nodes that are ready to be pinged. */
+    pub(crate) fn start_resurrect(&self) -> Vec<NodeAddress> {
+        self.inner.read().expect("lock poisoned").nodes.start_resurrect()
+    }
+
     /**
     Get the next async address or refresh.
 
@@ -119,7 +215,7 @@ impl<TSender> SniffedNodes<TSender> {
 
         // Perform the refresh
         let inner = self.inner.clone();
-        let req = self.sendable_request();
+        let req = Self::sendable_request(&self.refresh_params);
         let refresh_params = self.refresh_params.clone();
 
         let refresh_nodes = refresh(req)
@@ -144,7 +240,7 @@ impl<TSender> SniffedNodes<TSender> {
         }
 
         // Perform the refresh
-        let req = self.sendable_request();
+        let req = Self::sendable_request(&self.refresh_params);
 
         let fresh_nodes = refresh(req);
         Self::finish_refresh(&self.inner, &self.refresh_params, fresh_nodes)
@@ -162,6 +258,8 @@ impl SniffedNodesBuilder {
         SniffedNodesBuilder {
             base_url: address.into(),
             wait: None,
+            background: false,
+            filters: Vec::new(),
         }
     }
 
@@ -184,6 +282,65 @@ impl SniffedNodesBuilder {
         self
     }
 
+    /**
+    Refresh the set of node addresses in the background instead of while sending a request.
+
+    Synchronous clients refresh nodes on a separate thread and asynchronous clients refresh them on a task spawned on the current runtime.
+    The background refresher is started by the first request sent by the client and stops when the client is dropped.
+    Nodes are refreshed every `wait` and whenever a request to a node fails to connect.
+    If a refresh fails then the last good set of nodes is kept.
+
+    # Examples
+
+    Refresh nodes every 30 seconds in the background:
+
+    ```
+    # use std::time::Duration;
+    # use elastic::prelude::*;
+    let builder = SyncClientBuilder::new()
+        .sniff_nodes_fluent("http://localhost:9200", |n| n
+            .wait(Duration::from_secs(30))
+            .refresh_in_background(true));
+    ```
+    */
+    pub fn refresh_in_background(mut self, background: bool) -> Self {
+        self.background = background;
+        self
+    }
+
+    /**
+    Only send requests to sniffed nodes that match the given filter.
+
+    The filter is called for each node whenever the set of nodes is refreshed.
+    If this method is called more than once then a node must match every filter.
+    If no nodes match then the refresh fails.
+
+    # Examples
+
+    Only send requests to data nodes in zone `a`:
+
+    ```
+    # use elastic::prelude::*;
+    let builder = SyncClientBuilder::new()
+        .sniff_nodes_fluent("http://localhost:9200", |n| n
+            .filter(|node| node.has_role("data"))
+            .filter(|node| node.attribute("zone") == Some("a")));
+    ```
+
+    Don't send requests to dedicated master nodes:
+
+    ```
+    # use elastic::prelude::*;
+    let builder = SyncClientBuilder::new()
+        .sniff_nodes_fluent("http://localhost:9200", |n| n
+            .filter(|node| !node.is_master_only()));
+    ```
+    */
+    pub fn filter(mut self, filter: impl Fn(&NodeInfo) -> bool + Send + Sync + 'static) -> Self {
+        self.filters.push(Arc::new(filter));
+        self
+    }
+
     /**
     Build a cluster sniffer using the given sender and parameters.
 
@@ -192,29 +349,45 @@ impl SniffedNodesBuilder {
     pub(crate) fn build<TSender>(
         self,
         base_params: PreRequestParams,
+        health: NodeHealth,
+        strategy: Arc<dyn Strategy>,
         sender: TSender,
     ) -> SniffedNodes<TSender> {
-        let nodes = StaticNodes::round_robin(vec![self.base_url.clone()], base_params.clone());
+        let nodes =
+            StaticNodes::new(vec![self.base_url.clone()], strategy.clone(), base_params.clone())
+                .with_health(health.clone());
         let wait = self.wait.unwrap_or_else(|| Duration::from_secs(90));
 
         // Specify a `filter_path` when updating node stats because deserialisation occurs on tokio thread
         // This should change in the future if:
         // - we can provide a cpu pool to deserialise on
-        // - we want more metadata about the nodes
+        // - we want more metadata about the nodes than is needed to filter them
         // The publish_address may not correspond to the address the node is actually available on
         // In this case, we might want to offer some kind of filter function that consumers can use to transform nodes
-        let refresh_params = RequestParams::from_parts(self.base_url, base_params)
-            .url_param("filter_path", "nodes.*.http.publish_address");
+        let refresh_params = RequestParams::from_parts(self.base_url, base_params).url_param(
+            "filter_path",
+            "nodes.*.name,nodes.*.roles,nodes.*.attributes,nodes.*.http.publish_address",
+        );
+
+        let inner = Arc::new(RwLock::new(SniffedNodesInner {
+            last_update: None,
+            wait,
+            refreshing: false,
+            background: self.background,
+            waker: None,
+            filters: self.filters,
+            nodes,
+        }));
+
+        let feedback = NodeFeedback::new(health, strategy).with_sniff(SniffTrigger {
+            inner: inner.clone(),
+        });
 
         SniffedNodes {
             sender,
             refresh_params,
-            inner: Arc::new(RwLock::new(SniffedNodesInner {
-                last_update: None,
-                wait,
-                refreshing: false,
-                nodes,
-            })),
+            feedback,
+            inner,
         }
     }
 }
@@ -277,11 +450,11 @@ impl<TSender> SniffedNodes<TSender> {
     }
 
     fn sendable_request(
-        &self,
+        refresh_params: &RequestParams,
     ) -> SendableRequest<NodesInfoRequest<'static>, RequestParams, DefaultBody> {
         SendableRequest::new(
             NodesInfoRequest::new(),
-            SendableRequestParams::Value(self.refresh_params.clone()),
+            SendableRequestParams::Value(refresh_params.clone()),
         )
     }
 
@@ -293,19 +466,65 @@ impl<TSender> SniffedNodes<TSender> {
         let mut inner = inner.write().expect("lock poisoned");
 
         inner.refreshing = false;
+        inner.update_nodes(refresh_params, fresh_nodes)?;
 
-        // TODO: We need to deal with the scheme better here
-        // The `NodeAddress` should one day be a properly typed url we can interrogate
-        let parsed_url =
-            Url::parse(refresh_params.get_base_url().as_ref()).map_err(error::request)?;
-        let scheme = parsed_url.scheme();
+        inner.nodes.next().map_err(error::request)
+    }
 
-        let fresh_nodes = fresh_nodes?;
-        let next = inner.update_nodes_and_next(fresh_nodes, scheme)?;
+    /**
+    Update the nodes from a background refresh.
 
-        inner.last_update = Some(Instant::now());
+    If the refresh failed then the current nodes are kept.
+    */
+    fn finish_background_refresh(
+        inner: &RwLock<SniffedNodesInner>,
+        refresh_params: &RequestParams,
+        fresh_nodes: Result<NodesInfoResponse, Error>,
+    ) {
+        let mut inner = inner.write().expect("lock poisoned");
 
-        Ok(next)
+        if let Err(e) = inner.update_nodes(refresh_params, fresh_nodes) {
+            warn!(
+                "Elasticsearch Node Sniffing: keeping the last good set of nodes, error: '{:?}'",
+                e
+            );
+        }
+    }
+
+    /**
+    Check whether a background refresher needs to be started.
+
+    Returns the time to wait between refreshes if it does.
+    */
+    fn should_start_background(&self) -> Option<Duration> {
+        let inner = self.inner.read().expect("lock poisoned");
+
+        if inner.background && inner.waker.is_none() {
+            Some(inner.wait)
+        } else {
+            None
+        }
+    }
+
+    /**
+    Claim the background refresher by recording its waker so requests won't refresh nodes themselves.
+
+    The check and the claim happen under the same lock, so only one of several concurrent requests will get the time to wait between refreshes and should start the refresher.
+    */
+    fn claim_background(&self, waker: BackgroundWaker) -> Option<Duration> {
+        let mut inner = self.inner.write().expect("lock poisoned");
+
+        if inner.background && inner.waker.is_none() {
+            inner.waker = Some(waker);
+            Some(inner.wait)
+        } else {
+            None
+        }
+    }
+
+    /** Release the claim on the background refresher if it couldn't be started. */
+    fn release_background(&self) {
+        self.inner.write().expect("lock poisoned").waker = None;
     }
 }
 
@@ -317,35 +536,187 @@ impl SniffedNodesInner {
             .as_ref()
             .map(|last_update| last_update.elapsed() > self.wait);
 
-        !self.refreshing && last_update_is_stale.unwrap_or(true)
+        // Nodes are never refreshed by requests while a background refresher is running
+        let is_background = self.waker.is_some();
+
+        !self.refreshing && !is_background && last_update_is_stale.unwrap_or(true)
     }
 
-    fn update_nodes_and_next(
+    fn update_nodes(
         &mut self,
-        parsed: NodesInfoResponse,
-        scheme: &str,
-    ) -> Result<RequestParams, Error> {
-        let nodes: Vec<_> = parsed
-            .into_iter_addrs()
+        refresh_params: &RequestParams,
+        fresh_nodes: Result<NodesInfoResponse, Error>,
+    ) -> Result<(), Error> {
+        // TODO: We need to deal with the scheme better here
+        // The `NodeAddress` should one day be a properly typed url we can interrogate
+        let parsed_url =
+            Url::parse(refresh_params.get_base_url().as_ref()).map_err(error::request)?;
+        let scheme = parsed_url.scheme();
+
+        let fresh_nodes = fresh_nodes?;
+        let filters = &self.filters;
+
+        let nodes: Vec<_> = fresh_nodes
+            .iter_nodes()
+            .filter(|node| filters.iter().all(|filter| filter(node)))
+            .filter_map(|node| node.publish_address())
             .map(|publish_address| format!("{}://{}", scheme, publish_address).into())
             .collect();
 
         self.nodes.set(nodes)?;
-        self.nodes.next().map_err(error::request)
+        self.last_update = Some(Instant::now());
+
+        Ok(())
     }
 }
 
 impl<TSender> private::Sealed for SniffedNodes<TSender> {}
 
+impl SniffedNodes<AsyncSender> {
+    /**
+    Start refreshing nodes on a task if they should be refreshed in the background.
+
+    The task is spawned on the current runtime.
+    If there's no runtime then nodes keep being refreshed by requests until a request is polled on one.
+    */
+    fn start_background(&self) {
+        if self.should_start_background().is_none() {
+            return;
+        }
+
+        let (waker, wakes) = async_mpsc::unbounded();
+
+        let wait = match self.claim_background(BackgroundWaker::Async(waker)) {
+            Some(wait) => wait,
+            None => return,
+        };
+
+        let sender = self.sender.clone();
+        let refresh_params = self.refresh_params.clone();
+        let weak_inner = Arc::downgrade(&self.inner);
+
+        let refresh = loop_fn(wakes, move |wakes| {
+            let weak_inner = weak_inner.clone();
+            let refresh_params = refresh_params.clone();
+
+            sender
+                .send(Self::sendable_request(&refresh_params))
+                .and_then(|res| res.into_response::<NodesInfoResponse>())
+                .then(move |fresh_nodes| {
+                    let inner = match weak_inner.upgrade() {
+                        Some(inner) => inner,
+                        None => return Either::A(Ok(Loop::Break(())).into_future()),
+                    };
+
+                    Self::finish_background_refresh(&inner, &refresh_params, fresh_nodes);
+
+                    // Wait until the next refresh is due or a refresh is asked for
+                    let timeout = Delay::new(Instant::now() + wait);
+                    let next = wakes.into_future().select2(timeout).then(|res| match res {
+                        Ok(Either::A(((Some(()), wakes), _))) => Ok(Loop::Continue(wakes)),
+                        Ok(Either::B((_, wake))) => match wake.into_inner() {
+                            Some(wakes) => Ok(Loop::Continue(wakes)),
+                            None => Ok(Loop::Break(())),
+                        },
+                        // The client has been dropped or the timer has shut down
+                        _ => Ok(Loop::Break(())),
+                    });
+
+                    Either::B(next)
+                })
+        });
+
+        if DefaultExecutor::current().spawn(Box::new(refresh)).is_err() {
+            self.release_background();
+        }
+    }
+}
+
+impl SniffedNodes<SyncSender> {
+    /** Start refreshing nodes on a thread if they should be refreshed in the background. */
+    fn start_background(&self) {
+        if self.should_start_background().is_none() {
+            return;
+        }
+
+        let (waker, wakes) = mpsc::channel();
+
+        let wait = match self.claim_background(BackgroundWaker::Sync(waker)) {
+            Some(wait) => wait,
+            None => return,
+        };
+
+        let sender = self.sender.clone();
+        let refresh_params = self.refresh_params.clone();
+        let weak_inner = Arc::downgrade(&self.inner);
+
+        let spawned = thread::Builder::new()
+            .name("elastic-sniffer".to_owned())
+            .spawn(move || {
+                Self::background_refresh(sender, refresh_params, weak_inner, wakes, wait)
+            });
+
+        if let Err(e) = spawned {
+            warn!(
+                "Elasticsearch Node Sniffing: failed to start background refresh, error: '{:?}'",
+                e
+            );
+
+            self.release_background();
+        }
+    }
+
+    fn background_refresh(
+        sender: SyncSender,
+        refresh_params: RequestParams,
+        weak_inner: Weak<RwLock<SniffedNodesInner>>,
+        wakes: mpsc::Receiver<()>,
+        wait: Duration,
+    ) {
+        loop {
+            let fresh_nodes = sender
+                .send(Self::sendable_request(&refresh_params))
+                .and_then(|res| res.into_response::<NodesInfoResponse>());
+
+            let inner = match weak_inner.upgrade() {
+                Some(inner) => inner,
+                None => return,
+            };
+
+            Self::finish_background_refresh(&inner, &refresh_params, fresh_nodes);
+
+            // Don't keep the nodes alive while waiting so the thread stops when the client is dropped
+            drop(inner);
+
+            // Wait until the next refresh is due or a refresh is asked for
+            match wakes.recv_timeout(wait) {
+                Ok(()) | Err(RecvTimeoutError::Timeout) => (),
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+
+            // Refreshes asked for while refreshing are covered by the next one
+            while wakes.try_recv().is_ok() {}
+        }
+    }
+}
+
 impl NextParams for SniffedNodes<AsyncSender> {
     type Params = Box<dyn Future<Item = RequestParams, Error = Error> + Send>;
 
     fn next(&self) -> Self::Params {
-        self.async_next(|req| {
-            self.sender
-                .send(req)
-                .and_then(|res| res.into_response::<NodesInfoResponse>())
-        })
+        let sniffer = self.clone();
+
+        // Wait until the request is polled so the background refresher is spawned on its runtime
+        Box::new(lazy(move || {
+            sniffer.start_background();
+
+            sniffer.async_next(|req| {
+                sniffer
+                    .sender
+                    .send(req)
+                    .and_then(|res| res.into_response::<NodesInfoResponse>())
+            })
+        }))
     }
 }
 
@@ -353,6 +724,8 @@ impl NextParams for SniffedNodes<SyncSender> {
     type Params = Result<RequestParams, Error>;
 
     fn next(&self) -> Self::Params {
+        self.start_background();
+
         self.sync_next(|req| {
             self.sender
                 .send(req)
@@ -364,11 +737,59 @@ impl NextParams for SniffedNodes<SyncSender> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        http::{
+            sender::{
+                static_nodes::RoundRobin,
+                MockRequest,
+                MockResponse,
+                MockTransport,
+            },
+            Method,
+            StatusCode,
+        },
+        prelude::*,
+    };
     use futures::Future;
     use serde_json;
+    use std::sync::{
+        atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+        Barrier,
+    };
 
     fn sender() -> SniffedNodes<()> {
-        SniffedNodesBuilder::new(initial_address()).build(PreRequestParams::default(), ())
+        SniffedNodesBuilder::new(initial_address()).build(
+            PreRequestParams::default(),
+            NodeHealth::default(),
+            Arc::new(RoundRobin::default()),
+            (),
+        )
+    }
+
+    fn background_sender() -> SniffedNodes<()> {
+        SniffedNodesBuilder::new(initial_address())
+            .refresh_in_background(true)
+            .build(
+                PreRequestParams::default(),
+                NodeHealth::default(),
+                Arc::new(RoundRobin::default()),
+                (),
+            )
+    }
+
+    fn filtered_sender() -> SniffedNodes<()> {
+        SniffedNodesBuilder::new(initial_address())
+            .filter(|node| node.has_role("data"))
+            .filter(|node| node.attribute("zone") == Some("a"))
+            .build(
+                PreRequestParams::default(),
+                NodeHealth::default(),
+                Arc::new(RoundRobin::default()),
+                (),
+            )
     }
 
     fn expected_nodes() -> NodesInfoResponse {
@@ -552,4 +973,202 @@ mod tests {
         assert_refreshing_equal(&nodes, false);
         assert_should_refresh_equal(&nodes, true);
     }
+
+    #[test]
+    fn sync_refresh_filters_nodes() {
+        let nodes = filtered_sender();
+
+        let res = nodes.sync_next(move |_| {
+            Ok(serde_json::from_value(json!({
+                "nodes": {
+                    "master": {
+                        "roles": ["master"],
+                        "attributes": { "zone": "a" },
+                        "http": { "publish_address": "master:9200" }
+                    },
+                    "data_a": {
+                        "roles": ["data", "ingest"],
+                        "attributes": { "zone": "a" },
+                        "http": { "publish_address": "data_a:9200" }
+                    },
+                    "data_b": {
+                        "roles": ["data", "ingest"],
+                        "attributes": { "zone": "b" },
+                        "http": { "publish_address": "data_b:9200" }
+                    }
+                }
+            }))
+            .unwrap())
+        });
+
+        assert!(res.is_ok());
+
+        assert_node_addresses_equal(&nodes, vec!["http://data_a:9200"]);
+    }
+
+    #[test]
+    fn sync_refresh_fail_on_all_filtered() {
+        let nodes = filtered_sender();
+
+        let res = nodes.sync_next(move |_| Ok(expected_nodes()));
+
+        assert!(res.is_err());
+
+        assert_node_addresses_equal(&nodes, vec![initial_address()]);
+        assert_should_refresh_equal(&nodes, true);
+    }
+
+    #[test]
+    fn should_refresh_is_false_in_background() {
+        let nodes = background_sender();
+        let (waker, _wakes) = mpsc::channel();
+
+        assert!(nodes.should_start_background().is_some());
+        assert!(nodes.claim_background(BackgroundWaker::Sync(waker)).is_some());
+
+        assert_should_refresh_equal(&nodes, false);
+        assert!(nodes.should_start_background().is_none());
+    }
+
+    #[test]
+    fn claim_background_is_ignored_if_not_in_background() {
+        let nodes = sender();
+        let (waker, _wakes) = mpsc::channel();
+
+        assert!(nodes.claim_background(BackgroundWaker::Sync(waker)).is_none());
+        assert_should_refresh_equal(&nodes, true);
+    }
+
+    #[test]
+    fn claim_background_once_from_many_threads() {
+        let nodes = background_sender();
+        let barrier = Arc::new(Barrier::new(8));
+
+        let claims = (0..8)
+            .map(|_| {
+                let nodes = nodes.clone();
+                let barrier = barrier.clone();
+
+                thread::spawn(move || {
+                    let (waker, _wakes) = mpsc::channel();
+                    barrier.wait();
+
+                    nodes.claim_background(BackgroundWaker::Sync(waker)).is_some()
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|claim| claim.join().unwrap())
+            .filter(|claimed| *claimed)
+            .count();
+
+        assert_eq!(1, claims);
+    }
+
+    #[test]
+    fn start_background_once_from_many_threads() {
+        let sniffs = Arc::new(AtomicUsize::new(0));
+
+        let mock = {
+            let sniffs = sniffs.clone();
+
+            MockTransport::new()
+                .respond(MockRequest::new(Method::GET, "/"), MockResponse::new(StatusCode::OK))
+                .respond_with(MockRequest::new(Method::GET, "/_nodes"), move |_| {
+                    sniffs.fetch_add(1, Ordering::SeqCst);
+
+                    MockResponse::new(StatusCode::OK).body(json!({
+                        "nodes": { "node1": { "http": { "publish_address": "a:9200" } } }
+                    }))
+                })
+        };
+
+        let client = SyncClientBuilder::new()
+            .mock(mock)
+            .sniff_nodes_fluent(initial_address(), |n| n.refresh_in_background(true))
+            .build()
+            .unwrap();
+
+        let barrier = Arc::new(Barrier::new(8));
+
+        let requests = (0..8)
+            .map(|_| {
+                let client = client.clone();
+                let barrier = barrier.clone();
+
+                thread::spawn(move || {
+                    barrier.wait();
+
+                    client.request(PingRequest::new()).send().unwrap();
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for request in requests {
+            request.join().unwrap();
+        }
+
+        // Wait for the background refresher to sniff, then give any others the chance to
+        let start = Instant::now();
+        while sniffs.load(Ordering::SeqCst) == 0 && start.elapsed() < Duration::from_secs(5) {
+            thread::sleep(Duration::from_millis(10));
+        }
+        thread::sleep(Duration::from_millis(100));
+
+        assert_eq!(1, sniffs.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn background_refresh_success() {
+        let nodes = sender();
+
+        SniffedNodes::<()>::finish_background_refresh(
+            &nodes.inner,
+            &nodes.refresh_params,
+            Ok(expected_nodes()),
+        );
+
+        assert_node_addresses_equal(&nodes, expected_addresses());
+    }
+
+    #[test]
+    fn background_refresh_keeps_nodes_on_fail() {
+        let nodes = sender();
+
+        SniffedNodes::<()>::finish_background_refresh(
+            &nodes.inner,
+            &nodes.refresh_params,
+            Ok(expected_nodes()),
+        );
+        SniffedNodes::<()>::finish_background_refresh(
+            &nodes.inner,
+            &nodes.refresh_params,
+            Err(error::test()),
+        );
+        SniffedNodes::<()>::finish_background_refresh(
+            &nodes.inner,
+            &nodes.refresh_params,
+            Ok(empty_nodes()),
+        );
+
+        assert_node_addresses_equal(&nodes, expected_addresses());
+    }
+
+    #[test]
+    fn sniff_trigger_wakes_background() {
+        let nodes = background_sender();
+        let trigger = SniffTrigger {
+            inner: nodes.inner.clone(),
+        };
+
+        // Nothing is woken unless there's a background refresher
+        trigger.sniff_now();
+
+        let (waker, wakes) = mpsc::channel();
+        nodes.claim_background(BackgroundWaker::Sync(waker));
+
+        trigger.sniff_now();
+
+        assert!(wakes.try_recv().is_ok());
+    }
 }
@@ -10,25 +10,41 @@ use crate::{
     http::sender::{
         NextParams,
         NodeAddress,
+        NodeHealth,
         PreRequestParams,
         RequestParams,
     },
     private,
 };
-use std::sync::{
-    atomic::{
-        AtomicUsize,
-        Ordering,
+use rand::{
+    self,
+    Rng,
+};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+        Arc,
+        Mutex,
     },
-    Arc,
+    time::Duration,
 };
 
-/** Select a base address for a given request using some strategy. */
+/**
+Select a base address for a given request using some strategy.
+
+Nodes that fail to connect are skipped until they can be pinged again.
+If every node has failed then the strategy selects from all of them.
+*/
 #[derive(Clone)]
 pub struct StaticNodes<TStrategy = RoundRobin> {
     nodes: Vec<NodeAddress>,
     strategy: TStrategy,
     params: PreRequestParams,
+    health: NodeHealth,
 }
 
 impl<TStrategy> NextParams for StaticNodes<TStrategy>
@@ -39,7 +55,7 @@ where
 
     fn next(&self) -> Self::Params {
         self.strategy
-            .try_next(&self.nodes)
+            .try_next(&self.health.alive(&self.nodes))
             .map(|address| RequestParams::from_parts(address, self.params.clone()))
             .map_err(error::request)
     }
@@ -55,11 +71,43 @@ impl<TStrategy> StaticNodes<TStrategy> {
             )));
         }
 
+        self.health.retain(&nodes);
         self.nodes = nodes;
 
         Ok(())
     }
 
+    pub(crate) fn new(
+        nodes: Vec<NodeAddress>,
+        strategy: TStrategy,
+        params: PreRequestParams,
+    ) -> Self {
+        StaticNodes {
+            nodes,
+            strategy,
+            params,
+            health: NodeHealth::default(),
+        }
+    }
+
+    pub(crate) fn with_health(mut self, health: NodeHealth) -> Self {
+        self.health = health;
+        self
+    }
+
+    pub(crate) fn health(&self) -> &NodeHealth {
+        &self.health
+    }
+
+    pub(crate) fn strategy(&self) -> &TStrategy {
+        &self.strategy
+    }
+
+    /** Get the dead nodes that are ready to be pinged. */
+    pub(crate) fn start_resurrect(&self) -> Vec<NodeAddress> {
+        self.health.start_resurrect(&self.nodes)
+    }
+
     #[cfg(test)]
     pub(crate) fn get(&self) -> &[NodeAddress] {
         &self.nodes
@@ -75,20 +123,56 @@ impl StaticNodes<RoundRobin> {
     {
         let nodes: Vec<_> = nodes.into_iter().map(Into::into).collect();
 
-        let strategy = RoundRobin::default();
-
-        StaticNodes {
-            nodes,
-            strategy,
-            params,
-        }
+        StaticNodes::new(nodes, RoundRobin::default(), params)
     }
 }
 
-/** The strategy selects an address from a given collection. */
+/**
+The strategy selects an address from a given collection.
+
+Strategies are told when a request is sent to a node and when it finishes.
+Strategies that don't depend on how requests to nodes are going can ignore these calls.
+*/
 pub trait Strategy: Send + Sync {
     /** Try get the next address. */
     fn try_next(&self, nodes: &[NodeAddress]) -> Result<NodeAddress, StrategyError>;
+
+    /** A request is about to be sent to the given node. */
+    fn request_started(&self, _node: &NodeAddress) {}
+
+    /** A response was received from the given node after the elapsed time. */
+    fn request_finished(&self, _node: &NodeAddress, _elapsed: Duration) {}
+
+    /** A request to the given node failed without a response after the elapsed time. */
+    fn request_failed(&self, _node: &NodeAddress, _elapsed: Duration) {}
+
+    /** A request to the given node was dropped before it finished. */
+    fn request_cancelled(&self, _node: &NodeAddress) {}
+}
+
+impl<TStrategy> Strategy for Arc<TStrategy>
+where
+    TStrategy: Strategy + ?Sized,
+{
+    fn try_next(&self, nodes: &[NodeAddress]) -> Result<NodeAddress, StrategyError> {
+        (**self).try_next(nodes)
+    }
+
+    fn request_started(&self, node: &NodeAddress) {
+        (**self).request_started(node)
+    }
+
+    fn request_finished(&self, node: &NodeAddress, elapsed: Duration) {
+        (**self).request_finished(node, elapsed)
+    }
+
+    fn request_failed(&self, node: &NodeAddress, elapsed: Duration) {
+        (**self).request_failed(node, elapsed)
+    }
+
+    fn request_cancelled(&self, node: &NodeAddress) {
+        (**self).request_cancelled(node)
+    }
 }
 
 quick_error! {
@@ -135,6 +219,244 @@ impl Strategy for RoundRobin {
     }
 }
 
+/** A random strategy selects any node with equal probability. */
+#[derive(Clone, Default)]
+pub struct Random {
+    _private: (),
+}
+
+impl Strategy for Random {
+    fn try_next(&self, nodes: &[NodeAddress]) -> Result<NodeAddress, StrategyError> {
+        if nodes.is_empty() {
+            Err(StrategyError::Empty)
+        } else {
+            let i = rand::thread_rng().gen_range(0, nodes.len());
+            Ok(nodes[i].clone())
+        }
+    }
+}
+
+/**
+A weighted strategy selects nodes randomly in proportion to their weight.
+
+Nodes that haven't been given a weight have a weight of `1`.
+A node with a weight of `0` is only selected if every node has a weight of `0`.
+
+# Examples
+
+Send twice as many requests to `http://a:9200` as to any other node:
+
+```
+# use elastic::prelude::*;
+# use elastic::http::sender::static_nodes::Weighted;
+let strategy = Weighted::default().weight("http://a:9200", 2);
+
+let builder = SyncClientBuilder::new()
+    .static_nodes(vec!["http://a:9200", "http://b:9200"])
+    .strategy(strategy);
+```
+*/
+#[derive(Clone, Default)]
+pub struct Weighted {
+    weights: HashMap<NodeAddress, u32>,
+}
+
+impl Weighted {
+    /** Set the weight for a node. */
+    pub fn weight(mut self, node: impl Into<NodeAddress>, weight: u32) -> Self {
+        self.weights.insert(node.into(), weight);
+        self
+    }
+
+    fn weight_of(&self, node: &NodeAddress) -> u64 {
+        u64::from(self.weights.get(node).cloned().unwrap_or(1))
+    }
+}
+
+impl Strategy for Weighted {
+    fn try_next(&self, nodes: &[NodeAddress]) -> Result<NodeAddress, StrategyError> {
+        if nodes.is_empty() {
+            return Err(StrategyError::Empty);
+        }
+
+        let total: u64 = nodes.iter().map(|node| self.weight_of(node)).sum();
+
+        if total == 0 {
+            return Random::default().try_next(nodes);
+        }
+
+        let mut target = rand::thread_rng().gen_range(0, total);
+        for node in nodes {
+            let weight = self.weight_of(node);
+
+            if target < weight {
+                return Ok(node.clone());
+            }
+
+            target -= weight;
+        }
+
+        unreachable!("the target is less than the total weight of the nodes")
+    }
+}
+
+/**
+A least-in-flight strategy selects the node with the fewest requests that haven't finished yet.
+
+Ties between nodes are broken in a round-robin order.
+*/
+#[derive(Clone, Default)]
+pub struct LeastInFlight {
+    in_flight: Arc<Mutex<HashMap<NodeAddress, usize>>>,
+    index: Arc<AtomicUsize>,
+}
+
+#[cfg(test)]
+impl LeastInFlight {
+    pub(crate) fn in_flight(&self, node: &str) -> usize {
+        let in_flight = self.in_flight.lock().expect("lock poisoned");
+
+        in_flight.get(node).cloned().unwrap_or(0)
+    }
+}
+
+impl Strategy for LeastInFlight {
+    fn try_next(&self, nodes: &[NodeAddress]) -> Result<NodeAddress, StrategyError> {
+        if nodes.is_empty() {
+            return Err(StrategyError::Empty);
+        }
+
+        let in_flight = self.in_flight.lock().expect("lock poisoned");
+        let start = self.index.fetch_add(1, Ordering::Relaxed);
+
+        let node = (0..nodes.len())
+            .map(|i| &nodes[(start + i) % nodes.len()])
+            .min_by_key(|node| in_flight.get(*node).cloned().unwrap_or(0))
+            .expect("the list of addresses isn't empty");
+
+        Ok(node.clone())
+    }
+
+    fn request_started(&self, node: &NodeAddress) {
+        let mut in_flight = self.in_flight.lock().expect("lock poisoned");
+
+        *in_flight.entry(node.clone()).or_insert(0) += 1;
+    }
+
+    fn request_finished(&self, node: &NodeAddress, _elapsed: Duration) {
+        self.request_cancelled(node)
+    }
+
+    fn request_failed(&self, node: &NodeAddress, _elapsed: Duration) {
+        self.request_cancelled(node)
+    }
+
+    fn request_cancelled(&self, node: &NodeAddress) {
+        let mut in_flight = self.in_flight.lock().expect("lock poisoned");
+
+        if let Some(count) = in_flight.get_mut(node) {
+            *count = count.saturating_sub(1);
+        }
+    }
+}
+
+const DEFAULT_LATENCY_DECAY: f64 = 0.3;
+const DEFAULT_FAILED_LATENCY: Duration = Duration::from_secs(1);
+
+/**
+A latency-aware strategy prefers nodes that have responded quickly.
+
+The latency of each node is tracked as an exponentially weighted moving average of the time taken by requests to it.
+Two nodes are picked at random and the one with the lower average latency is selected.
+Picking from two random nodes instead of always using the fastest one stops every request going to the same node.
+Nodes that haven't been sent any requests yet have no latency so they're tried early.
+
+A request that fails without a response counts as taking at least 1 second.
+
+# Examples
+
+Weigh recent requests more heavily when averaging latency:
+
+```
+# use elastic::prelude::*;
+# use elastic::http::sender::static_nodes::LatencyAware;
+let builder = SyncClientBuilder::new()
+    .static_nodes(vec!["http://a:9200", "http://b:9200"])
+    .strategy(LatencyAware::default().decay(0.5));
+```
+*/
+#[derive(Clone)]
+pub struct LatencyAware {
+    latencies: Arc<Mutex<HashMap<NodeAddress, f64>>>,
+    decay: f64,
+}
+
+impl LatencyAware {
+    /**
+    Set how much weight the latest request has in the average latency for a node.
+
+    The decay is between `0` and `1`, where `1` only uses the latest request.
+    The default is `0.3`.
+    */
+    pub fn decay(mut self, decay: f64) -> Self {
+        self.decay = decay.clamp(0.0, 1.0);
+        self
+    }
+
+    fn latency_of(&self, latencies: &HashMap<NodeAddress, f64>, node: &NodeAddress) -> f64 {
+        latencies.get(node).cloned().unwrap_or(0.0)
+    }
+
+    fn record(&self, node: &NodeAddress, elapsed: Duration) {
+        let sample = elapsed.as_secs_f64() * 1000.0;
+        let mut latencies = self.latencies.lock().expect("lock poisoned");
+
+        let latency = latencies.entry(node.clone()).or_insert(sample);
+        *latency += self.decay * (sample - *latency);
+    }
+}
+
+impl Default for LatencyAware {
+    fn default() -> Self {
+        LatencyAware {
+            latencies: Arc::new(Mutex::new(HashMap::new())),
+            decay: DEFAULT_LATENCY_DECAY,
+        }
+    }
+}
+
+impl Strategy for LatencyAware {
+    fn try_next(&self, nodes: &[NodeAddress]) -> Result<NodeAddress, StrategyError> {
+        match nodes.len() {
+            0 => Err(StrategyError::Empty),
+            1 => Ok(nodes[0].clone()),
+            len => {
+                let mut rng = rand::thread_rng();
+
+                let a = rng.gen_range(0, len);
+                let b = (a + rng.gen_range(1, len)) % len;
+
+                let latencies = self.latencies.lock().expect("lock poisoned");
+
+                let (a, b) = (&nodes[a], &nodes[b]);
+                if self.latency_of(&latencies, b) < self.latency_of(&latencies, a) {
+                    Ok(b.clone())
+                } else {
+                    Ok(a.clone())
+                }
+            }
+        }
+    }
+
+    fn request_finished(&self, node: &NodeAddress, elapsed: Duration) {
+        self.record(node, elapsed)
+    }
+
+    fn request_failed(&self, node: &NodeAddress, elapsed: Duration) {
+        self.record(node, elapsed.max(DEFAULT_FAILED_LATENCY))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,10 +495,153 @@ mod tests {
         }
     }
 
+    #[test]
+    fn round_robin_next_skips_dead_nodes() {
+        let nodes = round_robin(expected_addresses());
+        nodes.health().mark_dead(&"http://b:9200".into());
+
+        for _ in 0..10 {
+            let actual = nodes.next().unwrap();
+
+            assert_ne!("http://b:9200", actual.get_base_url());
+        }
+    }
+
+    #[test]
+    fn round_robin_next_all_dead_uses_all_nodes() {
+        let nodes = round_robin(expected_addresses());
+
+        for address in expected_addresses() {
+            nodes.health().mark_dead(&address.into());
+        }
+
+        for expected in expected_addresses() {
+            let actual = nodes.next().unwrap();
+
+            assert_eq!(expected, actual.get_base_url());
+        }
+    }
+
     #[test]
     fn round_robin_next_empty_fails() {
         let nodes = round_robin(vec![]);
 
         assert!(nodes.next().is_err());
     }
+
+    fn addresses() -> Vec<NodeAddress> {
+        expected_addresses().into_iter().map(Into::into).collect()
+    }
+
+    #[test]
+    fn random_next_uses_all_nodes() {
+        let strategy = Random::default();
+        let nodes = addresses();
+
+        let mut selected = Vec::new();
+        for _ in 0..100 {
+            selected.push(strategy.try_next(&nodes).unwrap());
+        }
+
+        for node in &nodes {
+            assert!(selected.contains(node));
+        }
+    }
+
+    #[test]
+    fn weighted_next_skips_zero_weight() {
+        let strategy = Weighted::default().weight("http://b:9200", 0);
+        let nodes = addresses();
+
+        for _ in 0..100 {
+            let actual = strategy.try_next(&nodes).unwrap();
+
+            assert_ne!("http://b:9200", actual.as_ref());
+        }
+    }
+
+    #[test]
+    fn weighted_next_all_zero_weight_uses_all_nodes() {
+        let strategy = Weighted::default()
+            .weight("http://a:9200", 0)
+            .weight("http://b:9200", 0)
+            .weight("http://c:9200", 0);
+
+        assert!(strategy.try_next(&addresses()).is_ok());
+    }
+
+    #[test]
+    fn least_in_flight_next() {
+        let strategy = LeastInFlight::default();
+        let nodes = addresses();
+
+        strategy.request_started(&nodes[0]);
+        strategy.request_started(&nodes[1]);
+
+        for _ in 0..10 {
+            assert_eq!(nodes[2].as_ref(), strategy.try_next(&nodes).unwrap().as_ref());
+        }
+
+        strategy.request_started(&nodes[2]);
+        strategy.request_started(&nodes[2]);
+        strategy.request_finished(&nodes[1], Duration::from_millis(1));
+
+        for _ in 0..10 {
+            assert_eq!(nodes[1].as_ref(), strategy.try_next(&nodes).unwrap().as_ref());
+        }
+    }
+
+    #[test]
+    fn least_in_flight_cancelled_requests_finish() {
+        let strategy = LeastInFlight::default();
+        let nodes = addresses();
+
+        strategy.request_started(&nodes[0]);
+        strategy.request_cancelled(&nodes[0]);
+
+        assert_eq!(0, strategy.in_flight(nodes[0].as_ref()));
+    }
+
+    #[test]
+    fn least_in_flight_next_ties_round_robin() {
+        let strategy = LeastInFlight::default();
+        let nodes = addresses();
+
+        for expected in &nodes {
+            assert_eq!(expected.as_ref(), strategy.try_next(&nodes).unwrap().as_ref());
+        }
+    }
+
+    #[test]
+    fn latency_aware_next_prefers_fast_nodes() {
+        let strategy = LatencyAware::default();
+        let nodes = &addresses()[..2];
+
+        strategy.request_finished(&nodes[0], Duration::from_millis(500));
+        strategy.request_finished(&nodes[1], Duration::from_millis(10));
+
+        for _ in 0..10 {
+            assert_eq!(nodes[1].as_ref(), strategy.try_next(nodes).unwrap().as_ref());
+        }
+
+        strategy.request_failed(&nodes[1], Duration::from_millis(10));
+        strategy.request_failed(&nodes[1], Duration::from_millis(10));
+
+        for _ in 0..10 {
+            assert_eq!(nodes[0].as_ref(), strategy.try_next(nodes).unwrap().as_ref());
+        }
+    }
+
+    #[test]
+    fn latency_aware_moving_average() {
+        let strategy = LatencyAware::default().decay(0.5);
+        let node = &addresses()[0];
+
+        strategy.request_finished(node, Duration::from_millis(100));
+        strategy.request_finished(node, Duration::from_millis(200));
+
+        let latencies = strategy.latencies.lock().unwrap();
+
+        assert_eq!(150.0, strategy.latency_of(&latencies, node));
+    }
 }
@@ -1,11 +1,16 @@
 use reqwest::{
     Client as SyncHttpClient,
+    Request as RawRequest,
     RequestBuilder as SyncHttpRequestBuilder,
 };
 use std::{
     error::Error as StdError,
     sync::Arc,
+    thread,
+    time::Instant,
 };
+use tracing::Span;
+use uuid::Uuid;
 
 use crate::{
     endpoints::Endpoint,
@@ -19,19 +24,57 @@ use crate::{
             SyncResponseBuilder,
         },
         sender::{
+            attempt::{
+                attempt_span,
+                build_request,
+                prepare_request,
+                request_span,
+                Attempt,
+                AttemptOutcome,
+                AttemptResult,
+                AttemptTimer,
+                LogErr,
+                PreparedRequest,
+                Transport,
+            },
             build_reqwest_method,
-            build_url,
+            health::{
+                build_ping,
+                PING_TIMEOUT,
+            },
+            middleware::{
+                after_receive,
+                before_send_sync,
+                BeforeSendResult,
+                MiddlewareChain,
+            },
+            retry::retry_params,
+            timeouts::{
+                send_error,
+                DeadlineRead,
+            },
+            BeforeSend,
+            BufferedResponse,
+            ClientTimeouts,
+            CredentialsProvider,
+            FixtureRecorder,
+            HttpClients,
+            MetricsObserver,
+            MetricsRequest,
+            Middleware,
+            MockTransport,
             NextParams,
             NodeAddresses,
             NodeAddressesInner,
             RequestParams,
+            RetryPolicy,
             SendableRequest,
             SendableRequestParams,
             Sender,
         },
+        StatusCode,
         SyncBody,
         SyncHttpRequest,
-        Url,
     },
     private,
 };
@@ -42,12 +85,44 @@ pub(crate) type SyncPreSend =
 /** A synchronous request sender. */
 #[derive(Clone)]
 pub struct SyncSender {
-    pub(crate) http: SyncHttpClient,
-    pub(crate) pre_send: Option<Arc<SyncPreSend>>,
+    pub(crate) http: HttpClients<SyncHttpClient>,
+    pub(crate) middleware: MiddlewareChain<SyncBody>,
+    pub(crate) metrics: Option<Arc<dyn MetricsObserver>>,
+    pub(crate) credentials: Option<Arc<dyn CredentialsProvider>>,
+    pub(crate) gzip: bool,
+    pub(crate) mock: Option<MockTransport>,
+    pub(crate) recorder: Option<FixtureRecorder>,
+    pub(crate) retry_policy: Arc<RetryPolicy>,
 }
 
 impl private::Sealed for SyncSender {}
 
+impl SyncSender {
+    /**
+    Ping a node to see whether it's alive.
+
+    The ping is sent straight to the node with a short timeout.
+    It isn't retried and doesn't go through middleware, metrics or fixture recording.
+    */
+    fn ping(&self, params: &RequestParams) -> Result<StatusCode, Error> {
+        let req = build_ping(params, self.credentials.as_deref())?;
+
+        let res = match self.mock {
+            Some(ref mock) => mock.send_sync(req)?,
+            None => {
+                let http = self.http.get(PING_TIMEOUTS)?;
+                let req = build_reqwest(&http, req).build().map_err(error::request)?;
+
+                http.execute(req)
+                    .map_err(|e| send_error(e).1)
+                    .and_then(sync_response)?
+            }
+        };
+
+        Ok(res.status())
+    }
+}
+
 impl Sender for SyncSender {
     type Body = SyncBody;
     type Response = Result<SyncResponseBuilder, Error>;
@@ -63,18 +138,33 @@ impl Sender for SyncSender {
         TParams: Into<Self::Params> + Send + 'static,
     {
         let correlation_id = request.correlation_id;
-        let params = request.params;
-        let endpoint = request.inner.into();
+        let Endpoint {
+            name: endpoint,
+            url,
+            method,
+            body,
+            ..
+        } = request.inner.into();
+
+        let span = request_span(correlation_id, endpoint, &method);
+        let _span = span.enter();
+        let request_start = Instant::now();
+        let _record_duration = RecordDuration::new(&span, request_start);
 
         info!(
             "Elasticsearch Request: correlation_id: '{}', path: '{}'",
             correlation_id,
-            endpoint.url.as_ref()
+            url.as_ref()
         );
 
-        let params = match params {
-            SendableRequestParams::Value(params) => params,
-            SendableRequestParams::Builder { params, builder } => {
+        let (mut params, next_params, feedback) = match request.params {
+            SendableRequestParams::Value(params) => (params, None, None),
+            SendableRequestParams::Builder {
+                params,
+                next,
+                feedback,
+                builder,
+            } => {
                 let params = params.into().inner.log_err(|e| {
                     error!(
                         "Elasticsearch Node Selection: correlation_id: '{}', error: '{:?}'",
@@ -82,52 +172,169 @@ impl Sender for SyncSender {
                     )
                 })?;
 
-                builder.into_value(move || params)
+                (builder.into_value(move || params), Some(next), Some(feedback))
             }
         };
 
-        let mut req = build_req(endpoint, params).log_err(|e| {
-            error!(
-                "Elasticsearch Request: correlation_id: '{}', error: '{:?}'",
-                correlation_id, e
-            )
-        })?;
+        let deadline = params.get_timeouts().deadline(request_start);
+
+        let mut body = body.map(|body| body.into());
+        let mut attempt = 1;
 
-        if let Some(ref pre_send) = self.pre_send {
-            pre_send(&mut req)
-                .map_err(error::wrapped)
+        if self.gzip {
+            body = body
+                .map(SyncBody::gzip)
+                .transpose()
                 .map_err(error::request)
                 .log_err(|e| {
                     error!(
-                        "Elasticsearch Request Pre-send: correlation_id: '{}', error: '{:?}'",
+                        "Elasticsearch Request: correlation_id: '{}', error: '{:?}'",
                         correlation_id, e
                     )
                 })?;
         }
 
-        let req = build_reqwest(&self.http, req)
-            .build()
-            .map_err(error::request)?;
+        loop {
+            let attempt_span = attempt_span(attempt, &params);
+            let _attempt_span = attempt_span.enter();
 
-        let res = match self.http.execute(req).map_err(error::request) {
-            Ok(res) => {
-                info!(
-                    "Elasticsearch Response: correlation_id: '{}', status: '{}'",
-                    correlation_id,
-                    res.status()
-                );
-                res
-            }
-            Err(e) => {
+            // Keep a copy of the body in case the request needs to be retried
+            let retry_body = if self.retry_policy.can_retry_after(&method, attempt) {
+                match body {
+                    Some(ref body) => body.try_clone().map(Some),
+                    None => Some(None),
+                }
+            } else {
+                None
+            };
+
+            let req = build_request(
+                correlation_id,
+                &url,
+                method.clone(),
+                body,
+                &params,
+                self.gzip,
+                self.credentials.as_deref(),
+            )?;
+
+            let before_send = before_send_sync(&self.middleware, req).log_err(|e| {
                 error!(
-                    "Elasticsearch Response: correlation_id: '{}', error: '{:?}'",
+                    "Elasticsearch Request Middleware: correlation_id: '{}', error: '{:?}'",
                     correlation_id, e
-                );
-                return Err(e);
+                )
+            })?;
+
+            let prepared = self.prepare(correlation_id, before_send, client_timeouts(&params))?;
+
+            let node = params.node_address().clone();
+            let sent = Attempt::start(
+                correlation_id,
+                attempt_span.clone(),
+                MetricsRequest::new(endpoint, method.clone(), node, attempt),
+                prepared.transport.is_sent(),
+                prepared.body_len,
+                self.metrics.as_ref(),
+                feedback.as_ref(),
+            );
+
+            let res = self.send_prepared(prepared, sent.timer(), deadline);
+
+            match sent.finish(res, &self.retry_policy, retry_body.is_some(), deadline) {
+                AttemptOutcome::Done(res) => {
+                    if let Ok(ref res) = res {
+                        span.record("status", res.status().as_u16());
+                    }
+
+                    return res;
+                }
+                AttemptOutcome::Retry(backoff) => thread::sleep(backoff),
+            }
+
+            if let Some(ref next_params) = next_params {
+                params = retry_params(params, next_params().into().inner, correlation_id);
+            }
+
+            body = retry_body.flatten();
+            attempt += 1;
+        }
+    }
+}
+
+/** A request that's been through middleware and is ready to be sent synchronously. */
+type SyncPreparedRequest = PreparedRequest<SyncBody, SyncHttpClient, RawRequest>;
+
+impl SyncSender {
+    /** Pick the transport to send a single attempt of a request with after it's been through middleware. */
+    fn prepare(
+        &self,
+        correlation_id: Uuid,
+        before_send: BeforeSendResult<SyncBody>,
+        timeouts: ClientTimeouts,
+    ) -> Result<SyncPreparedRequest, Error> {
+        prepare_request(
+            correlation_id,
+            before_send,
+            &self.middleware,
+            self.mock.as_ref(),
+            self.recorder.is_some(),
+            |req| {
+                let http = self.http.get(timeouts)?;
+                let req = build_reqwest(&http, req).build().map_err(error::request)?;
+
+                Ok((http, req))
+            },
+        )
+    }
+
+    /** Send a single attempt of a request and read its response, passing it to any recorder and middleware. */
+    fn send_prepared(
+        &self,
+        prepared: SyncPreparedRequest,
+        timer: &AttemptTimer,
+        deadline: Option<Instant>,
+    ) -> AttemptResult<SyncResponseBuilder> {
+        let PreparedRequest {
+            transport,
+            recorded,
+            receivers,
+            ..
+        } = prepared;
+
+        let res = match transport {
+            Transport::Respond(res) => Ok(res.into_sync()),
+            Transport::Mock(mock, req) => mock.send_sync(req).map_err(|e| (None, e)),
+            Transport::Http(http, req) => http
+                .execute(req)
+                .map_err(send_error)
+                .and_then(|res| sync_response(res).map_err(|e| (None, e)))
+                .map(|res| match deadline {
+                    Some(deadline) => {
+                        res.map_body(|body| Box::new(DeadlineRead::new(body, deadline)))
+                    }
+                    None => res,
+                }),
+        };
+
+        let res = timer.received(res);
+
+        let res = match (&self.recorder, recorded) {
+            (Some(recorder), Some(req)) => {
+                res.and_then(|res| recorder.record_sync(&req, res).map_err(|e| (None, e)))
             }
+            _ => res,
         };
 
-        sync_response(res)
+        match receivers {
+            Some((req, called)) => res.and_then(|res| {
+                let mut res = BufferedResponse::from_sync(res).map_err(|e| (None, e))?;
+
+                after_receive(&self.middleware, called, &req, &mut res).map_err(|e| (None, e))?;
+
+                Ok(res.into_sync())
+            }),
+            None => res,
+        }
     }
 }
 
@@ -135,6 +342,8 @@ impl NextParams for NodeAddresses<SyncSender> {
     type Params = Params;
 
     fn next(&self) -> Self::Params {
+        self.resurrect();
+
         match self.inner {
             NodeAddressesInner::Static(ref nodes) => Params::new(nodes.next()),
             NodeAddressesInner::Sniffed(ref sniffer) => Params::new(sniffer.next()),
@@ -142,6 +351,41 @@ impl NextParams for NodeAddresses<SyncSender> {
     }
 }
 
+impl NodeAddresses<SyncSender> {
+    /** Ping any dead nodes that are ready to be tried again on a thread and mark them alive if they respond. */
+    fn resurrect(&self) {
+        let resurrect_params = self.resurrect_params();
+
+        if resurrect_params.is_empty() {
+            return;
+        }
+
+        let health = self.health();
+        let nodes: Vec<_> = resurrect_params.iter().map(|(node, _)| node.clone()).collect();
+
+        let sender = self.sender.clone();
+        let pinged_health = health.clone();
+        let spawned = thread::Builder::new()
+            .name("elastic-resurrect".to_owned())
+            .spawn(move || {
+                for (node, params) in resurrect_params {
+                    pinged_health.finish_resurrect(&node, sender.ping(&params));
+                }
+            });
+
+        if let Err(e) = spawned {
+            warn!(
+                "Elasticsearch Node Health: failed to start pinging dead nodes, error: '{:?}'",
+                e
+            );
+
+            for node in &nodes {
+                health.stop_resurrect(node);
+            }
+        }
+    }
+}
+
 /** A set of parameters returned by calling `next` on a sync set of `NodeAddresses`. */
 pub struct Params {
     inner: Result<RequestParams, Error>,
@@ -151,6 +395,10 @@ impl Params {
     fn new(res: Result<RequestParams, Error>) -> Self {
         Params { inner: res }
     }
+
+    pub(crate) fn into_inner(self) -> Result<RequestParams, Error> {
+        self.inner
+    }
 }
 
 impl From<RequestParams> for Params {
@@ -159,19 +407,67 @@ impl From<RequestParams> for Params {
     }
 }
 
-/** Build an Elasticsearch request from an endpoint. */
-fn build_req(
-    endpoint: Endpoint<impl Into<SyncBody>>,
-    params: RequestParams,
-) -> Result<SyncHttpRequest, Error> {
-    let endpoint = SyncHttpRequest {
-        url: Url::parse(&build_url(&endpoint.url, &params)).map_err(error::request)?,
-        method: endpoint.method,
-        headers: params.get_headers(),
-        body: endpoint.body.map(|body| body.into()),
+/** Run a function given to `pre_send_raw` as the last middleware. */
+pub(crate) struct SyncPreSendMiddleware(pub(crate) Arc<SyncPreSend>);
+
+impl Middleware<SyncBody> for SyncPreSendMiddleware {
+    fn before_send(
+        &self,
+        req: &mut SyncHttpRequest,
+    ) -> Result<BeforeSend, Box<dyn StdError + Send + Sync>> {
+        (self.0)(req)?;
+
+        Ok(BeforeSend::Continue)
+    }
+}
+
+/** The timeouts for pinging a dead node. */
+const PING_TIMEOUTS: ClientTimeouts = ClientTimeouts {
+    connect: Some(PING_TIMEOUT),
+    timeout: Some(PING_TIMEOUT),
+};
+
+/**
+Records how long a request took on its span when it's dropped.
+
+This covers every way a request can return, including early errors from selecting a node or authorizing the request.
+*/
+struct RecordDuration<'a> {
+    span: &'a Span,
+    start: Instant,
+}
+
+impl<'a> RecordDuration<'a> {
+    fn new(span: &'a Span, start: Instant) -> Self {
+        RecordDuration { span, start }
+    }
+}
+
+impl Drop for RecordDuration<'_> {
+    fn drop(&mut self) {
+        self.span.record("duration_ms", self.start.elapsed().as_millis() as u64);
+    }
+}
+
+/**
+Get the timeouts to build a client for the request with.
+
+The synchronous client's timeout applies to waiting for the response and each read of its body.
+It's the read timeout, or the total timeout if that's shorter, so a single wait can't outlast the total timeout.
+The deadline for the total timeout is checked between attempts and reads of the body.
+*/
+fn client_timeouts(params: &RequestParams) -> ClientTimeouts {
+    let timeouts = params.get_timeouts();
+
+    let timeout = match (timeouts.read, timeouts.total) {
+        (Some(read), Some(total)) => Some(read.min(total)),
+        (read, total) => read.or(total),
     };
 
-    Ok(endpoint)
+    ClientTimeouts {
+        connect: timeouts.connect,
+        timeout,
+    }
 }
 
 /** Build a synchronous `reqwest::RequestBuilder` from an Elasticsearch request. */
@@ -198,21 +494,1011 @@ fn build_reqwest(client: &SyncHttpClient, req: SyncHttpRequest) -> SyncHttpReque
     req
 }
 
-trait LogErr<E> {
-    fn log_err<F>(self, log: F) -> Self
-    where
-        F: FnOnce(&E);
-}
+#[cfg(test)]
+mod tests {
+    use flate2::read::GzDecoder;
+    use std::{
+        collections::BTreeMap,
+        env,
+        error::Error as StdError,
+        fmt,
+        fs,
+        io::{
+            Read,
+            Write,
+        },
+        net::TcpListener,
+        sync::{
+            atomic::{
+                AtomicUsize,
+                Ordering,
+            },
+            mpsc::{
+                self,
+                Receiver,
+            },
+            Arc,
+            Mutex,
+        },
+        process,
+        thread,
+        time::{
+            Duration,
+            Instant,
+        },
+    };
 
-impl<T, E> LogErr<E> for Result<T, E> {
-    fn log_err<F>(self, log: F) -> Self
-    where
-        F: FnOnce(&E),
-    {
-        if let Err(ref e) = self {
-            log(e);
+    use serde_json::Value;
+    use tracing::{
+        field::{
+            Field,
+            Visit,
+        },
+        span::{
+            Attributes,
+            Id,
+            Record,
+        },
+        Event,
+        Metadata,
+        Subscriber,
+    };
+    use uuid::Uuid;
+
+    use crate::{
+        error::Error,
+        http::{
+            header::{
+                HeaderName,
+                HeaderValue,
+                AUTHORIZATION,
+            },
+            sender::{
+                health::PING_TIMEOUT,
+                BeforeSend,
+                BufferedResponse,
+                Fixtures,
+                InMemoryMetrics,
+                Middleware,
+                MockRequest,
+                MockResponse,
+                MockTransport,
+            },
+            HttpRequest,
+            Method,
+            StatusCode,
+            SyncBody,
+        },
+        prelude::*,
+    };
+
+    /** Serve a response with each of the given statuses in order. */
+    fn serve(statuses: Vec<u16>) -> String {
+        serve_on(TcpListener::bind("127.0.0.1:0").unwrap(), statuses).0
+    }
+
+    /** Serve a response with each of the given statuses and send the method of each request. */
+    fn serve_on(listener: TcpListener, statuses: Vec<u16>) -> (String, Receiver<String>) {
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, stream) in statuses.into_iter().zip(listener.incoming()) {
+                let mut stream = stream.unwrap();
+
+                let mut buf = [0; 4096];
+                let len = stream.read(&mut buf).unwrap_or(0);
+
+                let req = String::from_utf8_lossy(&buf[..len]);
+                let method = req.split(' ').next().unwrap_or_default().to_owned();
+                let _ = tx.send(method);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Status\r\ncontent-length: 2\r\nconnection: close\r\n\r\n{{}}",
+                    status
+                )
+                .unwrap();
+            }
+        });
+
+        (address, rx)
+    }
+
+    /**
+    Serve a nodes info response that points back to this server for sniffing, or an empty response otherwise.
+
+    The path of each request is sent without its query string.
+    */
+    fn serve_sniff(requests: usize) -> (String, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let publish_address = listener.local_addr().unwrap().to_string();
+        let address = format!("http://{}", publish_address);
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+
+                let mut buf = [0; 4096];
+                let len = stream.read(&mut buf).unwrap_or(0);
+
+                let req = String::from_utf8_lossy(&buf[..len]);
+                let url = req.split(' ').nth(1).unwrap_or_default();
+                let path = url.split('?').next().unwrap_or_default().to_owned();
+
+                let body = if path.starts_with("/_nodes") {
+                    json!({
+                        "nodes": {
+                            "node": { "http": { "publish_address": publish_address } }
+                        }
+                    })
+                    .to_string()
+                } else {
+                    "{}".to_owned()
+                };
+
+                let _ = tx.send(path);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        (address, rx)
+    }
+
+    /** Serve a response to each request after waiting for the given delay. */
+    fn serve_slowly(requests: usize, delay: Duration) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+
+        thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+
+                let mut buf = [0; 4096];
+                let _ = stream.read(&mut buf);
+
+                thread::sleep(delay);
+
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\n{{}}"
+                );
+            }
+        });
+
+        address
+    }
+
+    /** Serve a response whose body is written one byte at a time with a delay between each. */
+    fn serve_trickle(delay: Duration) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+
+        thread::spawn(move || {
+            let mut stream = listener.incoming().next().unwrap().unwrap();
+
+            let mut buf = [0; 4096];
+            let _ = stream.read(&mut buf);
+
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\ncontent-length: 100\r\nconnection: close\r\n\r\n"
+            );
+
+            for _ in 0..100 {
+                thread::sleep(delay);
+
+                if stream.write_all(b" ").is_err() {
+                    break;
+                }
+            }
+        });
+
+        address
+    }
+
+    /** Serve a single response and send the headers and body of the request. */
+    fn serve_capture() -> (String, Receiver<(String, Vec<u8>)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let mut stream = listener.incoming().next().unwrap().unwrap();
+
+            let mut req = Vec::new();
+            let mut buf = [0; 4096];
+
+            // Read until the headers and the whole body have been received
+            let (head, body) = loop {
+                let len = stream.read(&mut buf).unwrap();
+                req.extend_from_slice(&buf[..len]);
+
+                let end = match req.windows(4).position(|w| w == b"\r\n\r\n") {
+                    Some(end) => end,
+                    None if len > 0 => continue,
+                    None => break (String::new(), Vec::new()),
+                };
+
+                let head = String::from_utf8_lossy(&req[..end]).to_lowercase();
+                let content_length = head
+                    .lines()
+                    .find(|line| line.starts_with("content-length:"))
+                    .and_then(|line| line["content-length:".len()..].trim().parse().ok())
+                    .unwrap_or(0);
+
+                if req.len() >= end + 4 + content_length || len == 0 {
+                    break (head, req[end + 4..].to_vec());
+                }
+            };
+
+            let _ = tx.send((head, body));
+
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\n{{}}"
+            );
+        });
+
+        (address, rx)
+    }
+
+    /** Get an address that refuses connections. */
+    fn refuse() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    fn client(address: String) -> SyncClient {
+        let retry_policy = RetryPolicy::new()
+            .jitter(false)
+            .backoff(Duration::from_millis(1), Duration::from_millis(1));
+
+        SyncClientBuilder::new()
+            .static_node(address)
+            .retry_policy(retry_policy)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn retry_retryable_status() {
+        let client = client(serve(vec![503, 429, 200]));
+
+        let res = client.request(PingRequest::new()).send().unwrap();
+
+        assert_eq!(StatusCode::OK, res.status());
+    }
+
+    #[test]
+    fn retry_until_max_attempts() {
+        let client = client(serve(vec![503, 503, 503, 200]));
+
+        let res = client.request(PingRequest::new()).send().unwrap();
+
+        assert_eq!(StatusCode::SERVICE_UNAVAILABLE, res.status());
+    }
+
+    #[test]
+    fn dont_retry_non_idempotent() {
+        let client = client(serve(vec![503, 200]));
+
+        let res = client
+            .request(SearchRequest::new(empty_body()))
+            .send()
+            .unwrap();
+
+        assert_eq!(StatusCode::SERVICE_UNAVAILABLE, res.status());
+    }
+
+    #[test]
+    fn skip_dead_nodes() {
+        let client = SyncClientBuilder::new()
+            .static_nodes(vec![refuse(), serve(vec![200, 200])])
+            .build()
+            .unwrap();
+
+        // The first node fails to connect and is marked dead
+        assert!(client.request(PingRequest::new()).send().is_err());
+
+        for _ in 0..2 {
+            let res = client.request(PingRequest::new()).send().unwrap();
+
+            assert_eq!(StatusCode::OK, res.status());
         }
+    }
+
+    #[test]
+    fn retry_dead_node_on_next_node() {
+        let client = SyncClientBuilder::new()
+            .static_nodes(vec![refuse(), serve(vec![200])])
+            .retry_policy(RetryPolicy::new().jitter(false))
+            .build()
+            .unwrap();
+
+        let res = client.request(PingRequest::new()).send().unwrap();
+
+        assert_eq!(StatusCode::OK, res.status());
+    }
+
+    #[test]
+    fn resurrect_dead_nodes() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let client = SyncClientBuilder::new()
+            .static_node(format!("http://{}", local_addr))
+            .dead_node_timeout(Duration::from_millis(0), Duration::from_millis(0))
+            .build()
+            .unwrap();
+
+        assert!(client.request(PingRequest::new()).send().is_err());
+
+        // The node is pinged in the background when it's due to be tried again
+        let (_, requests) = serve_on(TcpListener::bind(local_addr).unwrap(), vec![200, 200]);
+
+        let res = client.request(PingRequest::new()).send().unwrap();
+
+        assert_eq!(StatusCode::OK, res.status());
+
+        let mut methods: Vec<_> = requests.iter().take(2).collect();
+        methods.sort();
+
+        assert_eq!(vec!["GET", "HEAD"], methods);
+    }
+
+    #[test]
+    fn slow_dead_node_doesnt_delay_requests() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let client = SyncClientBuilder::new()
+            .static_nodes(vec![format!("http://{}", local_addr), serve(vec![200, 200])])
+            .dead_node_timeout(Duration::from_millis(0), Duration::from_millis(0))
+            .retry_policy(RetryPolicy::new().jitter(false))
+            .build()
+            .unwrap();
+
+        // The first node refuses the connection so it's marked dead
+        client.request(PingRequest::new()).send().unwrap();
+
+        // The dead node accepts the ping but never responds to it
+        let _slow = TcpListener::bind(local_addr).unwrap();
+
+        let start = Instant::now();
+        let res = client.request(PingRequest::new()).send().unwrap();
+
+        assert_eq!(StatusCode::OK, res.status());
+        assert!(start.elapsed() < PING_TIMEOUT);
+    }
+
+    #[test]
+    fn sniff_nodes_in_background() {
+        let (address, requests) = serve_sniff(2);
+
+        let client = SyncClientBuilder::new()
+            .sniff_nodes_fluent(address, |n| n.refresh_in_background(true))
+            .build()
+            .unwrap();
+
+        // The request isn't blocked by the first refresh
+        let res = client.request(PingRequest::new()).send().unwrap();
+
+        assert_eq!(StatusCode::OK, res.status());
+
+        let mut paths: Vec<_> = requests.iter().take(2).collect();
+        paths.sort();
+
+        assert_eq!(vec!["/", "/_nodes"], paths);
+    }
+
+    #[test]
+    fn timeout_slow_response() {
+        let client = SyncClientBuilder::new()
+            .static_node(serve_slowly(1, Duration::from_secs(2)))
+            .params_fluent(|p| p.total_timeout(Duration::from_millis(100)))
+            .build()
+            .unwrap();
+
+        let res = client.request(PingRequest::new()).send();
+
+        assert!(matches!(res, Err(Error::Timeout(_))));
+    }
+
+    #[test]
+    fn request_timeout_overrides_client_timeout() {
+        let client = SyncClientBuilder::new()
+            .static_node(serve_slowly(1, Duration::from_millis(200)))
+            .params_fluent(|p| p.total_timeout(Duration::from_millis(50)))
+            .build()
+            .unwrap();
+
+        let res = client
+            .request(PingRequest::new())
+            .total_timeout(Duration::from_secs(5))
+            .send()
+            .unwrap();
+
+        assert_eq!(StatusCode::OK, res.status());
+    }
+
+    #[test]
+    fn read_timeout_slow_response() {
+        let client = SyncClientBuilder::new()
+            .static_node(serve_slowly(1, Duration::from_secs(2)))
+            .params_fluent(|p| p.read_timeout(Duration::from_millis(100)))
+            .build()
+            .unwrap();
+
+        let res = client.request(PingRequest::new()).send();
+
+        assert!(matches!(res, Err(Error::Timeout(_))));
+    }
+
+    #[test]
+    fn total_timeout_stops_retrying() {
+        let (address, requests) =
+            serve_on(TcpListener::bind("127.0.0.1:0").unwrap(), vec![503; 5]);
+
+        let retry_policy = RetryPolicy::new()
+            .jitter(false)
+            .max_attempts(5)
+            .backoff(Duration::from_millis(200), Duration::from_millis(200));
+
+        let client = SyncClientBuilder::new()
+            .static_node(address)
+            .retry_policy(retry_policy)
+            .params_fluent(|p| p.total_timeout(Duration::from_millis(300)))
+            .build()
+            .unwrap();
+
+        // There's only time for one backoff so the second response is returned
+        let res = client.request(PingRequest::new()).send().unwrap();
+
+        assert_eq!(StatusCode::SERVICE_UNAVAILABLE, res.status());
+        assert_eq!(2, requests.try_iter().count());
+    }
+
+    #[test]
+    fn total_timeout_covers_reading_body() {
+        let client = SyncClientBuilder::new()
+            .static_node(serve_trickle(Duration::from_millis(50)))
+            .params_fluent(|p| {
+                p.read_timeout(Duration::from_secs(1))
+                    .total_timeout(Duration::from_millis(300))
+            })
+            .build()
+            .unwrap();
+
+        let start = Instant::now();
+        let res = client
+            .request(PingRequest::new())
+            .send()
+            .unwrap()
+            .into_response::<Value>();
+
+        assert!(res.is_err());
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn credentials_provider_is_called_for_sniff_requests() {
+        let (address, requests) = serve_sniff(2);
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let client = {
+            let calls = calls.clone();
+
+            SyncClientBuilder::new()
+                .sniff_nodes(address)
+                .credentials_provider(move || {
+                    calls.fetch_add(1, Ordering::SeqCst);
+
+                    Ok(Some(Credentials::bearer_token("a token")))
+                })
+                .build()
+                .unwrap()
+        };
+
+        client.request(PingRequest::new()).send().unwrap();
+
+        assert_eq!(vec!["/_nodes", "/"], requests.iter().take(2).collect::<Vec<_>>());
+        assert_eq!(2, calls.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn gzip_request_body() {
+        let (address, requests) = serve_capture();
+
+        let client = SyncClientBuilder::new()
+            .static_node(address)
+            .gzip(true)
+            .build()
+            .unwrap();
+
+        let res = client
+            .request(SearchRequest::new(r#"{"query":{"match_all":{}}}"#))
+            .send()
+            .unwrap();
+
+        let (head, body) = requests.recv().unwrap();
+
+        let mut decoded = String::new();
+        GzDecoder::new(&body[..])
+            .read_to_string(&mut decoded)
+            .unwrap();
+
+        assert_eq!(StatusCode::OK, res.status());
+        assert!(head.contains("content-encoding: gzip"));
+        assert!(head.contains("accept-encoding: gzip"));
+        assert_eq!(r#"{"query":{"match_all":{}}}"#, decoded);
+    }
+
+    #[test]
+    fn mock_typed_requests() {
+        let mock = MockTransport::new()
+            .respond(
+                MockRequest::new(Method::GET, "/myindex/_doc/1"),
+                MockResponse::new(StatusCode::OK).body(json!({
+                    "_index": "myindex",
+                    "_type": "_doc",
+                    "_id": "1",
+                    "found": true,
+                    "_source": { "title": "A document" }
+                })),
+            )
+            .respond(
+                MockRequest::new(Method::POST, "/myindex/_doc/2").body(json!({
+                    "title": "Another document"
+                })),
+                MockResponse::new(StatusCode::CREATED).body(json!({
+                    "_index": "myindex",
+                    "_type": "_doc",
+                    "_id": "2",
+                    "result": "created",
+                    "_shards": { "total": 1, "successful": 1, "failed": 0 }
+                })),
+            );
+
+        let client = SyncClientBuilder::new().mock(mock.clone()).build().unwrap();
+
+        let res = client
+            .document::<Value>()
+            .get_raw("myindex", 1)
+            .send()
+            .unwrap();
+
+        assert_eq!(Some(json!({ "title": "A document" })), res.into_document());
+
+        let res = client
+            .document::<Value>()
+            .index_raw("myindex", json!({ "title": "Another document" }))
+            .id(2)
+            .send()
+            .unwrap();
+
+        assert!(res.created());
+
+        let methods: Vec<_> = mock.requests().iter().map(|req| req.method().clone()).collect();
+        assert_eq!(vec![Method::GET, Method::POST], methods);
+    }
+
+    #[test]
+    fn mock_unexpected_request() {
+        let mock = MockTransport::new();
+        let client = SyncClientBuilder::new().mock(mock.clone()).build().unwrap();
+
+        assert!(client.request(PingRequest::new()).send().is_err());
+        assert_eq!("/", mock.requests()[0].url().path());
+    }
+
+    #[test]
+    fn retry_mock_status() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+
+        let mock = {
+            let attempts = attempts.clone();
+
+            MockTransport::new().respond_with(MockRequest::new(Method::GET, "/"), move |_| {
+                match attempts.fetch_add(1, Ordering::SeqCst) {
+                    0 => MockResponse::new(StatusCode::SERVICE_UNAVAILABLE),
+                    _ => MockResponse::new(StatusCode::OK),
+                }
+            })
+        };
+
+        let client = SyncClientBuilder::new()
+            .mock(mock.clone())
+            .retry_policy(RetryPolicy::new().jitter(false))
+            .build()
+            .unwrap();
+
+        let res = client.request(PingRequest::new()).send().unwrap();
+
+        assert_eq!(StatusCode::OK, res.status());
+        assert_eq!(2, mock.requests().len());
+    }
+
+    #[test]
+    fn record_and_replay_fixtures() {
+        let path = env::temp_dir().join(format!("elastic-fixtures-{}.ndjson", process::id()));
+
+        let search = |timestamp: u64| {
+            SearchRequest::for_index("myindex", json!({ "query": { "term": { "at": timestamp } } }))
+        };
+
+        let client = SyncClientBuilder::new()
+            .static_node(serve(vec![503, 200]))
+            .fixtures(Fixtures::record(&path).normalize("at", 0))
+            .build()
+            .unwrap();
+
+        for timestamp in 1..3 {
+            client.request(search(timestamp)).send().unwrap();
+        }
+
+        let recorded = fs::read_to_string(&path).unwrap();
+        assert_eq!(2, recorded.lines().count());
+        assert!(recorded.contains(r#""at":0"#));
+
+        // Replaying doesn't connect to the node and returns the recorded responses in order
+        let client = SyncClientBuilder::new()
+            .static_node(refuse())
+            .fixtures(Fixtures::replay(&path).normalize("at", 0))
+            .build()
+            .unwrap();
+
+        fs::remove_file(&path).unwrap();
+
+        let res = client.request(search(3)).send().unwrap();
+        assert_eq!(StatusCode::SERVICE_UNAVAILABLE, res.status());
+
+        let res = client.request(search(4)).send().unwrap();
+        assert_eq!(StatusCode::OK, res.status());
+
+        // Requests that weren't recorded fail
+        assert!(client.request(PingRequest::new()).send().is_err());
+    }
+
+    /** Fail the first request with a `503` without sending it. */
+    struct FailOnce(AtomicUsize);
+
+    impl<TBody> Middleware<TBody> for FailOnce {
+        fn before_send(
+            &self,
+            _: &mut HttpRequest<TBody>,
+        ) -> Result<BeforeSend, Box<dyn StdError + Send + Sync>> {
+            if self.0.fetch_add(1, Ordering::SeqCst) == 0 {
+                let res = BufferedResponse::new(StatusCode::SERVICE_UNAVAILABLE, "{}");
+
+                Ok(BeforeSend::Respond(res))
+            } else {
+                Ok(BeforeSend::Continue)
+            }
+        }
+    }
+
+    /** Sign requests and tag responses, recording the order it's called in. */
+    struct Sign(Arc<Mutex<Vec<String>>>);
+
+    impl Middleware<SyncBody> for Sign {
+        fn before_send(
+            &self,
+            req: &mut HttpRequest<SyncBody>,
+        ) -> Result<BeforeSend, Box<dyn StdError + Send + Sync>> {
+            self.0.lock().unwrap().push("sign".to_owned());
+
+            req.headers_mut()
+                .insert(AUTHORIZATION, HeaderValue::from_static("signed"));
+
+            Ok(BeforeSend::Continue)
+        }
+
+        fn after_receive(
+            &self,
+            req: &HttpRequest<()>,
+            res: &mut BufferedResponse,
+        ) -> Result<(), Box<dyn StdError + Send + Sync>> {
+            self.0.lock().unwrap().push(format!("receive {}", req.url().path()));
+
+            *res.status_mut() = StatusCode::ACCEPTED;
+            res.set_body(r#"{"tagged":true}"#);
+
+            Ok(())
+        }
+
+        fn receives_responses(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn middleware_responds_without_sending() {
+        let mock = MockTransport::new().respond(
+            MockRequest::new(Method::GET, "/"),
+            MockResponse::new(StatusCode::OK),
+        );
+
+        let client = SyncClientBuilder::new()
+            .mock(mock.clone())
+            .middleware(FailOnce(AtomicUsize::new(0)))
+            .retry_policy(RetryPolicy::new().jitter(false))
+            .build()
+            .unwrap();
+
+        // The response from the middleware is retried and the second attempt is sent
+        let res = client.request(PingRequest::new()).send().unwrap();
+
+        assert_eq!(StatusCode::OK, res.status());
+        assert_eq!(1, mock.requests().len());
+    }
+
+    #[test]
+    fn middleware_changes_requests_and_responses() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mock = MockTransport::new().respond(
+            MockRequest::new(Method::GET, "/"),
+            MockResponse::new(StatusCode::OK),
+        );
+
+        let client = {
+            let pre_send_calls = calls.clone();
+
+            SyncClientBuilder::new()
+                .mock(mock.clone())
+                .pre_send_raw(move |req| {
+                    let signed = req.headers().contains_key(AUTHORIZATION);
+                    let mut calls = pre_send_calls.lock().unwrap();
+                    calls.push(format!("pre_send {}", signed));
+
+                    Ok(())
+                })
+                .middleware(Sign(calls.clone()))
+                .build()
+                .unwrap()
+        };
+
+        let res = client.request(PingRequest::new()).send().unwrap();
+
+        assert_eq!(StatusCode::ACCEPTED, res.status());
+        assert_eq!(json!({ "tagged": true }), res.into_response::<Value>().unwrap());
+        assert_eq!("signed", mock.requests()[0].headers()[AUTHORIZATION]);
+        assert_eq!(
+            vec!["sign", "pre_send true", "receive /"],
+            *calls.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn metrics_for_each_attempt() {
+        let attempts = AtomicUsize::new(0);
+        let mock = MockTransport::new().respond_with(
+            MockRequest::new(Method::POST, "/myindex/_search"),
+            move |_| match attempts.fetch_add(1, Ordering::SeqCst) {
+                0 => MockResponse::new(StatusCode::SERVICE_UNAVAILABLE),
+                _ => MockResponse::new(StatusCode::OK).body(r#"{"hits":{"hits":[]}}"#),
+            },
+        );
+
+        let metrics = InMemoryMetrics::new();
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:9200")
+            .mock(mock)
+            .metrics(metrics.clone())
+            .build()
+            .unwrap();
+
+        for _ in 0..2 {
+            let mut res = client
+                .request(SearchRequest::for_index("myindex", r#"{"size":0}"#))
+                .send()
+                .unwrap()
+                .into_raw();
+
+            res.read_to_end(&mut Vec::new()).unwrap();
+        }
+
+        let snapshot = metrics.snapshot();
+        let search = &snapshot.endpoints["search"];
+
+        assert_eq!(2, search.requests);
+        assert_eq!(0, search.retries);
+        assert_eq!(20, search.bytes_sent);
+        assert_eq!(22, search.bytes_received);
+        assert_eq!(Some(&1), search.statuses.get(&503));
+        assert_eq!(Some(&1), search.statuses.get(&200));
+        assert_eq!(2, search.latency.count);
+        assert_eq!(Some(&2), snapshot.nodes.get("http://localhost:9200"));
+    }
+
+    #[test]
+    fn metrics_for_custom_endpoints() {
+        let mock = MockTransport::new().respond(
+            MockRequest::new(Method::GET, "/_custom"),
+            MockResponse::new(StatusCode::OK).body(r#"{}"#),
+        );
+
+        let metrics = InMemoryMetrics::new();
+        let client = SyncClientBuilder::new()
+            .mock(mock)
+            .metrics(metrics.clone())
+            .build()
+            .unwrap();
+
+        let endpoint = Endpoint::new("custom", "/_custom", Method::GET, Some(empty_body()));
+
+        assert_eq!("custom", endpoint.name());
+        assert_eq!(&Method::GET, endpoint.method());
+
+        let res = client.request(endpoint).send().unwrap();
+
+        assert_eq!(StatusCode::OK, res.status());
+        assert_eq!(1, metrics.snapshot().endpoints["custom"].requests);
+    }
+
+    #[test]
+    fn metrics_for_retries() {
+        let metrics = InMemoryMetrics::new();
+        let client = SyncClientBuilder::new()
+            .static_node(refuse())
+            .metrics(metrics.clone())
+            .retry_policy(
+                RetryPolicy::new()
+                    .jitter(false)
+                    .backoff(Duration::from_millis(1), Duration::from_millis(1)),
+            )
+            .build()
+            .unwrap();
+
+        assert!(client.request(PingRequest::new()).send().is_err());
+
+        let ping = &metrics.snapshot().endpoints["ping"];
+
+        assert_eq!(3, ping.requests);
+        assert_eq!(2, ping.retries);
+        assert_eq!(3, ping.failures);
+    }
+
+    #[test]
+    fn send_correlation_id_as_opaque_id() {
+        let mock = MockTransport::new().respond(
+            MockRequest::new(Method::GET, "/"),
+            MockResponse::new(StatusCode::OK),
+        );
+
+        let client = SyncClientBuilder::new().mock(mock.clone()).build().unwrap();
+
+        client.request(PingRequest::new()).send().unwrap();
+        client
+            .request(PingRequest::new())
+            .params_fluent(|p| {
+                p.header(HeaderName::from_static("x-opaque-id"), HeaderValue::from_static("my-id"))
+            })
+            .send()
+            .unwrap();
+
+        let requests = mock.requests();
+        let opaque_id = requests[0].headers()["x-opaque-id"].to_str().unwrap();
+
+        assert!(Uuid::parse_str(opaque_id).is_ok());
+        assert_eq!("my-id", requests[1].headers()["x-opaque-id"]);
+    }
+
+    type SpanFields = BTreeMap<String, String>;
+
+    /** A subscriber that keeps the name and fields of each span. */
+    #[derive(Clone, Default)]
+    struct RecordSpans(Arc<Mutex<Vec<(String, SpanFields)>>>);
+
+    struct RecordFields<'a>(&'a mut SpanFields);
+
+    impl<'a> Visit for RecordFields<'a> {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0.insert(field.name().to_owned(), format!("{:?}", value));
+        }
+    }
+
+    impl Subscriber for RecordSpans {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes) -> Id {
+            let mut fields = BTreeMap::new();
+            span.record(&mut RecordFields(&mut fields));
+
+            let mut spans = self.0.lock().unwrap();
+            spans.push((span.metadata().name().to_owned(), fields));
+
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, span: &Id, values: &Record) {
+            let mut spans = self.0.lock().unwrap();
+            let (_, ref mut fields) = spans[span.into_u64() as usize - 1];
+
+            values.record(&mut RecordFields(fields));
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event) {}
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn trace_requests_and_attempts() {
+        let mock = MockTransport::new().respond(
+            MockRequest::new(Method::GET, "/"),
+            MockResponse::new(StatusCode::OK),
+        );
+
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:9200")
+            .mock(mock.clone())
+            .build()
+            .unwrap();
+
+        let subscriber = RecordSpans::default();
+        tracing::subscriber::with_default(subscriber.clone(), || {
+            client.request(PingRequest::new()).send().unwrap();
+        });
+
+        let spans = subscriber.0.lock().unwrap();
+        let (ref name, ref request) = spans[0];
+        let correlation_id = mock.requests()[0].headers()["x-opaque-id"].clone();
+
+        assert_eq!("elasticsearch.request", name);
+        assert_eq!(correlation_id, request["correlation_id"].as_str());
+        assert_eq!("\"ping\"", request["endpoint"]);
+        assert_eq!("GET", request["method"]);
+        assert_eq!("200", request["status"]);
+        assert!(request.contains_key("duration_ms"));
+
+        let (ref name, ref attempt) = spans[1];
+
+        assert_eq!("elasticsearch.attempt", name);
+        assert_eq!("1", attempt["attempt"]);
+        assert_eq!("\"http://localhost:9200\"", attempt["node"]);
+        assert_eq!("200", attempt["status"]);
+    }
+
+    #[test]
+    fn trace_requests_that_fail_before_sending() {
+        let mock = MockTransport::new();
+
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:9200")
+            .mock(mock.clone())
+            .credentials_provider(|| Err("no credentials".into()))
+            .build()
+            .unwrap();
+
+        let subscriber = RecordSpans::default();
+        tracing::subscriber::with_default(subscriber.clone(), || {
+            assert!(client.request(PingRequest::new()).send().is_err());
+        });
+
+        let spans = subscriber.0.lock().unwrap();
+        let (ref name, ref request) = spans[0];
 
-        self
+        assert_eq!("elasticsearch.request", name);
+        assert!(request.contains_key("duration_ms"));
+        assert!(!request.contains_key("status"));
+        assert_eq!(0, mock.requests().len());
     }
 }
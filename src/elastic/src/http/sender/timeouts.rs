@@ -0,0 +1,354 @@
+/*!
+Timeouts for sending requests.
+
+A `reqwest` client only supports timeouts that apply to every request it sends.
+To support timeouts for individual requests, a client is built for each distinct set of client timeouts and cached.
+Only a few clients are kept, so requests with many different timeouts don't each hold on to their own connection pool.
+
+Timeouts that aren't set on the client are enforced while sending the request and reading its response instead.
+*/
+
+use futures::{
+    Async,
+    Future,
+    Poll,
+    Stream,
+};
+use reqwest::Error as ReqwestError;
+use std::{
+    io::{
+        self,
+        Read,
+    },
+    sync::{
+        Arc,
+        RwLock,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+use tokio::timer::Delay;
+
+use crate::{
+    error::{
+        self,
+        Error,
+    },
+    http::sender::RetryableError,
+};
+
+/** The most clients with timeouts to keep at once. */
+const MAX_CLIENTS: usize = 8;
+
+/** The timeouts that are configured on a `reqwest` client. */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(crate) struct ClientTimeouts {
+    pub(crate) connect: Option<Duration>,
+    pub(crate) timeout: Option<Duration>,
+}
+
+pub(crate) type BuildHttpClient<TClient> =
+    dyn Fn(ClientTimeouts) -> Result<TClient, ReqwestError> + Send + Sync;
+
+/**
+A `reqwest` client for requests without timeouts and the clients built for requests with them.
+
+The clients built for requests with timeouts are kept in the order they were built.
+Once there are too many, the oldest is dropped.
+*/
+#[derive(Clone)]
+pub(crate) struct HttpClients<TClient> {
+    default: TClient,
+    build: Option<Arc<BuildHttpClient<TClient>>>,
+    with_timeouts: Arc<RwLock<Vec<(ClientTimeouts, TClient)>>>,
+}
+
+impl<TClient> HttpClients<TClient>
+where
+    TClient: Clone,
+{
+    /** Use the given function to build clients for requests with timeouts. */
+    pub(crate) fn new(
+        default: TClient,
+        build: impl Fn(ClientTimeouts) -> Result<TClient, ReqwestError> + Send + Sync + 'static,
+    ) -> Self {
+        HttpClients {
+            default,
+            build: Some(Arc::new(build)),
+            with_timeouts: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+
+    /**
+    Use the given client for all requests.
+
+    Clients given by the caller can't be rebuilt, so their own timeouts are used instead.
+    */
+    pub(crate) fn custom(client: TClient) -> Self {
+        HttpClients {
+            default: client,
+            build: None,
+            with_timeouts: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+
+    /** Get a client for requests with the given timeouts, building one if needed. */
+    pub(crate) fn get(&self, timeouts: ClientTimeouts) -> Result<TClient, Error> {
+        let build = match self.build {
+            Some(ref build) if timeouts != ClientTimeouts::default() => build,
+            _ => return Ok(self.default.clone()),
+        };
+
+        let cached = find_client(&self.with_timeouts.read().expect("lock poisoned"), timeouts);
+        if let Some(client) = cached {
+            return Ok(client);
+        }
+
+        let mut with_timeouts = self.with_timeouts.write().expect("lock poisoned");
+
+        if let Some(client) = find_client(&with_timeouts, timeouts) {
+            return Ok(client);
+        }
+
+        let client = build(timeouts).map_err(error::build)?;
+
+        if with_timeouts.len() >= MAX_CLIENTS {
+            with_timeouts.remove(0);
+        }
+        with_timeouts.push((timeouts, client.clone()));
+
+        Ok(client)
+    }
+}
+
+fn find_client<TClient>(
+    clients: &[(ClientTimeouts, TClient)],
+    timeouts: ClientTimeouts,
+) -> Option<TClient>
+where
+    TClient: Clone,
+{
+    clients
+        .iter()
+        .find(|(client_timeouts, _)| *client_timeouts == timeouts)
+        .map(|(_, client)| client.clone())
+}
+
+/**
+Get the earliest of the read timeout starting from now and the deadline for the total timeout.
+*/
+pub(crate) fn read_deadline(read: Option<Duration>, total: Option<Instant>) -> Option<Instant> {
+    let read = read.map(|read| Instant::now() + read);
+
+    match (read, total) {
+        (Some(read), Some(total)) => Some(read.min(total)),
+        (read, total) => read.or(total),
+    }
+}
+
+/**
+Whether or not there's time for another attempt after waiting for a backoff before the deadline for the total timeout.
+*/
+pub(crate) fn has_time_for(backoff: Duration, total: Option<Instant>) -> bool {
+    match total {
+        Some(total) => Instant::now() + backoff < total,
+        None => true,
+    }
+}
+
+const BODY_TIMEOUT: &str = "the response body wasn't read before the timeout elapsed";
+
+/**
+A synchronous response body that fails once the deadline for the total timeout has passed.
+
+The deadline is checked before each read.
+Each read is also limited by the timeout set on the client.
+*/
+pub(crate) struct DeadlineRead<T> {
+    inner: T,
+    deadline: Instant,
+}
+
+impl<T> DeadlineRead<T> {
+    pub(crate) fn new(inner: T, deadline: Instant) -> Self {
+        DeadlineRead { inner, deadline }
+    }
+}
+
+impl<T> Read for DeadlineRead<T>
+where
+    T: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if Instant::now() >= self.deadline {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, BODY_TIMEOUT));
+        }
+
+        self.inner.read(buf)
+    }
+}
+
+/**
+An asynchronous response body that fails if its next chunk doesn't arrive before the read timeout or the deadline for the total timeout.
+
+The read timeout starts again after each chunk.
+*/
+pub(crate) struct TimeoutBody<T> {
+    inner: T,
+    read: Option<Duration>,
+    total: Option<Instant>,
+    timer: Option<Delay>,
+}
+
+impl<T> TimeoutBody<T> {
+    pub(crate) fn new(inner: T, read: Option<Duration>, total: Option<Instant>) -> Self {
+        TimeoutBody {
+            inner,
+            read,
+            total,
+            timer: None,
+        }
+    }
+}
+
+impl<T> Stream for TimeoutBody<T>
+where
+    T: Stream<Error = Error>,
+{
+    type Item = T::Item;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        if let Async::Ready(chunk) = self.inner.poll()? {
+            // The read timeout for the next chunk starts once it's waited for
+            self.timer = None;
+
+            return Ok(Async::Ready(chunk));
+        }
+
+        if self.timer.is_none() {
+            self.timer = read_deadline(self.read, self.total).map(Delay::new);
+        }
+
+        match self.timer {
+            Some(ref mut timer) => match timer.poll().map_err(error::request)? {
+                Async::Ready(()) => Err(error::timeout(error::message(BODY_TIMEOUT))),
+                Async::NotReady => Ok(Async::NotReady),
+            },
+            None => Ok(Async::NotReady),
+        }
+    }
+}
+
+/**
+Get the kind of error for a request that failed without a response.
+
+Requests that timed out are returned as an `Error::Timeout`.
+*/
+pub(crate) fn send_error(err: ReqwestError) -> (Option<RetryableError>, Error) {
+    let kind = RetryableError::from_reqwest(&err);
+
+    let err = if kind == Some(RetryableError::Timeout) {
+        error::timeout(err)
+    } else {
+        error::request(err)
+    };
+
+    (kind, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{
+        AtomicUsize,
+        Ordering,
+    };
+
+    fn timeouts(secs: u64) -> ClientTimeouts {
+        ClientTimeouts {
+            connect: None,
+            timeout: Some(Duration::from_secs(secs)),
+        }
+    }
+
+    #[test]
+    fn default_client_without_timeouts() {
+        let clients = HttpClients::new(0, |_| Ok(1));
+
+        assert_eq!(0, clients.get(ClientTimeouts::default()).unwrap());
+    }
+
+    #[test]
+    fn build_client_once_per_timeouts() {
+        let built = Arc::new(AtomicUsize::new(0));
+
+        let clients = {
+            let built = built.clone();
+            HttpClients::new(0, move |_| Ok(built.fetch_add(1, Ordering::SeqCst) + 1))
+        };
+
+        assert_eq!(1, clients.get(timeouts(1)).unwrap());
+        assert_eq!(1, clients.get(timeouts(1)).unwrap());
+        assert_eq!(2, clients.get(timeouts(2)).unwrap());
+        assert_eq!(2, built.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn custom_client_ignores_timeouts() {
+        let clients = HttpClients::custom(0);
+
+        assert_eq!(0, clients.get(timeouts(1)).unwrap());
+    }
+
+    #[test]
+    fn cache_at_most_max_clients() {
+        let built = Arc::new(AtomicUsize::new(0));
+
+        let clients = {
+            let built = built.clone();
+            HttpClients::new(0, move |_| Ok(built.fetch_add(1, Ordering::SeqCst) + 1))
+        };
+
+        for secs in 0..=MAX_CLIENTS as u64 {
+            clients.get(timeouts(secs)).unwrap();
+        }
+
+        assert_eq!(MAX_CLIENTS, clients.with_timeouts.read().unwrap().len());
+
+        // The oldest client was evicted so it's built again
+        assert_eq!(MAX_CLIENTS + 2, clients.get(timeouts(0)).unwrap());
+        assert_eq!(MAX_CLIENTS + 1, clients.get(timeouts(MAX_CLIENTS as u64)).unwrap());
+    }
+
+    #[test]
+    fn read_deadline_is_earliest() {
+        let now = Instant::now();
+        let total = now + Duration::from_millis(50);
+
+        assert_eq!(None, read_deadline(None, None));
+        assert_eq!(Some(total), read_deadline(None, Some(total)));
+        assert_eq!(Some(total), read_deadline(Some(Duration::from_secs(60)), Some(total)));
+        assert!(read_deadline(Some(Duration::from_millis(1)), Some(total)).unwrap() < total);
+    }
+
+    #[test]
+    fn has_time_for_backoff() {
+        let total = Instant::now() + Duration::from_secs(60);
+
+        assert!(has_time_for(Duration::from_secs(1), None));
+        assert!(has_time_for(Duration::from_secs(1), Some(total)));
+        assert!(!has_time_for(Duration::from_secs(120), Some(total)));
+    }
+
+    #[test]
+    fn deadline_read_times_out() {
+        let mut read = DeadlineRead::new(&b"{}"[..], Instant::now() - Duration::from_millis(1));
+
+        let err = read.read(&mut [0; 2]).unwrap_err();
+
+        assert_eq!(io::ErrorKind::TimedOut, err.kind());
+    }
+}
@@ -10,12 +10,14 @@ use std::{
     },
 };
 
-use reqwest::{
-    Body,
-    Response as RawResponse,
+use flate2::{
+    read::GzEncoder,
+    Compression,
 };
+use reqwest::Body;
 
 use crate::http::{
+    gzip,
     HttpRequest,
     StatusCode,
 };
@@ -81,6 +83,53 @@ impl SyncBody {
         }
     }
 
+    /**
+    Compress the body with gzip.
+
+    Buffered bodies are compressed immediately.
+    Bodies that can only be read once are compressed as they're read.
+    */
+    pub(crate) fn gzip(self) -> Result<SyncBody, io::Error> {
+        match self.0 {
+            SyncBodyInner::UnBuffered(reader) => {
+                let reader = GzEncoder::new(reader, Compression::default());
+
+                Ok(SyncBody(SyncBodyInner::UnBuffered(Box::new(reader))))
+            }
+            SyncBodyInner::Buffered(ref inner) => {
+                let bytes: &[u8] = AsRef::as_ref(inner);
+
+                Ok(gzip(bytes)?.into())
+            }
+        }
+    }
+
+    /**
+    Get the length of the body in bytes.
+
+    Bodies that can only be read once, like files, don't have a known length.
+    */
+    pub(crate) fn buffered_len(&self) -> Option<u64> {
+        match self.0 {
+            SyncBodyInner::UnBuffered(_) => None,
+            SyncBodyInner::Buffered(ref inner) => Some(AsRef::<[u8]>::as_ref(inner).len() as u64),
+        }
+    }
+
+    /**
+    Try to clone the body so it can be sent again.
+
+    Bodies that can only be read once, like files, can't be cloned.
+    */
+    pub(crate) fn try_clone(&self) -> Option<SyncBody> {
+        match self.0 {
+            SyncBodyInner::UnBuffered(_) => None,
+            SyncBodyInner::Buffered(ref inner) => {
+                Some(SyncBody(SyncBodyInner::Buffered(inner.clone())))
+            }
+        }
+    }
+
     /**
     Get a reader over the synchronous body.
 
@@ -191,12 +240,15 @@ impl From<File> for SyncBody {
     }
 }
 
+/** The body of a synchronous response. */
+pub(crate) type SyncResponseBody = Box<dyn Read + Send>;
+
 /** A raw HTTP response that can be buffered using `Read`. */
-pub struct SyncHttpResponse(StatusCode, RawResponse);
+pub struct SyncHttpResponse(StatusCode, SyncResponseBody);
 
 impl SyncHttpResponse {
-    pub(crate) fn from_raw(status: StatusCode, response: RawResponse) -> Self {
-        SyncHttpResponse(status, response)
+    pub(crate) fn from_raw(status: StatusCode, body: SyncResponseBody) -> Self {
+        SyncHttpResponse(status, body)
     }
 
     /** Get the HTTP status for the response. */
@@ -215,6 +267,16 @@ impl Read for SyncHttpResponse {
 mod tests {
     use super::*;
     use crate::http::empty_body;
+    use flate2::read::GzDecoder;
+
+    fn gunzip(mut body: SyncBody) -> String {
+        let mut decoded = String::new();
+        GzDecoder::new(body.reader())
+            .read_to_string(&mut decoded)
+            .unwrap();
+
+        decoded
+    }
 
     #[test]
     fn owned_string_into_body() {
@@ -247,4 +309,22 @@ mod tests {
     fn json_value_into_body() {
         SyncBody::from(json!({}));
     }
+
+    #[test]
+    fn gzip_buffered_body() {
+        let body = SyncBody::from("{\"query\":{}}").gzip().unwrap();
+
+        assert!(body.try_clone().is_some());
+        assert_eq!("{\"query\":{}}", gunzip(body));
+    }
+
+    #[test]
+    fn gzip_unbuffered_body() {
+        let reader = Cursor::new(b"{\"query\":{}}".to_vec());
+        let body = SyncBody(SyncBodyInner::UnBuffered(Box::new(reader)))
+            .gzip()
+            .unwrap();
+
+        assert_eq!("{\"query\":{}}", gunzip(body));
+    }
 }
@@ -0,0 +1,68 @@
+use elastic::{
+    error::Error,
+    prelude::*,
+};
+use futures::Future;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, ElasticType)]
+#[elastic(index = "delete_by_query_idx")]
+pub struct Doc {
+    #[elastic(id)]
+    id: String,
+    title: String,
+}
+
+fn doc(id: &str, title: &str) -> Doc {
+    Doc {
+        id: id.to_owned(),
+        title: title.to_owned(),
+    }
+}
+
+test! {
+    const description: &'static str = "delete by query";
+
+    type Response = DeleteByQueryResponse;
+
+    // Ensure the index doesn't exist
+    fn prepare(&self, client: AsyncClient) -> Box<dyn Future<Item = (), Error = Error>> {
+        let delete_res = client
+            .index(Doc::static_index())
+            .delete()
+            .send()
+            .map(|_| ());
+
+        Box::new(delete_res)
+    }
+
+    // Index some documents, then delete the ones matching a query
+    fn request(
+        &self,
+        client: AsyncClient,
+    ) -> Box<dyn Future<Item = Self::Response, Error = Error>> {
+        let index_res = client
+            .bulk()
+            .index(Doc::static_index())
+            .push(bulk::<Doc>().index(doc("1", "delete")))
+            .push(bulk::<Doc>().index(doc("2", "delete")))
+            .push(bulk::<Doc>().index(doc("3", "keep")))
+            .params_fluent(|p| p.url_param("refresh", true))
+            .send();
+
+        let delete_res = client
+            .document::<Doc>()
+            .delete_by_query(json!({
+                "match": {
+                    "title": "delete"
+                }
+            }))
+            .send();
+
+        Box::new(index_res.and_then(|_| delete_res))
+    }
+
+    // Ensure only the matching documents were deleted
+    fn assert_ok(&self, res: &Self::Response) -> bool {
+        res.is_ok() && res.deleted() == 2 && res.total() == 2
+    }
+}
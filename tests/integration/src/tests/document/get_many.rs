@@ -0,0 +1,70 @@
+use elastic::{
+    error::Error,
+    prelude::*,
+};
+use futures::Future;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, ElasticType)]
+#[elastic(index = "get_many_idx")]
+pub struct Doc {
+    #[elastic(id)]
+    id: String,
+    title: String,
+}
+
+const ID: &str = "1";
+const MISSING_ID: &str = "2";
+
+fn doc() -> Doc {
+    Doc {
+        id: ID.to_owned(),
+        title: "A document title".to_owned(),
+    }
+}
+
+test! {
+    const description: &'static str = "index then get many";
+
+    type Response = GetManyResponse<Doc>;
+
+    // Ensure the index doesn't exist
+    fn prepare(&self, client: AsyncClient) -> Box<dyn Future<Item = (), Error = Error>> {
+        let delete_res = client
+            .index(Doc::static_index())
+            .delete()
+            .send()
+            .map(|_| ());
+
+        Box::new(delete_res)
+    }
+
+    // Index a document, then get it along with a document that doesn't exist
+    fn request(
+        &self,
+        client: AsyncClient,
+    ) -> Box<dyn Future<Item = Self::Response, Error = Error>> {
+        let index_res = client
+            .document()
+            .index(doc())
+            .params_fluent(|p| p.url_param("refresh", true))
+            .send();
+
+        let get_res = client.document().get_many(vec![MISSING_ID, ID]).send();
+
+        Box::new(index_res.and_then(|_| get_res))
+    }
+
+    // Ensure the response contains the expected documents in order
+    fn assert_ok(&self, res: &Self::Response) -> bool {
+        let found: Vec<_> = res
+            .iter()
+            .map(|doc| doc.map(|doc| (doc.id().to_string(), doc.document())))
+            .collect();
+
+        found
+            == vec![
+                Ok((MISSING_ID.to_owned(), None)),
+                Ok((ID.to_owned(), Some(&doc()))),
+            ]
+    }
+}
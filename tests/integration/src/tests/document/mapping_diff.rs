@@ -0,0 +1,75 @@
+use elastic::{
+    error::Error,
+    prelude::*,
+};
+use futures::Future;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, ElasticType)]
+#[elastic(index = "mapping_diff")]
+pub struct Doc {
+    #[elastic(id)]
+    id: String,
+    status: Keyword<DefaultKeywordMapping>,
+    timestamp: Date<DefaultDateMapping>,
+}
+
+test! {
+    const description: &'static str = "diff a derived mapping and put only additive changes";
+
+    type Response = (MappingDiffResponse, MappingDiffResponse);
+
+    // Ensure the index exists with a conflicting mapping for `status`
+    fn prepare(&self, client: AsyncClient) -> Box<dyn Future<Item = (), Error = Error>> {
+        let delete_res = client.index(Doc::static_index()).delete().send();
+
+        let create_res = client
+            .index(Doc::static_index())
+            .create()
+            .body(json!({
+                "mappings": {
+                    "properties": {
+                        "status": { "type": "text" }
+                    }
+                }
+            }))
+            .send();
+
+        Box::new(delete_res.then(|_| create_res).map(|_| ()))
+    }
+
+    // Diff the mapping, put it, then diff it again
+    fn request(
+        &self,
+        client: AsyncClient,
+    ) -> Box<dyn Future<Item = Self::Response, Error = Error>> {
+        let before_res = client.document::<Doc>().mapping_diff().send();
+        let put_res = client.document::<Doc>().put_mapping().only_additive().send();
+        let after_res = client.document::<Doc>().mapping_diff().send();
+
+        Box::new(
+            before_res
+                .and_then(|before| put_res.map(|_| before))
+                .and_then(|before| after_res.map(|after| (before, after))),
+        )
+    }
+
+    // Ensure the new fields were added and the conflicting field was left alone
+    fn assert_ok(&self, res: &Self::Response) -> bool {
+        let (ref before, ref after) = *res;
+
+        let before = match before.get("mapping_diff") {
+            Some(diff) => diff,
+            None => return false,
+        };
+
+        let after = match after.get("mapping_diff") {
+            Some(diff) => diff,
+            None => return false,
+        };
+
+        before.added().count() > 0
+            && !before.is_compatible()
+            && after.added().count() == 0
+            && after.type_changed().map(|change| change.path()).eq(vec!["status"])
+    }
+}
@@ -1,7 +1,12 @@
 test_cases![
     delete,
+    delete_by_query,
+    get_many,
+    mapping_diff,
+    reindex,
     simple_index_get,
     simple_mapping,
+    update_by_query,
     update_no_index,
     update_with_doc,
     update_with_inline_script,
@@ -0,0 +1,77 @@
+use elastic::{
+    client::responses::bulk_by_scroll::BulkByScrollStatus,
+    error::Error,
+    prelude::*,
+};
+use futures::{
+    Future,
+    Stream,
+};
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, ElasticType)]
+#[elastic(index = "reindex_source_idx")]
+pub struct Doc {
+    #[elastic(id)]
+    id: String,
+    title: String,
+}
+
+const DEST_INDEX: &str = "reindex_dest_idx";
+
+fn doc(id: &str) -> Doc {
+    Doc {
+        id: id.to_owned(),
+        title: "A document title".to_owned(),
+    }
+}
+
+test! {
+    const description: &'static str = "reindex in a background task";
+
+    type Response = (Vec<BulkByScrollStatus>, ReindexResponse);
+
+    // Ensure the indices don't exist
+    fn prepare(&self, client: AsyncClient) -> Box<dyn Future<Item = (), Error = Error>> {
+        let delete_source = client.index(Doc::static_index()).delete().send();
+        let delete_dest = client.index(DEST_INDEX).delete().send();
+
+        Box::new(delete_source.then(|_| delete_dest).map(|_| ()))
+    }
+
+    // Index some documents, then reindex them while reporting progress
+    fn request(
+        &self,
+        client: AsyncClient,
+    ) -> Box<dyn Future<Item = Self::Response, Error = Error>> {
+        let index_res = client
+            .bulk()
+            .index(Doc::static_index())
+            .push(bulk::<Doc>().index(doc("1")))
+            .push(bulk::<Doc>().index(doc("2")))
+            .params_fluent(|p| p.url_param("refresh", true))
+            .send();
+
+        let reindex_res = client
+            .reindex(Doc::static_index(), DEST_INDEX)
+            .send_task()
+            .and_then(|task| {
+                let task = task.poll_interval(Duration::from_millis(100));
+
+                task.progress()
+                    .collect()
+                    .and_then(move |progress| task.wait().map(|res| (progress, res)))
+            });
+
+        Box::new(index_res.and_then(|_| reindex_res))
+    }
+
+    // Ensure all documents were reindexed
+    fn assert_ok(&self, res: &Self::Response) -> bool {
+        let (ref progress, ref reindex) = *res;
+
+        let final_progress = progress.last().map(|status| status.created());
+
+        reindex.created() == 2 && final_progress == Some(2)
+    }
+}
@@ -0,0 +1,81 @@
+use elastic::{
+    error::Error,
+    prelude::*,
+};
+use futures::Future;
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, ElasticType)]
+#[elastic(index = "update_by_query_idx")]
+pub struct Doc {
+    #[elastic(id)]
+    id: String,
+    title: String,
+}
+
+const EXPECTED_TITLE: &str = "Edited title";
+
+fn doc(id: &str) -> Doc {
+    Doc {
+        id: id.to_owned(),
+        title: "Not edited title".to_owned(),
+    }
+}
+
+test! {
+    const description: &'static str = "update by query in a background task";
+
+    type Response = (UpdateByQueryResponse, SearchResponse<Doc>);
+
+    // Ensure the index doesn't exist
+    fn prepare(&self, client: AsyncClient) -> Box<dyn Future<Item = (), Error = Error>> {
+        let delete_res = client
+            .index(Doc::static_index())
+            .delete()
+            .send()
+            .map(|_| ());
+
+        Box::new(delete_res)
+    }
+
+    // Index some documents, update them in a task, then search for them
+    fn request(
+        &self,
+        client: AsyncClient,
+    ) -> Box<dyn Future<Item = Self::Response, Error = Error>> {
+        let index_res = client
+            .bulk()
+            .index(Doc::static_index())
+            .push(bulk::<Doc>().index(doc("1")))
+            .push(bulk::<Doc>().index(doc("2")))
+            .params_fluent(|p| p.url_param("refresh", true))
+            .send();
+
+        let update_res = client
+            .document::<Doc>()
+            .update_by_query(
+                json!({ "match_all": {} }),
+                format!("ctx._source.title = \"{}\"", EXPECTED_TITLE),
+            )
+            .params_fluent(|p| p.url_param("refresh", true))
+            .send_task()
+            .and_then(|task| task.poll_interval(Duration::from_millis(100)).wait());
+
+        let search_res = client.document::<Doc>().search().send();
+
+        Box::new(
+            index_res
+                .and_then(|_| update_res)
+                .and_then(|update| search_res.map(|search| (update, search))),
+        )
+    }
+
+    // Ensure all documents were updated
+    fn assert_ok(&self, res: &Self::Response) -> bool {
+        let (ref update, ref search) = *res;
+
+        update.updated() == 2
+            && search.documents().count() == 2
+            && search.documents().all(|doc| doc.title == EXPECTED_TITLE)
+    }
+}
@@ -0,0 +1,59 @@
+use elastic::{
+    error::Error,
+    prelude::*,
+};
+use futures::Future;
+
+const INDEX: &str = "aliases_idx";
+const ALIAS: &str = "aliases_idx_errors";
+
+test! {
+    const description: &'static str = "add, list and resolve aliases";
+
+    type Response = (AliasesResponse, AliasExistsResponse, AliasesResponse);
+
+    // Ensure the index exists without any aliases
+    fn prepare(&self, client: AsyncClient) -> Box<dyn Future<Item = (), Error = Error>> {
+        let delete_res = client.index(INDEX).delete().send();
+        let create_res = client.index(INDEX).create().send();
+
+        Box::new(delete_res.then(|_| create_res).map(|_| ()))
+    }
+
+    // Add a filtered alias, then look it up a few different ways
+    fn request(
+        &self,
+        client: AsyncClient,
+    ) -> Box<dyn Future<Item = Self::Response, Error = Error>> {
+        let add_res = client
+            .index(INDEX)
+            .add_alias(ALIAS)
+            .filter(json!({ "term": { "level": "error" } }))
+            .routing("1")
+            .send();
+
+        let get_res = client.index(INDEX).get_aliases().send();
+        let exists_res = client.index(INDEX).alias_exists(ALIAS).send();
+        let resolve_res = client.index(ALIAS).resolve_alias().send();
+
+        Box::new(
+            add_res
+                .and_then(|_| get_res)
+                .and_then(|get| exists_res.map(|exists| (get, exists)))
+                .and_then(|(get, exists)| resolve_res.map(|resolve| (get, exists, resolve))),
+        )
+    }
+
+    // Ensure the alias was added with its filter and resolves to the index
+    fn assert_ok(&self, res: &Self::Response) -> bool {
+        let (ref get, ref exists, ref resolve) = *res;
+
+        let alias = get.aliases().next();
+
+        alias.map(|alias| alias.name()) == Some(ALIAS)
+            && alias.and_then(|alias| alias.filter()).is_some()
+            && alias.and_then(|alias| alias.index_routing()) == Some("1")
+            && exists.exists()
+            && resolve.indices().collect::<Vec<_>>() == vec![INDEX]
+    }
+}
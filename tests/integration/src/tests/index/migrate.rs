@@ -0,0 +1,89 @@
+use elastic::{
+    error::Error,
+    prelude::*,
+};
+use futures::Future;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, ElasticType)]
+#[elastic(index = "migrate_idx")]
+pub struct Doc {
+    #[elastic(id)]
+    id: String,
+    title: String,
+}
+
+const OLD_INDEX: &str = "migrate_idx_v1";
+const NEW_INDEX: &str = "migrate_idx_v2";
+
+fn doc(id: &str) -> Doc {
+    Doc {
+        id: id.to_owned(),
+        title: "A document title".to_owned(),
+    }
+}
+
+test! {
+    const description: &'static str = "migrate an alias to a new index";
+
+    type Response = (IndexMigrateResponse, IndicesExistsResponse);
+
+    // Point the alias at an old index with some documents in it
+    fn prepare(&self, client: AsyncClient) -> Box<dyn Future<Item = (), Error = Error>> {
+        let delete_old = client.index(OLD_INDEX).delete().send();
+        let delete_new = client.index(NEW_INDEX).delete().send();
+
+        let create_old = client.index(OLD_INDEX).create().send();
+
+        let index_docs = client
+            .bulk()
+            .index(OLD_INDEX)
+            .push(bulk::<Doc>().index(doc("1")))
+            .push(bulk::<Doc>().index(doc("2")))
+            .params_fluent(|p| p.url_param("refresh", true))
+            .send();
+
+        let add_alias = client
+            .index(OLD_INDEX)
+            .add_alias(Doc::static_index().to_string())
+            .send();
+
+        Box::new(
+            delete_old
+                .then(|_| delete_new)
+                .then(|_| create_old)
+                .and_then(|_| index_docs)
+                .and_then(|_| add_alias)
+                .map(|_| ()),
+        )
+    }
+
+    // Migrate the alias to the new index and remove the old one
+    fn request(
+        &self,
+        client: AsyncClient,
+    ) -> Box<dyn Future<Item = Self::Response, Error = Error>> {
+        let migrate_res = client
+            .index(NEW_INDEX)
+            .migrate::<Doc>()
+            .delete_old_indices(true)
+            .send();
+
+        Box::new(migrate_res.and_then(move |migrate| {
+            client
+                .index(OLD_INDEX)
+                .exists()
+                .send()
+                .map(|exists| (migrate, exists))
+        }))
+    }
+
+    // Ensure the documents were moved and the old index is gone
+    fn assert_ok(&self, res: &Self::Response) -> bool {
+        let (ref migrate, ref exists) = *res;
+
+        migrate.doc_count() == 2
+            && migrate.old_indices() == [OLD_INDEX]
+            && migrate.new_index() == NEW_INDEX
+            && !exists.exists()
+    }
+}
@@ -1,4 +1,8 @@
 test_cases![
+    aliases,
     does_not_exist,
-    exists
+    exists,
+    migrate,
+    settings,
+    template
 ];
@@ -0,0 +1,81 @@
+use elastic::{
+    error::Error,
+    prelude::*,
+};
+use futures::Future;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, ElasticType)]
+pub struct Doc {
+    id: String,
+    title: String,
+}
+
+const INDEX: &str = "index_settings";
+
+test! {
+    const description: &'static str = "create an index with settings, then update them";
+
+    type Response = IndexSettingsResponse;
+
+    // Ensure the index doesn't exist
+    fn prepare(&self, client: AsyncClient) -> Box<dyn Future<Item = (), Error = Error>> {
+        let delete_res = client.index(INDEX).delete().send().map(|_| ());
+
+        Box::new(delete_res)
+    }
+
+    // Create the index with settings and a mapping, update its settings and then get them
+    fn request(
+        &self,
+        client: AsyncClient,
+    ) -> Box<dyn Future<Item = Self::Response, Error = Error>> {
+        let mut settings = IndexSettings {
+            number_of_shards: Some(2),
+            number_of_replicas: Some(0),
+            ..Default::default()
+        };
+
+        settings.analysis.normalizer.insert(
+            "lowercase".to_owned(),
+            json!({ "type": "custom", "filter": ["lowercase"] }),
+        );
+
+        let create_res = client
+            .index(INDEX)
+            .create()
+            .settings(settings)
+            .mapping::<Doc>()
+            .send();
+
+        let put_res = client
+            .index(INDEX)
+            .put_settings(IndexSettings {
+                refresh_interval: Some("30s".to_owned()),
+                max_result_window: Some(20000),
+                ..Default::default()
+            })
+            .send();
+
+        let get_res = client.index(INDEX).settings().send();
+
+        Box::new(
+            create_res
+                .and_then(|_| put_res)
+                .and_then(|_| get_res),
+        )
+    }
+
+    // Ensure the settings were applied
+    fn assert_ok(&self, res: &Self::Response) -> bool {
+        match res.get(INDEX) {
+            Some(settings) => {
+                settings.number_of_shards == Some(2)
+                    && settings.number_of_replicas == Some(0)
+                    && settings.refresh_interval.as_ref().map(|s| s.as_ref()) == Some("30s")
+                    && settings.max_result_window == Some(20000)
+                    && settings.analysis.normalizer.contains_key("lowercase")
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use elastic::{
+    error::Error,
+    prelude::*,
+};
+use futures::Future;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, ElasticType)]
+pub struct LogEntry {
+    level: String,
+    message: String,
+}
+
+const TEMPLATE: &str = "template_logs";
+const INDEX: &str = "template_logs-2026.10.16";
+const ALIAS: &str = "template_logs";
+
+test! {
+    const description: &'static str = "put an index template from a document mapping";
+
+    type Response = (IndexTemplatesResponse, IndexTemplateExistsResponse, AliasesResponse);
+
+    // Ensure the template and any index it was applied to don't exist
+    fn prepare(&self, client: AsyncClient) -> Box<dyn Future<Item = (), Error = Error>> {
+        let delete_template = client.index_template(TEMPLATE).delete().send();
+        let delete_index = client.index(INDEX).delete().send();
+
+        Box::new(delete_template.then(|_| delete_index).map(|_| ()))
+    }
+
+    // Put the template, then create an index that matches it
+    fn request(
+        &self,
+        client: AsyncClient,
+    ) -> Box<dyn Future<Item = Self::Response, Error = Error>> {
+        let put_res = client
+            .index_template(TEMPLATE)
+            .put("template_logs-*")
+            .settings(json!({ "index": { "number_of_shards": 1 } }))
+            .mapping::<LogEntry>()
+            .alias(ALIAS)
+            .send();
+
+        let create_res = client.index(INDEX).create().send();
+
+        let get_res = client.index_template(TEMPLATE).get().send();
+        let exists_res = client.index_template(TEMPLATE).exists().send();
+        let aliases_res = client.index(INDEX).get_aliases().send();
+
+        Box::new(
+            put_res
+                .and_then(|_| create_res)
+                .and_then(|_| get_res)
+                .and_then(|get| exists_res.map(|exists| (get, exists)))
+                .and_then(|(get, exists)| aliases_res.map(|aliases| (get, exists, aliases))),
+        )
+    }
+
+    // Ensure the template was stored and applied to the new index
+    fn assert_ok(&self, res: &Self::Response) -> bool {
+        let (ref get, ref exists, ref aliases) = *res;
+
+        let template = get.get(TEMPLATE);
+        let has_mapping = template
+            .map(|template| !template.mappings()["properties"]["message"].is_null())
+            .unwrap_or(false);
+
+        has_mapping
+            && exists.exists()
+            && aliases.aliases().any(|alias| alias.name() == ALIAS)
+    }
+}
@@ -1,5 +1,8 @@
 test_cases![
     empty_query,
+    msearch,
     no_index,
-    raw_query_string
+    raw_query_string,
+    scroll,
+    search_after
 ];
@@ -0,0 +1,81 @@
+use elastic::{
+    error::{
+        ApiError,
+        Error,
+    },
+    prelude::*,
+};
+use futures::{
+    future,
+    Future,
+};
+
+#[derive(Debug, Serialize, Deserialize, ElasticType)]
+#[elastic(index = "msearch_idx")]
+pub struct Doc {
+    #[elastic(id)]
+    id: String,
+}
+
+fn doc() -> Doc {
+    Doc { id: "1".to_owned() }
+}
+
+const MISSING_INDEX: &str = "msearch_missing_idx";
+
+test! {
+    const description: &'static str = "multi search";
+
+    type Response = MsearchResponse<Doc>;
+
+    // Ensure one index exists and another doesn't
+    fn prepare(&self, client: AsyncClient) -> Box<dyn Future<Item = (), Error = Error>> {
+        let delete_res = client.index(Doc::static_index()).delete().send();
+        let delete_missing_res = client.index(MISSING_INDEX).delete().send();
+
+        let index_reqs = future::join_all((0..5).map(move |_| {
+            client
+                .document()
+                .index(doc())
+                .params_fluent(|p| p.url_param("refresh", true))
+                .send()
+        }));
+
+        Box::new(
+            delete_res
+                .then(|_| delete_missing_res)
+                .then(|_| index_reqs.map(|_| ())),
+        )
+    }
+
+    // Execute a search against each index in a single request
+    fn request(
+        &self,
+        client: AsyncClient,
+    ) -> Box<dyn Future<Item = Self::Response, Error = Error>> {
+        let res = client
+            .msearch()
+            .push(msearch().index(Doc::static_index()))
+            .push(msearch().index(MISSING_INDEX))
+            .send();
+
+        Box::new(res)
+    }
+
+    // Ensure the first search succeeded and the second failed
+    fn assert_ok(&self, res: &Self::Response) -> bool {
+        let mut items = res.iter();
+
+        let found = match items.next() {
+            Some(Ok(search)) => search.documents().count() > 0,
+            _ => false,
+        };
+
+        let missing = match items.next() {
+            Some(Err(ApiError::IndexNotFound { ref index })) => index == MISSING_INDEX,
+            _ => false,
+        };
+
+        found && missing
+    }
+}
@@ -0,0 +1,67 @@
+use elastic::{
+    error::Error,
+    prelude::*,
+};
+use futures::{
+    future,
+    Future,
+    Stream,
+};
+use std::time::Duration;
+
+#[derive(Debug, Serialize, Deserialize, ElasticType)]
+#[elastic(index = "scroll_idx")]
+pub struct Doc {
+    #[elastic(id)]
+    id: String,
+}
+
+fn doc(id: i32) -> Doc {
+    Doc { id: id.to_string() }
+}
+
+const DOC_COUNT: i32 = 10;
+
+test! {
+    const description: &'static str = "scroll";
+
+    type Response = Vec<Doc>;
+
+    // Ensure the index contains a known set of documents
+    fn prepare(&self, client: AsyncClient) -> Box<dyn Future<Item = (), Error = Error>> {
+        let delete_res = client.index(Doc::static_index()).delete().send();
+
+        let index_reqs = future::join_all((0..DOC_COUNT).map(move |i| {
+            client
+                .document()
+                .index(doc(i))
+                .params_fluent(|p| p.url_param("refresh", true))
+                .send()
+        }));
+
+        Box::new(delete_res.then(|_| index_reqs.map(|_| ())))
+    }
+
+    // Scroll through all documents a few at a time
+    fn request(
+        &self,
+        client: AsyncClient,
+    ) -> Box<dyn Future<Item = Self::Response, Error = Error>> {
+        let res = client
+            .document::<Doc>()
+            .search()
+            .body(json!({
+                "size": 3
+            }))
+            .scroll(Duration::from_secs(60))
+            .filter_map(|hit| hit.into_document())
+            .collect();
+
+        Box::new(res)
+    }
+
+    // Ensure every document was returned
+    fn assert_ok(&self, res: &Self::Response) -> bool {
+        res.len() == DOC_COUNT as usize
+    }
+}
@@ -0,0 +1,73 @@
+use elastic::{
+    error::Error,
+    prelude::*,
+};
+use futures::{
+    future,
+    Future,
+    Stream,
+};
+
+#[derive(Debug, Serialize, Deserialize, ElasticType)]
+#[elastic(index = "search_after_idx")]
+pub struct Doc {
+    #[elastic(id)]
+    id: String,
+    n: i32,
+}
+
+fn doc(n: i32) -> Doc {
+    Doc {
+        id: n.to_string(),
+        n,
+    }
+}
+
+const DOC_COUNT: i32 = 10;
+
+test! {
+    const description: &'static str = "search after";
+
+    type Response = Vec<Doc>;
+
+    // Ensure the index contains a known set of documents
+    fn prepare(&self, client: AsyncClient) -> Box<dyn Future<Item = (), Error = Error>> {
+        let delete_res = client.index(Doc::static_index()).delete().send();
+
+        let index_reqs = future::join_all((0..DOC_COUNT).map(move |n| {
+            client
+                .document()
+                .index(doc(n))
+                .params_fluent(|p| p.url_param("refresh", true))
+                .send()
+        }));
+
+        Box::new(delete_res.then(|_| index_reqs.map(|_| ())))
+    }
+
+    // Paginate through all documents a few at a time
+    fn request(
+        &self,
+        client: AsyncClient,
+    ) -> Box<dyn Future<Item = Self::Response, Error = Error>> {
+        let res = client
+            .document::<Doc>()
+            .search()
+            .body(json!({
+                "size": 3,
+                "sort": [
+                    { "n": "asc" }
+                ]
+            }))
+            .search_after()
+            .filter_map(|hit| hit.into_document())
+            .collect();
+
+        Box::new(res)
+    }
+
+    // Ensure every document was returned in order
+    fn assert_ok(&self, res: &Self::Response) -> bool {
+        res.len() == DOC_COUNT as usize && res.iter().zip(0..DOC_COUNT).all(|(doc, n)| doc.n == n)
+    }
+}
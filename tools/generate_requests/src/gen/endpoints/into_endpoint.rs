@@ -13,14 +13,16 @@ use quote;
 use syn;
 
 pub struct Builder {
+    name: String,
     req_ty: syn::Ty,
     has_body: bool,
     http_verb: Method,
 }
 
 impl Builder {
-    pub fn new(http_verb: Method, has_body: bool, request_ty: syn::Ty) -> Self {
+    pub fn new(name: &str, http_verb: Method, has_body: bool, request_ty: syn::Ty) -> Self {
         Builder {
+            name: name.to_owned(),
             req_ty: request_ty,
             has_body,
             http_verb,
@@ -28,6 +30,7 @@ impl Builder {
     }
 
     pub fn build(self) -> quote::Tokens {
+        let name = lit(self.name);
         let req_ty = self.req_ty;
         let method_ty = http::method::ty();
 
@@ -48,6 +51,7 @@ impl Builder {
                 impl <'a, #generic_body> Into<#endpoint_ty<'a, #generic_body> > for #req_ty {
                     fn into(self) -> #endpoint_ty<'a, #generic_body> {
                         #endpoint_ty {
+                            name: #name,
                             url: self.url,
                             method: #method,
                             body: Some(self.body)
@@ -62,6 +66,7 @@ impl Builder {
                 impl <'a> Into<#endpoint_ty<'a, #default_body> > for #req_ty {
                     fn into(self) -> #endpoint_ty<'a, #default_body> {
                         #endpoint_ty {
+                            name: #name,
                             url: self.url,
                             method: #method,
                             body: None
@@ -75,12 +80,12 @@ impl Builder {
 
 impl<'a> From<(&'a (String, Endpoint), &'a syn::Ty)> for Builder {
     fn from(value: (&'a (String, Endpoint), &'a syn::Ty)) -> Self {
-        let (&(_, ref endpoint), ref req_ty) = value;
+        let (&(ref name, ref endpoint), ref req_ty) = value;
 
         let has_body = endpoint.has_body();
         let verb = endpoint.methods[0];
 
-        Builder::new(verb, has_body, (*req_ty).to_owned())
+        Builder::new(name, verb, has_body, (*req_ty).to_owned())
     }
 }
 
@@ -110,6 +115,7 @@ mod tests {
             impl<'a, B> Into<Endpoint<'a, B> > for Request<'a, B> {
                 fn into(self) -> Endpoint<'a, B> {
                     Endpoint {
+                        name: "indices.exists_alias",
                         url: self.url,
                         method: Method::GET,
                         body: Some(self.body)
@@ -140,6 +146,7 @@ mod tests {
             impl<'a> Into<Endpoint<'a, DefaultBody> > for Request<'a> {
                 fn into(self) -> Endpoint<'a, DefaultBody> {
                     Endpoint {
+                        name: "indices.exists_alias",
                         url: self.url,
                         method: Method::GET,
                         body: None
@@ -19,6 +19,7 @@ pub fn tokens() -> quote::Tokens {
     let method_ty = method::ty();
 
     let request_ty = helpers::ty(ident());
+    let request_ident = helpers::ident(ident());
 
     let url_ty = url::ty();
 
@@ -28,9 +29,49 @@ pub fn tokens() -> quote::Tokens {
         /// A general request type that all endpoints can be converted into.
         #[derive(Debug, Clone, PartialEq)]
         pub struct #request_ty<'a, #body_ty> {
-            pub url: #url_ty,
-            pub method: #method_ty,
-            pub body: Option<#body_ty>
+            pub(crate) name: &'static str,
+            pub(crate) url: #url_ty,
+            pub(crate) method: #method_ty,
+            pub(crate) body: Option<#body_ty>
+        }
+
+        impl<'a, #body_ty> #request_ty<'a, #body_ty> {
+            /// Create an endpoint.
+            ///
+            /// The name identifies requests to the endpoint in metrics and traces.
+            pub fn new(
+                name: &'static str,
+                url: impl Into<#url_ty>,
+                method: #method_ty,
+                body: Option<#body_ty>,
+            ) -> Self {
+                #request_ident {
+                    name,
+                    url: url.into(),
+                    method,
+                    body,
+                }
+            }
+
+            /// The name of the endpoint.
+            pub fn name(&self) -> &'static str {
+                self.name
+            }
+
+            /// The url path of the endpoint.
+            pub fn url(&self) -> &#url_ty {
+                &self.url
+            }
+
+            /// The HTTP method of the endpoint.
+            pub fn method(&self) -> &#method_ty {
+                &self.method
+            }
+
+            /// The body of the endpoint.
+            pub fn body(&self) -> Option<&#body_ty> {
+                self.body.as_ref()
+            }
         }
     )
 }